@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, ParallelSlice},
+};
+use bevy_replicon::{network_event::client_event::ClientEventChannel, prelude::*};
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::{Deserialize, Serialize};
+
+const CLIENTS: u64 = 20;
+const MESSAGES_PER_CLIENT: usize = 500;
+const CHUNK_SIZE: usize = 256;
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct InputEvent {
+    sequence: u32,
+    direction: [f32; 2],
+    checksum: u64,
+}
+
+impl InputEvent {
+    fn new(sequence: u32) -> Self {
+        let direction = [sequence as f32, -(sequence as f32)];
+        Self {
+            sequence,
+            direction,
+            checksum: checksum(sequence, direction),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.checksum == checksum(self.sequence, self.direction)
+    }
+}
+
+/// Deliberately expensive to simulate real validation work.
+fn checksum(sequence: u32, direction: [f32; 2]) -> u64 {
+    let mut hash = sequence as u64;
+    for _ in 0..200 {
+        hash = hash
+            .wrapping_mul(0x100000001b3)
+            .wrapping_add(direction[0].to_bits() as u64 ^ direction[1].to_bits() as u64);
+    }
+    hash
+}
+
+fn validate(message: &Bytes) -> bool {
+    bincode::deserialize::<InputEvent>(message).is_ok_and(|event| event.is_valid())
+}
+
+fn receive(c: &mut Criterion) {
+    c.bench_function("sequential receive validation", |b| {
+        b.iter_custom(|iter| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iter {
+                let (mut app, channel_id) = create_app();
+                let mut server = app.world.resource_mut::<RepliconServer>();
+
+                let instant = Instant::now();
+                let valid = server
+                    .receive(channel_id)
+                    .filter(|(_, message)| validate(message))
+                    .count();
+                elapsed += instant.elapsed();
+                assert_eq!(valid, CLIENTS as usize * MESSAGES_PER_CLIENT);
+            }
+
+            elapsed
+        })
+    });
+
+    c.bench_function("parallel receive validation", |b| {
+        b.iter_custom(|iter| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iter {
+                let (mut app, channel_id) = create_app();
+                let mut server = app.world.resource_mut::<RepliconServer>();
+
+                let instant = Instant::now();
+                let batch = server.take_received(channel_id);
+                let valid: usize = batch
+                    .messages()
+                    .par_chunk_map(ComputeTaskPool::get(), CHUNK_SIZE, |_, messages| {
+                        messages
+                            .iter()
+                            .filter(|(_, message)| validate(message))
+                            .count()
+                    })
+                    .into_iter()
+                    .sum();
+                elapsed += instant.elapsed();
+                assert_eq!(valid, CLIENTS as usize * MESSAGES_PER_CLIENT);
+            }
+
+            elapsed
+        })
+    });
+}
+
+/// Creates a server with received input events from multiple clients.
+fn create_app() -> (App, u8) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_event::<InputEvent>(ChannelKind::Ordered);
+    app.update();
+
+    let channel_id = *app.world.resource::<ClientEventChannel<InputEvent>>();
+    let channel_id = u8::from(channel_id);
+
+    let mut server = app.world.resource_mut::<RepliconServer>();
+    server.set_running(true);
+    for client_raw in 0..CLIENTS {
+        for sequence in 0..MESSAGES_PER_CLIENT {
+            let message = bincode::serialize(&InputEvent::new(sequence as u32)).unwrap();
+            server.insert_received(ClientId::new(client_raw), channel_id, message);
+        }
+    }
+
+    (app, channel_id)
+}
+
+criterion_group!(receive_benches, receive);
+criterion_main!(receive_benches);
@@ -95,6 +95,7 @@ fn server_event() {
     server_app.world.send_event(ToClients {
         mode: SendMode::Broadcast,
         event: DummyEvent,
+        apply_tick: None,
     });
 
     server_app.update();
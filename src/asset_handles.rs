@@ -0,0 +1,279 @@
+use std::{io::Cursor, mem};
+
+use bevy::{
+    asset::{Asset, AssetServer, Handle},
+    ecs::entity::EntityHashMap,
+    prelude::*,
+};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        command_markers::AppMarkerExt,
+        common_conditions::client_connected,
+        replication_fns::{
+            command_fns,
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        replication_rules::AppRuleExt,
+    },
+};
+
+/// An extension trait for [`App`] for replicating components with asset handles.
+pub trait AppHandleExt {
+    /**
+    Creates a replication rule for a component with an asset handle.
+
+    The handle is sent as [`AssetKey`] obtained from resolver `R` and resolved back into a handle on client.
+    The rest of the component is serialized with [`serde`], so the handle field should be skipped.
+    If the handle can't be resolved yet, the component will be inserted only after a successful resolution.
+
+    Entities inside such components are not mapped.
+
+    # Examples
+
+    Replicate a handle by its asset path:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{asset_handles::PathResolver, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_handle::<Skin, PathResolver>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Skin {
+        #[serde(skip)]
+        image: Handle<Image>,
+        tint: [u8; 3],
+    }
+
+    impl HandleComponent for Skin {
+        type Asset = Image;
+
+        fn handle(&self) -> &Handle<Image> {
+            &self.image
+        }
+
+        fn handle_mut(&mut self) -> &mut Handle<Image> {
+            &mut self.image
+        }
+    }
+    # #[derive(Asset, TypePath)]
+    # struct Image;
+    ```
+    */
+    fn replicate_handle<C, R>(&mut self) -> &mut Self
+    where
+        C: HandleComponent,
+        R: AssetResolver<C::Asset>;
+}
+
+impl AppHandleExt for App {
+    fn replicate_handle<C, R>(&mut self) -> &mut Self
+    where
+        C: HandleComponent,
+        R: AssetResolver<C::Asset>,
+    {
+        self.init_resource::<PendingHandles<C>>()
+            .add_systems(
+                PreUpdate,
+                (
+                    resolve_pending::<C, R>
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    reset::<C>.in_set(ClientSet::Reset),
+                ),
+            )
+            .replicate_with(RuleFns::new(serialize::<C, R>, deserialize::<C>))
+            .set_command_fns(write::<C, R>, command_fns::default_remove::<C>)
+    }
+}
+
+/// A component that contains an asset handle.
+///
+/// See [`AppHandleExt::replicate_handle`].
+pub trait HandleComponent: Component + Serialize + DeserializeOwned {
+    type Asset: Asset;
+
+    fn handle(&self) -> &Handle<Self::Asset>;
+
+    fn handle_mut(&mut self) -> &mut Handle<Self::Asset>;
+}
+
+/**
+Converts handles into network-friendly keys and back.
+
+Conversion into a key happens on server during serialization, so it can't access the world.
+Resolution happens on client with the full world access.
+
+# Examples
+
+Resolve handles by stable IDs for generated assets:
+
+```
+use bevy::{asset::AssetId, prelude::*, utils::HashMap};
+use bevy_replicon::asset_handles::{AssetKey, AssetResolver};
+
+struct GeneratedResolver;
+
+impl AssetResolver<Mesh> for GeneratedResolver {
+    fn key(handle: &Handle<Mesh>) -> Option<AssetKey> {
+        match handle.id() {
+            AssetId::Uuid { uuid } => Some(AssetKey::Id(uuid.as_u128())),
+            AssetId::Index { .. } => None,
+        }
+    }
+
+    fn resolve(key: &AssetKey, world: &mut World) -> Option<Handle<Mesh>> {
+        let AssetKey::Id(id) = *key else {
+            return None;
+        };
+        world.resource::<GeneratedMeshes>().0.get(&id).cloned()
+    }
+}
+
+/// Filled when client generates meshes.
+#[derive(Resource)]
+struct GeneratedMeshes(HashMap<u128, Handle<Mesh>>);
+# #[derive(Asset, TypePath)]
+# struct Mesh;
+```
+**/
+pub trait AssetResolver<A: Asset>: 'static {
+    /// Returns a key for a handle.
+    ///
+    /// If [`None`] is returned, the component will be inserted on client with the default handle.
+    fn key(handle: &Handle<A>) -> Option<AssetKey>;
+
+    /// Returns a handle for a key.
+    ///
+    /// If [`None`] is returned, the resolution will be retried each frame until it succeeds.
+    fn resolve(key: &AssetKey, world: &mut World) -> Option<Handle<A>>;
+}
+
+/// Resolves handles by their asset paths via [`AssetServer`].
+///
+/// Handles without paths are replaced with the default handle.
+pub struct PathResolver;
+
+impl<A: Asset> AssetResolver<A> for PathResolver {
+    fn key(handle: &Handle<A>) -> Option<AssetKey> {
+        handle.path().map(|path| AssetKey::Path(path.to_string()))
+    }
+
+    fn resolve(key: &AssetKey, world: &mut World) -> Option<Handle<A>> {
+        let AssetKey::Path(path) = key else {
+            return None;
+        };
+        let asset_server = world.get_resource::<AssetServer>()?;
+        Some(asset_server.load(path.clone()))
+    }
+}
+
+/// Network representation of an asset handle.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum AssetKey {
+    Path(String),
+    Id(u128),
+}
+
+/// Components with keys that couldn't be resolved yet.
+#[derive(Resource)]
+struct PendingHandles<C>(EntityHashMap<(C, AssetKey)>);
+
+impl<C> Default for PendingHandles<C> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+fn serialize<C: HandleComponent, R: AssetResolver<C::Asset>>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(&mut *cursor, component)?;
+    DefaultOptions::new().serialize_into(cursor, &R::key(component.handle()))
+}
+
+/// Deserializes the component with the default handle.
+///
+/// Used only to consume the value, [`write`] performs the actual resolution.
+fn deserialize<C: HandleComponent>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    let component = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let _: Option<AssetKey> = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(component)
+}
+
+/// Deserializes the component and inserts it after resolving its handle.
+fn write<C: HandleComponent, R: AssetResolver<C::Asset>>(
+    ctx: &mut WriteCtx,
+    _rule_fns: &RuleFns<C>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let mut component: C = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let key: Option<AssetKey> = DefaultOptions::new().deserialize_from(cursor)?;
+    let entity = entity.id();
+
+    ctx.commands.add(move |world: &mut World| {
+        // Newer value overrides pending one.
+        world.resource_mut::<PendingHandles<C>>().0.remove(&entity);
+
+        if let Some(key) = key {
+            match R::resolve(&key, world) {
+                Some(handle) => *component.handle_mut() = handle,
+                None => {
+                    debug!("deferring insertion for `{entity:?}` until `{key:?}` is resolved");
+                    world
+                        .resource_mut::<PendingHandles<C>>()
+                        .0
+                        .insert(entity, (component, key));
+                    return;
+                }
+            }
+        }
+
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.insert(component);
+        }
+    });
+
+    Ok(())
+}
+
+/// Retries resolution for all pending components.
+fn resolve_pending<C: HandleComponent, R: AssetResolver<C::Asset>>(world: &mut World) {
+    let pending = mem::take(&mut world.resource_mut::<PendingHandles<C>>().0);
+    for (entity, (mut component, key)) in pending {
+        if world.get_entity(entity).is_none() {
+            continue;
+        }
+
+        match R::resolve(&key, world) {
+            Some(handle) => {
+                *component.handle_mut() = handle;
+                world.entity_mut(entity).insert(component);
+            }
+            None => {
+                world
+                    .resource_mut::<PendingHandles<C>>()
+                    .0
+                    .insert(entity, (component, key));
+            }
+        }
+    }
+}
+
+fn reset<C: Component>(mut pending: ResMut<PendingHandles<C>>) {
+    pending.0.clear();
+}
@@ -0,0 +1,251 @@
+use std::mem;
+
+use bevy::{
+    ecs::{
+        archetype::{ArchetypeGeneration, ArchetypeId},
+        component::{ComponentId, Tick},
+        system::SystemChangeTick,
+    },
+    prelude::*,
+    utils::HashSet,
+};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_fns::ReplicationFns,
+        Replicated,
+    },
+    network_event::server_event::MappedServerEvents,
+    server::{connected_clients::ConnectedClients, VisibilityPolicy},
+};
+
+/**
+Warns about common authority misconfigurations at runtime.
+
+Checks the following:
+- The server is running while the client is connected in the same app.
+Client events will be sent over the network instead of being resent locally.
+- Replicated components are changed on client outside of replication.
+These changes will be overwritten by the server. Writes of interpolation and extrapolation
+in [`ClientSet::Interpolate`] are considered a part of replication.
+Only entities with [`Replicated`] are checked and each component stops being checked after the first report.
+- Mapped server events are registered while entities can be hidden by [`VisibilityPolicy`].
+Such events should be sent only to clients that can see the mapped entities,
+otherwise mapping on client will fail.
+
+Each problem is reported only once. Checks are performed only in debug builds,
+in release builds the plugin does nothing.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, AuthorityWatchdogPlugin));
+```
+**/
+pub struct AuthorityWatchdogPlugin;
+
+impl Plugin for AuthorityWatchdogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AuthorityWatchdog>();
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        app.add_systems(Startup, Self::check_mapped_events)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::check_client_changes
+                        .before(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::mark_applied
+                        .after(ClientSet::Interpolate)
+                        .run_if(client_connected),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::check_both_roles
+                    .before(ClientSet::Send)
+                    .run_if(server_running)
+                    .run_if(client_connected),
+            );
+    }
+}
+
+impl AuthorityWatchdogPlugin {
+    fn check_mapped_events(
+        mapped_events: Option<Res<MappedServerEvents>>,
+        connected_clients: Option<Res<ConnectedClients>>,
+    ) {
+        let (Some(mapped_events), Some(connected_clients)) = (mapped_events, connected_clients)
+        else {
+            return;
+        };
+
+        let policy = connected_clients.visibility_policy();
+        if matches!(policy, VisibilityPolicy::All) {
+            return;
+        }
+
+        for type_name in &mapped_events.0 {
+            warn!(
+                "mapped server event `{type_name}` is registered with `{policy:?}` visibility policy, \
+                make sure to send it only to clients that can see entities inside it"
+            );
+        }
+    }
+
+    fn check_client_changes(world: &mut World, mut archetypes: Local<WatchedArchetypes>) {
+        let Some(replicated_id) = world.component_id::<Replicated>() else {
+            return;
+        };
+        let this_run = world.change_tick();
+
+        world.resource_scope(|world, mut watchdog: Mut<AuthorityWatchdog>| {
+            let Some(applied_tick) = watchdog.applied_tick else {
+                return;
+            };
+
+            archetypes.update(world, replicated_id);
+            for (archetype_id, component_ids) in &mut archetypes.archetypes {
+                component_ids.retain(|component_id| {
+                    !watchdog.ignored.contains(component_id)
+                        && !watchdog.reported.contains(component_id)
+                });
+
+                let archetype = &world.archetypes()[*archetype_id];
+                for &component_id in &*component_ids {
+                    let changed_entity = archetype.entities().iter().find(|entity| {
+                        world
+                            .entity(entity.id())
+                            .get_change_ticks_by_id(component_id)
+                            .is_some_and(|ticks| ticks.is_changed(applied_tick, this_run))
+                    });
+
+                    if let Some(entity) = changed_entity {
+                        let name = world
+                            .components()
+                            .get_info(component_id)
+                            .map(|info| info.name())
+                            .unwrap_or_default();
+                        warn!(
+                            "replicated component `{name}` was changed on client for {:?} without authority, \
+                            it will be overwritten by the server",
+                            entity.id()
+                        );
+                        watchdog.reported.insert(component_id);
+                    }
+                }
+            }
+            archetypes
+                .archetypes
+                .retain(|(_, component_ids)| !component_ids.is_empty());
+        });
+    }
+
+    fn mark_applied(mut watchdog: ResMut<AuthorityWatchdog>, change_tick: SystemChangeTick) {
+        watchdog.applied_tick = Some(change_tick.this_run());
+    }
+
+    fn check_both_roles(mut watchdog: ResMut<AuthorityWatchdog>) {
+        if !watchdog.both_roles_reported {
+            warn!(
+                "the server is running while the client is connected in the same app, \
+                client events will be sent over the network instead of being resent locally"
+            );
+            watchdog.both_roles_reported = true;
+        }
+    }
+}
+
+/// State of [`AuthorityWatchdogPlugin`].
+///
+/// Components that are expected to change on client, for example, with client-side prediction
+/// or interpolation, can be excluded from checks via [`Self::ignore`].
+#[derive(Resource, Default)]
+pub struct AuthorityWatchdog {
+    /// Components that are allowed to change on client.
+    ignored: HashSet<ComponentId>,
+
+    /// Components for which a change on client was already reported.
+    reported: HashSet<ComponentId>,
+
+    /// Tick after the last application of replication on client.
+    applied_tick: Option<Tick>,
+
+    both_roles_reported: bool,
+}
+
+impl AuthorityWatchdog {
+    /// Excludes a component from checks for changes on client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::{authority_watchdog::AuthorityWatchdog, prelude::*};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins((MinimalPlugins, RepliconPlugins, AuthorityWatchdogPlugin));
+    /// let component_id = app.world_mut().init_component::<Transform>();
+    /// app.world_mut()
+    ///     .resource_mut::<AuthorityWatchdog>()
+    ///     .ignore(component_id);
+    /// ```
+    pub fn ignore(&mut self, component_id: ComponentId) {
+        self.ignored.insert(component_id);
+    }
+
+    /// Returns `true` if a change of the component on client was reported.
+    pub fn is_reported(&self, component_id: ComponentId) -> bool {
+        self.reported.contains(&component_id)
+    }
+}
+
+/// Replicated archetypes with replicated components that are still checked for changes on client.
+struct WatchedArchetypes {
+    /// Highest processed archetype ID.
+    generation: ArchetypeGeneration,
+
+    archetypes: Vec<(ArchetypeId, Vec<ComponentId>)>,
+}
+
+impl WatchedArchetypes {
+    /// Adds archetypes with [`Replicated`] that were created since the last call.
+    fn update(&mut self, world: &World, replicated_id: ComponentId) {
+        let old_generation = mem::replace(&mut self.generation, world.archetypes().generation());
+        let replication_fns = world.resource::<ReplicationFns>();
+
+        // Archetypes are never removed, iterate over newly added since the last update.
+        for archetype in world.archetypes()[old_generation..]
+            .iter()
+            .filter(|archetype| archetype.contains(replicated_id))
+        {
+            let component_ids: Vec<_> = replication_fns
+                .component_ids()
+                .filter(|&component_id| archetype.contains(component_id))
+                .collect();
+            if !component_ids.is_empty() {
+                self.archetypes.push((archetype.id(), component_ids));
+            }
+        }
+    }
+}
+
+impl Default for WatchedArchetypes {
+    fn default() -> Self {
+        Self {
+            generation: ArchetypeGeneration::initial(),
+            archetypes: Default::default(),
+        }
+    }
+}
@@ -1,30 +1,54 @@
 pub mod confirmed;
+pub mod despawn_prediction;
 pub mod diagnostics;
+pub mod duplicate_spawns;
+pub mod entity_map_gc;
+pub mod fast_forward;
+pub mod interpolation;
+pub mod message_hooks;
+pub mod obscured;
+pub mod previous_values;
 pub mod replicon_client;
 pub mod server_entity_map;
+pub mod tick_changes;
+pub mod upstream_budget;
 
-use std::{io::Cursor, mem};
+use std::{io::Cursor, iter};
 
-use bevy::{ecs::world::CommandQueue, prelude::*};
+use bevy::{
+    ecs::{component::Tick, system::SystemChangeTick, world::CommandQueue},
+    prelude::*,
+};
 use bincode::{DefaultOptions, Options};
 use bytes::Bytes;
 use varint_rs::VarintReader;
 
 use crate::core::{
-    command_markers::{CommandMarkers, EntityMarkers},
+    command_markers::{CommandMarkers, EntityMarkers, SpawnMarkers},
     common_conditions::{client_connected, client_just_connected, client_just_disconnected},
+    replay_log::ReplayLog,
     replication_fns::{
-        ctx::{DespawnCtx, RemoveCtx, WriteCtx},
-        ReplicationFns,
+        ctx::{DespawnCtx, RemoveCtx, SpawnCtx, WriteCtx},
+        FnsId, FnsIdRemap, ReplicationFns,
     },
+    replication_sections::ReplicationSections,
     replicon_channels::{ReplicationChannel, RepliconChannels},
     replicon_tick::RepliconTick,
-    Replicated,
+    update_acks, Replicated,
 };
 use confirmed::Confirmed;
+use despawn_prediction::DespawnPredictionTimeout;
 use diagnostics::ClientStats;
+use duplicate_spawns::{DuplicateSpawn, DuplicateSpawnPolicy};
+use entity_map_gc::EntityMapGc;
+use fast_forward::{FastForward, FastForwarded};
+use message_hooks::MessageHooks;
+use obscured::Obscured;
+use previous_values::PreviousValues;
 use replicon_client::RepliconClient;
 use server_entity_map::ServerEntityMap;
+use tick_changes::{TickChangesApplied, TickChangesBuffer};
+use upstream_budget::UpstreamBudget;
 
 pub struct ClientPlugin;
 
@@ -33,7 +57,18 @@ impl Plugin for ClientPlugin {
         app.init_resource::<RepliconClient>()
             .init_resource::<ServerEntityMap>()
             .init_resource::<ServerInitTick>()
+            .init_resource::<ServerUpdateTick>()
             .init_resource::<BufferedUpdates>()
+            .init_resource::<ApplyState>()
+            .init_resource::<DespawnPredictionTimeout>()
+            .init_resource::<UpstreamBudget>()
+            .init_resource::<MessageHooks>()
+            .init_resource::<DuplicateSpawnPolicy>()
+            .init_resource::<EntityMapGc>()
+            .init_resource::<FastForward>()
+            .add_event::<DuplicateSpawn>()
+            .add_event::<TickChangesApplied>()
+            .add_event::<FastForwarded>()
             .configure_sets(
                 PreUpdate,
                 (
@@ -43,6 +78,7 @@ impl Plugin for ClientPlugin {
                         ClientSet::Reset.run_if(client_just_disconnected),
                     ),
                     ClientSet::Receive,
+                    ClientSet::Interpolate,
                 )
                     .chain(),
             )
@@ -51,14 +87,77 @@ impl Plugin for ClientPlugin {
                 (ClientSet::Send, ClientSet::SendPackets).chain(),
             )
             .add_systems(Startup, Self::setup_channels)
+            .configure_sets(
+                PreUpdate,
+                (
+                    ClientSet::ApplyDespawns,
+                    ClientSet::ApplyRemovals,
+                    ClientSet::ApplySpawns,
+                    ClientSet::ApplyInserts,
+                    ClientSet::ApplyUpdates,
+                )
+                    .chain()
+                    .in_set(ClientSet::Receive),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::receive_replication
+                        .map(Result::unwrap)
+                        .in_set(ClientSet::ApplyDespawns),
+                    Self::apply_removals
+                        .map(Result::unwrap)
+                        .in_set(ClientSet::ApplyRemovals),
+                    Self::apply_spawns
+                        .map(Result::unwrap)
+                        .in_set(ClientSet::ApplySpawns),
+                    Self::apply_inserts
+                        .map(Result::unwrap)
+                        .in_set(ClientSet::ApplyInserts),
+                    Self::apply_updates
+                        .map(Result::unwrap)
+                        .in_set(ClientSet::ApplyUpdates),
+                )
+                    .run_if(client_connected),
+            )
             .add_systems(
                 PreUpdate,
-                Self::receive_replication
-                    .map(Result::unwrap)
+                (
+                    entity_map_gc::clear_on_connect.run_if(client_just_connected),
+                    entity_map_gc::collect_ghosts.run_if(client_connected),
+                )
+                    .chain()
+                    .after(ClientSet::ReceivePackets)
+                    .before(ClientSet::Receive),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::start_receive
+                        .after(ClientSet::ReceivePackets)
+                        .before(ClientSet::Receive),
+                    Self::finish_receive.after(ClientSet::Receive),
+                )
+                    .run_if(client_connected),
+            )
+            .add_systems(
+                PreUpdate,
+                despawn_prediction::restore_mispredicted
+                    .after(ClientSet::ApplyUpdates)
                     .in_set(ClientSet::Receive)
                     .run_if(client_connected),
             )
-            .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+            .add_systems(
+                PostUpdate,
+                upstream_budget::limit_sent
+                    .after(ClientSet::Send)
+                    .before(ClientSet::SendPackets)
+                    .run_if(client_connected),
+            )
+            .add_systems(
+                PreUpdate,
+                (Self::reset, upstream_budget::reset).in_set(ClientSet::Reset),
+            );
     }
 }
 
@@ -67,10 +166,12 @@ impl ClientPlugin {
         client.setup_server_channels(channels.server_channels().len());
     }
 
-    /// Receives and applies replication messages from the server.
+    /// Receives replication messages from the server and starts applying them.
     ///
     /// Tick init messages are sent over the [`ReplicationChannel::Init`] and are applied first to ensure valid state
-    /// for entity updates.
+    /// for entity updates. If multiple init messages were received, all except the last are applied at once.
+    /// Sections of the last init message are applied in the corresponding [`ClientSet`] apply stages,
+    /// starting from mappings and despawns in this system.
     ///
     /// Entity update messages are sent over [`ReplicationChannel::Update`], which means they may appear
     /// ahead-of or behind init messages from the same server tick. An update will only be applied if its
@@ -78,99 +179,225 @@ impl ClientPlugin {
     /// Since entity updates can arrive in any order, updates will only be applied if they correspond to a more
     /// recent server tick than the last acked server tick for each entity.
     ///
-    /// Buffered entity update messages are processed last.
+    /// All update messages are buffered and applied in [`ClientSet::ApplyUpdates`].
     ///
-    /// Acknowledgments for received entity update messages are sent back to the server.
+    /// Acknowledgments for received entity update messages are sent back to the server in a single message,
+    /// see [`update_acks::serialize_acks`].
+    ///
+    /// If there are too many messages, [`FastForward`] will be activated for the current update.
     ///
     /// See also [`ReplicationMessages`](crate::server::replication_messages::ReplicationMessages).
-    pub(super) fn receive_replication(
-        world: &mut World,
-        mut queue: Local<CommandQueue>,
-        mut entity_markers: Local<EntityMarkers>,
-    ) -> bincode::Result<()> {
-        world.resource_scope(|world, mut client: Mut<RepliconClient>| {
-            world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
-                world.resource_scope(|world, mut buffered_updates: Mut<BufferedUpdates>| {
+    fn receive_replication(world: &mut World) -> bincode::Result<()> {
+        with_params(world, |world, params, client, buffered_updates| {
+            let messages: Vec<_> = client.receive(ReplicationChannel::Init).collect();
+            let messages_count = messages.len() + client.received_count(ReplicationChannel::Update);
+            if world.resource_mut::<FastForward>().update(messages_count) {
+                debug!("fast-forwarding through {messages_count} replication messages");
+                world.send_event(FastForwarded {
+                    messages: messages_count,
+                });
+            }
+
+            if let Some((last_message, messages)) = messages.split_last() {
+                for message in messages {
+                    apply_init_message(world, params, buffered_updates, message)?;
+                }
+                start_init_message(world, params, buffered_updates, last_message.clone())?;
+            }
+
+            let mut update_indices =
+                Vec::with_capacity(client.received_count(ReplicationChannel::Update));
+            for message in client.receive(ReplicationChannel::Update) {
+                let update_index = read_update_message(params, buffered_updates, message)?;
+                update_indices.push(update_index);
+            }
+            if !update_indices.is_empty() {
+                let acks = update_acks::serialize_acks(&update_indices)?;
+                client.send(ReplicationChannel::Init, acks);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Applies component removals from the last init message.
+    fn apply_removals(world: &mut World) -> bincode::Result<()> {
+        with_params(world, |world, params, _, _| {
+            apply_pending_sections(world, params, &[InitSection::Removals])
+        })
+    }
+
+    /// Spawns entities from the last init message.
+    fn apply_spawns(world: &mut World) -> bincode::Result<()> {
+        with_params(world, |world, params, _, _| {
+            apply_pending_sections(world, params, &[InitSection::Spawns])
+        })
+    }
+
+    /// Inserts components from the last init message, obscures hidden entities and applies custom sections.
+    fn apply_inserts(world: &mut World) -> bincode::Result<()> {
+        with_params(world, |world, params, _, _| {
+            apply_pending_sections(
+                world,
+                params,
+                &[
+                    InitSection::Inserts,
+                    InitSection::Hidden,
+                    InitSection::Custom,
+                ],
+            )
+        })
+    }
+
+    /// Applies buffered update messages and emits [`TickChangesApplied`] for all applied messages.
+    fn apply_updates(world: &mut World) -> bincode::Result<()> {
+        with_params(world, |world, params, _, buffered_updates| {
+            let init_tick = *world.resource::<ServerInitTick>();
+            apply_update_messages(world, params, buffered_updates, init_tick)?;
+            params.tick_changes.send(world);
+
+            Ok(())
+        })
+    }
+
+    /// Stores the tick before applying replication to detect local changes.
+    fn start_receive(mut apply_state: ResMut<ApplyState>, change_tick: SystemChangeTick) {
+        apply_state.local_changes.1 = change_tick.this_run();
+    }
+
+    /// Stores the tick after applying replication to detect local changes.
+    fn finish_receive(mut apply_state: ResMut<ApplyState>, change_tick: SystemChangeTick) {
+        apply_state.local_changes.0 = change_tick.this_run();
+    }
+
+    fn reset(
+        mut init_tick: ResMut<ServerInitTick>,
+        mut update_tick: ResMut<ServerUpdateTick>,
+        mut entity_map: ResMut<ServerEntityMap>,
+        mut buffered_updates: ResMut<BufferedUpdates>,
+        mut apply_state: ResMut<ApplyState>,
+        mut fast_forward: ResMut<FastForward>,
+        mut spawn_markers: ResMut<SpawnMarkers>,
+    ) {
+        *init_tick = Default::default();
+        *update_tick = Default::default();
+        entity_map.clear();
+        buffered_updates.clear();
+        apply_state.pending_init = None;
+        apply_state.previous_values.clear();
+        fast_forward.reset();
+        spawn_markers.clear();
+    }
+}
+
+/// Borrows all resources needed for applying replication and passes them to `f`.
+///
+/// Does nothing while waiting for the server's stable IDs.
+fn with_params(
+    world: &mut World,
+    f: impl FnOnce(
+        &mut World,
+        &mut ReceiveParams,
+        &mut RepliconClient,
+        &mut BufferedUpdates,
+    ) -> bincode::Result<()>,
+) -> bincode::Result<()> {
+    if world
+        .get_resource::<FnsIdRemap>()
+        .is_some_and(|remap| !remap.is_received())
+    {
+        // Keep messages until the server's stable IDs arrive.
+        return Ok(());
+    }
+
+    world.resource_scope(|world, mut client: Mut<RepliconClient>| {
+        world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
+            world.resource_scope(|world, mut buffered_updates: Mut<BufferedUpdates>| {
+                world.resource_scope(|world, mut apply_state: Mut<ApplyState>| {
                     world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
                         world.resource_scope(|world, replication_fns: Mut<ReplicationFns>| {
+                            let fns_remap = world.remove_resource::<FnsIdRemap>();
                             let mut stats = world.remove_resource::<ClientStats>();
+                            let mut replay_log = world.remove_resource::<ReplayLog>();
+                            let message_hooks = world.remove_resource::<MessageHooks>();
+                            let fast_forward = world.resource::<FastForward>().is_active();
+                            let apply_state = &mut *apply_state;
                             let mut params = ReceiveParams {
-                                queue: &mut queue,
-                                entity_markers: &mut entity_markers,
+                                queue: &mut apply_state.queue,
+                                entity_markers: &mut apply_state.entity_markers,
                                 entity_map: &mut entity_map,
                                 stats: stats.as_mut(),
+                                replay_log: replay_log.as_mut(),
                                 command_markers: &command_markers,
                                 replication_fns: &replication_fns,
+                                fns_remap: fns_remap.as_ref(),
+                                message_hooks: message_hooks.as_ref(),
+                                tick_changes: &mut apply_state.tick_changes,
+                                pending_init: &mut apply_state.pending_init,
+                                local_changes: apply_state.local_changes,
+                                previous_values: &mut apply_state.previous_values,
+                                fast_forward,
                             };
 
-                            apply_replication(
-                                world,
-                                &mut params,
-                                &mut client,
-                                &mut buffered_updates,
-                            )?;
+                            let result =
+                                (f)(world, &mut params, &mut client, &mut buffered_updates);
 
                             if let Some(stats) = stats {
                                 world.insert_resource(stats);
                             }
+                            if let Some(replay_log) = replay_log {
+                                world.insert_resource(replay_log);
+                            }
+                            if let Some(fns_remap) = fns_remap {
+                                world.insert_resource(fns_remap);
+                            }
+                            if let Some(message_hooks) = message_hooks {
+                                world.insert_resource(message_hooks);
+                            }
 
-                            Ok(())
+                            result
                         })
                     })
                 })
             })
         })
-    }
-
-    fn reset(
-        mut init_tick: ResMut<ServerInitTick>,
-        mut entity_map: ResMut<ServerEntityMap>,
-        mut buffered_updates: ResMut<BufferedUpdates>,
-    ) {
-        *init_tick = Default::default();
-        entity_map.clear();
-        buffered_updates.clear();
-    }
+    })
 }
 
-/// Reads all received messages and applies them.
-///
-/// Sends acknowledgments for update messages back.
-fn apply_replication(
+/// Applies [`InitMessage`](crate::server::replication_messages::InitMessage) at once.
+fn apply_init_message(
     world: &mut World,
     params: &mut ReceiveParams,
-    client: &mut RepliconClient,
     buffered_updates: &mut BufferedUpdates,
+    message: &Bytes,
 ) -> bincode::Result<()> {
-    for message in client.receive(ReplicationChannel::Init) {
-        apply_init_message(world, params, &message)?;
-    }
-
-    // Unlike init messages, we read all updates first, sort them by tick
-    // in descending order to ensure that the last update will be applied first.
-    // Since update messages manually split by packet size, we apply all messages,
-    // but skip outdated data per-entity by checking last received tick for it
-    // (unless user requested history via marker).
-    let init_tick = *world.resource::<ServerInitTick>();
-    let acks_size = mem::size_of::<u16>() * client.received_count(ReplicationChannel::Update);
-    let mut acks = Vec::with_capacity(acks_size);
-    for message in client.receive(ReplicationChannel::Update) {
-        let update_index = read_update_message(params, buffered_updates, message)?;
-        bincode::serialize_into(&mut acks, &update_index)?;
-    }
-    client.send(ReplicationChannel::Init, acks);
-
-    apply_update_messages(world, params, buffered_updates, init_tick)
+    start_init_message(world, params, buffered_updates, message.clone())?;
+    apply_pending_sections(
+        world,
+        params,
+        &[
+            InitSection::Removals,
+            InitSection::Spawns,
+            InitSection::Inserts,
+            InitSection::Hidden,
+            InitSection::Custom,
+        ],
+    )
 }
 
-/// Applies [`InitMessage`](crate::server::replication_messages::InitMessage).
-fn apply_init_message(
+/// Reads the tick of [`InitMessage`](crate::server::replication_messages::InitMessage) and applies
+/// its mappings and despawns.
+///
+/// Message without any sections is a reset request.
+/// If the message has more sections, it will be stored as pending.
+fn start_init_message(
     world: &mut World,
     params: &mut ReceiveParams,
-    message: &[u8],
+    buffered_updates: &mut BufferedUpdates,
+    message: Bytes,
 ) -> bincode::Result<()> {
     let end_pos: u64 = message.len().try_into().unwrap();
-    let mut cursor = Cursor::new(message);
+    let mut cursor = Cursor::new(&*message);
     if let Some(stats) = &mut params.stats {
         stats.packets += 1;
         stats.bytes += end_pos;
@@ -179,36 +406,124 @@ fn apply_init_message(
     let message_tick = bincode::deserialize_from(&mut cursor)?;
     trace!("applying init message for {message_tick:?}");
     world.resource_mut::<ServerInitTick>().0 = message_tick;
-    debug_assert!(cursor.position() < end_pos, "init message can't be empty");
-
-    apply_entity_mappings(world, params, &mut cursor)?;
+    params.tick_changes.begin(message_tick);
     if cursor.position() == end_pos {
+        apply_reset(world, params, buffered_updates, message_tick);
         return Ok(());
     }
 
-    apply_despawns(world, params, &mut cursor, message_tick)?;
-    if cursor.position() == end_pos {
-        return Ok(());
+    if let Some(message_hooks) = params.message_hooks {
+        message_hooks.begin(world, message_tick);
     }
 
-    apply_init_components(
+    *params.pending_init = Some(PendingInit {
+        position: cursor.position(),
+        message_tick,
+        message,
+    });
+
+    apply_pending_sections(
         world,
         params,
-        ComponentsKind::Removal,
-        &mut cursor,
-        message_tick,
-    )?;
-    if cursor.position() == end_pos {
+        &[InitSection::Mappings, InitSection::Despawns],
+    )
+}
+
+/// Despawns all replicated entities and clears all mappings.
+///
+/// See [`RepliconServer::reset_clients`](crate::server::replicon_server::RepliconServer::reset_clients).
+fn apply_reset(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    buffered_updates: &mut BufferedUpdates,
+    message_tick: RepliconTick,
+) {
+    debug!("resetting replicated entities for {message_tick:?}");
+    let client_entities: Vec<_> = params.entity_map.to_client().values().copied().collect();
+    params.entity_map.clear();
+    buffered_updates.clear();
+
+    if let Some(stats) = &mut params.stats {
+        stats.despawns += client_entities.len() as u32;
+    }
+    let ctx = DespawnCtx { message_tick };
+    for client_entity in client_entities {
+        // Could be already despawned with its parent.
+        if let Some(client_entity) = world.get_entity_mut(client_entity) {
+            params
+                .tick_changes
+                .despawn(message_tick, client_entity.id());
+            params.previous_values.remove_entity(client_entity.id());
+            params
+                .entity_markers
+                .read(params.command_markers, &client_entity);
+            let despawn = params.replication_fns.despawn_fn(params.entity_markers);
+            (despawn)(&ctx, client_entity);
+        }
+    }
+}
+
+/// Applies `sections` of the pending init message.
+///
+/// Sections at the end of the message are skipped if they are empty.
+/// When the message is fully applied, it's no longer pending.
+fn apply_pending_sections(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    sections: &[InitSection],
+) -> bincode::Result<()> {
+    let Some(pending) = params.pending_init.take() else {
         return Ok(());
+    };
+
+    let end_pos = pending.message.len() as u64;
+    let mut cursor = Cursor::new(&*pending.message);
+    cursor.set_position(pending.position);
+    for &section in sections {
+        if cursor.position() == end_pos {
+            break;
+        }
+
+        match section {
+            InitSection::Mappings => {
+                apply_entity_mappings(world, params, &mut cursor, pending.message_tick)?
+            }
+            InitSection::Despawns => {
+                apply_despawns(world, params, &mut cursor, pending.message_tick)?
+            }
+            InitSection::Removals => apply_init_components(
+                world,
+                params,
+                ComponentsKind::Removal,
+                &mut cursor,
+                pending.message_tick,
+            )?,
+            InitSection::Spawns => {
+                prespawn_entities(world, params, &mut cursor, pending.message_tick)?
+            }
+            InitSection::Inserts => apply_init_components(
+                world,
+                params,
+                ComponentsKind::Insert,
+                &mut cursor,
+                pending.message_tick,
+            )?,
+            InitSection::Hidden => apply_hidden(world, params, &mut cursor)?,
+            InitSection::Custom => apply_custom_sections(world, &mut cursor, pending.message_tick)?,
+        }
     }
 
-    apply_init_components(
-        world,
-        params,
-        ComponentsKind::Insert,
-        &mut cursor,
-        message_tick,
-    )?;
+    let position = cursor.position();
+    if position == end_pos || sections.contains(&InitSection::Custom) {
+        if let Some(message_hooks) = params.message_hooks {
+            message_hooks.end(world, pending.message_tick);
+        }
+    } else {
+        *params.pending_init = Some(PendingInit {
+            position,
+            ..pending
+        });
+    }
 
     Ok(())
 }
@@ -256,6 +571,16 @@ fn apply_update_messages(
         }
 
         trace!("applying update message for {:?}", update.message_tick);
+        let mut update_tick = world.resource_mut::<ServerUpdateTick>();
+        if update.message_tick > **update_tick {
+            update_tick.0 = update.message_tick;
+        }
+
+        params.tick_changes.begin(update.message_tick);
+        if let Some(message_hooks) = params.message_hooks {
+            message_hooks.begin(world, update.message_tick);
+        }
+
         if let Err(e) = apply_update_components(
             world,
             params,
@@ -265,6 +590,10 @@ fn apply_update_messages(
             result = Err(e);
         }
 
+        if let Some(message_hooks) = params.message_hooks {
+            message_hooks.end(world, update.message_tick);
+        }
+
         false
     });
 
@@ -276,6 +605,7 @@ fn apply_entity_mappings(
     world: &mut World,
     params: &mut ReceiveParams,
     cursor: &mut Cursor<&[u8]>,
+    message_tick: RepliconTick,
 ) -> bincode::Result<()> {
     let mappings_len: u16 = bincode::deserialize_from(&mut *cursor)?;
     if let Some(stats) = &mut params.stats {
@@ -288,7 +618,33 @@ fn apply_entity_mappings(
         if let Some(mut entity) = world.get_entity_mut(client_entity) {
             debug!("received mapping from {server_entity:?} to {client_entity:?}");
             entity.insert(Replicated);
-            params.entity_map.insert(server_entity, client_entity);
+            match params.entity_map.get_by_server(server_entity) {
+                Some(existing_entity)
+                    if existing_entity != client_entity
+                        && world.get_entity(existing_entity).is_some() =>
+                {
+                    duplicate_spawns::resolve(
+                        world,
+                        params.entity_map,
+                        params.replication_fns,
+                        server_entity,
+                        existing_entity,
+                        client_entity,
+                        message_tick,
+                    );
+                }
+                Some(existing_entity) if existing_entity != client_entity => {
+                    // Previous entity was despawned on client, replace the stale mapping.
+                    params.entity_map.remove_by_server(server_entity);
+                    params.entity_map.insert(server_entity, client_entity);
+                    params.tick_changes.spawn(message_tick, client_entity);
+                }
+                Some(_) => (),
+                None => {
+                    params.entity_map.insert(server_entity, client_entity);
+                    params.tick_changes.spawn(message_tick, client_entity);
+                }
+            }
         } else {
             // Entity could be despawned on client already.
             debug!("received mapping from {server_entity:?} to {client_entity:?}, but the entity doesn't exists");
@@ -297,6 +653,63 @@ fn apply_entity_mappings(
     Ok(())
 }
 
+/// Spawns client entities for all unmapped server entities from the insertion section at once.
+///
+/// Reads only entities and skips their data using its size, the cursor position is restored afterwards.
+/// All entities from the message are mapped before any component is applied. Components are still
+/// inserted per entity, so entities move between archetypes as usual.
+/// If [`ReplicationFns::spawn`] is set or [`SpawnMarkers`] are assigned, entities are spawned one by one instead.
+fn prespawn_entities(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    cursor: &mut Cursor<&[u8]>,
+    message_tick: RepliconTick,
+) -> bincode::Result<()> {
+    let start_pos = cursor.position();
+    let entities_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    let mut server_entities = Vec::new();
+    for _ in 0..entities_len {
+        let server_entity = deserialize_entity(cursor)?;
+        let data_size: u16 = bincode::deserialize_from(&mut *cursor)?;
+        if params.entity_map.get_by_server(server_entity).is_none() {
+            server_entities.push(server_entity);
+        }
+        cursor.set_position(cursor.position() + data_size as u64);
+    }
+    cursor.set_position(start_pos);
+
+    if server_entities.is_empty() {
+        return Ok(());
+    }
+
+    trace!("prespawning {} entities", server_entities.len());
+    let client_entities: Vec<_> =
+        if params.replication_fns.spawn.is_some() || !world.resource::<SpawnMarkers>().is_empty() {
+            server_entities
+                .iter()
+                .map(|&server_entity| {
+                    let ctx = SpawnCtx {
+                        message_tick,
+                        server_entity,
+                    };
+                    params
+                        .replication_fns
+                        .spawn_entity(&ctx, world, params.command_markers)
+                })
+                .collect()
+        } else {
+            world
+                .spawn_batch(iter::repeat(Replicated).take(server_entities.len()))
+                .collect()
+        };
+    for (server_entity, client_entity) in server_entities.into_iter().zip(client_entities) {
+        params.entity_map.insert(server_entity, client_entity);
+        params.tick_changes.spawn(message_tick, client_entity);
+    }
+
+    Ok(())
+}
+
 /// Deserializes replicated components of `components_kind` and applies them to the `world`.
 fn apply_init_components(
     world: &mut World,
@@ -310,9 +723,30 @@ fn apply_init_components(
         let server_entity = deserialize_entity(cursor)?;
         let data_size: u16 = bincode::deserialize_from(&mut *cursor)?;
 
+        let mut spawned = false;
         let client_entity = params
             .entity_map
-            .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
+            .get_by_server_or_insert(server_entity, || {
+                spawned = true;
+                let ctx = SpawnCtx {
+                    message_tick,
+                    server_entity,
+                };
+                params
+                    .replication_fns
+                    .spawn_entity(&ctx, world, params.command_markers)
+            });
+        if world.get_entity(client_entity).is_none() {
+            trace!("ignoring data for despawned client's {client_entity:?}");
+            params.entity_map.insert_ghost(client_entity);
+            cursor.set_position(cursor.position() + data_size as u64);
+            continue;
+        }
+        if spawned {
+            params.tick_changes.spawn(message_tick, client_entity);
+        } else {
+            params.tick_changes.update(message_tick, client_entity);
+        }
 
         let world_cell = world.as_unsafe_world_cell();
         // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
@@ -323,6 +757,12 @@ fn apply_init_components(
             .entity_markers
             .read(params.command_markers, &client_entity);
 
+        if matches!(components_kind, ComponentsKind::Insert) && client_entity.contains::<Obscured>()
+        {
+            trace!("revealing {:?}", client_entity.id());
+            commands.entity(client_entity.id()).remove::<Obscured>();
+        }
+
         if let Some(mut confirmed) = client_entity.get_mut::<Confirmed>() {
             confirmed.set_last_tick(message_tick);
         } else {
@@ -334,11 +774,19 @@ fn apply_init_components(
         let end_pos = cursor.position() + data_size as u64;
         let mut components_len = 0u32;
         while cursor.position() < end_pos {
+            let start_pos = cursor.position();
             let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-            let (component_fns, rule_fns) = params.replication_fns.get(fns_id);
+            let local_id = local_fns_id(params.replication_fns, params.fns_remap, fns_id)?;
+            let (component_fns, rule_fns) = params.replication_fns.get(local_id);
+            let track_previous = params.entity_markers.need_previous() && !rule_fns.has_delta();
             match components_kind {
                 ComponentsKind::Insert => {
+                    let data_pos = cursor.position();
                     let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick);
+                    ctx.local_changes = Some(params.local_changes);
+                    if track_previous {
+                        ctx.previous = params.previous_values.take(client_entity.id(), local_id);
+                    }
 
                     // SAFETY: `rule_fns` and `component_fns` were created for the same type.
                     unsafe {
@@ -350,10 +798,35 @@ fn apply_init_components(
                             cursor,
                         )?;
                     }
+
+                    if track_previous {
+                        let bytes =
+                            &cursor.get_ref()[data_pos as usize..cursor.position() as usize];
+                        params.previous_values.insert(
+                            client_entity.id(),
+                            local_id,
+                            message_tick,
+                            bytes,
+                        );
+                    }
+
+                    if let Some(replay_log) = &mut params.replay_log {
+                        let bytes =
+                            &cursor.get_ref()[start_pos as usize..cursor.position() as usize];
+                        replay_log.record(message_tick, server_entity, fns_id, bytes);
+                    }
                 }
                 ComponentsKind::Removal => {
+                    if track_previous {
+                        params.previous_values.remove(client_entity.id(), local_id);
+                    }
                     let mut ctx = RemoveCtx::new(&mut commands, message_tick);
-                    component_fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
+                    component_fns.remove(
+                        &mut ctx,
+                        rule_fns,
+                        params.entity_markers,
+                        &mut client_entity,
+                    );
                 }
             }
             components_len += 1;
@@ -370,6 +843,65 @@ fn apply_init_components(
     Ok(())
 }
 
+/// Deserializes entities that are no longer visible and marks them with [`Obscured`].
+fn apply_hidden(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let entities_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    for _ in 0..entities_len {
+        let server_entity = deserialize_entity(cursor)?;
+        if let Some(mut client_entity) = params
+            .entity_map
+            .get_by_server(server_entity)
+            .and_then(|entity| world.get_entity_mut(entity))
+        {
+            trace!("obscuring {:?}", client_entity.id());
+            client_entity.insert(Obscured);
+        }
+    }
+
+    Ok(())
+}
+
+/// Deserializes custom sections and calls their apply functions from [`ReplicationSections`].
+fn apply_custom_sections(
+    world: &mut World,
+    cursor: &mut Cursor<&[u8]>,
+    message_tick: RepliconTick,
+) -> bincode::Result<()> {
+    let sections_len: u16 = bincode::deserialize_from(&mut *cursor)?;
+    for _ in 0..sections_len {
+        let id: u8 = bincode::deserialize_from(&mut *cursor)?;
+        let len: u64 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+        let start = cursor.position() as usize;
+        let message = *cursor.get_ref();
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| start.checked_add(len))
+            .filter(|&end| end <= message.len())
+            .ok_or_else(|| {
+                bincode::ErrorKind::Custom(format!("section length {len} exceeds message"))
+            })?;
+        let bytes = &message[start..end];
+        cursor.set_position(end as u64);
+
+        let Some(apply) = world
+            .resource::<ReplicationSections>()
+            .get(id)
+            .map(|section| section.apply)
+        else {
+            error!("received unknown section with ID {id}");
+            continue;
+        };
+        trace!("applying section with ID {id} for {message_tick:?}");
+        (apply)(world, message_tick, bytes);
+    }
+
+    Ok(())
+}
+
 /// Deserializes despawns and applies them to the `world`.
 fn apply_despawns(
     world: &mut World,
@@ -391,8 +923,16 @@ fn apply_despawns(
             .remove_by_server(server_entity)
             .and_then(|entity| world.get_entity_mut(entity))
         {
+            params
+                .tick_changes
+                .despawn(message_tick, client_entity.id());
+            params.previous_values.remove_entity(client_entity.id());
+            params
+                .entity_markers
+                .read(params.command_markers, &client_entity);
             let ctx = DespawnCtx { message_tick };
-            (params.replication_fns.despawn)(&ctx, client_entity);
+            let despawn = params.replication_fns.despawn_fn(params.entity_markers);
+            (despawn)(&ctx, client_entity);
         }
     }
 
@@ -419,6 +959,12 @@ fn apply_update_components(
             cursor.set_position(cursor.position() + data_size as u64);
             continue;
         };
+        if world.get_entity(client_entity).is_none() {
+            trace!("ignoring update for despawned client's {client_entity:?}");
+            params.entity_map.insert_ghost(client_entity);
+            cursor.set_position(cursor.position() + data_size as u64);
+            continue;
+        }
 
         let world_cell = world.as_unsafe_world_cell();
         // SAFETY: access is unique and used to obtain `EntityMut`, which is just a wrapper over `UnsafeEntityCell`.
@@ -436,7 +982,7 @@ fn apply_update_components(
         if new_entity {
             confirmed.set_last_tick(message_tick);
         } else {
-            if !params.entity_markers.need_history() {
+            if !params.entity_markers.need_history() || params.fast_forward {
                 trace!(
                     "ignoring outdated update for client's {:?}",
                     client_entity.id()
@@ -461,9 +1007,19 @@ fn apply_update_components(
         let end_pos = cursor.position() + data_size as u64;
         let mut components_count = 0u32;
         while cursor.position() < end_pos {
+            let start_pos = cursor.position();
             let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-            let (component_fns, rule_fns) = params.replication_fns.get(fns_id);
+            let local_id = local_fns_id(params.replication_fns, params.fns_remap, fns_id)?;
+            let (component_fns, rule_fns) = params.replication_fns.get(local_id);
+            // Outdated values from history aren't tracked to keep the latest one.
+            let track_previous =
+                new_entity && params.entity_markers.need_previous() && !rule_fns.has_delta();
+            let data_pos = cursor.position();
             let mut ctx = WriteCtx::new(&mut commands, params.entity_map, message_tick);
+            ctx.local_changes = Some(params.local_changes);
+            if track_previous {
+                ctx.previous = params.previous_values.take(client_entity.id(), local_id);
+            }
 
             // SAFETY: `rule_fns` and `component_fns` were created for the same type.
             unsafe {
@@ -487,6 +1043,18 @@ fn apply_update_components(
                 }
             }
 
+            if track_previous {
+                let bytes = &cursor.get_ref()[data_pos as usize..cursor.position() as usize];
+                params
+                    .previous_values
+                    .insert(client_entity.id(), local_id, message_tick, bytes);
+            }
+
+            if let Some(replay_log) = &mut params.replay_log {
+                let bytes = &cursor.get_ref()[start_pos as usize..cursor.position() as usize];
+                replay_log.record(message_tick, server_entity, fns_id, bytes);
+            }
+
             components_count += 1;
         }
 
@@ -495,12 +1063,28 @@ fn apply_update_components(
             stats.components_changed += components_count;
         }
 
+        params.tick_changes.update(message_tick, client_entity.id());
+
         params.queue.apply(world);
     }
 
     Ok(())
 }
 
+/// Translates a server network ID into client ID.
+///
+/// Uses the mapping from [`StableFnsIdsPlugin`](crate::stable_fns_ids::StableFnsIdsPlugin) if it's present.
+fn local_fns_id(
+    replication_fns: &ReplicationFns,
+    fns_remap: Option<&FnsIdRemap>,
+    network_id: FnsId,
+) -> bincode::Result<FnsId> {
+    match fns_remap {
+        Some(fns_remap) => fns_remap.get(network_id),
+        None => replication_fns.local_id(network_id),
+    }
+}
+
 /// Deserializes `entity` from compressed index and generation.
 ///
 /// For details see
@@ -527,8 +1111,74 @@ struct ReceiveParams<'a> {
     entity_markers: &'a mut EntityMarkers,
     entity_map: &'a mut ServerEntityMap,
     stats: Option<&'a mut ClientStats>,
+    replay_log: Option<&'a mut ReplayLog>,
     command_markers: &'a CommandMarkers,
     replication_fns: &'a ReplicationFns,
+    fns_remap: Option<&'a FnsIdRemap>,
+    message_hooks: Option<&'a MessageHooks>,
+    tick_changes: &'a mut TickChangesBuffer,
+    pending_init: &'a mut Option<PendingInit>,
+
+    /// See [`ApplyState::local_changes`].
+    local_changes: (Tick, Tick),
+
+    previous_values: &'a mut PreviousValues,
+
+    /// Whether [`FastForward`] is active for the current update.
+    fast_forward: bool,
+}
+
+/// State shared between systems of apply stages.
+#[derive(Resource)]
+struct ApplyState {
+    queue: CommandQueue,
+    entity_markers: EntityMarkers,
+    tick_changes: TickChangesBuffer,
+
+    /// The last received init message that is applied across stages.
+    pending_init: Option<PendingInit>,
+
+    /// Ticks after the previous [`ClientSet::Receive`] and before the current one.
+    ///
+    /// See [`WriteCtx::is_changed_locally`].
+    local_changes: (Tick, Tick),
+
+    previous_values: PreviousValues,
+}
+
+impl FromWorld for ApplyState {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            queue: Default::default(),
+            entity_markers: EntityMarkers::from_world(world),
+            tick_changes: Default::default(),
+            pending_init: None,
+            local_changes: Default::default(),
+            previous_values: Default::default(),
+        }
+    }
+}
+
+/// Partially applied [`InitMessage`](crate::server::replication_messages::InitMessage).
+struct PendingInit {
+    /// Position of the next section.
+    position: u64,
+    message_tick: RepliconTick,
+    message: Bytes,
+}
+
+/// Section of [`InitMessage`](crate::server::replication_messages::InitMessage) in the order of application.
+///
+/// [`Self::Spawns`] doesn't consume any data, it spawns entities from [`Self::Inserts`] in advance.
+#[derive(Clone, Copy, PartialEq)]
+enum InitSection {
+    Mappings,
+    Despawns,
+    Removals,
+    Spawns,
+    Inserts,
+    Hidden,
+    Custom,
 }
 
 /// Type of components replication.
@@ -554,6 +1204,40 @@ pub enum ClientSet {
     ///
     /// Runs in [`PreUpdate`].
     Receive,
+    /// Systems that apply entity mappings and despawns from the server.
+    ///
+    /// Apply stages run inside [`ClientSet::Receive`] in the declaration order, so user systems can
+    /// be scheduled between them. Removals are applied before spawns and insertions to keep
+    /// components that were removed and inserted back during the same server tick.
+    ///
+    /// Runs in [`PreUpdate`].
+    ApplyDespawns,
+    /// Systems that apply component removals from the server.
+    ///
+    /// Runs in [`PreUpdate`].
+    ApplyRemovals,
+    /// Systems that spawn new entities from the server.
+    ///
+    /// Spawned entities contain only [`Replicated`] and don't have any components from the server yet.
+    ///
+    /// Runs in [`PreUpdate`].
+    ApplySpawns,
+    /// Systems that apply component insertions from the server.
+    ///
+    /// Custom sections from [`ReplicationSections`] are also applied in this set, right after insertions.
+    ///
+    /// Runs in [`PreUpdate`].
+    ApplyInserts,
+    /// Systems that apply component updates from the server.
+    ///
+    /// Runs in [`PreUpdate`].
+    ApplyUpdates,
+    /// Systems that write interpolated and extrapolated values of replicated components.
+    ///
+    /// See [`AppInterpolationExt`](interpolation::AppInterpolationExt).
+    ///
+    /// Runs in [`PreUpdate`] after [`ClientSet::Receive`].
+    Interpolate,
     /// Systems that send data to [`RepliconClient`].
     ///
     /// Used by `bevy_replicon`.
@@ -595,6 +1279,24 @@ pub enum ClientSet {
 #[derive(Clone, Copy, Debug, Default, Deref, Resource)]
 pub struct ServerInitTick(RepliconTick);
 
+/// Last tick of applied update messages from server.
+///
+/// Unlike [`ServerInitTick`], updated when components change.
+/// But update messages are sent only for ticks with changes, so this value may lag behind the server.
+#[derive(Clone, Copy, Debug, Default, Deref, Resource)]
+pub struct ServerUpdateTick(RepliconTick);
+
+/// Returns the latest server tick reached by replication on client.
+///
+/// It's the largest of [`ServerInitTick`] and [`ServerUpdateTick`].
+pub fn replicated_tick(init_tick: ServerInitTick, update_tick: ServerUpdateTick) -> RepliconTick {
+    if *update_tick > *init_tick {
+        *update_tick
+    } else {
+        *init_tick
+    }
+}
+
 /// All cached buffered updates, used by the replicon client to align replication updates with initialization
 /// messages.
 ///
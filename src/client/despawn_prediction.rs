@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bevy::{ecs::world::Command, prelude::*};
+
+use super::{confirmed::Confirmed, replicon_client::RepliconClient, ServerInitTick};
+use crate::core::replicon_tick::RepliconTick;
+
+/// Extension for [`Commands`] to predict despawns of replicated entities on client.
+pub trait PredictedDespawnExt {
+    /**
+    Hides a replicated entity until the server confirms its despawn.
+
+    The entity is not despawned immediately. Instead, [`PredictedDespawn`] is inserted,
+    so the entity and its mapping in [`ServerEntityMap`](super::server_entity_map::ServerEntityMap)
+    stay alive and receive updates as usual. Your systems should filter such entities out with
+    `Without<PredictedDespawn>` to make them disappear for the player immediately.
+
+    If the server despawns the entity, it will be despawned on client as usual.
+    If the client receives a replication message for the entity with a tick newer than
+    [`PredictedDespawn::tick`], the server still has the entity, so the prediction is
+    considered wrong and [`PredictedDespawn`] will be removed, making the entity visible
+    again with the latest received state. If no such message arrives and the server doesn't
+    despawn the entity within [`DespawnPredictionTimeout`], it will be restored the same way.
+
+    If the client is not connected, the entity will be despawned immediately.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_systems(Update, pick_up);
+
+    /// Picks up an item without waiting for the server.
+    fn pick_up(mut commands: Commands, items: Query<Entity, (With<Item>, Without<PredictedDespawn>)>) {
+        for entity in &items {
+            commands.predicted_despawn(entity);
+        }
+    }
+
+    #[derive(Component)]
+    struct Item;
+    ```
+    **/
+    fn predicted_despawn(&mut self, entity: Entity);
+}
+
+impl PredictedDespawnExt for Commands<'_, '_> {
+    fn predicted_despawn(&mut self, entity: Entity) {
+        self.add(PredictDespawn(entity));
+    }
+}
+
+/// Command that inserts [`PredictedDespawn`] or despawns an entity if the client is not connected.
+struct PredictDespawn(Entity);
+
+impl Command for PredictDespawn {
+    fn apply(self, world: &mut World) {
+        let connected = world
+            .get_resource::<RepliconClient>()
+            .filter(|client| client.is_connected())
+            .is_some();
+
+        if !connected {
+            if let Some(entity) = world.get_entity_mut(self.0) {
+                entity.despawn_recursive();
+            }
+            return;
+        }
+
+        let init_tick = **world.resource::<ServerInitTick>();
+        let elapsed = world.resource::<Time>().elapsed();
+        let Some(mut entity) = world.get_entity_mut(self.0) else {
+            debug!("ignoring predicted despawn for missing {:?}", self.0);
+            return;
+        };
+
+        let tick = entity
+            .get::<Confirmed>()
+            .map(|confirmed| confirmed.last_tick())
+            .filter(|&tick| tick > init_tick)
+            .unwrap_or(init_tick);
+
+        debug!("predicting despawn for {:?} at {tick:?}", self.0);
+        entity.insert(PredictedDespawn { tick, elapsed });
+    }
+}
+
+/// Marks a client entity whose despawn was predicted via [`PredictedDespawnExt::predicted_despawn`].
+///
+/// Present only on client.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PredictedDespawn {
+    tick: RepliconTick,
+    elapsed: Duration,
+}
+
+impl PredictedDespawn {
+    /// Returns the last known server tick for the entity when the despawn was predicted.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the [`Time::elapsed`] when the despawn was predicted.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// Time after which a predicted despawn that wasn't confirmed by the server will be rolled back.
+///
+/// Used only as a fallback when the server doesn't send anything for the entity.
+///
+/// See also [`PredictedDespawnExt::predicted_despawn`].
+///
+/// By default set to 1 second.
+#[derive(Resource, Clone, Copy, Debug, Deref, DerefMut)]
+pub struct DespawnPredictionTimeout(pub Duration);
+
+impl Default for DespawnPredictionTimeout {
+    fn default() -> Self {
+        Self(Duration::from_secs(1))
+    }
+}
+
+/// Removes [`PredictedDespawn`] from entities that received newer data from the server
+/// or whose despawn wasn't confirmed in time.
+pub(super) fn restore_mispredicted(
+    mut commands: Commands,
+    timeout: Res<DespawnPredictionTimeout>,
+    time: Res<Time>,
+    predicted: Query<(Entity, &PredictedDespawn, Option<&Confirmed>)>,
+) {
+    for (entity, predicted_despawn, confirmed) in &predicted {
+        let updated =
+            confirmed.is_some_and(|confirmed| confirmed.last_tick() > predicted_despawn.tick);
+        if updated {
+            debug!("restoring mispredicted despawn for {entity:?} after receiving newer data");
+            commands.entity(entity).remove::<PredictedDespawn>();
+        } else if time.elapsed().saturating_sub(predicted_despawn.elapsed) >= **timeout {
+            debug!("restoring mispredicted despawn for {entity:?} after timeout");
+            commands.entity(entity).remove::<PredictedDespawn>();
+        }
+    }
+}
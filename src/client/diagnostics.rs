@@ -6,6 +6,8 @@ use bevy::{
 };
 use std::time::Duration;
 
+use super::server_entity_map::ServerEntityMap;
+
 /// Replication stats during packet processing.
 ///
 /// Flushed to Diagnostics system periodically.
@@ -66,6 +68,16 @@ impl Plugin for ClientDiagnosticsPlugin {
             Diagnostic::new(Self::BYTES)
                 .with_suffix("bytes per second")
                 .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::ENTITY_MAP_SIZE)
+                .with_suffix("mapped entities")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::ENTITY_MAP_GHOSTS)
+                .with_suffix("ghost mappings")
+                .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
         );
     }
 }
@@ -85,11 +97,23 @@ impl ClientDiagnosticsPlugin {
     pub const PACKETS: DiagnosticPath = DiagnosticPath::const_new("replication.client.packets");
     /// How many bytes of replication packets payloads per second.
     pub const BYTES: DiagnosticPath = DiagnosticPath::const_new("replication.client.bytes");
+    /// How many entities are mapped in [`ServerEntityMap`].
+    pub const ENTITY_MAP_SIZE: DiagnosticPath =
+        DiagnosticPath::const_new("replication.client.entity_map_size");
+    /// How many ghost mappings are kept in [`ServerEntityMap`].
+    ///
+    /// See [`EntityMapGc`](super::entity_map_gc::EntityMapGc).
+    pub const ENTITY_MAP_GHOSTS: DiagnosticPath =
+        DiagnosticPath::const_new("replication.client.entity_map_ghosts");
 
     /// Max diagnostic history length.
     pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
 
-    fn add_measurements(mut stats: ResMut<ClientStats>, mut diagnostics: Diagnostics) {
+    fn add_measurements(
+        mut stats: ResMut<ClientStats>,
+        mut diagnostics: Diagnostics,
+        entity_map: Res<ServerEntityMap>,
+    ) {
         diagnostics.add_measurement(&Self::ENTITY_CHANGES, || {
             if stats.packets == 0 {
                 0_f64
@@ -126,6 +150,8 @@ impl ClientDiagnosticsPlugin {
             }
         });
         diagnostics.add_measurement(&Self::PACKETS, || stats.packets as f64);
+        diagnostics.add_measurement(&Self::ENTITY_MAP_SIZE, || entity_map.len() as f64);
+        diagnostics.add_measurement(&Self::ENTITY_MAP_GHOSTS, || entity_map.ghosts_len() as f64);
         *stats = ClientStats::default();
     }
 }
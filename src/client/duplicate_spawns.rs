@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use super::server_entity_map::ServerEntityMap;
+use crate::core::{
+    replication_fns::{ctx::DespawnCtx, ReplicationFns},
+    replicon_tick::RepliconTick,
+};
+
+/**
+Configures how client resolves spawns of server entities that already have a client entity.
+
+Such duplicates could appear when a server entity is re-sent as a new spawn, for example,
+a mapping for a pre-spawned entity arrives after the server entity was already replicated
+because of a visibility flap or a resync.
+
+Each detected duplicate also emits [`DuplicateSpawn`] for diagnosis.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.insert_resource(DuplicateSpawnPolicy::Replace)
+    .add_systems(Update, report_duplicates);
+
+fn report_duplicates(mut duplicates: EventReader<DuplicateSpawn>) {
+    for duplicate in duplicates.read() {
+        error!("duplicate spawn of {:?}", duplicate.server_entity);
+    }
+}
+```
+**/
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateSpawnPolicy {
+    /// Keep the existing client entity and despawn the new one.
+    #[default]
+    Reuse,
+    /// Despawn the existing client entity and map the server entity to the new one.
+    Replace,
+    /// Map the server entity to the new client entity, but keep both alive and log a warning.
+    ///
+    /// The existing entity will no longer receive replication.
+    /// Useful to inspect duplicates during development.
+    Warn,
+}
+
+/// An event that indicates a spawn of a server entity that was already spawned on client.
+///
+/// See [`DuplicateSpawnPolicy`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateSpawn {
+    pub server_entity: Entity,
+    /// Client entity that was mapped to the server entity before.
+    pub existing_entity: Entity,
+    /// Client entity from the duplicate spawn.
+    pub new_entity: Entity,
+    /// Policy that was applied.
+    pub policy: DuplicateSpawnPolicy,
+    /// Tick of the message with the duplicate spawn.
+    pub message_tick: RepliconTick,
+}
+
+/// Resolves a duplicate according to [`DuplicateSpawnPolicy`] and emits [`DuplicateSpawn`].
+pub(super) fn resolve(
+    world: &mut World,
+    entity_map: &mut ServerEntityMap,
+    replication_fns: &ReplicationFns,
+    server_entity: Entity,
+    existing_entity: Entity,
+    new_entity: Entity,
+    message_tick: RepliconTick,
+) {
+    let policy = *world.resource::<DuplicateSpawnPolicy>();
+    let ctx = DespawnCtx { message_tick };
+    match policy {
+        DuplicateSpawnPolicy::Reuse => {
+            debug!("reusing {existing_entity:?} for duplicate spawn of {server_entity:?}, despawning {new_entity:?}");
+            (replication_fns.despawn)(&ctx, world.entity_mut(new_entity));
+        }
+        DuplicateSpawnPolicy::Replace => {
+            debug!("replacing {existing_entity:?} with {new_entity:?} for duplicate spawn of {server_entity:?}");
+            entity_map.remove_by_server(server_entity);
+            entity_map.insert(server_entity, new_entity);
+            (replication_fns.despawn)(&ctx, world.entity_mut(existing_entity));
+        }
+        DuplicateSpawnPolicy::Warn => {
+            warn!("{server_entity:?} is already spawned as {existing_entity:?}, but spawned again as {new_entity:?}");
+            entity_map.remove_by_server(server_entity);
+            entity_map.insert(server_entity, new_entity);
+        }
+    }
+
+    world.send_event(DuplicateSpawn {
+        server_entity,
+        existing_entity,
+        new_entity,
+        policy,
+        message_tick,
+    });
+}
@@ -0,0 +1,86 @@
+use bevy::{ecs::entity::Entities, prelude::*};
+
+use super::server_entity_map::ServerEntityMap;
+use crate::core::Replicated;
+
+/**
+Configures cleanup of stale entries in [`ServerEntityMap`].
+
+If a replicated entity is despawned on client before the server despawns it, its mapping is kept
+as a ghost until the server confirms the despawn. While the entity is a ghost, all received data for it is ignored,
+so the server can't accidentally spawn it again. But the confirmation may never arrive, for example,
+after a desync or if the server stopped replicating the entity without despawning it.
+To avoid leaking such mappings, the number of ghosts is limited and the least recently used are evicted.
+
+Map size could be tracked via [`ServerEntityMap::len`] and [`ServerEntityMap::ghosts_len`]
+or with [`ClientDiagnosticsPlugin`](super::diagnostics::ClientDiagnosticsPlugin).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.insert_resource(EntityMapGc {
+    max_ghosts: 256,
+    ..Default::default()
+});
+```
+**/
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct EntityMapGc {
+    /// Maximum number of ghost mappings to keep until the server confirms despawns.
+    ///
+    /// Set to 0 to remove mappings immediately after a local despawn.
+    ///
+    /// By default set to 1024.
+    pub max_ghosts: usize,
+
+    /// Clears all mappings when the client connects.
+    ///
+    /// Useful when [`ClientSet::Reset`](super::ClientSet::Reset) is disabled to keep entities after disconnect,
+    /// since their mappings are no longer valid after reconnect.
+    ///
+    /// By default set to `false`.
+    pub clear_on_connect: bool,
+}
+
+impl Default for EntityMapGc {
+    fn default() -> Self {
+        Self {
+            max_ghosts: 1024,
+            clear_on_connect: false,
+        }
+    }
+}
+
+/// Clears the map on connection if [`EntityMapGc::clear_on_connect`] is set.
+pub(super) fn clear_on_connect(gc: Res<EntityMapGc>, mut entity_map: ResMut<ServerEntityMap>) {
+    if gc.clear_on_connect && !entity_map.is_empty() {
+        debug!(
+            "clearing {} mappings from the previous connection",
+            entity_map.len()
+        );
+        entity_map.clear();
+    }
+}
+
+/// Marks mappings of locally despawned entities as ghosts and evicts the least recently used ones.
+pub(super) fn collect_ghosts(
+    mut removed_replicated: RemovedComponents<Replicated>,
+    mut entity_map: ResMut<ServerEntityMap>,
+    gc: Res<EntityMapGc>,
+    entities: &Entities,
+) {
+    for client_entity in removed_replicated.read() {
+        if !entities.contains(client_entity) && entity_map.to_server().contains_key(&client_entity)
+        {
+            debug!("keeping mapping for despawned client's {client_entity:?} as a ghost");
+            entity_map.insert_ghost(client_entity);
+        }
+    }
+
+    entity_map.evict_ghosts(gc.max_ghosts);
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+/**
+Collapses a backlog of replication messages after a long pause.
+
+When the client resumes after being suspended (for example, a mobile app in background or an inactive browser tab),
+it receives all messages from the server at once. If the number of received replication messages
+reaches [`Self::backlog_threshold`], the client fast-forwards for this update:
+- All init messages are still applied since they contain spawns, despawns, insertions and removals.
+- Only the latest update is applied for each entity, intermediate updates are not written into history
+even if the entity has markers that require it.
+- Server events registered with
+[`ServerEventAppExt::skip_on_fast_forward`](crate::network_event::server_event::ServerEventAppExt::skip_on_fast_forward)
+are discarded. All other events are considered critical and delivered.
+
+[`FastForwarded`] will be emitted for each update with fast-forward.
+
+Disabled by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_server_event::<Explosion>(ChannelKind::Unordered)
+    .skip_on_fast_forward::<Explosion>();
+
+app.world.resource_mut::<FastForward>().backlog_threshold = Some(30);
+
+/// Purely visual event, no reason to play all of them after a pause.
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Explosion;
+```
+**/
+#[derive(Resource, Default)]
+pub struct FastForward {
+    /// Minimum number of replication messages received in one update to fast-forward.
+    ///
+    /// [`None`] disables fast-forward.
+    pub backlog_threshold: Option<usize>,
+
+    /// Whether fast-forward is active for the current update.
+    active: bool,
+}
+
+impl FastForward {
+    /// Returns `true` if the client fast-forwards in the current update.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Activates fast-forward if the number of received messages reaches the threshold.
+    ///
+    /// Returns `true` if fast-forward is activated.
+    pub(super) fn update(&mut self, messages: usize) -> bool {
+        self.active = self
+            .backlog_threshold
+            .is_some_and(|threshold| messages >= threshold);
+
+        self.active
+    }
+
+    /// Deactivates fast-forward.
+    pub(super) fn reset(&mut self) {
+        self.active = false;
+    }
+}
+
+/// Returns `true` if the client fast-forwards in the current update.
+///
+/// See [`FastForward`].
+pub fn fast_forwarding(fast_forward: Option<Res<FastForward>>) -> bool {
+    fast_forward.is_some_and(|fast_forward| fast_forward.is_active())
+}
+
+/// An event indicating that the client fast-forwarded through a backlog of replication messages.
+///
+/// See [`FastForward`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct FastForwarded {
+    /// Number of replication messages received in this update.
+    pub messages: usize,
+}
+
+/// Discards all received `T` events while fast-forwarding.
+pub(crate) fn discard<T: Event>(mut events: ResMut<Events<T>>) {
+    let discarded_count = events.drain().count();
+    if discarded_count > 0 {
+        debug!(
+            "discarded {discarded_count} events `{}` due to fast-forward",
+            std::any::type_name::<T>()
+        );
+    }
+}
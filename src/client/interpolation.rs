@@ -0,0 +1,760 @@
+use std::{f32::consts::TAU, io::Cursor, mem};
+
+use bevy::{
+    ecs::{query::QueryFilter, world::EntityMut},
+    prelude::*,
+    utils::Duration,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::ClientSet;
+use crate::core::{
+    command_markers::AppMarkerExt,
+    replication_fns::{
+        ctx::{RemoveCtx, WriteCtx},
+        rule_fns::RuleFns,
+    },
+    replication_rules::AppRuleExt,
+};
+
+/// Interpolation functions for [`App`].
+pub trait AppInterpolationExt {
+    /// Same as [`AppRuleExt::replicate`], but received values are blended over time using
+    /// [`Interpolate::interpolate`].
+    ///
+    /// See also [`Self::replicate_interpolated_with`].
+    fn replicate_interpolated<C>(&mut self) -> &mut Self
+    where
+        C: Component + Interpolate + Clone + Serialize + DeserializeOwned,
+    {
+        self.replicate_interpolated_with::<C>(C::interpolate)
+    }
+
+    /**
+    Same as [`Self::replicate_interpolated`], but uses the specified function to blend values.
+
+    Useful for components that don't implement [`Interpolate`] or need a different blending,
+    such as angular interpolation.
+
+    Instead of overwriting the component, each received value becomes the new interpolation target.
+    Blending starts from the current value and takes as long as the interval between the last two
+    received values, so rendered values lag behind the server by about one update.
+
+    Replaces default command functions for the component. Functions set for markers
+    via [`AppMarkerExt::set_marker_fns`] still take precedence.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{client::interpolation, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.replicate_interpolated_with::<Heading>(interpolate_heading);
+
+    fn interpolate_heading(from: &Heading, to: &Heading, t: f32) -> Heading {
+        Heading(interpolation::interpolate_angle(from.0, to.0, t))
+    }
+
+    /// Rotation around the Z axis in radians.
+    #[derive(Component, Clone, Deserialize, Serialize)]
+    struct Heading(f32);
+    ```
+    **/
+    fn replicate_interpolated_with<C>(&mut self, blend: InterpolateFn<C>) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned;
+
+    /// Same as [`Self::interpolate_marked_with`], but uses [`Interpolate::interpolate`] to blend values.
+    fn interpolate_marked<C>(&mut self) -> &mut Self
+    where
+        C: Component + Interpolate + Clone,
+    {
+        self.interpolate_marked_with::<C>(C::interpolate)
+    }
+
+    /// Interpolates received values of `C` only on entities with [`Interpolated`].
+    ///
+    /// The component should be replicated separately, for example, with [`AppRuleExt::replicate`].
+    /// Entities without the marker receive values as is.
+    /// Can't be combined with [`Self::replicate_interpolated_with`] for the same component.
+    ///
+    /// See [`InterpolationPlugin`] for details.
+    fn interpolate_marked_with<C>(&mut self, blend: InterpolateFn<C>) -> &mut Self
+    where
+        C: Component + Clone;
+
+    /// Same as [`AppRuleExt::replicate`], but values are predicted past the last received one using
+    /// [`Extrapolate::extrapolate`] and corrected with [`Interpolate::interpolate`].
+    ///
+    /// See also [`Self::replicate_extrapolated_with`].
+    fn replicate_extrapolated<C>(&mut self) -> &mut Self
+    where
+        C: Component + Extrapolate + Interpolate + Clone + Serialize + DeserializeOwned,
+    {
+        self.replicate_extrapolated_with::<C>(C::extrapolate, C::interpolate)
+    }
+
+    /**
+    Same as [`Self::replicate_extrapolated`], but uses the specified functions to predict and blend values.
+
+    Instead of waiting for the next value like interpolation does, the component keeps changing
+    at the rate between the last two received values for up to [`ExtrapolationSettings::max_duration`].
+    This hides latency for fast-moving objects at the cost of occasional mispredictions.
+    When a new value arrives, the component is blended from its current value to the new prediction
+    over [`ExtrapolationSettings::correction_duration`] instead of snapping.
+
+    The rate is computed from the difference between received values. To use a replicated velocity
+    instead, replicate it inside the same component and ignore the previous value in `extrapolate`.
+
+    Replaces default command functions for the component. Functions set for markers
+    via [`AppMarkerExt::set_marker_fns`] still take precedence.
+
+    # Examples
+
+    ```
+    use bevy::{prelude::*, utils::Duration};
+    use bevy_replicon::{client::interpolation::Interpolate, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.replicate_extrapolated_with::<Kinematics>(extrapolate_kinematics, Kinematics::interpolate);
+
+    fn extrapolate_kinematics(
+        _previous: &Kinematics,
+        last: &Kinematics,
+        _interval: Duration,
+        elapsed: Duration,
+    ) -> Kinematics {
+        Kinematics {
+            position: last.position + last.velocity * elapsed.as_secs_f32(),
+            velocity: last.velocity,
+        }
+    }
+
+    #[derive(Component, Clone, Deserialize, Serialize)]
+    struct Kinematics {
+        position: Vec3,
+        velocity: Vec3,
+    }
+
+    impl Interpolate for Kinematics {
+        fn interpolate(&self, other: &Self, t: f32) -> Self {
+            Self {
+                position: self.position.interpolate(&other.position, t),
+                velocity: self.velocity.interpolate(&other.velocity, t),
+            }
+        }
+    }
+    ```
+    **/
+    fn replicate_extrapolated_with<C>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: InterpolateFn<C>,
+    ) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned;
+}
+
+impl AppInterpolationExt for App {
+    fn replicate_interpolated_with<C>(&mut self, blend: InterpolateFn<C>) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned,
+    {
+        self.replicate::<C>()
+            .set_command_fns(write_interpolated::<C>, remove_interpolated::<C>)
+            .insert_resource(InterpolationBlend(blend))
+            .add_systems(
+                PreUpdate,
+                interpolate::<C, ()>.in_set(ClientSet::Interpolate),
+            )
+    }
+
+    fn interpolate_marked_with<C>(&mut self, blend: InterpolateFn<C>) -> &mut Self
+    where
+        C: Component + Clone,
+    {
+        self.set_marker_fns::<Interpolated, C>(write_interpolated::<C>, remove_interpolated::<C>)
+            .insert_resource(InterpolationBlend(blend))
+            .add_systems(
+                PreUpdate,
+                interpolate::<C, With<Interpolated>>.in_set(ClientSet::Interpolate),
+            )
+    }
+
+    fn replicate_extrapolated_with<C>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: InterpolateFn<C>,
+    ) -> &mut Self
+    where
+        C: Component + Clone + Serialize + DeserializeOwned,
+    {
+        self.replicate::<C>()
+            .set_command_fns(write_extrapolated::<C>, remove_extrapolated::<C>)
+            .init_resource::<ExtrapolationSettings>()
+            .insert_resource(ExtrapolationFns { extrapolate, blend })
+            .add_systems(
+                PreUpdate,
+                extrapolate_values::<C>.in_set(ClientSet::Interpolate),
+            )
+    }
+}
+
+/// Blends the current value of `C` towards the last received one on entities matching `F`.
+fn interpolate<C: Component + Clone, F: QueryFilter>(
+    time: Res<Time>,
+    blend: Res<InterpolationBlend<C>>,
+    mut components: Query<(&mut C, &mut InterpolationBuffer<C>), F>,
+) {
+    let now = time.elapsed();
+    for (mut component, mut buffer) in &mut components {
+        if let Some(progress) = buffer.advance(&component, now, time.delta()) {
+            *component = (blend.0)(&buffer.from, &buffer.to, progress);
+        }
+    }
+}
+
+/// Predicts the value of `C` from the last received ones and blends the current value towards it.
+fn extrapolate_values<C: Component + Clone>(
+    time: Res<Time>,
+    settings: Res<ExtrapolationSettings>,
+    fns: Res<ExtrapolationFns<C>>,
+    mut components: Query<(&mut C, &mut ExtrapolationBuffer<C>)>,
+) {
+    let now = time.elapsed();
+    for (mut component, mut buffer) in &mut components {
+        if let Some(elapsed) = buffer.advance(now, time.delta(), &settings) {
+            let predicted = if buffer.interval.is_zero() {
+                buffer.last.clone()
+            } else {
+                let elapsed = elapsed.min(settings.max_duration);
+                (fns.extrapolate)(&buffer.previous, &buffer.last, buffer.interval, elapsed)
+            };
+            let correction = correction_progress(elapsed, settings.correction_duration);
+            *component = (fns.blend)(&*component, &predicted, correction);
+        }
+    }
+}
+
+/// Returns the blending factor for the current value after `elapsed` since the last received one.
+fn correction_progress(elapsed: Duration, correction_duration: Duration) -> f32 {
+    if correction_duration.is_zero() {
+        1.0
+    } else {
+        (elapsed.as_secs_f32() / correction_duration.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Inserts the first received value as is and uses the next ones as interpolation targets.
+fn write_interpolated<C: Component + Clone>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    if let Some(mut buffer) = entity.get_mut::<InterpolationBuffer<C>>() {
+        buffer.received = Some(component);
+    } else {
+        ctx.commands
+            .entity(entity.id())
+            .insert((InterpolationBuffer::new(component.clone()), component));
+    }
+
+    Ok(())
+}
+
+/// Removes component `C` and its interpolation buffer.
+fn remove_interpolated<C: Component>(ctx: &mut RemoveCtx, entity: &mut EntityMut) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<InterpolationBuffer<C>>()
+        .remove::<C>();
+}
+
+/// Inserts the first received value as is and uses the next ones for extrapolation.
+fn write_extrapolated<C: Component + Clone>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    if let Some(mut buffer) = entity.get_mut::<ExtrapolationBuffer<C>>() {
+        buffer.received = Some(component);
+    } else {
+        ctx.commands
+            .entity(entity.id())
+            .insert((ExtrapolationBuffer::new(component.clone()), component));
+    }
+
+    Ok(())
+}
+
+/// Removes component `C` and its extrapolation buffer.
+fn remove_extrapolated<C: Component>(ctx: &mut RemoveCtx, entity: &mut EntityMut) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<ExtrapolationBuffer<C>>()
+        .remove::<C>();
+}
+
+/**
+Registers [`Interpolated`] marker to interpolate components only on marked entities.
+
+Unlike [`AppInterpolationExt::replicate_interpolated`], which interpolates a component on all entities,
+components registered with [`AppInterpolationExt::interpolate_marked`] are interpolated
+only on entities with [`Interpolated`]. For example, the locally controlled player can be
+predicted while other players are interpolated.
+
+Received values are buffered in [`InterpolationBuffer`] and blended the same way as for
+[`AppInterpolationExt::replicate_interpolated_with`].
+The marker is registered with the default [`MarkerConfig`](crate::core::command_markers::MarkerConfig),
+use [`AppMarkerExt::register_marker_with`] after adding the plugin to change its priority.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.add_plugins(InterpolationPlugin)
+    .replicate::<Transform>()
+    .interpolate_marked::<Transform>()
+    .add_systems(PreUpdate, mark_remote_players.after(ClientSet::Receive));
+
+/// Interpolates all players except the local one, which is predicted.
+fn mark_remote_players(
+    mut commands: Commands,
+    players: Query<(Entity, &Player), Added<Player>>,
+    client: Res<RepliconClient>,
+) {
+    for (entity, player) in &players {
+        if Some(player.0) != client.id() {
+            commands.entity(entity).insert(Interpolated);
+        }
+    }
+}
+
+#[derive(Component)]
+struct Player(ClientId);
+```
+**/
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_marker::<Interpolated>();
+    }
+}
+
+/// Marks entities whose components registered with [`AppInterpolationExt::interpolate_marked`]
+/// should be interpolated.
+///
+/// Present only on client. Can be inserted automatically with [`AppMarkerExt::insert_marker_on`].
+#[derive(Component, Default)]
+pub struct Interpolated;
+
+/// Blending function for components registered with [`AppInterpolationExt::replicate_interpolated_with`].
+///
+/// Accepts the start value, the target value and progress in range `0.0..=1.0`.
+pub type InterpolateFn<C> = fn(&C, &C, f32) -> C;
+
+#[derive(Resource)]
+struct InterpolationBlend<C>(InterpolateFn<C>);
+
+/// Prediction function for components registered with [`AppInterpolationExt::replicate_extrapolated_with`].
+///
+/// Accepts the previous received value, the last received value, the interval between them
+/// and the time passed since the last one was received.
+pub type ExtrapolateFn<C> = fn(&C, &C, Duration, Duration) -> C;
+
+#[derive(Resource)]
+struct ExtrapolationFns<C> {
+    extrapolate: ExtrapolateFn<C>,
+    blend: InterpolateFn<C>,
+}
+
+/// Limits for components registered with [`AppInterpolationExt::replicate_extrapolated`]
+/// or [`AppInterpolationExt::replicate_extrapolated_with`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ExtrapolationSettings {
+    /// Maximum time to predict values past the last received one.
+    ///
+    /// After it passes, the component stays at the last prediction until a new value arrives.
+    ///
+    /// By default set to 250 milliseconds.
+    pub max_duration: Duration,
+
+    /// Time to blend from the current value to the new prediction after receiving a value.
+    ///
+    /// Set to [`Duration::ZERO`] to snap to predictions immediately.
+    ///
+    /// By default set to 100 milliseconds.
+    pub correction_duration: Duration,
+}
+
+impl Default for ExtrapolationSettings {
+    fn default() -> Self {
+        Self {
+            max_duration: Duration::from_millis(250),
+            correction_duration: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Interpolation state of `C` received from server.
+///
+/// Present only on client for components registered with
+/// [`AppInterpolationExt::replicate_interpolated`], [`AppInterpolationExt::replicate_interpolated_with`]
+/// or on entities with [`Interpolated`] for components registered with [`AppInterpolationExt::interpolate_marked`].
+#[derive(Component)]
+pub struct InterpolationBuffer<C> {
+    /// Value from which the interpolation starts.
+    from: C,
+
+    /// Last received value.
+    to: C,
+
+    /// Value received since the last interpolation, applied on the next one.
+    received: Option<C>,
+
+    /// Time when the last value was received.
+    ///
+    /// [`None`] until the buffer is processed for the first time.
+    last_received: Option<Duration>,
+
+    /// Interval between the last two received values.
+    interval: Duration,
+
+    /// Time passed since the last received value.
+    elapsed: Duration,
+}
+
+impl<C: Clone> InterpolationBuffer<C> {
+    fn new(value: C) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            received: None,
+            last_received: None,
+            interval: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Returns the last received value.
+    pub fn target(&self) -> &C {
+        &self.to
+    }
+
+    /// Returns the current interpolation progress in range `0.0..=1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.interval.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f32() / self.interval.as_secs_f32()).min(1.0)
+        }
+    }
+
+    /// Advances the interpolation and returns the new progress.
+    ///
+    /// Returns [`None`] if the interpolation was already finished.
+    fn advance(&mut self, current: &C, now: Duration, delta: Duration) -> Option<f32> {
+        let Some(last_received) = self.last_received else {
+            self.last_received = Some(now);
+            return None;
+        };
+
+        if let Some(received) = self.received.take() {
+            self.from = current.clone();
+            self.to = received;
+            self.interval = now - last_received;
+            self.elapsed = Duration::ZERO;
+            self.last_received = Some(now);
+        } else if self.elapsed >= self.interval {
+            return None;
+        } else {
+            self.elapsed += delta;
+        }
+
+        Some(self.progress())
+    }
+}
+
+/// Extrapolation state of `C` received from server.
+///
+/// Present only on client for components registered with
+/// [`AppInterpolationExt::replicate_extrapolated`] or [`AppInterpolationExt::replicate_extrapolated_with`].
+#[derive(Component)]
+pub struct ExtrapolationBuffer<C> {
+    /// Value received before the last one.
+    previous: C,
+
+    /// Last received value.
+    last: C,
+
+    /// Value received since the last extrapolation, applied on the next one.
+    received: Option<C>,
+
+    /// Time when the last value was received.
+    ///
+    /// [`None`] until the buffer is processed for the first time.
+    last_received: Option<Duration>,
+
+    /// Interval between the last two received values.
+    interval: Duration,
+
+    /// Time passed since the last received value.
+    elapsed: Duration,
+}
+
+impl<C: Clone> ExtrapolationBuffer<C> {
+    fn new(value: C) -> Self {
+        Self {
+            previous: value.clone(),
+            last: value,
+            received: None,
+            last_received: None,
+            interval: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Returns the last received value.
+    pub fn last(&self) -> &C {
+        &self.last
+    }
+
+    /// Advances the extrapolation and returns the time passed since the last received value.
+    ///
+    /// Returns [`None`] if both prediction and correction were already finished.
+    fn advance(
+        &mut self,
+        now: Duration,
+        delta: Duration,
+        settings: &ExtrapolationSettings,
+    ) -> Option<Duration> {
+        let Some(last_received) = self.last_received else {
+            self.last_received = Some(now);
+            return None;
+        };
+
+        let max_elapsed = settings.max_duration.max(settings.correction_duration);
+        if let Some(received) = self.received.take() {
+            self.previous = mem::replace(&mut self.last, received);
+            self.interval = now - last_received;
+            self.elapsed = Duration::ZERO;
+            self.last_received = Some(now);
+        } else if self.elapsed >= max_elapsed {
+            return None;
+        } else {
+            self.elapsed = (self.elapsed + delta).min(max_elapsed);
+        }
+
+        Some(self.elapsed)
+    }
+}
+
+/// Linear interpolation between two values.
+///
+/// Used by [`AppInterpolationExt::replicate_interpolated`].
+pub trait Interpolate {
+    /// Returns a value between `self` and `other` based on `t` in range `0.0..=1.0`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Vec2 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
+impl Interpolate for Vec3 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
+impl Interpolate for Quat {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.slerp(*other, t)
+    }
+}
+
+impl Interpolate for Transform {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.interpolate(&other.translation, t),
+            rotation: self.rotation.interpolate(&other.rotation, t),
+            scale: self.scale.interpolate(&other.scale, t),
+        }
+    }
+}
+
+/// Linear extrapolation from two values.
+///
+/// Used by [`AppInterpolationExt::replicate_extrapolated`].
+pub trait Extrapolate {
+    /// Returns a value that continues the change from `self` to `other` for `elapsed` after `other`.
+    ///
+    /// `interval` is the time it took to change from `self` to `other` and never zero.
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self;
+}
+
+impl Extrapolate for f32 {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        other + (other - self) * time_ratio(interval, elapsed)
+    }
+}
+
+impl Extrapolate for Vec2 {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        *other + (*other - *self) * time_ratio(interval, elapsed)
+    }
+}
+
+impl Extrapolate for Vec3 {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        *other + (*other - *self) * time_ratio(interval, elapsed)
+    }
+}
+
+impl Extrapolate for Quat {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        let mut delta = *other * self.inverse();
+        if delta.w < 0.0 {
+            // Rotate over the shortest arc.
+            delta = -delta;
+        }
+        let rotation = delta.to_scaled_axis() * time_ratio(interval, elapsed);
+        (Quat::from_scaled_axis(rotation) * *other).normalize()
+    }
+}
+
+impl Extrapolate for Transform {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        Self {
+            translation: self
+                .translation
+                .extrapolate(&other.translation, interval, elapsed),
+            rotation: self
+                .rotation
+                .extrapolate(&other.rotation, interval, elapsed),
+            scale: self.scale.extrapolate(&other.scale, interval, elapsed),
+        }
+    }
+}
+
+/// Returns how many times `elapsed` is longer than `interval`.
+fn time_ratio(interval: Duration, elapsed: Duration) -> f32 {
+    elapsed.as_secs_f32() / interval.as_secs_f32()
+}
+
+/// Interpolates between two angles in radians over the shortest arc.
+pub fn interpolate_angle(from: f32, to: f32, t: f32) -> f32 {
+    let difference = (to - from).rem_euclid(TAU);
+    let shortest = if difference > TAU / 2.0 {
+        difference - TAU
+    } else {
+        difference
+    };
+
+    from + shortest * t
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn angle() {
+        assert!((interpolate_angle(0.0, PI / 2.0, 0.5) - PI / 4.0).abs() < 1e-5);
+        assert!((interpolate_angle(PI / 2.0, 0.0, 0.5) - PI / 4.0).abs() < 1e-5);
+
+        let wrapped = interpolate_angle(0.1, TAU - 0.1, 0.5);
+        assert!(wrapped.abs() < 1e-5, "should go through zero instead of PI");
+    }
+
+    #[test]
+    fn progress() {
+        let mut buffer = InterpolationBuffer::new(0.0);
+        assert_eq!(buffer.advance(&0.0, Duration::ZERO, Duration::ZERO), None);
+
+        buffer.received = Some(1.0);
+        let interval = Duration::from_millis(100);
+        assert_eq!(buffer.advance(&0.0, interval, interval), Some(0.0));
+        assert_eq!(
+            buffer.advance(&0.0, interval * 3 / 2, interval / 2),
+            Some(0.5)
+        );
+        assert_eq!(buffer.advance(&0.5, interval * 2, interval / 2), Some(1.0));
+        assert_eq!(buffer.advance(&1.0, interval * 3, interval), None);
+    }
+
+    #[test]
+    fn extrapolation() {
+        let interval = Duration::from_millis(100);
+        assert_eq!(1.0_f32.extrapolate(&2.0, interval, interval / 2), 2.5);
+        assert_eq!(
+            Vec2::ZERO.extrapolate(&Vec2::X, interval, interval * 2),
+            Vec2::X * 3.0
+        );
+
+        let from = Quat::from_rotation_z(0.0);
+        let to = Quat::from_rotation_z(0.1);
+        let rotation = from.extrapolate(&to, interval, interval);
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_z(0.2), 1e-5));
+    }
+
+    #[test]
+    fn extrapolation_elapsed() {
+        let settings = ExtrapolationSettings {
+            max_duration: Duration::from_millis(200),
+            correction_duration: Duration::from_millis(100),
+        };
+        let mut buffer = ExtrapolationBuffer::new(0.0);
+        assert_eq!(
+            buffer.advance(Duration::ZERO, Duration::ZERO, &settings),
+            None
+        );
+
+        buffer.received = Some(1.0);
+        let interval = Duration::from_millis(100);
+        assert_eq!(
+            buffer.advance(interval, interval, &settings),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(buffer.previous, 0.0);
+        assert_eq!(buffer.last, 1.0);
+        assert_eq!(buffer.interval, interval);
+
+        assert_eq!(
+            buffer.advance(interval * 2, interval, &settings),
+            Some(interval)
+        );
+        assert_eq!(
+            buffer.advance(interval * 4, interval * 2, &settings),
+            Some(settings.max_duration),
+            "elapsed time should be limited"
+        );
+        assert_eq!(buffer.advance(interval * 5, interval, &settings), None);
+    }
+}
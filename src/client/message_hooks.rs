@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+Functions that will be called around the application of each replication message on client.
+
+Useful for systems that need to bracket a whole server message instead of individual component writes,
+for example, to take physics snapshots.
+
+Hooks are called for both init and update messages with the server tick of the message.
+All changes from the message are applied to the world before calling end hooks.
+Resources used for receiving, such as [`RepliconClient`](super::replicon_client::RepliconClient)
+or [`ServerEntityMap`](super::server_entity_map::ServerEntityMap), are temporarily
+removed from the world and not available inside hooks.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::replicon_tick::RepliconTick, prelude::*};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+let mut hooks = app.world.resource_mut::<MessageHooks>();
+hooks.on_message_begin(begin_snapshot);
+hooks.on_message_end(end_snapshot);
+
+fn begin_snapshot(world: &mut World, tick: RepliconTick) {
+    // ...
+}
+
+fn end_snapshot(world: &mut World, tick: RepliconTick) {
+    // ...
+}
+```
+**/
+#[derive(Resource, Default)]
+pub struct MessageHooks {
+    begin: Vec<MessageHookFn>,
+    end: Vec<MessageHookFn>,
+}
+
+impl MessageHooks {
+    /// Registers a function that will be called before applying a message.
+    pub fn on_message_begin(&mut self, hook: MessageHookFn) {
+        self.begin.push(hook);
+    }
+
+    /// Registers a function that will be called after applying a message.
+    pub fn on_message_end(&mut self, hook: MessageHookFn) {
+        self.end.push(hook);
+    }
+
+    /// Calls all begin hooks in the registration order.
+    pub(super) fn begin(&self, world: &mut World, tick: RepliconTick) {
+        for hook in &self.begin {
+            (hook)(world, tick);
+        }
+    }
+
+    /// Calls all end hooks in the registration order.
+    pub(super) fn end(&self, world: &mut World, tick: RepliconTick) {
+        for hook in &self.end {
+            (hook)(world, tick);
+        }
+    }
+}
+
+/// Signature of message hooks.
+pub type MessageHookFn = fn(&mut World, RepliconTick);
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+/**
+Marks a replicated entity that is no longer visible to this client on server.
+
+Inserted only with [`RevealPolicy::Incremental`](crate::server::RevealPolicy::Incremental).
+The entity keeps its last received state and doesn't receive updates until it becomes
+visible again, after which this component will be removed.
+
+Your systems should filter such entities out with `Without<Obscured>` if outdated state shouldn't be displayed.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn hide_obscured(mut entities: Query<&mut Visibility, (With<Replicated>, Added<Obscured>)>) {
+    for mut visibility in &mut entities {
+        *visibility = Visibility::Hidden;
+    }
+}
+```
+**/
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Obscured;
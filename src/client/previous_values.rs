@@ -0,0 +1,53 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashMap};
+
+use crate::core::{replication_fns::FnsId, replicon_tick::RepliconTick};
+
+/// Serialized server values of components that were written last, along with their message ticks.
+///
+/// Tracked only for entities with markers that need previous values,
+/// see [`MarkerConfig::need_previous`](crate::core::command_markers::MarkerConfig::need_previous).
+#[derive(Default)]
+pub(crate) struct PreviousValues(EntityHashMap<HashMap<FnsId, (RepliconTick, Vec<u8>)>>);
+
+impl PreviousValues {
+    /// Takes the previous value of a component to pass it into the writing function.
+    pub(crate) fn take(
+        &mut self,
+        entity: Entity,
+        fns_id: FnsId,
+    ) -> Option<(RepliconTick, Vec<u8>)> {
+        self.0
+            .get_mut(&entity)
+            .and_then(|components| components.remove(&fns_id))
+    }
+
+    /// Stores the written value of a component.
+    pub(crate) fn insert(
+        &mut self,
+        entity: Entity,
+        fns_id: FnsId,
+        message_tick: RepliconTick,
+        bytes: &[u8],
+    ) {
+        self.0
+            .entry(entity)
+            .or_default()
+            .insert(fns_id, (message_tick, bytes.to_vec()));
+    }
+
+    /// Removes the value of a removed component.
+    pub(crate) fn remove(&mut self, entity: Entity, fns_id: FnsId) {
+        if let Some(components) = self.0.get_mut(&entity) {
+            components.remove(&fns_id);
+        }
+    }
+
+    /// Removes all values of a despawned entity.
+    pub(crate) fn remove_entity(&mut self, entity: Entity) {
+        self.0.remove(&entity);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bytes::Bytes;
 
+use super::upstream_budget::UpstreamBudget;
 use crate::core::ClientId;
 
 /// Stores information about a client independent from the messaging backend.
@@ -27,6 +28,9 @@ pub struct RepliconClient {
 
     /// List of sent messages and their channels since the last tick.
     sent_messages: Vec<(u8, Bytes)>,
+
+    /// Function to pass messages to the messaging backend from [`Self::flush_now`].
+    flush_fn: Option<ClientFlushFn>,
 }
 
 impl RepliconClient {
@@ -139,6 +143,14 @@ impl RepliconClient {
         }
     }
 
+    /// Returns an iterator over sent messages and their channels without removing them.
+    ///
+    /// Used for testing.
+    #[cfg(feature = "test_utils")]
+    pub(crate) fn iter_sent(&self) -> impl Iterator<Item = &(u8, Bytes)> {
+        self.sent_messages.iter()
+    }
+
     /// Removes all sent messages, returning them as an iterator with channel.
     ///
     /// Should be called only from the messaging backend.
@@ -146,6 +158,42 @@ impl RepliconClient {
         self.sent_messages.drain(..)
     }
 
+    /// Sets a function that passes a message to the messaging backend immediately.
+    ///
+    /// Should be called only from the messaging backend.
+    /// Without it, [`Self::flush_now`] does nothing.
+    pub fn set_flush_fn(&mut self, flush_fn: impl FnMut(u8, Bytes) + Send + Sync + 'static) {
+        self.flush_fn = Some(Box::new(flush_fn));
+    }
+
+    /// Hands all messages sent so far to the messaging backend immediately instead of waiting for
+    /// [`ClientSet::SendPackets`](super::ClientSet::SendPackets).
+    ///
+    /// Messages pass through [`UpstreamBudget`] first, so messages deferred from previous updates are flushed
+    /// before new ones and messages that don't fit into the budget stay deferred.
+    /// Client events are serialized in [`ClientSet::Send`](super::ClientSet::Send),
+    /// so only messages already passed to [`Self::send`] are flushed.
+    ///
+    /// Does nothing if the messaging backend didn't set a function via [`Self::set_flush_fn`].
+    /// See also [`RepliconServer::flush_now`](crate::server::replicon_server::RepliconServer::flush_now).
+    pub fn flush_now(&mut self, budget: &mut UpstreamBudget) {
+        if self.flush_fn.is_none() {
+            trace!("ignoring flush because the messaging backend doesn't support it");
+            return;
+        }
+
+        budget.limit(self);
+
+        let Some(flush_fn) = &mut self.flush_fn else {
+            return;
+        };
+
+        trace!("flushing {} messages", self.sent_messages.len());
+        for (channel_id, message) in self.sent_messages.drain(..) {
+            (flush_fn)(channel_id, message);
+        }
+    }
+
     /// Adds a message from the server to the list of received messages.
     ///
     /// Should be called only from the messaging backend.
@@ -179,3 +227,8 @@ pub enum RepliconClientStatus {
     /// Needed only for users to access ID independent from messaging library.
     Connected { client_id: Option<ClientId> },
 }
+
+/// Signature of functions that pass messages to the messaging backend on client.
+///
+/// See [`RepliconClient::set_flush_fn`].
+pub type ClientFlushFn = Box<dyn FnMut(u8, Bytes) + Send + Sync>;
@@ -1,13 +1,28 @@
+use std::collections::BTreeMap;
+
 use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::hashbrown::hash_map::Entry};
 
 /// Maps server entities to client entities and vice versa.
 ///
 /// If [`ClientSet::Reset`](crate::client::ClientSet) is disabled, then this needs to be cleaned up manually
-/// via [`Self::remove_by_client`] or [`Self::clear`].
+/// via [`Self::remove_by_client`] or [`Self::clear`], or automatically with
+/// [`EntityMapGc::clear_on_connect`](super::entity_map_gc::EntityMapGc::clear_on_connect).
+///
+/// Mappings of client entities that were despawned locally are kept as ghosts until the server
+/// confirms the despawn, see [`EntityMapGc`](super::entity_map_gc::EntityMapGc).
 #[derive(Default, Resource)]
 pub struct ServerEntityMap {
     server_to_client: EntityHashMap<Entity>,
     client_to_server: EntityHashMap<Entity>,
+
+    /// Client entities from the map that no longer exist, mapped to their last use.
+    ghosts: EntityHashMap<u64>,
+
+    /// Ghosts from [`Self::ghosts`] keyed by their last use, from least to most recently used.
+    ghosts_by_use: BTreeMap<u64, Entity>,
+
+    /// Use counter for [`Self::ghosts`].
+    next_use: u64,
 }
 
 impl ServerEntityMap {
@@ -66,6 +81,7 @@ impl ServerEntityMap {
         let client_entity = self.server_to_client.remove(&server_entity);
         if let Some(client_entity) = client_entity {
             self.client_to_server.remove(&client_entity);
+            self.remove_ghost(client_entity);
         }
         client_entity
     }
@@ -77,10 +93,53 @@ impl ServerEntityMap {
         let server_entity = self.client_to_server.remove(&client_entity);
         if let Some(server_entity) = server_entity {
             self.server_to_client.remove(&server_entity);
+            self.remove_ghost(client_entity);
         }
         server_entity
     }
 
+    /// Marks a mapped client entity that no longer exists as a ghost or marks an existing ghost as recently used.
+    pub(super) fn insert_ghost(&mut self, client_entity: Entity) {
+        self.remove_ghost(client_entity);
+        self.ghosts.insert(client_entity, self.next_use);
+        self.ghosts_by_use.insert(self.next_use, client_entity);
+        self.next_use += 1;
+    }
+
+    /// Removes the least recently used ghosts with their mappings until their number is within `max_ghosts`.
+    pub(super) fn evict_ghosts(&mut self, max_ghosts: usize) {
+        while self.ghosts.len() > max_ghosts {
+            let (_, client_entity) = self.ghosts_by_use.pop_first().unwrap();
+            self.ghosts.remove(&client_entity);
+            if let Some(server_entity) = self.client_to_server.remove(&client_entity) {
+                debug!("evicting ghost mapping from {server_entity:?} to {client_entity:?}");
+                self.server_to_client.remove(&server_entity);
+            }
+        }
+    }
+
+    fn remove_ghost(&mut self, client_entity: Entity) {
+        if let Some(last_use) = self.ghosts.remove(&client_entity) {
+            self.ghosts_by_use.remove(&last_use);
+        }
+    }
+
+    /// Returns the number of mappings.
+    pub fn len(&self) -> usize {
+        self.server_to_client.len()
+    }
+
+    /// Returns `true` if there are no mappings.
+    pub fn is_empty(&self) -> bool {
+        self.server_to_client.is_empty()
+    }
+
+    /// Returns the number of mappings whose client entities were despawned locally,
+    /// but the server hasn't confirmed their despawn yet.
+    pub fn ghosts_len(&self) -> usize {
+        self.ghosts.len()
+    }
+
     #[inline]
     pub fn to_client(&self) -> &EntityHashMap<Entity> {
         &self.server_to_client
@@ -95,5 +154,7 @@ impl ServerEntityMap {
     pub fn clear(&mut self) {
         self.client_to_server.clear();
         self.server_to_client.clear();
+        self.ghosts.clear();
+        self.ghosts_by_use.clear();
     }
 }
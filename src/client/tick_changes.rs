@@ -0,0 +1,121 @@
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+A summary of replication changes applied on client for a single server tick.
+
+Emitted once per applied tick after all messages for it received in this update were applied.
+Useful for coarse-grained reactions, such as rebuilding navigation or refreshing UI,
+without change detection over all replicated components.
+
+Events are emitted in the order of their ticks.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn log_changes(mut tick_changes: EventReader<TickChangesApplied>) {
+    for changes in tick_changes.read() {
+        info!(
+            "{:?}: {} spawned, {} despawned, {} updated",
+            changes.tick,
+            changes.spawned.len(),
+            changes.despawned.len(),
+            changes.updated
+        );
+    }
+}
+```
+**/
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TickChangesApplied {
+    /// Server tick of the applied changes.
+    pub tick: RepliconTick,
+
+    /// Client entities that were spawned or mapped from pre-spawned entities.
+    pub spawned: Vec<Entity>,
+
+    /// Client entities that were despawned.
+    pub despawned: Vec<Entity>,
+
+    /// Number of existing entities whose components were inserted, changed or removed.
+    ///
+    /// Entities from [`Self::spawned`] are not counted.
+    pub updated: usize,
+}
+
+/// Changes collected during receive for each tick until they are emitted as [`TickChangesApplied`].
+#[derive(Default)]
+pub(crate) struct TickChangesBuffer(Vec<BufferedChanges>);
+
+impl TickChangesBuffer {
+    /// Registers a tick as applied even if it doesn't contain any changes.
+    pub(super) fn begin(&mut self, tick: RepliconTick) {
+        self.get_mut(tick);
+    }
+
+    pub(super) fn spawn(&mut self, tick: RepliconTick, entity: Entity) {
+        let buffered = self.get_mut(tick);
+        buffered.touched.insert(entity);
+        buffered.changes.spawned.push(entity);
+    }
+
+    pub(super) fn despawn(&mut self, tick: RepliconTick, entity: Entity) {
+        self.get_mut(tick).changes.despawned.push(entity);
+    }
+
+    /// Counts an entity as updated if it wasn't spawned or updated in this tick before.
+    pub(super) fn update(&mut self, tick: RepliconTick, entity: Entity) {
+        let buffered = self.get_mut(tick);
+        if buffered.touched.insert(entity) {
+            buffered.changes.updated += 1;
+        }
+    }
+
+    /// Emits all collected changes.
+    pub(super) fn send(&mut self, world: &mut World) {
+        if self.0.is_empty() {
+            return;
+        }
+
+        world.send_event_batch(self.0.drain(..).map(|buffered| buffered.changes));
+    }
+
+    /// Returns changes for a tick, inserting an empty entry if it doesn't exist.
+    ///
+    /// Maintains sorting by tick in ascending order.
+    fn get_mut(&mut self, tick: RepliconTick) -> &mut BufferedChanges {
+        let index = self
+            .0
+            .partition_point(|buffered| buffered.changes.tick < tick);
+        if self.0.get(index).map(|buffered| buffered.changes.tick) != Some(tick) {
+            self.0.insert(index, BufferedChanges::new(tick));
+        }
+
+        &mut self.0[index]
+    }
+}
+
+struct BufferedChanges {
+    changes: TickChangesApplied,
+
+    /// Spawned and updated entities to count each entity only once.
+    touched: EntityHashSet,
+}
+
+impl BufferedChanges {
+    fn new(tick: RepliconTick) -> Self {
+        Self {
+            changes: TickChangesApplied {
+                tick,
+                spawned: Default::default(),
+                despawned: Default::default(),
+                updated: 0,
+            },
+            touched: Default::default(),
+        }
+    }
+}
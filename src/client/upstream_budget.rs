@@ -0,0 +1,159 @@
+use std::{cmp::Reverse, mem};
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::replicon_client::RepliconClient;
+use crate::core::replicon_channels::ReplicationChannel;
+
+/**
+Limits the number of bytes sent from client per update.
+
+Messages that don't fit into the budget are deferred to the next updates instead of being sent in a burst,
+for example, after a frame hitch. Messages are sent in the order of their channel priorities,
+channels with the same priority preserve the sending order.
+Once a message doesn't fit, all messages after it are deferred too to keep the order within channels.
+A single message that exceeds the budget is sent alone.
+
+Replication acknowledgments are never deferred, but count towards the budget.
+
+Unlimited by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{network_event::client_event::ClientEventChannel, prelude::*};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_client_event::<Chat>(ChannelKind::Ordered)
+    .add_client_event::<Shoot>(ChannelKind::Ordered);
+app.finish(); // Channel IDs are assigned when plugins are finished.
+
+let shoot_channel = *app.world.resource::<ClientEventChannel<Shoot>>();
+let mut budget = app.world.resource_mut::<UpstreamBudget>();
+budget.set_max_bytes(Some(1200));
+budget.set_priority(shoot_channel, 1); // Shots will be sent before chat messages.
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Chat(String);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Shoot;
+```
+**/
+#[derive(Resource, Default)]
+pub struct UpstreamBudget {
+    max_bytes: Option<usize>,
+
+    /// Priorities for client channels.
+    ///
+    /// Channels without an entry have priority 0.
+    priorities: HashMap<u8, u32>,
+
+    /// Messages that didn't fit into the budget with their channels.
+    deferred: Vec<(u8, Bytes)>,
+
+    /// Bytes sent during the current update, including flushed messages.
+    bytes: usize,
+
+    /// Indicates that a message other than acknowledgments was sent during the current update.
+    sent_any: bool,
+}
+
+impl UpstreamBudget {
+    /// Sets the maximum number of bytes per update.
+    ///
+    /// [`None`] disables the limit, all deferred messages will be sent on the next update.
+    pub fn set_max_bytes(&mut self, max_bytes: Option<usize>) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Returns the maximum number of bytes per update.
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Sets priority for a client channel.
+    ///
+    /// Messages from channels with higher priority are sent first.
+    /// For events, you can pass [`ClientEventChannel<T>`](crate::network_event::client_event::ClientEventChannel).
+    pub fn set_priority<I: Into<u8>>(&mut self, channel_id: I, priority: u32) {
+        self.priorities.insert(channel_id.into(), priority);
+    }
+
+    /// Returns priority for a client channel.
+    pub fn priority<I: Into<u8>>(&self, channel_id: I) -> u32 {
+        self.priorities
+            .get(&channel_id.into())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of messages waiting to be sent.
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+
+    /// Discards all deferred messages.
+    pub fn clear(&mut self) {
+        self.deferred.clear();
+    }
+
+    /// Takes sent messages from [`RepliconClient`] and sends back deferred and new messages that fit into the budget.
+    ///
+    /// Also called from [`RepliconClient::flush_now`], so the budget is shared with flushed messages.
+    pub(super) fn limit(&mut self, client: &mut RepliconClient) {
+        if self.max_bytes.is_none() && self.deferred.is_empty() {
+            return;
+        }
+
+        let mut messages = mem::take(&mut self.deferred);
+        messages.extend(client.drain_sent());
+        messages.sort_by_key(|&(channel_id, _)| {
+            Reverse((is_acks(channel_id), self.priority(channel_id)))
+        });
+
+        let mut deferred = Vec::new();
+        for (channel_id, message) in messages {
+            let acks = is_acks(channel_id);
+            let exceeds = self
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes + message.len() > max_bytes);
+            if acks || (deferred.is_empty() && (!exceeds || !self.sent_any)) {
+                self.bytes += message.len();
+                self.sent_any |= !acks;
+                client.send(channel_id, message);
+            } else {
+                deferred.push((channel_id, message));
+            }
+        }
+
+        if !deferred.is_empty() {
+            trace!(
+                "deferring {} messages to fit into {} bytes",
+                deferred.len(),
+                self.bytes
+            );
+        }
+        self.deferred = deferred;
+    }
+}
+
+/// Re-sends messages from [`RepliconClient`] according to [`UpstreamBudget`] and starts a new budget period.
+pub(super) fn limit_sent(mut client: ResMut<RepliconClient>, mut budget: ResMut<UpstreamBudget>) {
+    budget.limit(&mut client);
+    budget.bytes = 0;
+    budget.sent_any = false;
+}
+
+fn is_acks(channel_id: u8) -> bool {
+    channel_id == u8::from(ReplicationChannel::Init)
+}
+
+/// Discards deferred messages on disconnect.
+pub(super) fn reset(mut budget: ResMut<UpstreamBudget>) {
+    budget.clear();
+}
@@ -0,0 +1,200 @@
+use bevy::{app::AppLabel, ecs::event::ManualEventReader, prelude::*};
+
+use crate::single_player;
+
+/**
+Extension for [`App`] to connect to multiple servers at once.
+
+Replication state, registered events and [`RepliconClient`](crate::client::replicon_client::RepliconClient)
+are global for a world, so each connection runs in its own world stored in a [`SubApp`]
+with the [`ClientEndpoint`] label. This way all endpoints have independent event registrations
+and can use different messaging backends or protocols.
+
+Events are routed between the main world and endpoints after each main world update,
+right before updating endpoints. Send [`ToEndpoint<T>`] in the main world to deliver `T` to the specified endpoint,
+and read [`FromEndpoint<T>`] to receive `T` emitted in endpoints. Received events appear in the main world
+one update later because endpoints are updated after it.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const LOBBY: ClientEndpoint = ClientEndpoint(0);
+const GAME: ClientEndpoint = ClientEndpoint(1);
+
+let mut app = App::new();
+app.add_plugins(MinimalPlugins)
+    .add_client_endpoint(LOBBY, lobby_setup)
+    .add_client_endpoint(GAME, game_setup)
+    .route_client_event::<Ping>()
+    .add_systems(Update, ping_both);
+
+fn lobby_setup(app: &mut App) {
+    app.add_client_event::<Ping>(ChannelKind::Ordered);
+    // Add messaging backend for the lobby server here...
+}
+
+fn game_setup(app: &mut App) {
+    app.add_client_event::<Ping>(ChannelKind::Ordered);
+    // Add messaging backend for the game server here...
+}
+
+fn ping_both(mut ping_events: EventWriter<ToEndpoint<Ping>>) {
+    ping_events.send(ToEndpoint {
+        endpoint: LOBBY,
+        event: Ping,
+    });
+    ping_events.send(ToEndpoint {
+        endpoint: GAME,
+        event: Ping,
+    });
+}
+
+#[derive(Clone, Event, Deserialize, Serialize, TypePath)]
+struct Ping;
+```
+**/
+pub trait ClientEndpointAppExt {
+    /// Adds a [`SubApp`] for a connection labeled with the endpoint.
+    ///
+    /// [`TimePlugin`](bevy::time::TimePlugin) and [`RepliconPlugins`](crate::RepliconPlugins) are added to the endpoint app before calling `setup`.
+    /// Use `setup` to register replication rules, events and the messaging backend for this connection.
+    fn add_client_endpoint(&mut self, endpoint: ClientEndpoint, setup: fn(&mut App)) -> &mut Self;
+
+    /// Routes [`ToEndpoint<T>`] from the main world as `T` to endpoints.
+    ///
+    /// `T` should be registered in endpoints, usually as a client event.
+    /// Events are cloned for routing because the same events are read for each endpoint.
+    fn route_client_event<T: Event + Clone>(&mut self) -> &mut Self;
+
+    /// Routes `T` from endpoints as [`FromEndpoint<T>`] to the main world.
+    ///
+    /// `T` should be registered in endpoints, usually as a server event.
+    /// Events are drained from endpoints, so they won't be visible for systems inside endpoints.
+    fn route_server_event<T: Event>(&mut self) -> &mut Self;
+}
+
+impl ClientEndpointAppExt for App {
+    fn add_client_endpoint(&mut self, endpoint: ClientEndpoint, setup: fn(&mut App)) -> &mut Self {
+        let mut sub_app = single_player::client_sub_app(setup);
+        sub_app.set_extract(move |main_world, endpoint_world| {
+            route_events(main_world, endpoint_world, endpoint)
+        });
+
+        self.init_resource::<EndpointRoutes>()
+            .insert_sub_app(endpoint, sub_app);
+
+        self
+    }
+
+    fn route_client_event<T: Event + Clone>(&mut self) -> &mut Self {
+        self.add_event::<ToEndpoint<T>>()
+            .init_resource::<EndpointRoutes>()
+            .world_mut()
+            .resource_mut::<EndpointRoutes>()
+            .0
+            .push(send_to_endpoint::<T>);
+
+        self
+    }
+
+    fn route_server_event<T: Event>(&mut self) -> &mut Self {
+        self.add_event::<FromEndpoint<T>>()
+            .init_resource::<EndpointRoutes>()
+            .world_mut()
+            .resource_mut::<EndpointRoutes>()
+            .0
+            .push(receive_from_endpoint::<T>);
+
+        self
+    }
+}
+
+/// Calls all registered routes for an endpoint.
+///
+/// Called by Bevy after each update of the main world and before updating the endpoint.
+fn route_events(main_world: &mut World, endpoint_world: &mut World, endpoint: ClientEndpoint) {
+    main_world.resource_scope(|main_world, routes: Mut<EndpointRoutes>| {
+        for route in &routes.0 {
+            (route)(main_world, endpoint_world, endpoint);
+        }
+    });
+}
+
+/// Sends `T` from unread [`ToEndpoint<T>`] in the main world to the endpoint.
+///
+/// Each endpoint keeps its own reader in its world since all endpoints read the same events.
+fn send_to_endpoint<T: Event + Clone>(
+    main_world: &mut World,
+    endpoint_world: &mut World,
+    endpoint: ClientEndpoint,
+) {
+    let routed_events = main_world.resource::<Events<ToEndpoint<T>>>();
+    endpoint_world.init_resource::<RoutedReader<T>>();
+    endpoint_world.resource_scope(|endpoint_world, mut reader: Mut<RoutedReader<T>>| {
+        for routed in reader
+            .0
+            .read(routed_events)
+            .filter(|routed| routed.endpoint == endpoint)
+        {
+            endpoint_world.send_event(routed.event.clone());
+        }
+    });
+}
+
+/// Drains `T` from the endpoint and sends them as [`FromEndpoint<T>`] to the main world.
+fn receive_from_endpoint<T: Event>(
+    main_world: &mut World,
+    endpoint_world: &mut World,
+    endpoint: ClientEndpoint,
+) {
+    let mut events = endpoint_world.resource_mut::<Events<T>>();
+    let mut routed_events = main_world.resource_mut::<Events<FromEndpoint<T>>>();
+    for event in events.drain() {
+        routed_events.send(FromEndpoint { endpoint, event });
+    }
+}
+
+/// Routing functions registered via [`ClientEndpointAppExt`].
+#[derive(Resource, Default)]
+struct EndpointRoutes(Vec<RouteFn>);
+
+/// Reader for [`ToEndpoint<T>`] from the main world, stored inside an endpoint world.
+#[derive(Resource)]
+struct RoutedReader<T: Event>(ManualEventReader<ToEndpoint<T>>);
+
+impl<T: Event> Default for RoutedReader<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// Signature of routing functions.
+type RouteFn = fn(&mut World, &mut World, ClientEndpoint);
+
+/// Handle of a connection added with [`ClientEndpointAppExt::add_client_endpoint`].
+///
+/// Also used as a label for the endpoint's [`SubApp`], so the endpoint world can be accessed via [`App::sub_app`].
+#[derive(AppLabel, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ClientEndpoint(pub u8);
+
+/// An event that will be sent as `T` to the specified endpoint.
+///
+/// See [`ClientEndpointAppExt::route_client_event`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct ToEndpoint<T> {
+    pub endpoint: ClientEndpoint,
+    pub event: T,
+}
+
+/// An event indicating that `T` was emitted in an endpoint.
+///
+/// See [`ClientEndpointAppExt::route_server_event`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct FromEndpoint<T> {
+    pub endpoint: ClientEndpoint,
+    pub event: T,
+}
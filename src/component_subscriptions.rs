@@ -0,0 +1,197 @@
+use std::{
+    any::{self, TypeId},
+    mem,
+};
+
+use bevy::{prelude::*, utils::HashSet};
+use bincode::{DefaultOptions, Options};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_fns::ReplicationFns,
+        replicon_channels::{ChannelKind, KeyedChannels},
+        Replicated,
+    },
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, ServerSet,
+    },
+};
+
+/// Sends client subscriptions to optional components to the server.
+///
+/// Added automatically on the first registration of an optional component,
+/// see [`RuleFns::with_optional`](crate::core::replication_fns::rule_fns::RuleFns::with_optional).
+pub(crate) struct SubscriptionPlugin;
+
+impl Plugin for SubscriptionPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_client(
+            app.world_mut(),
+            SubscriptionChannel::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.insert_resource(SubscriptionChannel(id)),
+        );
+
+        app.init_resource::<ComponentSubscriptions>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_subscriptions
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (remove_unsubscribed, send_subscriptions)
+                    .chain()
+                    .in_set(ClientSet::Send)
+                    .run_if(client_connected),
+            );
+    }
+}
+
+/// Receives subscriptions from clients and stores them in [`ConnectedClients`].
+fn receive_subscriptions(
+    mut server: ResMut<RepliconServer>,
+    mut connected_clients: ResMut<ConnectedClients>,
+    replication_fns: Res<ReplicationFns>,
+    channel: Res<SubscriptionChannel>,
+    names: Res<ClientNames>,
+) {
+    for (client_id, message) in server.receive(channel.0) {
+        let type_names: Vec<String> = match DefaultOptions::new().deserialize(&message) {
+            Ok(type_names) => type_names,
+            Err(e) => {
+                debug!(
+                    "unable to deserialize subscriptions from {}: {e}",
+                    names.label(client_id)
+                );
+                continue;
+            }
+        };
+
+        let Some(client) = connected_clients.get_client_mut(client_id) else {
+            debug!(
+                "ignoring subscriptions from disconnected {}",
+                names.label(client_id)
+            );
+            continue;
+        };
+
+        let subscriptions = type_names
+            .iter()
+            .flat_map(|type_name| replication_fns.fns_ids_by_type_name(type_name))
+            .filter(|&fns_id| {
+                let (_, rule_fns) = replication_fns.get(fns_id);
+                rule_fns.optional()
+            })
+            .collect();
+        client.set_subscriptions(subscriptions);
+    }
+}
+
+/// Removes components from replicated entities after unsubscribing from them.
+///
+/// The server stops sending them, so the last received values would be stale otherwise.
+fn remove_unsubscribed(world: &mut World) {
+    let mut subscriptions = world.resource_mut::<ComponentSubscriptions>();
+    let unsubscribed = mem::take(&mut subscriptions.unsubscribed);
+    let unsubscribed: Vec<_> = unsubscribed
+        .into_iter()
+        .filter_map(|type_id| world.components().get_id(type_id))
+        .collect();
+    if unsubscribed.is_empty() {
+        return;
+    }
+
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<Replicated>>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        let mut entity = world.entity_mut(entity);
+        for &component_id in &unsubscribed {
+            if entity.contains_id(component_id) {
+                entity.remove_by_id(component_id);
+            }
+        }
+    }
+}
+
+/// Sends all subscriptions to the server if they changed.
+fn send_subscriptions(
+    mut client: ResMut<RepliconClient>,
+    mut subscriptions: ResMut<ComponentSubscriptions>,
+    channel: Res<SubscriptionChannel>,
+) {
+    if !subscriptions.changed {
+        return;
+    }
+    subscriptions.changed = false;
+
+    let type_names: Vec<_> = subscriptions.subscribed.iter().collect();
+    let message = DefaultOptions::new()
+        .serialize(&type_names)
+        .expect("subscriptions should be serializable");
+    client.send(channel.0, message);
+}
+
+/// Marks subscriptions as changed to resend them after reconnecting.
+fn reset(mut subscriptions: ResMut<ComponentSubscriptions>) {
+    subscriptions.changed = !subscriptions.subscribed.is_empty();
+}
+
+/**
+Subscriptions of the client to optional components.
+
+Optional components are sent only to clients that subscribed to them,
+see [`AppRuleExt::replicate_optional`](crate::core::replication_rules::AppRuleExt::replicate_optional).
+Subscriptions can be changed at any time and are sent to the server with other client messages.
+On subscription the server sends current values of the component for all visible entities.
+On unsubscription the component is removed from all replicated entities on client.
+
+Subscriptions persist across reconnects and are sent again on each connection.
+**/
+#[derive(Resource, Default)]
+pub struct ComponentSubscriptions {
+    /// Type names of subscribed components.
+    subscribed: HashSet<&'static str>,
+
+    /// Components that should be removed from replicated entities.
+    unsubscribed: HashSet<TypeId>,
+
+    /// Indicates that subscriptions should be sent to the server.
+    changed: bool,
+}
+
+impl ComponentSubscriptions {
+    /// Subscribes to an optional component.
+    pub fn subscribe<C: Component>(&mut self) {
+        if self.subscribed.insert(any::type_name::<C>()) {
+            self.unsubscribed.remove(&TypeId::of::<C>());
+            self.changed = true;
+        }
+    }
+
+    /// Unsubscribes from an optional component.
+    pub fn unsubscribe<C: Component>(&mut self) {
+        if self.subscribed.remove(any::type_name::<C>()) {
+            self.unsubscribed.insert(TypeId::of::<C>());
+            self.changed = true;
+        }
+    }
+
+    /// Returns `true` if subscribed to an optional component.
+    pub fn is_subscribed<C: Component>(&self) -> bool {
+        self.subscribed.contains(any::type_name::<C>())
+    }
+}
+
+/// ID of the client channel for subscriptions.
+#[derive(Resource, Clone, Copy, TypePath)]
+struct SubscriptionChannel(u8);
@@ -1,17 +1,24 @@
 pub mod command_markers;
 pub mod common_conditions;
+pub mod entity_containers;
+pub mod protocol_validation;
+pub mod replay_log;
 pub mod replication_fns;
 pub mod replication_rules;
+pub mod replication_sections;
 pub mod replicon_channels;
 pub mod replicon_tick;
+pub(crate) mod update_acks;
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use command_markers::CommandMarkers;
+use command_markers::{CommandMarkers, SpawnMarkers};
+use protocol_validation::ProtocolValidation;
 use replication_fns::ReplicationFns;
 use replication_rules::ReplicationRules;
-use replicon_channels::RepliconChannels;
+use replication_sections::ReplicationSections;
+use replicon_channels::{KeyedChannels, RepliconChannels};
 
 pub struct RepliconCorePlugin;
 
@@ -19,9 +26,36 @@ impl Plugin for RepliconCorePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Replicated>()
             .init_resource::<RepliconChannels>()
+            .init_resource::<KeyedChannels>()
             .init_resource::<ReplicationFns>()
             .init_resource::<ReplicationRules>()
-            .init_resource::<CommandMarkers>();
+            .init_resource::<ReplicationSections>()
+            .init_resource::<CommandMarkers>()
+            .init_resource::<SpawnMarkers>()
+            .init_resource::<ProtocolValidation>()
+            .add_systems(PreStartup, KeyedChannels::assign_ids);
+    }
+
+    fn finish(&self, app: &mut App) {
+        KeyedChannels::assign_ids(app.world_mut());
+
+        let validation = app.world().resource::<ProtocolValidation>();
+        let errors = validation.validate(app.world());
+        if errors.is_empty() {
+            return;
+        }
+
+        let mut message = format!("found {} protocol mistakes:", errors.len());
+        for error in errors {
+            message.push_str("\n- ");
+            message.push_str(&error);
+        }
+
+        if validation.strict {
+            panic!("{message}");
+        } else {
+            error!("{message}");
+        }
     }
 }
 
@@ -33,6 +67,13 @@ pub type Replication = Replicated;
 #[reflect(Component)]
 pub struct Replicated;
 
+/// Client that owns an entity.
+///
+/// Used to send private components only to their owner,
+/// see [`AppRuleExt::replicate_to_owner`](replication_rules::AppRuleExt::replicate_to_owner).
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct Owner(pub ClientId);
+
 /// Unique client ID.
 ///
 /// Could be a client or a dual server-client.
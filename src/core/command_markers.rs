@@ -1,9 +1,18 @@
-use std::cmp::Reverse;
-
-use bevy::{ecs::component::ComponentId, prelude::*};
-
-use super::replication_fns::command_fns::{RemoveFn, WriteFn};
-use crate::core::replication_fns::ReplicationFns;
+use std::{any::TypeId, cmp::Reverse};
+
+use bevy::{
+    ecs::{component::ComponentId, entity::EntityHashMap},
+    prelude::*,
+};
+
+use super::{
+    common_conditions::client_connected,
+    replication_fns::{
+        command_fns::{RemoveFn, WriteFn},
+        DespawnFn, SpawnFn,
+    },
+};
+use crate::{client::ClientSet, core::replication_fns::ReplicationFns, Replicated};
 
 /// Marker-based functions for [`App`].
 ///
@@ -22,6 +31,9 @@ pub trait AppMarkerExt {
     ///
     /// This function registers markers with default [`MarkerConfig`].
     /// See also [`Self::register_marker_with`].
+    ///
+    /// Markers can be registered at any time, including after plugins are built.
+    /// Registering an already registered marker replaces its configuration.
     fn register_marker<M: Component>(&mut self) -> &mut Self;
 
     /// Same as [`Self::register_marker`], but also accepts marker configuration.
@@ -36,6 +48,8 @@ pub trait AppMarkerExt {
     [`default_remove`](super::replication_fns::command_fns::default_remove).
     See also [`Self::set_command_fns`].
 
+    If the marker wasn't registered, it will be registered with default [`MarkerConfig`].
+
     # Examples
 
     In this example we write all received updates for [`Transform`] into user's
@@ -119,6 +133,133 @@ pub trait AppMarkerExt {
     /// [`default_remove`](super::replication_fns::command_fns::default_remove).
     /// See also [`Self::set_marker_fns`].
     fn set_command_fns<C: Component>(&mut self, write: WriteFn<C>, remove: RemoveFn) -> &mut Self;
+
+    /**
+    Associates a despawn function with a marker.
+
+    If this marker is present on an entity and its priority is the highest among markers
+    with despawn functions, then this function will be called when the server despawns the entity
+    instead of [`ReplicationFns::despawn`].
+
+    The entity is already unmapped from its server entity when the function is called,
+    so it's safe to keep it around as a regular client entity.
+
+    # Examples
+
+    Play a death animation for ragdolls instead of despawning them immediately:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::ctx::DespawnCtx, prelude::*};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.register_marker::<Ragdollable>()
+        .set_marker_despawn::<Ragdollable>(start_ragdoll);
+
+    /// Keeps the entity and lets user systems despawn it after the animation.
+    fn start_ragdoll(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
+        entity.remove::<Replicated>().insert(Ragdoll::default());
+    }
+
+    /// If this marker is present on an entity, it will turn into a ragdoll after despawn on server.
+    ///
+    /// Present only on client.
+    #[derive(Component)]
+    struct Ragdollable;
+
+    /// Death sequence state.
+    #[derive(Component, Default)]
+    struct Ragdoll {
+        elapsed: f32,
+    }
+    ```
+    **/
+    fn set_marker_despawn<M: Component>(&mut self, despawn: DespawnFn) -> &mut Self;
+
+    /**
+    Associates a spawn function with a marker.
+
+    Client entities don't exist before spawning, so markers for spawning are assigned
+    to server entities in advance via [`SpawnMarkers`]. If this marker is assigned to a server entity
+    and its priority is the highest among assigned markers with spawn functions, then this function
+    will be called to create the client entity instead of [`ReplicationFns::spawn`].
+
+    [`Replicated`] is inserted automatically on the returned entity, but the marker isn't.
+
+    # Examples
+
+    Take entities for projectiles from a pool. The server reserves an entity for a projectile,
+    announces it and spawns it on the next tick, so the announcement is received before the entity:
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::{command_markers::SpawnMarkers, replication_fns::ctx::SpawnCtx},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.init_resource::<ProjectilePool>()
+        .add_server_event::<Fired>(ChannelKind::Ordered)
+        .set_marker_spawn::<Projectile>(spawn_projectile)
+        .add_systems(PreUpdate, read_fired.after(ClientSet::Receive));
+
+    /// Assigns the marker to announced projectiles before they are replicated.
+    fn read_fired(mut fired_events: EventReader<Fired>, mut spawn_markers: ResMut<SpawnMarkers>) {
+        for event in fired_events.read() {
+            spawn_markers.insert::<Projectile>(event.server_entity);
+        }
+    }
+
+    fn spawn_projectile(_ctx: &SpawnCtx, world: &mut World) -> Entity {
+        match world.resource_mut::<ProjectilePool>().0.pop() {
+            Some(entity) => entity,
+            None => world.spawn(Projectile).id(),
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct Fired {
+        server_entity: Entity,
+    }
+
+    #[derive(Component)]
+    struct Projectile;
+
+    #[derive(Resource, Default)]
+    struct ProjectilePool(Vec<Entity>);
+    ```
+    **/
+    fn set_marker_spawn<M: Component>(&mut self, spawn: SpawnFn) -> &mut Self;
+
+    /**
+    Automatically inserts marker `M` on client entities when component `C` is replicated to them.
+
+    Replaces systems that insert markers for entities with [`Added<C>`].
+    The marker is inserted right after [`ClientSet::ApplyInserts`], so the initial value of `C`
+    is written without the marker, but all following updates respect it.
+    The marker doesn't have to be registered, but it's usually combined with [`Self::set_marker_fns`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.register_marker::<Interpolated>()
+        .insert_marker_on::<Interpolated, Transform>();
+
+    /// Marks entities whose updates should be interpolated.
+    #[derive(Component, Default)]
+    struct Interpolated;
+    ```
+    **/
+    fn insert_marker_on<M: Component + Default, C: Component>(&mut self) -> &mut Self;
 }
 
 impl AppMarkerExt for App {
@@ -127,16 +268,7 @@ impl AppMarkerExt for App {
     }
 
     fn register_marker_with<M: Component>(&mut self, config: MarkerConfig) -> &mut Self {
-        let component_id = self.world_mut().init_component::<M>();
-        let mut command_markers = self.world_mut().resource_mut::<CommandMarkers>();
-        let marker_id = command_markers.insert(CommandMarker {
-            component_id,
-            config,
-        });
-
-        let mut replicaton_fns = self.world_mut().resource_mut::<ReplicationFns>();
-        replicaton_fns.register_marker(marker_id);
-
+        register_marker::<M>(self.world_mut(), config);
         self
     }
 
@@ -145,14 +277,7 @@ impl AppMarkerExt for App {
         write: WriteFn<C>,
         remove: RemoveFn,
     ) -> &mut Self {
-        let component_id = self.world_mut().init_component::<M>();
-        let command_markers = self.world_mut().resource::<CommandMarkers>();
-        let marker_id = command_markers.marker_id(component_id);
-        self.world_mut()
-            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
-                replication_fns.set_marker_fns::<C>(world, marker_id, write, remove);
-            });
-
+        set_marker_fns::<M, C>(self.world_mut(), write, remove);
         self
     }
 
@@ -164,43 +289,283 @@ impl AppMarkerExt for App {
 
         self
     }
+
+    fn set_marker_despawn<M: Component>(&mut self, despawn: DespawnFn) -> &mut Self {
+        let marker_id = marker_id_or_register::<M>(self.world_mut());
+        self.world_mut()
+            .resource_mut::<ReplicationFns>()
+            .set_marker_despawn(marker_id, despawn);
+
+        self
+    }
+
+    fn set_marker_spawn<M: Component>(&mut self, spawn: SpawnFn) -> &mut Self {
+        let marker_id = marker_id_or_register::<M>(self.world_mut());
+        self.world_mut()
+            .resource_mut::<ReplicationFns>()
+            .set_marker_spawn(marker_id, spawn);
+
+        self
+    }
+
+    fn insert_marker_on<M: Component + Default, C: Component>(&mut self) -> &mut Self {
+        self.add_systems(
+            PreUpdate,
+            insert_marker::<M, C>
+                .after(ClientSet::ApplyInserts)
+                .before(ClientSet::ApplyUpdates)
+                .run_if(client_connected),
+        )
+    }
+}
+
+/**
+Marker registration and configuration changes for [`Commands`].
+
+Useful to enable features only when they are needed, such as history recording during a killcam.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::command_markers::MarkerConfig, prelude::*};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.register_marker_with::<ComponentsHistory>(MarkerConfig {
+    need_history: true,
+    ..Default::default()
+})
+.add_systems(Startup, |mut commands: Commands| {
+    commands.set_marker_enabled::<ComponentsHistory>(false)
+})
+.add_systems(Update, toggle_history);
+
+fn toggle_history(mut commands: Commands, killcam: Query<(), Added<Killcam>>) {
+    if !killcam.is_empty() {
+        commands.set_marker_enabled::<ComponentsHistory>(true);
+    }
+}
+
+#[derive(Component)]
+struct ComponentsHistory;
+
+#[derive(Component)]
+struct Killcam;
+```
+**/
+pub trait MarkerCommandsExt {
+    /// Same as [`AppMarkerExt::register_marker_with`], but can be used at runtime.
+    fn register_marker_with<M: Component>(&mut self, config: MarkerConfig);
+
+    /// Same as [`AppMarkerExt::set_marker_fns`], but can be used at runtime.
+    fn set_marker_fns<M: Component, C: Component>(&mut self, write: WriteFn<C>, remove: RemoveFn);
+
+    /// Enables or disables a marker.
+    ///
+    /// Disabled markers are treated as absent on all entities and aren't checked during replication.
+    /// All markers are enabled by default.
+    ///
+    /// Registers the marker with default [`MarkerConfig`] if it wasn't registered.
+    fn set_marker_enabled<M: Component>(&mut self, enabled: bool);
+
+    /// Changes [`MarkerConfig::priority`] of a marker.
+    ///
+    /// Registers the marker with default [`MarkerConfig`] if it wasn't registered.
+    fn set_marker_priority<M: Component>(&mut self, priority: usize);
+}
+
+impl MarkerCommandsExt for Commands<'_, '_> {
+    fn register_marker_with<M: Component>(&mut self, config: MarkerConfig) {
+        self.add(move |world: &mut World| {
+            register_marker::<M>(world, config);
+        });
+    }
+
+    fn set_marker_fns<M: Component, C: Component>(&mut self, write: WriteFn<C>, remove: RemoveFn) {
+        self.add(move |world: &mut World| set_marker_fns::<M, C>(world, write, remove));
+    }
+
+    fn set_marker_enabled<M: Component>(&mut self, enabled: bool) {
+        self.add(move |world: &mut World| {
+            let marker_id = marker_id_or_register::<M>(world);
+            world
+                .resource_mut::<CommandMarkers>()
+                .set_enabled(marker_id, enabled);
+        });
+    }
+
+    fn set_marker_priority<M: Component>(&mut self, priority: usize) {
+        self.add(move |world: &mut World| {
+            let marker_id = marker_id_or_register::<M>(world);
+            world
+                .resource_mut::<CommandMarkers>()
+                .set_priority(marker_id, priority);
+        });
+    }
+}
+
+/// Registers `M` as a marker or replaces its config if it's already registered.
+fn register_marker<M: Component>(world: &mut World, config: MarkerConfig) -> CommandMarkerIndex {
+    let component_id = world.init_component::<M>();
+    let mut command_markers = world.resource_mut::<CommandMarkers>();
+    if let Some(marker_id) = command_markers.marker_id(component_id) {
+        command_markers.set_config(marker_id, config);
+        return marker_id;
+    }
+
+    let marker_id = command_markers.insert(CommandMarker {
+        component_id,
+        config,
+        enabled: true,
+    });
+    world
+        .resource_mut::<ReplicationFns>()
+        .register_marker(marker_id);
+
+    marker_id
+}
+
+/// Returns ID of marker `M`, registering it with default [`MarkerConfig`] if needed.
+fn marker_id_or_register<M: Component>(world: &mut World) -> CommandMarkerIndex {
+    let component_id = world.init_component::<M>();
+    match world.resource::<CommandMarkers>().marker_id(component_id) {
+        Some(marker_id) => marker_id,
+        None => register_marker::<M>(world, MarkerConfig::default()),
+    }
+}
+
+/// Associates command functions with marker `M` for component `C`.
+fn set_marker_fns<M: Component, C: Component>(
+    world: &mut World,
+    write: WriteFn<C>,
+    remove: RemoveFn,
+) {
+    let marker_id = marker_id_or_register::<M>(world);
+    world.resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+        replication_fns.set_marker_fns::<C>(world, marker_id, write, remove);
+    });
+}
+
+/// Inserts marker `M` on replicated entities that just received `C`.
+fn insert_marker<M: Component + Default, C: Component>(
+    mut commands: Commands,
+    entities: Query<Entity, (Added<C>, With<Replicated>, Without<M>)>,
+) {
+    for entity in &entities {
+        commands.entity(entity).insert(M::default());
+    }
 }
 
 /// Registered markers that override command functions if present.
 #[derive(Resource, Default)]
-pub(crate) struct CommandMarkers(Vec<CommandMarker>);
+pub(crate) struct CommandMarkers {
+    /// Markers in registration order, indexed by [`CommandMarkerIndex`].
+    markers: Vec<CommandMarker>,
 
-impl CommandMarkers {
-    /// Inserts a new marker, maintaining sorting by their priority in descending order.
+    /// Marker indices sorted by priority in descending order.
     ///
-    /// May invalidate previously returned [`CommandMarkerIndex`] due to sorting.
+    /// Markers with the same priority are ordered from the last registered.
+    order: Vec<CommandMarkerIndex>,
+}
+
+impl CommandMarkers {
+    /// Inserts a new marker and returns its index.
     ///
     /// Use [`ReplicationFns::register_marker`] to register a slot for command functions for this marker.
     fn insert(&mut self, marker: CommandMarker) -> CommandMarkerIndex {
-        let key = Reverse(marker.config.priority);
-        let index = self
-            .0
-            .binary_search_by_key(&key, |marker| Reverse(marker.config.priority))
-            .unwrap_or_else(|index| index);
+        let marker_id = CommandMarkerIndex(self.markers.len());
+        self.markers.push(marker);
+        self.order.push(marker_id);
+        self.sort();
 
-        self.0.insert(index, marker);
-
-        CommandMarkerIndex(index)
+        marker_id
     }
 
-    /// Returns marker ID from its component ID.
-    fn marker_id(&self, component_id: ComponentId) -> CommandMarkerIndex {
-        let index = self
-            .0
+    /// Returns marker ID from its component ID if it was registered.
+    fn marker_id(&self, component_id: ComponentId) -> Option<CommandMarkerIndex> {
+        self.markers
             .iter()
             .position(|marker| marker.component_id == component_id)
-            .unwrap_or_else(|| panic!("marker {component_id:?} wasn't registered"));
+            .map(CommandMarkerIndex)
+    }
+
+    /// Replaces configuration of a marker.
+    fn set_config(&mut self, marker_id: CommandMarkerIndex, config: MarkerConfig) {
+        self.markers[*marker_id].config = config;
+        self.sort();
+    }
+
+    /// Enables or disables a marker.
+    fn set_enabled(&mut self, marker_id: CommandMarkerIndex, enabled: bool) {
+        self.markers[*marker_id].enabled = enabled;
+    }
+
+    /// Changes priority of a marker.
+    fn set_priority(&mut self, marker_id: CommandMarkerIndex, priority: usize) {
+        self.markers[*marker_id].config.priority = priority;
+        self.sort();
+    }
 
-        CommandMarkerIndex(index)
+    /// Returns enabled markers with their component IDs, sorted by priority in descending order.
+    pub(super) fn iter_enabled(
+        &self,
+    ) -> impl Iterator<Item = (CommandMarkerIndex, ComponentId)> + '_ {
+        self.order.iter().filter_map(|&marker_id| {
+            let marker = &self.markers[*marker_id];
+            marker.enabled.then_some((marker_id, marker.component_id))
+        })
     }
 
-    pub(super) fn iter_require_history(&self) -> impl Iterator<Item = bool> + '_ {
-        self.0.iter().map(|marker| marker.config.need_history)
+    /// Returns `true` if the marker needs to process old updates.
+    pub(super) fn need_history(&self, marker_id: CommandMarkerIndex) -> bool {
+        self.markers[*marker_id].config.need_history
+    }
+
+    fn sort(&mut self) {
+        let markers = &self.markers;
+        self.order.sort_unstable_by_key(|&marker_id| {
+            Reverse((markers[*marker_id].config.priority, marker_id.0))
+        });
+    }
+}
+
+/// Markers assigned to server entities whose client entities are not spawned yet.
+///
+/// Used to select spawn functions set via [`AppMarkerExt::set_marker_spawn`].
+/// Markers for a server entity are removed when its client entity is spawned.
+/// Cleared on client disconnect.
+#[derive(Resource, Default)]
+pub struct SpawnMarkers(EntityHashMap<Vec<TypeId>>);
+
+impl SpawnMarkers {
+    /// Assigns marker `M` to a server entity.
+    pub fn insert<M: Component>(&mut self, server_entity: Entity) {
+        let markers = self.0.entry(server_entity).or_default();
+        let type_id = TypeId::of::<M>();
+        if !markers.contains(&type_id) {
+            markers.push(type_id);
+        }
+    }
+
+    /// Removes all markers assigned to a server entity.
+    pub fn remove(&mut self, server_entity: Entity) {
+        self.0.remove(&server_entity);
+    }
+
+    /// Returns `true` if no markers are assigned.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes all markers assigned to a server entity and returns them.
+    pub(super) fn take(&mut self, server_entity: Entity) -> Option<Vec<TypeId>> {
+        self.0.remove(&server_entity)
+    }
+
+    /// Removes all assigned markers.
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
     }
 }
 
@@ -213,6 +578,11 @@ struct CommandMarker {
 
     /// User-registered configuration.
     config: MarkerConfig,
+
+    /// Whether the marker is checked during replication.
+    ///
+    /// See [`MarkerCommandsExt::set_marker_enabled`].
+    enabled: bool,
 }
 
 /// Parameters for a marker.
@@ -235,12 +605,25 @@ pub struct MarkerConfig {
     ///
     /// By default set to `false`.
     pub need_history: bool,
+
+    /// Represents whether a marker needs the previously written server value of a component.
+    ///
+    /// If this field is set to `true`, the client will keep the last written data for each component
+    /// of entities with this marker. Writing functions can obtain it with
+    /// [`RuleFns::deserialize_previous`](super::replication_fns::rule_fns::RuleFns::deserialize_previous),
+    /// which is useful for reconciliation without mirroring server values in user components.
+    ///
+    /// Not tracked for components with [`RuleFns::with_delta`](super::replication_fns::rule_fns::RuleFns::with_delta).
+    ///
+    /// By default set to `false`.
+    pub need_previous: bool,
 }
 
 /// Stores which markers are present on an entity.
 pub(crate) struct EntityMarkers {
-    markers: Vec<bool>,
+    markers: Vec<CommandMarkerIndex>,
     need_history: bool,
+    need_previous: bool,
 }
 
 impl EntityMarkers {
@@ -251,21 +634,25 @@ impl EntityMarkers {
     ) {
         self.markers.clear();
         self.need_history = false;
+        self.need_previous = false;
 
         let entity = entity.into();
-        for marker in &markers.0 {
-            let contains = entity.contains_id(marker.component_id);
-            self.markers.push(contains);
-            if contains && marker.config.need_history {
-                self.need_history = true;
+        for &marker_id in &markers.order {
+            let marker = &markers.markers[*marker_id];
+            if marker.enabled && entity.contains_id(marker.component_id) {
+                self.markers.push(marker_id);
+                if marker.config.need_history {
+                    self.need_history = true;
+                }
+                if marker.config.need_previous {
+                    self.need_previous = true;
+                }
             }
         }
     }
 
-    /// Returns a slice of which markers are present on an entity.
-    ///
-    /// Indices corresponds markers in to [`CommandMarkers`].
-    pub(super) fn markers(&self) -> &[bool] {
+    /// Returns markers present on an entity, sorted by priority in descending order.
+    pub(super) fn markers(&self) -> &[CommandMarkerIndex] {
         &self.markers
     }
 
@@ -273,22 +660,29 @@ impl EntityMarkers {
     pub(crate) fn need_history(&self) -> bool {
         self.need_history
     }
+
+    /// Returns `true` if an entity has at least one marker that needs previous values.
+    pub(crate) fn need_previous(&self) -> bool {
+        self.need_previous
+    }
 }
 
 impl FromWorld for EntityMarkers {
     fn from_world(world: &mut World) -> Self {
         let markers = world.resource::<CommandMarkers>();
         Self {
-            markers: Vec::with_capacity(markers.0.len()),
+            markers: Vec::with_capacity(markers.markers.len()),
             need_history: false,
+            need_previous: false,
         }
     }
 }
 
-/// Can be obtained from [`CommandMarkers::insert`].
+/// Index of a marker in registration order.
 ///
-/// Shouldn't be stored anywhere since insertion may invalidate old indices.
-#[derive(Clone, Copy, Deref, Debug)]
+/// Can be obtained from [`CommandMarkers::insert`].
+/// Stays valid after registration of other markers and priority changes.
+#[derive(Clone, Copy, Deref, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(super) struct CommandMarkerIndex(usize);
 
 #[cfg(test)]
@@ -299,15 +693,26 @@ mod tests {
     use crate::core::replication_fns::{command_fns, ReplicationFns};
 
     #[test]
-    #[should_panic]
-    fn non_registered_marker() {
+    fn late_registration() {
         let mut app = App::new();
         app.init_resource::<CommandMarkers>()
             .init_resource::<ReplicationFns>()
             .set_marker_fns::<DummyMarkerA, DummyComponent>(
                 command_fns::default_write,
                 command_fns::default_remove::<DummyComponent>,
-            );
+            )
+            .register_marker::<DummyMarkerB>()
+            .register_marker_with::<DummyMarkerA>(MarkerConfig {
+                need_history: true,
+                ..Default::default()
+            });
+
+        let markers = app.world.resource::<CommandMarkers>();
+        assert_eq!(markers.markers.len(), 2, "markers shouldn't be duplicated");
+        assert!(
+            markers.need_history(CommandMarkerIndex(0)),
+            "config should be replaced on registration"
+        );
     }
 
     #[test]
@@ -326,13 +731,27 @@ mod tests {
             })
             .register_marker::<DummyMarkerD>();
 
-        let markers = app.world_mut().resource::<CommandMarkers>();
-        let priorities: Vec<_> = markers
-            .0
-            .iter()
-            .map(|marker| marker.config.priority)
-            .collect();
-        assert_eq!(priorities, [2, 1, 0, 0]);
+        let markers = app.world.resource::<CommandMarkers>();
+        let order: Vec<_> = markers.order.iter().map(|marker_id| **marker_id).collect();
+        assert_eq!(order, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn priority_change() {
+        let mut app = App::new();
+        app.init_resource::<CommandMarkers>()
+            .init_resource::<ReplicationFns>()
+            .register_marker::<DummyMarkerA>()
+            .register_marker_with::<DummyMarkerB>(MarkerConfig {
+                priority: 1,
+                ..Default::default()
+            });
+
+        let mut markers = app.world.resource_mut::<CommandMarkers>();
+        markers.set_priority(CommandMarkerIndex(0), 2);
+
+        let order: Vec<_> = markers.order.iter().map(|marker_id| **marker_id).collect();
+        assert_eq!(order, [0, 1], "indices should stay the same");
     }
 
     #[derive(Component)]
@@ -0,0 +1,292 @@
+/*!
+Helpers for replicating containers with entities inside components.
+
+# Examples
+
+```
+use bevy::{ecs::entity::{EntityHashSet, MapEntities}, prelude::*, utils::HashMap};
+use bevy_replicon::{
+    core::entity_containers::{self, MapEntityContainer},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_mapped::<Inventory>();
+
+#[derive(Component, Deserialize, Serialize)]
+struct Inventory {
+    #[serde(with = "entity_containers::compact_entities")]
+    items: Vec<Entity>,
+    #[serde(with = "entity_containers::compact_entities")]
+    equipped: EntityHashSet,
+    #[serde(with = "entity_containers::compact_keys")]
+    counts: HashMap<Entity, u32>,
+}
+
+impl MapEntities for Inventory {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.items.map_container(entity_mapper);
+        self.equipped.map_container(entity_mapper);
+        self.counts.map_container(entity_mapper);
+    }
+}
+```
+*/
+
+use std::{
+    fmt::{self, Formatter},
+    hash::BuildHasher,
+    marker::PhantomData,
+    mem,
+};
+
+use bevy::{
+    ecs::entity::EntityMapper,
+    prelude::*,
+    utils::hashbrown::{HashMap, HashSet},
+};
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Maps all entities inside a container.
+///
+/// Intended to be called from [`MapEntities`](bevy::ecs::entity::MapEntities) implementations for components
+/// with container fields.
+pub trait MapEntityContainer {
+    fn map_container<M: EntityMapper>(&mut self, entity_mapper: &mut M);
+}
+
+impl MapEntityContainer for Option<Entity> {
+    fn map_container<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Some(entity) = self {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl MapEntityContainer for Vec<Entity> {
+    fn map_container<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for entity in self {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl<S: BuildHasher + Default> MapEntityContainer for HashSet<Entity, S> {
+    fn map_container<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        *self = mem::take(self)
+            .into_iter()
+            .map(|entity| entity_mapper.map_entity(entity))
+            .collect();
+    }
+}
+
+/// Maps keys.
+///
+/// If values contain entities, they need to be mapped separately.
+impl<T, S: BuildHasher + Default> MapEntityContainer for HashMap<Entity, T, S> {
+    fn map_container<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        *self = mem::take(self)
+            .into_iter()
+            .map(|(entity, value)| (entity_mapper.map_entity(entity), value))
+            .collect();
+    }
+}
+
+/// Collection of entities that can be serialized with [`compact_entities`].
+pub trait EntityCollection: FromIterator<Entity> {
+    fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_;
+}
+
+impl EntityCollection for Vec<Entity> {
+    fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl<S: BuildHasher + Default> EntityCollection for HashSet<Entity, S> {
+    fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.iter().copied()
+    }
+}
+
+/// Map with entity keys that can be serialized with [`compact_keys`].
+pub trait EntityKeyedMap<T>: FromIterator<(Entity, T)> {
+    fn iter_entries<'a>(&'a self) -> impl Iterator<Item = (Entity, &'a T)>
+    where
+        T: 'a;
+}
+
+impl<T, S: BuildHasher + Default> EntityKeyedMap<T> for HashMap<Entity, T, S> {
+    fn iter_entries<'a>(&'a self) -> impl Iterator<Item = (Entity, &'a T)>
+    where
+        T: 'a,
+    {
+        self.iter().map(|(&entity, value)| (entity, value))
+    }
+}
+
+/// Serializes entity index and generation separately.
+///
+/// Takes much less space with varint encoding than [`Entity`] serialized as [`u64`].
+struct CompactEntity(Entity);
+
+impl Serialize for CompactEntity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Generations start from 1.
+        (self.0.index(), self.0.generation() - 1).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactEntity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (index, generation): (u32, u32) = Deserialize::deserialize(deserializer)?;
+        let generation = generation
+            .checked_add(1)
+            .ok_or_else(|| de::Error::custom("entity generation should fit into `u32`"))?;
+        let bits = (generation as u64) << 32 | index as u64;
+        Entity::try_from_bits(bits)
+            .map(CompactEntity)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Compact serialization for containers of entities, such as [`Vec<Entity>`] or
+/// [`EntityHashSet`](bevy::ecs::entity::EntityHashSet).
+///
+/// Use with `#[serde(with = "entity_containers::compact_entities")]`.
+pub mod compact_entities {
+    use super::*;
+
+    pub fn serialize<C: EntityCollection, S: Serializer>(
+        entities: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(entities.iter_entities().map(CompactEntity))
+    }
+
+    pub fn deserialize<'de, C: EntityCollection, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<C, D::Error> {
+        let entities = Vec::<CompactEntity>::deserialize(deserializer)?;
+        Ok(entities.into_iter().map(|entity| entity.0).collect())
+    }
+}
+
+/// Compact serialization for maps with entity keys, such as [`HashMap<Entity, T>`] or
+/// [`EntityHashMap<T>`](bevy::ecs::entity::EntityHashMap).
+///
+/// Use with `#[serde(with = "entity_containers::compact_keys")]`.
+pub mod compact_keys {
+    use super::*;
+
+    pub fn serialize<C, T, S>(map: &C, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: EntityKeyedMap<T>,
+        T: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(
+            map.iter_entries()
+                .map(|(entity, value)| (CompactEntity(entity), value)),
+        )
+    }
+
+    pub fn deserialize<'de, C, T, D>(deserializer: D) -> Result<C, D::Error>
+    where
+        C: EntityKeyedMap<T>,
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = deserializer.deserialize_map(EntriesVisitor(PhantomData))?;
+        Ok(entries.into_iter().collect())
+    }
+
+    /// Collects map entries into a [`Vec`] to let the caller build any map type.
+    struct EntriesVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for EntriesVisitor<T> {
+        type Value = Vec<(Entity, T)>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a map with entity keys")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::with_capacity(access.size_hint().unwrap_or_default());
+            while let Some((entity, value)) = access.next_entry::<CompactEntity, T>()? {
+                entries.push((entity.0, value));
+            }
+
+            Ok(entries)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+    use bincode::{DefaultOptions, Options};
+
+    use super::*;
+
+    #[test]
+    fn mapping() {
+        let mut vec = vec![Entity::from_raw(0)];
+        let mut set = EntityHashSet::from_iter([Entity::from_raw(0)]);
+        let mut map = EntityHashMap::from_iter([(Entity::from_raw(0), 1)]);
+        let mut option = Some(Entity::from_raw(0));
+
+        vec.map_container(&mut NextIndexMapper);
+        set.map_container(&mut NextIndexMapper);
+        map.map_container(&mut NextIndexMapper);
+        option.map_container(&mut NextIndexMapper);
+
+        assert_eq!(vec, [Entity::from_raw(1)]);
+        assert!(set.contains(&Entity::from_raw(1)));
+        assert_eq!(map.get(&Entity::from_raw(1)), Some(&1));
+        assert_eq!(option, Some(Entity::from_raw(1)));
+    }
+
+    #[test]
+    fn serialization() {
+        let container = Container {
+            vec: vec![Entity::from_raw(0)],
+            set: EntityHashSet::from_iter([Entity::from_raw(1)]),
+            map: EntityHashMap::from_iter([(Entity::from_raw(2), 3)]),
+        };
+
+        let bytes = DefaultOptions::new().serialize(&container).unwrap();
+        let deserialized: Container = DefaultOptions::new().deserialize(&bytes).unwrap();
+        assert_eq!(deserialized, container);
+
+        let entity = Entity::from_raw(0);
+        let compact_size = DefaultOptions::new()
+            .serialized_size(&CompactEntity(entity))
+            .unwrap();
+        let regular_size = DefaultOptions::new().serialized_size(&entity).unwrap();
+        assert!(compact_size < regular_size);
+    }
+
+    struct NextIndexMapper;
+
+    impl EntityMapper for NextIndexMapper {
+        fn map_entity(&mut self, entity: Entity) -> Entity {
+            Entity::from_raw(entity.index() + 1)
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Container {
+        #[serde(with = "compact_entities")]
+        vec: Vec<Entity>,
+        #[serde(with = "compact_entities")]
+        set: EntityHashSet,
+        #[serde(with = "compact_keys")]
+        map: EntityHashMap<u32>,
+    }
+}
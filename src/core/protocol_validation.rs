@@ -0,0 +1,171 @@
+use std::any::{self, TypeId};
+
+use bevy::{
+    prelude::*,
+    reflect::{TypeInfo, TypeRegistry, VariantInfo},
+};
+
+use super::{replication_fns::ReplicationFns, replicon_channels::RepliconChannels};
+
+/**
+Configuration for protocol validation that runs when the app finishes building.
+
+Validation checks for common protocol mistakes and reports all of them at once:
+- Events and components that contain [`Entity`], but registered without mapping.
+  Only types registered with [`App::register_type`] can be checked.
+- Number of channels that exceed the limits of the messaging backend.
+
+Validation runs in [`Plugin::finish`] of [`RepliconCorePlugin`](super::RepliconCorePlugin).
+Found mistakes are logged as an error by default.
+
+# Examples
+
+Panic on mistakes in debug builds:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.world_mut().resource_mut::<ProtocolValidation>().strict = cfg!(debug_assertions);
+```
+**/
+#[derive(Resource, Default)]
+pub struct ProtocolValidation {
+    /// Panic instead of logging an error if any mistakes are found.
+    pub strict: bool,
+
+    /// Maximum number of server channels supported by the messaging backend.
+    ///
+    /// Should be set by the messaging backend.
+    pub max_server_channels: Option<usize>,
+
+    /// Same as [`Self::max_server_channels`], but for client channels.
+    pub max_client_channels: Option<usize>,
+
+    /// Events registered without entity mapping.
+    unmapped_events: Vec<UnmappedEvent>,
+}
+
+impl ProtocolValidation {
+    /// Remembers an event registered without entity mapping to check it later.
+    pub(crate) fn add_unmapped_event<T: 'static>(world: &mut World, kind: EventKind) {
+        world
+            .get_resource_or_insert_with(Self::default)
+            .unmapped_events
+            .push(UnmappedEvent {
+                type_id: TypeId::of::<T>(),
+                type_name: any::type_name::<T>(),
+                kind,
+            });
+    }
+
+    /// Returns all found protocol mistakes.
+    pub(super) fn validate(&self, world: &World) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let registry = world.resource::<AppTypeRegistry>().read();
+        for event in &self.unmapped_events {
+            if contains_entity(&registry, event.type_id, &mut Vec::new()) {
+                let (kind, method) = match event.kind {
+                    EventKind::Server => ("server event", "add_mapped_server_event"),
+                    EventKind::Client => ("client event", "add_mapped_client_event"),
+                };
+                errors.push(format!(
+                    "{kind} `{}` contains `Entity`, but registered without mapping, use `{method}` instead",
+                    event.type_name,
+                ));
+            }
+        }
+
+        let replication_fns = world.resource::<ReplicationFns>();
+        for rule_fns in replication_fns
+            .iter_rule_fns()
+            .filter(|rule_fns| rule_fns.unmapped())
+        {
+            if contains_entity(&registry, rule_fns.type_id(), &mut Vec::new()) {
+                errors.push(format!(
+                    "component `{}` contains `Entity`, but replicated without mapping, use `replicate_mapped` instead",
+                    rule_fns.type_name(),
+                ));
+            }
+        }
+
+        let replicon_channels = world.resource::<RepliconChannels>();
+        let channels = [
+            (
+                "server",
+                replicon_channels.server_channels().len(),
+                self.max_server_channels,
+            ),
+            (
+                "client",
+                replicon_channels.client_channels().len(),
+                self.max_client_channels,
+            ),
+        ];
+        for (side, count, max) in channels {
+            if let Some(max) = max.filter(|&max| count > max) {
+                errors.push(format!(
+                    "{count} {side} channels exceed the messaging backend limit of {max}, send multiple events over a single channel using a wrapper event"
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+/// Returns `true` if the type or any of its fields is [`Entity`].
+///
+/// Types missing from the registry are considered to not contain entities.
+fn contains_entity(registry: &TypeRegistry, type_id: TypeId, visited: &mut Vec<TypeId>) -> bool {
+    if type_id == TypeId::of::<Entity>() {
+        return true;
+    }
+    if visited.contains(&type_id) {
+        return false;
+    }
+    visited.push(type_id);
+
+    let Some(type_info) = registry.get_type_info(type_id) else {
+        return false;
+    };
+
+    let field_ids: Vec<_> = match type_info {
+        TypeInfo::Struct(info) => info.iter().map(|field| field.type_id()).collect(),
+        TypeInfo::TupleStruct(info) => info.iter().map(|field| field.type_id()).collect(),
+        TypeInfo::Tuple(info) => info.iter().map(|field| field.type_id()).collect(),
+        TypeInfo::List(info) => vec![info.item_type_id()],
+        TypeInfo::Array(info) => vec![info.item_type_id()],
+        TypeInfo::Map(info) => vec![info.key_type_id(), info.value_type_id()],
+        TypeInfo::Enum(info) => info
+            .iter()
+            .flat_map(|variant| match variant {
+                VariantInfo::Struct(info) => info.iter().map(|field| field.type_id()).collect(),
+                VariantInfo::Tuple(info) => info.iter().map(|field| field.type_id()).collect(),
+                VariantInfo::Unit(_) => Vec::new(),
+            })
+            .collect(),
+        TypeInfo::Value(_) => Vec::new(),
+    };
+
+    field_ids
+        .into_iter()
+        .any(|type_id| contains_entity(registry, type_id, visited))
+}
+
+/// Event registered without entity mapping.
+struct UnmappedEvent {
+    type_id: TypeId,
+    type_name: &'static str,
+    kind: EventKind,
+}
+
+/// Direction of a network event.
+#[derive(Clone, Copy)]
+pub(crate) enum EventKind {
+    Server,
+    Client,
+}
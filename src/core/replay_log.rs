@@ -0,0 +1,279 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use super::{replication_fns::FnsId, replicon_tick::RepliconTick};
+
+/**
+Log of replicated component values for desync hunting.
+
+Recording is enabled by inserting this resource:
+- On server it records component values sent to clients.
+- On client it records component values applied from the server.
+
+Values are stored in the same format as they are sent over the network,
+so logs from both sides can be compared directly via [`Self::diff`].
+Entities are always stored as server entities.
+
+Values serialized for each client individually, such as differences from
+[`RuleFns::with_delta`](super::replication_fns::rule_fns::RuleFns::with_delta),
+are recorded on server once per client, so there could be multiple values for the same tick, entity and component.
+
+The log can be serialized to save it on disk and compare later.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::replay_log::ReplayLog, prelude::*};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+// Start recording on both server and client.
+app.init_resource::<ReplayLog>();
+
+// ...
+
+fn report_desync(server_log: &ReplayLog, client_log: &ReplayLog) {
+    if let Some(divergence) = server_log.diff(client_log) {
+        error!("{divergence}");
+    }
+}
+```
+**/
+#[derive(Resource, Default, Debug, Clone, Deserialize, Serialize)]
+pub struct ReplayLog(Vec<ReplayRecord>);
+
+impl ReplayLog {
+    /// Returns all recorded values in the recording order.
+    pub fn records(&self) -> &[ReplayRecord] {
+        &self.0
+    }
+
+    /// Removes all records.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Records a component value.
+    ///
+    /// `value` is the component serialized with its [`FnsId`].
+    pub(crate) fn record(
+        &mut self,
+        tick: RepliconTick,
+        entity: Entity,
+        fns_id: FnsId,
+        value: &[u8],
+    ) {
+        self.0.push(ReplayRecord {
+            tick,
+            entity: entity.to_bits(),
+            fns_id,
+            value: value.to_vec(),
+        });
+    }
+
+    /// Moves all records from `other` into this log.
+    pub(crate) fn append(&mut self, other: &mut ReplayLog) {
+        self.0.append(&mut other.0);
+    }
+
+    /// Compares a client log with this server log and returns the first divergence.
+    ///
+    /// Records are aligned by tick, entity and component. Values that the server sent,
+    /// but the client never applied are ignored since they could be lost or superseded by newer updates.
+    /// If the server sent multiple values for the same record, the applied value should match any of them.
+    /// Returns [`None`] if all values applied on client match the sent ones.
+    pub fn diff(&self, client_log: &ReplayLog) -> Option<ReplayDivergence> {
+        let mut sent = HashMap::<_, Vec<_>>::new();
+        for record in &self.0 {
+            sent.entry((record.tick, record.entity, record.fns_id))
+                .or_default()
+                .push(&record.value);
+        }
+
+        client_log.0.iter().find_map(|record| {
+            let kind = match sent.get(&(record.tick, record.entity, record.fns_id)) {
+                Some(values) if values.contains(&&record.value) => return None,
+                Some(values) => DivergenceKind::Mismatch {
+                    sent: values[0].clone(),
+                    applied: record.value.clone(),
+                },
+                None => DivergenceKind::NotSent {
+                    applied: record.value.clone(),
+                },
+            };
+
+            Some(ReplayDivergence {
+                tick: record.tick,
+                entity: record.entity(),
+                fns_id: record.fns_id,
+                kind,
+            })
+        })
+    }
+}
+
+/// A single value inside [`ReplayLog`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplayRecord {
+    tick: RepliconTick,
+    entity: u64,
+    fns_id: FnsId,
+    value: Vec<u8>,
+}
+
+impl ReplayRecord {
+    /// Returns the server tick for which the value was sent.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the server entity.
+    pub fn entity(&self) -> Entity {
+        Entity::from_bits(self.entity)
+    }
+
+    /// Returns network ID of the component replication functions.
+    ///
+    /// See [`ReplicationFns::network_id`](super::replication_fns::ReplicationFns::network_id).
+    pub fn fns_id(&self) -> FnsId {
+        self.fns_id
+    }
+
+    /// Returns the component serialized with its [`FnsId`].
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// First found difference between server and client logs.
+///
+/// See [`ReplayLog::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    pub tick: RepliconTick,
+    pub entity: Entity,
+    pub fns_id: FnsId,
+    pub kind: DivergenceKind,
+}
+
+impl Display for ReplayDivergence {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "divergence for server's {:?} with {:?} at {:?}: ",
+            self.entity, self.fns_id, self.tick
+        )?;
+        match &self.kind {
+            DivergenceKind::Mismatch { sent, applied } => {
+                write!(f, "sent {sent:?}, but applied {applied:?}")
+            }
+            DivergenceKind::NotSent { applied } => {
+                write!(f, "applied {applied:?} that was never sent")
+            }
+        }
+    }
+}
+
+/// Type of [`ReplayDivergence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// Client applied a different value than the server sent.
+    Mismatch { sent: Vec<u8>, applied: Vec<u8> },
+    /// Client applied a value the server didn't send in this tick.
+    NotSent { applied: Vec<u8> },
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::core::replication_fns::{rule_fns::RuleFns, ReplicationFns};
+
+    #[test]
+    fn matching() {
+        let entity = Entity::from_raw(0);
+        let tick = RepliconTick::new(1);
+        let fns_id = fns_id();
+
+        let mut server_log = ReplayLog::default();
+        server_log.record(tick, entity, fns_id, &[1]);
+        server_log.record(tick, Entity::from_raw(1), fns_id, &[2]); // Missing values should be ignored.
+
+        let mut client_log = ReplayLog::default();
+        client_log.record(tick, entity, fns_id, &[1]);
+
+        assert_eq!(server_log.diff(&client_log), None);
+    }
+
+    #[test]
+    fn mismatch() {
+        let entity = Entity::from_raw(0);
+        let fns_id = fns_id();
+
+        let mut server_log = ReplayLog::default();
+        server_log.record(RepliconTick::new(1), entity, fns_id, &[1]);
+        server_log.record(RepliconTick::new(2), entity, fns_id, &[2]);
+
+        let mut client_log = ReplayLog::default();
+        client_log.record(RepliconTick::new(1), entity, fns_id, &[1]);
+        client_log.record(RepliconTick::new(2), entity, fns_id, &[3]);
+        client_log.record(RepliconTick::new(3), entity, fns_id, &[4]);
+
+        let divergence = server_log.diff(&client_log).unwrap();
+        assert_eq!(divergence.tick, RepliconTick::new(2));
+        assert_eq!(
+            divergence.kind,
+            DivergenceKind::Mismatch {
+                sent: vec![2],
+                applied: vec![3]
+            }
+        );
+    }
+
+    #[test]
+    fn per_client() {
+        let entity = Entity::from_raw(0);
+        let tick = RepliconTick::new(1);
+        let fns_id = fns_id();
+
+        let mut server_log = ReplayLog::default();
+        server_log.record(tick, entity, fns_id, &[1]);
+        server_log.record(tick, entity, fns_id, &[2]);
+
+        let mut client_log = ReplayLog::default();
+        client_log.record(tick, entity, fns_id, &[2]);
+
+        assert_eq!(server_log.diff(&client_log), None);
+    }
+
+    #[test]
+    fn not_sent() {
+        let entity = Entity::from_raw(0);
+        let fns_id = fns_id();
+
+        let server_log = ReplayLog::default();
+        let mut client_log = ReplayLog::default();
+        client_log.record(RepliconTick::new(1), entity, fns_id, &[1]);
+
+        let divergence = server_log.diff(&client_log).unwrap();
+        assert_eq!(
+            divergence.kind,
+            DivergenceKind::NotSent { applied: vec![1] }
+        );
+    }
+
+    fn fns_id() -> FnsId {
+        let mut world = World::new();
+        let mut replication_fns = ReplicationFns::default();
+        replication_fns
+            .register_rule_fns(&mut world, RuleFns::<DummyComponent>::default())
+            .fns_id()
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct DummyComponent;
+}
@@ -1,27 +1,61 @@
 pub mod command_fns;
+#[cfg(feature = "compact_transform")]
+pub mod compact_transform;
 pub mod component_fns;
 pub mod ctx;
+pub mod group_fns;
 pub mod rule_fns;
 pub mod test_fns;
+pub mod view_fns;
+
+use std::time::Duration;
 
 use bevy::{ecs::component::ComponentId, prelude::*};
 use serde::{Deserialize, Serialize};
 
-use super::command_markers::CommandMarkerIndex;
+use super::command_markers::{CommandMarkerIndex, CommandMarkers, EntityMarkers, SpawnMarkers};
+use crate::Replicated;
 use command_fns::{RemoveFn, UntypedCommandFns, WriteFn};
 use component_fns::ComponentFns;
-use ctx::DespawnCtx;
+use ctx::{DespawnCtx, SpawnCtx};
+use group_fns::{ComponentGroup, GroupFns, UntypedGroupFns};
 use rule_fns::{RuleFns, UntypedRuleFns};
+use view_fns::UntypedViewFns;
 
 /// Stores configurable replication functions.
 #[derive(Resource)]
 pub struct ReplicationFns {
+    /// Custom function to handle entity spawning on clients.
+    ///
+    /// By default set to [`None`], which spawns entities with [`Replicated`] in batches.
+    /// Useful to take entities from a pool, attach them to a specific parent or spawn
+    /// them into reserved entities.
+    ///
+    /// Not used for server entities with assigned markers that have their own spawn functions,
+    /// see [`AppMarkerExt::set_marker_spawn`](super::command_markers::AppMarkerExt::set_marker_spawn).
+    ///
+    /// [`Replicated`] is inserted automatically on the returned entity.
+    pub spawn: Option<SpawnFn>,
+
     /// Custom function to handle entity despawning.
     ///
     /// By default uses [`despawn_recursive`].
     /// Useful if you need to intercept despawns and handle them in a special way.
+    ///
+    /// Not used for entities with markers that have their own despawn functions,
+    /// see [`AppMarkerExt::set_marker_despawn`](super::command_markers::AppMarkerExt::set_marker_despawn).
     pub despawn: DespawnFn,
 
+    /// Spawn functions for each registered marker.
+    ///
+    /// Indices correspond to markers in [`CommandMarkers`].
+    marker_spawns: Vec<Option<SpawnFn>>,
+
+    /// Despawn functions for each registered marker.
+    ///
+    /// Indices correspond to markers in [`CommandMarkers`](super::command_markers::CommandMarkers).
+    marker_despawns: Vec<Option<DespawnFn>>,
+
     /// Functions for replicated components.
     ///
     /// Unique for each component.
@@ -34,6 +68,19 @@ pub struct ReplicationFns {
     /// [`ReplicationRule`](super::replication_rules::ReplicationRule)
     rules: Vec<(UntypedRuleFns, usize)>,
 
+    /// Stable hash for each rule from [`Self::rules`].
+    ///
+    /// See [`Self::stable_id`].
+    stable_ids: Vec<u64>,
+
+    /// Rule IDs sorted by [`Self::stable_ids`].
+    ///
+    /// Position of an ID is used to identify its functions over the network.
+    network_order: Vec<FnsId>,
+
+    /// Position of each rule from [`Self::rules`] in [`Self::network_order`].
+    network_ids: Vec<FnsId>,
+
     /// Number of registered markers.
     ///
     /// Used to initialize new [`ComponentFns`] with the registered number of slots.
@@ -46,12 +93,108 @@ impl ReplicationFns {
     /// Should be used after calling
     /// [`CommandMarkers::insert`](super::command_markers::CommandMarkers::insert)
     pub(super) fn register_marker(&mut self, marker_id: CommandMarkerIndex) {
+        debug_assert_eq!(
+            *marker_id, self.marker_slots,
+            "markers should be registered in order"
+        );
         self.marker_slots += 1;
+        self.marker_spawns.push(None);
+        self.marker_despawns.push(None);
         for (command_fns, _) in &mut self.components {
-            command_fns.add_marker_slot(marker_id);
+            command_fns.add_marker_slot();
+        }
+    }
+
+    /// Associates a spawn function with a marker.
+    ///
+    /// **Must** be called **after** calling [`Self::register_marker`] with `marker_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the marker wasn't registered. Use [`Self::register_marker`] first.
+    pub(super) fn set_marker_spawn(&mut self, marker_id: CommandMarkerIndex, spawn: SpawnFn) {
+        let marker_spawn = self
+            .marker_spawns
+            .get_mut(*marker_id)
+            .unwrap_or_else(|| panic!("spawn fns should have a slot for {marker_id:?}"));
+
+        debug_assert!(
+            marker_spawn.is_none(),
+            "spawn function for {marker_id:?} can't be set twice"
+        );
+
+        *marker_spawn = Some(spawn);
+    }
+
+    /// Associates a despawn function with a marker.
+    ///
+    /// **Must** be called **after** calling [`Self::register_marker`] with `marker_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the marker wasn't registered. Use [`Self::register_marker`] first.
+    pub(super) fn set_marker_despawn(&mut self, marker_id: CommandMarkerIndex, despawn: DespawnFn) {
+        let marker_despawn = self
+            .marker_despawns
+            .get_mut(*marker_id)
+            .unwrap_or_else(|| panic!("despawn fns should have a slot for {marker_id:?}"));
+
+        debug_assert!(
+            marker_despawn.is_none(),
+            "despawn function for {marker_id:?} can't be set twice"
+        );
+
+        *marker_despawn = Some(despawn);
+    }
+
+    /// Spawns a client entity for [`SpawnCtx::server_entity`].
+    ///
+    /// Markers assigned to the server entity via [`SpawnMarkers`] are taken and the spawn function
+    /// of the first-found marker will be selected (the markers are sorted by priority).
+    /// If there is no such function, [`Self::spawn`] will be used, or [`Replicated`] will be spawned if it's not set.
+    pub(crate) fn spawn_entity(
+        &self,
+        ctx: &SpawnCtx,
+        world: &mut World,
+        command_markers: &CommandMarkers,
+    ) -> Entity {
+        let marker_spawn = world
+            .get_resource_mut::<SpawnMarkers>()
+            .and_then(|mut spawn_markers| spawn_markers.take(ctx.server_entity))
+            .and_then(|markers| {
+                let components = world.components();
+                command_markers
+                    .iter_enabled()
+                    .find_map(|(marker_id, component_id)| {
+                        let spawn = self.marker_spawns[*marker_id]?;
+                        let type_id = components.get_info(component_id)?.type_id()?;
+                        markers.contains(&type_id).then_some(spawn)
+                    })
+            });
+
+        match marker_spawn.or(self.spawn) {
+            Some(spawn) => {
+                let entity = (spawn)(ctx, world);
+                world.entity_mut(entity).insert(Replicated);
+                entity
+            }
+            None => world.spawn(Replicated).id(),
         }
     }
 
+    /// Returns the despawn function for an entity based on its markers.
+    ///
+    /// The first-found despawn function whose marker is present on the entity will be selected
+    /// (markers are sorted by priority).
+    /// If there is no such function, [`Self::despawn`] will be returned.
+    pub(crate) fn despawn_fn(&self, entity_markers: &EntityMarkers) -> DespawnFn {
+        entity_markers
+            .markers()
+            .iter()
+            .find_map(|&marker_id| self.marker_despawns[*marker_id])
+            .unwrap_or(self.despawn)
+    }
+
     /// Associates command functions with a marker for a component.
     ///
     /// **Must** be called **after** calling [`Self::register_marker`] with `marker_id`.
@@ -107,7 +250,15 @@ impl ReplicationFns {
         rule_fns: RuleFns<C>,
     ) -> FnsInfo {
         let (index, component_id) = self.init_component_fns::<C>(world);
+        let occurrence = self
+            .rules
+            .iter()
+            .filter(|&&(_, rule_index)| rule_index == index)
+            .count();
+        let stable_name = rule_fns.stable_name();
         self.rules.push((rule_fns.into(), index));
+        self.stable_ids.push(stable_hash(stable_name, occurrence));
+        self.update_network_ids();
 
         FnsInfo {
             component_id,
@@ -115,6 +266,60 @@ impl ReplicationFns {
         }
     }
 
+    /// Registers functions to replicate server component `S` as component `C` on clients.
+    ///
+    /// `S` is converted into `C` on server and `rule_fns` are used to serialize and deserialize `C`.
+    /// Returned data can be assigned to a
+    /// [`ReplicationRule`](super::replication_rules::ReplicationRule).
+    pub fn register_view_fns<S, C>(&mut self, world: &mut World, rule_fns: RuleFns<C>) -> FnsInfo
+    where
+        S: Component,
+        C: Component + for<'a> From<&'a S>,
+    {
+        let fns_info = self.register_rule_fns(world, RuleFns::<S>::view());
+        let (view_rule_fns, _) = &mut self.rules[fns_info.fns_id.0];
+        view_rule_fns.set_view(UntypedViewFns::new::<S, C>(rule_fns));
+
+        fns_info
+    }
+
+    /// Registers functions that serialize a group of components together.
+    ///
+    /// Each group component is registered individually and returned in the order of the group.
+    /// Returned data can be assigned to a
+    /// [`ReplicationRule`](super::replication_rules::ReplicationRule).
+    pub fn register_group_fns<G: ComponentGroup>(
+        &mut self,
+        world: &mut World,
+        group_fns: GroupFns<G>,
+    ) -> Vec<FnsInfo> {
+        let components = G::register_components(world, self);
+        let component_ids = components
+            .iter()
+            .map(|fns_info| fns_info.component_id)
+            .collect();
+
+        let first = components
+            .first()
+            .expect("group should contain at least one component");
+        let (rule_fns, _) = &mut self.rules[first.fns_id.0];
+        rule_fns.set_group(UntypedGroupFns::new(group_fns, component_ids));
+
+        components
+    }
+
+    /// Sorts rules by their stable IDs to assign network IDs that don't depend on registration order.
+    fn update_network_ids(&mut self) {
+        self.network_order = (0..self.rules.len()).map(FnsId).collect();
+        self.network_order
+            .sort_by_key(|fns_id| (self.stable_ids[fns_id.0], fns_id.0));
+
+        self.network_ids.resize(self.rules.len(), FnsId(0));
+        for (index, fns_id) in self.network_order.iter().enumerate() {
+            self.network_ids[fns_id.0] = FnsId(index);
+        }
+    }
+
     /// Initializes [`ComponentFns`] for a component and returns its index and ID.
     ///
     /// If a [`ComponentFns`] has already been created for this component,
@@ -134,6 +339,38 @@ impl ReplicationFns {
         (index, component_id)
     }
 
+    /// Returns IDs of all components with registered functions.
+    pub(crate) fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components
+            .iter()
+            .map(|&(_, component_id)| component_id)
+    }
+
+    /// Returns the longest timeout set by [`RuleFns::with_mutation_timeout`] across all rules.
+    pub(crate) fn max_mutation_timeout(&self) -> Option<Duration> {
+        self.rules
+            .iter()
+            .filter_map(|(rule_fns, _)| rule_fns.mutation_timeout())
+            .max()
+    }
+
+    /// Returns all registered rule functions.
+    pub(crate) fn iter_rule_fns(&self) -> impl Iterator<Item = &UntypedRuleFns> + '_ {
+        self.rules.iter().map(|(rule_fns, _)| rule_fns)
+    }
+
+    /// Returns IDs of all rule functions registered for a component with the specified type name.
+    pub(crate) fn fns_ids_by_type_name<'a>(
+        &'a self,
+        type_name: &'a str,
+    ) -> impl Iterator<Item = FnsId> + 'a {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(move |(_, (rule_fns, _))| rule_fns.type_name() == type_name)
+            .map(|(index, _)| FnsId(index))
+    }
+
     /// Returns associates functions.
     ///
     /// See also [`Self::register_rule_fns`].
@@ -148,14 +385,74 @@ impl ReplicationFns {
 
         (command_fns, rule_fns)
     }
+
+    /// Returns an ID that doesn't depend on registration order of other components.
+    ///
+    /// Calculated from the component type name and the number of previous registrations
+    /// for the same component. Uses the type path instead if
+    /// [`RuleFns::with_type_path`] was set.
+    pub fn stable_id(&self, fns_id: FnsId) -> u64 {
+        self.stable_ids[fns_id.0]
+    }
+
+    /// Returns an ID that is used to identify functions over the network.
+    ///
+    /// Network IDs are assigned in the order of [`Self::stable_id`], so apps that register
+    /// the same rules in a different order will produce identical messages.
+    pub fn network_id(&self, fns_id: FnsId) -> FnsId {
+        self.network_ids[fns_id.0]
+    }
+
+    /// Translates an ID obtained from [`Self::network_id`] back into [`FnsId`].
+    ///
+    /// Returns an error if there are no functions with this network ID.
+    pub(crate) fn local_id(&self, network_id: FnsId) -> bincode::Result<FnsId> {
+        self.network_order
+            .get(network_id.0)
+            .copied()
+            .ok_or_else(|| {
+                bincode::ErrorKind::Custom(format!("{network_id:?} has no matching functions"))
+                    .into()
+            })
+    }
+
+    /// Returns stable IDs for all registered functions in the order of their network IDs.
+    pub(crate) fn stable_ids(&self) -> Vec<u64> {
+        self.network_order
+            .iter()
+            .map(|fns_id| self.stable_ids[fns_id.0])
+            .collect()
+    }
+
+    /// Creates a mapping from network IDs of another instance with the specified stable IDs
+    /// to IDs of this instance.
+    ///
+    /// Stable IDs that are missing in this instance will be mapped to [`None`].
+    pub(crate) fn map_stable_ids(&self, stable_ids: &[u64]) -> Vec<Option<FnsId>> {
+        stable_ids
+            .iter()
+            .map(|stable_id| {
+                self.stable_ids
+                    .iter()
+                    .position(|id| id == stable_id)
+                    .map(FnsId)
+            })
+            .collect()
+    }
 }
 
 impl Default for ReplicationFns {
     fn default() -> Self {
         Self {
+            spawn: None,
             despawn: despawn_recursive,
+            marker_spawns: Default::default(),
+            marker_despawns: Default::default(),
             components: Default::default(),
             rules: Default::default(),
+            stable_ids: Default::default(),
+            network_order: Default::default(),
+            network_ids: Default::default(),
             marker_slots: 0,
         }
     }
@@ -171,6 +468,13 @@ pub struct FnsInfo {
 }
 
 impl FnsInfo {
+    pub(crate) fn new(component_id: ComponentId, fns_id: FnsId) -> Self {
+        Self {
+            component_id,
+            fns_id,
+        }
+    }
+
     pub(crate) fn component_id(&self) -> ComponentId {
         self.component_id
     }
@@ -183,9 +487,65 @@ impl FnsInfo {
 /// ID of replicaton functions for a component.
 ///
 /// Can be obtained from [`ReplicationFns::register_rule_fns`].
-#[derive(Clone, Copy, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct FnsId(usize);
 
+/// Mapping of server network IDs into client [`FnsId`]s based on [`ReplicationFns::stable_id`].
+///
+/// Inserted on client by [`StableFnsIdsPlugin`](crate::stable_fns_ids::StableFnsIdsPlugin).
+#[derive(Resource, Default)]
+pub(crate) struct FnsIdRemap(Option<Vec<Option<FnsId>>>);
+
+impl FnsIdRemap {
+    /// Sets mapping obtained from [`ReplicationFns::map_stable_ids`].
+    pub(crate) fn set(&mut self, mapping: Vec<Option<FnsId>>) {
+        self.0 = Some(mapping);
+    }
+
+    /// Returns `true` if mapping was received from the server.
+    pub(crate) fn is_received(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Clears the mapping.
+    pub(crate) fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Maps server's network ID into client's ID.
+    ///
+    /// Returns an error if the server's ID is unknown or has no associated functions on client.
+    pub(crate) fn get(&self, server_fns_id: FnsId) -> bincode::Result<FnsId> {
+        self.0
+            .as_ref()
+            .and_then(|mapping| mapping.get(server_fns_id.0).copied().flatten())
+            .ok_or_else(|| {
+                bincode::ErrorKind::Custom(format!(
+                    "server's {server_fns_id:?} has no matching functions on client"
+                ))
+                .into()
+            })
+    }
+}
+
+/// Computes FNV-1a hash which, unlike [`std::hash::DefaultHasher`], is guaranteed to be stable across builds.
+fn stable_hash(type_name: &str, occurrence: usize) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    type_name
+        .bytes()
+        .chain((occurrence as u64).to_le_bytes())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+/// Signature of the entity spawn function.
+///
+/// Returns the client entity that will be mapped to [`SpawnCtx::server_entity`].
+pub type SpawnFn = fn(&SpawnCtx, &mut World) -> Entity;
+
 /// Signature of the entity despawn function.
 pub type DespawnFn = fn(&DespawnCtx, EntityWorldMut);
 
@@ -196,6 +556,8 @@ pub fn despawn_recursive(_ctx: &DespawnCtx, entity: EntityWorldMut) {
 
 #[cfg(test)]
 mod tests {
+    use std::any;
+
     use bevy::ecs::entity::MapEntities;
 
     use super::*;
@@ -235,6 +597,97 @@ mod tests {
         assert_eq!(replication_fns.components.len(), 2);
     }
 
+    #[test]
+    fn stable_ids() {
+        let mut world = World::new();
+        let mut replication_fns = ReplicationFns::default();
+        let info_a =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentA>::default());
+        let info_b =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentB>::default());
+        let info_a2 =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentA>::default());
+
+        let mut reordered_world = World::new();
+        let mut reordered_fns = ReplicationFns::default();
+        reordered_fns.register_rule_fns(&mut reordered_world, RuleFns::<ComponentB>::default());
+        reordered_fns.register_rule_fns(&mut reordered_world, RuleFns::<ComponentA>::default());
+
+        assert_ne!(
+            replication_fns.stable_id(info_a.fns_id),
+            replication_fns.stable_id(info_a2.fns_id),
+            "repeated registrations should have different IDs"
+        );
+
+        let mapping = reordered_fns.map_stable_ids(&replication_fns.stable_ids());
+        let mapped = |fns_id| mapping[replication_fns.network_id(fns_id).0];
+        assert_eq!(
+            [
+                mapped(info_a.fns_id),
+                mapped(info_b.fns_id),
+                mapped(info_a2.fns_id)
+            ],
+            [Some(FnsId(1)), Some(FnsId(0)), None],
+            "IDs should match regardless of registration order"
+        );
+        assert_eq!(
+            reordered_fns.stable_id(FnsId(0)),
+            replication_fns.stable_id(info_b.fns_id)
+        );
+    }
+
+    #[test]
+    fn type_path_stable_ids() {
+        let mut world = World::new();
+        let mut replication_fns = ReplicationFns::default();
+        let info_name =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentC>::default());
+        let info_path = replication_fns.register_rule_fns(
+            &mut world,
+            RuleFns::<ComponentC>::default().with_type_path(),
+        );
+
+        assert_eq!(
+            replication_fns.stable_id(info_name.fns_id),
+            stable_hash(any::type_name::<ComponentC>(), 0)
+        );
+        assert_eq!(
+            replication_fns.stable_id(info_path.fns_id),
+            stable_hash(ComponentC::type_path(), 1)
+        );
+    }
+
+    #[test]
+    fn network_ids() {
+        let mut world = World::new();
+        let mut replication_fns = ReplicationFns::default();
+        let info_a =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentA>::default());
+        let info_b =
+            replication_fns.register_rule_fns(&mut world, RuleFns::<ComponentB>::default());
+
+        let mut reordered_world = World::new();
+        let mut reordered_fns = ReplicationFns::default();
+        let reordered_b =
+            reordered_fns.register_rule_fns(&mut reordered_world, RuleFns::<ComponentB>::default());
+        let reordered_a =
+            reordered_fns.register_rule_fns(&mut reordered_world, RuleFns::<ComponentA>::default());
+
+        assert_eq!(
+            replication_fns.network_id(info_a.fns_id),
+            reordered_fns.network_id(reordered_a.fns_id)
+        );
+        assert_eq!(
+            replication_fns.network_id(info_b.fns_id),
+            reordered_fns.network_id(reordered_b.fns_id)
+        );
+        assert_eq!(replication_fns.stable_ids(), reordered_fns.stable_ids());
+
+        let network_id = replication_fns.network_id(info_a.fns_id);
+        assert_eq!(replication_fns.local_id(network_id).unwrap(), info_a.fns_id);
+        assert!(replication_fns.local_id(FnsId(2)).is_err());
+    }
+
     #[derive(Component, Serialize, Deserialize)]
     struct ComponentA;
 
@@ -244,4 +697,7 @@ mod tests {
     impl MapEntities for ComponentB {
         fn map_entities<M: EntityMapper>(&mut self, _entity_mapper: &mut M) {}
     }
+
+    #[derive(Component, Deserialize, Serialize, TypePath)]
+    struct ComponentC;
 }
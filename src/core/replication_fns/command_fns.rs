@@ -79,6 +79,8 @@ pub type RemoveFn = fn(&mut RemoveCtx, &mut EntityMut);
 ///
 /// If the component does not exist on the entity, it will be deserialized with [`RuleFns::deserialize`] and inserted via [`Commands`].
 /// If the component exists on the entity, [`RuleFns::deserialize_in_place`] will be used directly on the entity's component.
+/// If the component was changed locally and [`RuleFns::with_conflict_resolution`] was set,
+/// the component will be deserialized and passed to the resolution function instead.
 pub fn default_write<C: Component>(
     ctx: &mut WriteCtx,
     rule_fns: &RuleFns<C>,
@@ -86,7 +88,13 @@ pub fn default_write<C: Component>(
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<()> {
     if let Some(mut component) = entity.get_mut::<C>() {
-        rule_fns.deserialize_in_place(ctx, &mut *component, cursor)?;
+        match rule_fns.conflict_resolution() {
+            Some(resolve) if ctx.is_changed_locally(component.last_changed()) => {
+                let received = rule_fns.deserialize(ctx, cursor)?;
+                (resolve)(ctx, &mut *component, received);
+            }
+            _ => rule_fns.deserialize_in_place(ctx, &mut *component, cursor)?,
+        }
     } else {
         let component: C = rule_fns.deserialize(ctx, cursor)?;
         ctx.commands.entity(entity.id()).insert(component);
@@ -0,0 +1,136 @@
+use std::{
+    f32::consts::FRAC_1_SQRT_2,
+    io::{Cursor, Read, Write},
+};
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+
+use super::ctx::{SerializeCtx, WriteCtx};
+
+/// Size of a translation quantization step in world units.
+///
+/// Translations are rounded to the nearest multiple of this value on serialization.
+/// Coordinates are stored as [`i32`], so values outside of about ±2 million units are clamped.
+pub const TRANSLATION_STEP: f32 = 1.0 / 1024.0;
+
+/// Number of bits for each of the three smallest quaternion components.
+///
+/// Together with 2 bits for the index of the largest component, a rotation fits into [`u32`].
+const ROTATION_BITS: u32 = 10;
+
+/// Maximum quantized value of a quaternion component.
+const ROTATION_MAX: u32 = (1 << ROTATION_BITS) - 1;
+
+/**
+Serializes [`Transform`] in a compact form.
+
+- Translation is quantized into fixed-point with [`TRANSLATION_STEP`] and written as variable-length integers.
+- Rotation is packed into 4 bytes using the "smallest three" encoding: the largest quaternion component is omitted
+  and restored from the others, since the quaternion is normalized.
+- Scale is omitted if it's equal to [`Vec3::ONE`].
+
+A transform near the origin without scale usually takes 8-14 bytes instead of 40.
+The precision is enough for most games, but values received by clients will be slightly different.
+
+Can also be used as a reference for writing custom [`RuleFns`](super::rule_fns::RuleFns).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication_fns::{compact_transform, rule_fns::RuleFns},
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_with(RuleFns::new(
+    compact_transform::serialize,
+    compact_transform::deserialize,
+));
+```
+**/
+pub fn serialize(
+    _ctx: &SerializeCtx,
+    transform: &Transform,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let translation = (transform.translation / TRANSLATION_STEP)
+        .round()
+        .to_array()
+        .map(|value| value as i32);
+    DefaultOptions::new().serialize_into(&mut *cursor, &translation)?;
+
+    cursor.write_all(&pack_rotation(transform.rotation).to_le_bytes())?;
+
+    let scale = (transform.scale != Vec3::ONE).then(|| transform.scale.to_array());
+    DefaultOptions::new().serialize_into(cursor, &scale)
+}
+
+/// Deserializes [`Transform`] written by [`serialize`].
+pub fn deserialize(_ctx: &mut WriteCtx, cursor: &mut Cursor<&[u8]>) -> bincode::Result<Transform> {
+    let translation: [i32; 3] = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+
+    let mut rotation = [0; 4];
+    cursor.read_exact(&mut rotation)?;
+
+    let scale: Option<[f32; 3]> = DefaultOptions::new().deserialize_from(cursor)?;
+
+    Ok(Transform {
+        translation: Vec3::from_array(translation.map(|value| value as f32)) * TRANSLATION_STEP,
+        rotation: unpack_rotation(u32::from_le_bytes(rotation)),
+        scale: scale.map_or(Vec3::ONE, Vec3::from_array),
+    })
+}
+
+/// Packs a rotation using the "smallest three" encoding.
+///
+/// The 2 most significant bits store the index of the largest component,
+/// followed by the remaining components in their order.
+fn pack_rotation(rotation: Quat) -> u32 {
+    let mut components = rotation.normalize().to_array();
+    let largest = (0..components.len())
+        .max_by(|&a, &b| components[a].abs().total_cmp(&components[b].abs()))
+        .unwrap_or_default();
+
+    // `q` and `-q` represent the same rotation, so the largest component
+    // can always be made positive to restore it without a sign.
+    if components[largest] < 0.0 {
+        components = components.map(|component| -component);
+    }
+
+    let mut packed = largest as u32;
+    for (index, component) in components.into_iter().enumerate() {
+        if index != largest {
+            // Other components can't exceed 1/√2 in absolute value.
+            let normalized = (component / FRAC_1_SQRT_2 * 0.5 + 0.5).clamp(0.0, 1.0);
+            let quantized = (normalized * ROTATION_MAX as f32).round() as u32;
+            packed = (packed << ROTATION_BITS) | quantized;
+        }
+    }
+
+    packed
+}
+
+/// Unpacks a rotation packed by [`pack_rotation`].
+fn unpack_rotation(packed: u32) -> Quat {
+    let largest = (packed >> (3 * ROTATION_BITS)) as usize;
+    let mut components = [0.0; 4];
+    let mut shift = 3 * ROTATION_BITS;
+    for index in (0..components.len()).filter(|&index| index != largest) {
+        shift -= ROTATION_BITS;
+        let quantized = (packed >> shift) & ROTATION_MAX;
+        let normalized = quantized as f32 / ROTATION_MAX as f32;
+        components[index] = (normalized - 0.5) * 2.0 * FRAC_1_SQRT_2;
+    }
+
+    let squares: f32 = components
+        .iter()
+        .map(|component| component * component)
+        .sum();
+    components[largest] = (1.0 - squares).max(0.0).sqrt();
+
+    Quat::from_array(components).normalize()
+}
@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::{any::Any, io::Cursor, sync::Arc};
 
 use bevy::{prelude::*, ptr::Ptr};
 
@@ -14,6 +14,9 @@ use crate::core::command_markers::{CommandMarkerIndex, CommandMarkers, EntityMar
 /// Stores type-erased command functions and functions that will restore original types.
 pub(crate) struct ComponentFns {
     serialize: UntypedSerializeFn,
+    serialize_delta: UntypedSerializeDeltaFn,
+    clone_previous: UntypedClonePreviousFn,
+    should_send: UntypedShouldSendFn,
     write: UntypedWriteFn,
     consume: UntypedConsumeFn,
     commands: UntypedCommandFns,
@@ -25,6 +28,9 @@ impl ComponentFns {
     pub(super) fn new<C: Component>(marker_slots: usize) -> Self {
         Self {
             serialize: untyped_serialize::<C>,
+            serialize_delta: untyped_serialize_delta::<C>,
+            clone_previous: untyped_clone_previous::<C>,
+            should_send: untyped_should_send::<C>,
             write: untyped_write::<C>,
             consume: untyped_consume::<C>,
             commands: UntypedCommandFns::default_fns::<C>(),
@@ -32,11 +38,11 @@ impl ComponentFns {
         }
     }
 
-    /// Adds new empty slot for a marker.
+    /// Adds new empty slot for the next registered marker.
     ///
     /// Use [`Self::set_marker_fns`] to assign functions to it.
-    pub(super) fn add_marker_slot(&mut self, marker_id: CommandMarkerIndex) {
-        self.markers.insert(*marker_id, None);
+    pub(super) fn add_marker_slot(&mut self) {
+        self.markers.push(None);
     }
 
     /// Assigns functions to a marker slot.
@@ -75,19 +81,78 @@ impl ComponentFns {
         self.commands = command_fns;
     }
 
-    /// Restores erased type from `ptr` and `rule_fns` to the type for which this instance was created.
+    /// Restores erased type from `ptrs` and `rule_fns` to the type for which this instance was created.
+    ///
+    /// The first pointer should point to the component. If `rule_fns` have functions for a group,
+    /// the rest of the pointers should point to other group components and the whole group will be serialized.
+    /// If `rule_fns` have functions for a view, the component will be converted before serialization.
     ///
     /// # Safety
     ///
-    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    /// The caller must ensure that `ptrs` and `rule_fns` were created for the same type as this instance.
     pub(crate) unsafe fn serialize(
         &self,
         ctx: &SerializeCtx,
         rule_fns: &UntypedRuleFns,
+        ptrs: &[Ptr],
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        if let Some(group) = rule_fns.group() {
+            return group.serialize(ctx, ptrs, cursor);
+        }
+        if let Some(view) = rule_fns.view() {
+            return view.serialize(ctx, ptrs[0], cursor);
+        }
+
+        (self.serialize)(ctx, rule_fns, ptrs[0], cursor)
+    }
+
+    /// Same as [`Self::serialize`], but serializes the difference against `base`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance
+    /// and `base` was returned by [`Self::clone_previous`] of this instance.
+    pub(crate) unsafe fn serialize_delta(
+        &self,
+        ctx: &SerializeCtx,
+        rule_fns: &UntypedRuleFns,
+        base: &(dyn Any + Send + Sync),
         ptr: Ptr,
         cursor: &mut Cursor<Vec<u8>>,
     ) -> bincode::Result<()> {
-        (self.serialize)(ctx, rule_fns, ptr, cursor)
+        (self.serialize_delta)(ctx, rule_fns, base, ptr, cursor)
+    }
+
+    /// Clones the component to use it as a base for [`Self::serialize_delta`]
+    /// or to compare it with future changes in [`Self::should_send`].
+    ///
+    /// Returns [`None`] if the component has neither delta functions nor a send filter.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    pub(crate) unsafe fn clone_previous(
+        &self,
+        rule_fns: &UntypedRuleFns,
+        ptr: Ptr,
+    ) -> Option<Arc<dyn Any + Send + Sync>> {
+        (self.clone_previous)(rule_fns, ptr)
+    }
+
+    /// Returns `true` if the change from `last_sent` to the component should be sent.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance
+    /// and `last_sent` was returned by [`Self::clone_previous`] of this instance.
+    pub(crate) unsafe fn should_send(
+        &self,
+        rule_fns: &UntypedRuleFns,
+        last_sent: &(dyn Any + Send + Sync),
+        ptr: Ptr,
+    ) -> bool {
+        (self.should_send)(rule_fns, last_sent, ptr)
     }
 
     /// Calls the assigned writing function based on entity markers.
@@ -95,6 +160,8 @@ impl ComponentFns {
     /// The first-found write function whose marker is present on the entity will be selected
     /// (the functions are sorted by priority).
     /// If there is no such function, it will use the default function.
+    /// If `rule_fns` have functions for a group, writes the whole group instead.
+    /// If `rule_fns` have functions for a view, writes the client component without markers.
     ///
     /// # Safety
     ///
@@ -107,12 +174,17 @@ impl ComponentFns {
         entity: &mut EntityMut,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        let command_fns = self
-            .markers
+        if let Some(group) = rule_fns.group() {
+            return group.write(ctx, entity, cursor);
+        }
+        if let Some(view) = rule_fns.view() {
+            return view.write(ctx, entity, cursor);
+        }
+
+        let command_fns = entity_markers
+            .markers()
             .iter()
-            .zip(entity_markers.markers())
-            .filter(|(_, &contains)| contains)
-            .find_map(|(&fns, _)| fns)
+            .find_map(|&marker_id| self.markers[*marker_id])
             .unwrap_or(self.commands);
 
         (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
@@ -135,14 +207,18 @@ impl ComponentFns {
         entity: &mut EntityMut,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        if let Some(command_fns) = self
-            .markers
+        if let Some(group) = rule_fns.group() {
+            return group.consume(ctx, cursor);
+        }
+        if let Some(view) = rule_fns.view() {
+            return view.consume(ctx, cursor);
+        }
+
+        if let Some(command_fns) = entity_markers
+            .markers()
             .iter()
-            .zip(entity_markers.markers())
-            .zip(command_markers.iter_require_history())
-            .filter(|((_, &contains), _)| contains)
-            .find_map(|((&fns, _), need_history)| fns.map(|fns| (fns, need_history)))
-            .and_then(|(fns, need_history)| need_history.then_some(fns))
+            .find_map(|&marker_id| self.markers[*marker_id].map(|fns| (fns, marker_id)))
+            .and_then(|(fns, marker_id)| command_markers.need_history(marker_id).then_some(fns))
         {
             (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
         } else {
@@ -154,15 +230,18 @@ impl ComponentFns {
     pub(crate) fn remove(
         &self,
         ctx: &mut RemoveCtx,
+        rule_fns: &UntypedRuleFns,
         entity_markers: &EntityMarkers,
         entity: &mut EntityMut,
     ) {
-        let command_fns = self
-            .markers
+        if let Some(view) = rule_fns.view() {
+            return view.remove(ctx, entity);
+        }
+
+        let command_fns = entity_markers
+            .markers()
             .iter()
-            .zip(entity_markers.markers())
-            .filter(|(_, &contains)| contains)
-            .find_map(|(&fns, _)| fns)
+            .find_map(|&marker_id| self.markers[*marker_id])
             .unwrap_or(self.commands);
 
         command_fns.remove(ctx, entity)
@@ -173,6 +252,21 @@ impl ComponentFns {
 type UntypedSerializeFn =
     unsafe fn(&SerializeCtx, &UntypedRuleFns, Ptr, &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
 
+/// Signature of component delta serialization functions that restore the original type.
+type UntypedSerializeDeltaFn = unsafe fn(
+    &SerializeCtx,
+    &UntypedRuleFns,
+    &(dyn Any + Send + Sync),
+    Ptr,
+    &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()>;
+
+/// Signature of component cloning functions that restore the original type.
+type UntypedClonePreviousFn = unsafe fn(&UntypedRuleFns, Ptr) -> Option<Arc<dyn Any + Send + Sync>>;
+
+/// Signature of component send filtering functions that restore the original type.
+type UntypedShouldSendFn = unsafe fn(&UntypedRuleFns, &(dyn Any + Send + Sync), Ptr) -> bool;
+
 /// Signature of component writing functions that restore the original type.
 type UntypedWriteFn = unsafe fn(
     &mut WriteCtx,
@@ -201,6 +295,57 @@ unsafe fn untyped_serialize<C: Component>(
     rule_fns.serialize(ctx, ptr.deref::<C>(), cursor)
 }
 
+/// Dereferences a component from a pointer and serializes its difference against `base`.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn untyped_serialize_delta<C: Component>(
+    ctx: &SerializeCtx,
+    rule_fns: &UntypedRuleFns,
+    base: &(dyn Any + Send + Sync),
+    ptr: Ptr,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let base = base
+        .downcast_ref::<C>()
+        .expect("base should have the same type as the component");
+    let rule_fns = rule_fns.typed::<C>();
+    rule_fns.serialize_delta(ctx, base, ptr.deref::<C>(), cursor)
+}
+
+/// Dereferences a component from a pointer and clones it if `rule_fns` have delta functions or a send filter.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn untyped_clone_previous<C: Component>(
+    rule_fns: &UntypedRuleFns,
+    ptr: Ptr,
+) -> Option<Arc<dyn Any + Send + Sync>> {
+    let rule_fns = rule_fns.typed::<C>();
+    rule_fns
+        .clone_previous(ptr.deref::<C>())
+        .map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>)
+}
+
+/// Dereferences a component from a pointer and checks whether its change from `last_sent` should be sent.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn untyped_should_send<C: Component>(
+    rule_fns: &UntypedRuleFns,
+    last_sent: &(dyn Any + Send + Sync),
+    ptr: Ptr,
+) -> bool {
+    let last_sent = last_sent
+        .downcast_ref::<C>()
+        .expect("last sent value should have the same type as the component");
+    let rule_fns = rule_fns.typed::<C>();
+    rule_fns.should_send(ptr.deref::<C>(), last_sent)
+}
+
 /// Resolves `rule_fns` to `C` and calls [`UntypedCommandFns::write`] for `C`.
 ///
 /// # Safety
@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{ecs::component::Tick, prelude::*};
 
 use crate::{
     client::server_entity_map::ServerEntityMap, core::replicon_tick::RepliconTick, Replicated,
@@ -6,9 +6,35 @@ use crate::{
 
 /// Replication context for serialization function.
 #[non_exhaustive]
-pub struct SerializeCtx {
+pub struct SerializeCtx<'a> {
     /// Current tick.
     pub server_tick: RepliconTick,
+
+    /// Entity whose component is being serialized.
+    ///
+    /// Can be used to make the encoding depend on other components of the entity.
+    /// Changes of other components don't trigger replication of the serialized component,
+    /// so the data they affect should be replicated too or stay constant.
+    pub entity: EntityRef<'a>,
+
+    /// Last change tick acknowledged by the receiving client for the serialized entity.
+    ///
+    /// Set only for updates of components that are serialized for each client individually.
+    pub(crate) change_limit: Option<Tick>,
+
+    /// Change tick of the current replication run.
+    pub(crate) this_run: Tick,
+}
+
+impl<'a> SerializeCtx<'a> {
+    pub(crate) fn new(server_tick: RepliconTick, entity: EntityRef<'a>, this_run: Tick) -> Self {
+        Self {
+            server_tick,
+            entity,
+            change_limit: None,
+            this_run,
+        }
+    }
 }
 
 /// Replication context for writing and deserialization.
@@ -25,6 +51,16 @@ pub struct WriteCtx<'a, 'w, 's> {
 
     /// Disables mapping logic to avoid spawning entities for consume functions.
     pub(super) ignore_mapping: bool,
+
+    /// Ticks after the previous replication and before the current one.
+    ///
+    /// Changes between them were made on client outside of replication.
+    pub(crate) local_changes: Option<(Tick, Tick)>,
+
+    /// Previously written data of the component and its message tick.
+    ///
+    /// See [`RuleFns::deserialize_previous`](super::rule_fns::RuleFns::deserialize_previous).
+    pub(crate) previous: Option<(RepliconTick, Vec<u8>)>,
 }
 
 impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
@@ -38,8 +74,19 @@ impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
             entity_map,
             message_tick,
             ignore_mapping: false,
+            local_changes: None,
+            previous: None,
         }
     }
+
+    /// Returns `true` if a component with the given change tick was changed on client since the last replication.
+    ///
+    /// Such changes are usually made by prediction and conflict with the received data.
+    /// See also [`RuleFns::with_conflict_resolution`](super::rule_fns::RuleFns::with_conflict_resolution).
+    pub fn is_changed_locally(&self, last_changed: Tick) -> bool {
+        self.local_changes
+            .is_some_and(|(last_run, this_run)| last_changed.is_newer_than(last_run, this_run))
+    }
 }
 
 impl EntityMapper for WriteCtx<'_, '_, '_> {
@@ -72,6 +119,16 @@ impl<'a, 'w, 's> RemoveCtx<'a, 'w, 's> {
     }
 }
 
+/// Replication context for spawn.
+#[non_exhaustive]
+pub struct SpawnCtx {
+    /// Tick for the currently processing message.
+    pub message_tick: RepliconTick,
+
+    /// Server entity for which the client entity is spawned.
+    pub server_entity: Entity,
+}
+
 /// Replication context for despawn.
 #[non_exhaustive]
 pub struct DespawnCtx {
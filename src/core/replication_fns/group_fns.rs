@@ -0,0 +1,248 @@
+use std::{any, io::Cursor, mem};
+
+use bevy::{ecs::component::ComponentId, prelude::*, ptr::Ptr};
+
+use super::{
+    ctx::{SerializeCtx, WriteCtx},
+    rule_fns::RuleFns,
+    FnsInfo, ReplicationFns,
+};
+
+/// Type-erased version of [`GroupFns`].
+///
+/// Stored inside [`UntypedRuleFns`](super::rule_fns::UntypedRuleFns) of the first group component.
+pub(crate) struct UntypedGroupFns {
+    type_name: &'static str,
+    serialize: unsafe fn(),
+    deserialize: unsafe fn(),
+
+    untyped_serialize: UntypedGroupSerializeFn,
+    untyped_write: UntypedGroupWriteFn,
+    untyped_consume: UntypedGroupConsumeFn,
+
+    /// IDs of group components in the order of the group.
+    component_ids: Vec<ComponentId>,
+}
+
+impl UntypedGroupFns {
+    pub(super) fn new<G: ComponentGroup>(
+        group_fns: GroupFns<G>,
+        component_ids: Vec<ComponentId>,
+    ) -> Self {
+        // SAFETY: these functions won't be called until the type is restored.
+        Self {
+            type_name: any::type_name::<G>(),
+            serialize: unsafe { mem::transmute(group_fns.serialize) },
+            deserialize: unsafe { mem::transmute(group_fns.deserialize) },
+            untyped_serialize: untyped_serialize::<G>,
+            untyped_write: untyped_write::<G>,
+            untyped_consume: untyped_consume::<G>,
+            component_ids,
+        }
+    }
+
+    /// Returns IDs of all group components except the first one.
+    ///
+    /// The first component stores these functions.
+    pub(crate) fn other_ids(&self) -> &[ComponentId] {
+        &self.component_ids[1..]
+    }
+
+    /// Serializes all group components into a cursor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptrs` point to the group components in the order of [`Self::component_ids`].
+    pub(crate) unsafe fn serialize(
+        &self,
+        ctx: &SerializeCtx,
+        ptrs: &[Ptr],
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        debug_assert_eq!(
+            ptrs.len(),
+            self.component_ids.len(),
+            "`{}` should be serialized with pointers to all its components",
+            self.type_name,
+        );
+
+        (self.untyped_serialize)(self, ctx, ptrs, cursor)
+    }
+
+    /// Deserializes all group components and inserts them into the entity or updates existing.
+    pub(crate) fn write(
+        &self,
+        ctx: &mut WriteCtx,
+        entity: &mut EntityMut,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        // SAFETY: functions were created for the same type.
+        unsafe { (self.untyped_write)(self, ctx, entity, cursor) }
+    }
+
+    /// Deserializes all group components and discards them.
+    pub(crate) fn consume(
+        &self,
+        ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        // SAFETY: functions were created for the same type.
+        unsafe { (self.untyped_consume)(self, ctx, cursor) }
+    }
+}
+
+/**
+Serialization and deserialization functions for a group of components.
+
+All group components are serialized together by a single function,
+which is useful for correlated data that can be packed more efficiently as a whole.
+See also [`AppRuleExt::replicate_group_with`](crate::core::replication_rules::AppRuleExt::replicate_group_with).
+**/
+pub struct GroupFns<G: ComponentGroup> {
+    serialize: GroupSerializeFn<G>,
+    deserialize: GroupDeserializeFn<G>,
+}
+
+impl<G: ComponentGroup> GroupFns<G> {
+    /// Creates a new instance with the specified serialization and deserialization functions.
+    pub fn new(serialize: GroupSerializeFn<G>, deserialize: GroupDeserializeFn<G>) -> Self {
+        Self {
+            serialize,
+            deserialize,
+        }
+    }
+}
+
+/// Signature of group serialization functions.
+pub type GroupSerializeFn<G> = for<'a> fn(
+    &SerializeCtx,
+    <G as ComponentGroup>::Refs<'a>,
+    &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()>;
+
+/// Signature of group deserialization functions.
+pub type GroupDeserializeFn<G> = fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<G>;
+
+/// Signature of group serialization functions that restore the original type.
+type UntypedGroupSerializeFn =
+    unsafe fn(&UntypedGroupFns, &SerializeCtx, &[Ptr], &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
+
+/// Signature of group writing functions that restore the original type.
+type UntypedGroupWriteFn = unsafe fn(
+    &UntypedGroupFns,
+    &mut WriteCtx,
+    &mut EntityMut,
+    &mut Cursor<&[u8]>,
+) -> bincode::Result<()>;
+
+/// Signature of group consuming functions that restore the original type.
+type UntypedGroupConsumeFn =
+    unsafe fn(&UntypedGroupFns, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+
+/// Restores the original type of the serialization function and calls it with references to components.
+///
+/// # Safety
+///
+/// The caller must ensure that `group_fns` were created for `G` and `ptrs` point to its components.
+unsafe fn untyped_serialize<G: ComponentGroup>(
+    group_fns: &UntypedGroupFns,
+    ctx: &SerializeCtx,
+    ptrs: &[Ptr],
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let serialize: GroupSerializeFn<G> = mem::transmute(group_fns.serialize);
+    (serialize)(ctx, G::from_ptrs(ptrs), cursor)
+}
+
+/// Restores the original type of the deserialization function and writes the deserialized group.
+///
+/// # Safety
+///
+/// The caller must ensure that `group_fns` were created for `G`.
+unsafe fn untyped_write<G: ComponentGroup>(
+    group_fns: &UntypedGroupFns,
+    ctx: &mut WriteCtx,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let deserialize: GroupDeserializeFn<G> = mem::transmute(group_fns.deserialize);
+    let group = (deserialize)(ctx, cursor)?;
+    group.write(ctx, entity);
+
+    Ok(())
+}
+
+/// Restores the original type of the deserialization function and discards the deserialized group.
+///
+/// # Safety
+///
+/// The caller must ensure that `group_fns` were created for `G`.
+unsafe fn untyped_consume<G: ComponentGroup>(
+    group_fns: &UntypedGroupFns,
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let deserialize: GroupDeserializeFn<G> = mem::transmute(group_fns.deserialize);
+    ctx.ignore_mapping = true;
+    let result = (deserialize)(ctx, cursor);
+    ctx.ignore_mapping = false;
+    result?;
+
+    Ok(())
+}
+
+/// A set of components that can be serialized together with [`GroupFns`].
+///
+/// Implemented for tuples of up to 15 components.
+pub trait ComponentGroup: Sized + Send + Sync + 'static {
+    /// References to the group components.
+    type Refs<'a>;
+
+    /// Converts pointers into references to the group components.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptrs` point to the group components in the order of the group.
+    unsafe fn from_ptrs<'a>(ptrs: &[Ptr<'a>]) -> Self::Refs<'a>;
+
+    /// Registers each group component in [`ReplicationFns`] and returns their information in the order of the group.
+    fn register_components(world: &mut World, replication_fns: &mut ReplicationFns)
+        -> Vec<FnsInfo>;
+
+    /// Inserts components into the entity or updates existing.
+    fn write(self, ctx: &mut WriteCtx, entity: &mut EntityMut);
+}
+
+macro_rules! impl_component_group {
+    ($($type:ident),*) => {
+        impl<$($type: Component),*> ComponentGroup for ($($type,)*) {
+            type Refs<'a> = ($(&'a $type,)*);
+
+            unsafe fn from_ptrs<'a>(ptrs: &[Ptr<'a>]) -> Self::Refs<'a> {
+                let mut ptrs = ptrs.iter();
+                ($((*ptrs.next().unwrap_unchecked()).deref::<$type>(),)*)
+            }
+
+            fn register_components(
+                world: &mut World,
+                replication_fns: &mut ReplicationFns,
+            ) -> Vec<FnsInfo> {
+                vec![$(replication_fns.register_rule_fns(world, RuleFns::<$type>::group_member()),)*]
+            }
+
+            #[allow(non_snake_case)]
+            fn write(self, ctx: &mut WriteCtx, entity: &mut EntityMut) {
+                let ($($type,)*) = self;
+                $(
+                    if let Some(mut component) = entity.get_mut::<$type>() {
+                        *component = $type;
+                    } else {
+                        ctx.commands.entity(entity.id()).insert($type);
+                    }
+                )*
+            }
+        }
+    }
+}
+
+bevy::utils::all_tuples!(impl_component_group, 1, 15, C);
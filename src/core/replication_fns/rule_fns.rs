@@ -1,14 +1,23 @@
 use std::{
     any::{self, TypeId},
-    io::Cursor,
+    io::{Cursor, Write},
     mem,
+    time::Duration,
 };
 
 use bevy::{ecs::entity::MapEntities, prelude::*};
 use bincode::{DefaultOptions, Options};
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::ctx::{SerializeCtx, WriteCtx};
+use super::{
+    ctx::{SerializeCtx, WriteCtx},
+    group_fns::UntypedGroupFns,
+    view_fns::UntypedViewFns,
+};
+use crate::{
+    core::{replicon_channels::ReplicationChannel, replicon_tick::RepliconTick},
+    server::connected_clients::client_groups::GroupId,
+};
 
 /// Type-erased version of [`RuleFns`].
 ///
@@ -21,6 +30,25 @@ pub(crate) struct UntypedRuleFns {
     deserialize: unsafe fn(),
     deserialize_in_place: unsafe fn(),
     consume: unsafe fn(),
+    intercept: Option<unsafe fn()>,
+    resolve_conflict: Option<unsafe fn()>,
+    delta: Option<UntypedDeltaFns>,
+    filter: Option<UntypedFilterFns>,
+    group: Option<UntypedGroupFns>,
+    view: Option<UntypedViewFns>,
+    condition: Option<ConditionFn>,
+    client_group: Option<GroupId>,
+
+    insert_channel: ReplicationChannel,
+    mutation_channel: ReplicationChannel,
+    mutation_timeout: Option<Duration>,
+    send_interval: u32,
+    owner_only: bool,
+    once: bool,
+    optional: bool,
+    per_client: bool,
+    unmapped: bool,
+    group_member: bool,
 }
 
 impl UntypedRuleFns {
@@ -43,8 +71,138 @@ impl UntypedRuleFns {
             deserialize: unsafe { mem::transmute(self.deserialize) },
             deserialize_in_place: unsafe { mem::transmute(self.deserialize_in_place) },
             consume: unsafe { mem::transmute(self.consume) },
+            intercept: unsafe { mem::transmute(self.intercept) },
+            resolve_conflict: unsafe { mem::transmute(self.resolve_conflict) },
+            delta: self.delta.map(|delta| DeltaFns {
+                diff: unsafe { mem::transmute(delta.diff) },
+                apply_diff: unsafe { mem::transmute(delta.apply_diff) },
+                clone: unsafe { mem::transmute(delta.clone) },
+            }),
+            filter: self.filter.map(|filter| FilterFns {
+                should_send: unsafe { mem::transmute(filter.should_send) },
+                clone: unsafe { mem::transmute(filter.clone) },
+            }),
+            condition: self.condition,
+            client_group: self.client_group,
+            insert_channel: self.insert_channel,
+            mutation_channel: self.mutation_channel,
+            mutation_timeout: self.mutation_timeout,
+            send_interval: self.send_interval,
+            owner_only: self.owner_only,
+            once: self.once,
+            optional: self.optional,
+            per_client: self.per_client,
+            unmapped: self.unmapped,
+            group_member: self.group_member,
         }
     }
+
+    /// Assigns functions to serialize the whole group.
+    ///
+    /// Should be called only for the first component of the group.
+    pub(super) fn set_group(&mut self, group: UntypedGroupFns) {
+        debug_assert!(
+            self.group_member,
+            "`{}` should be registered as a group member",
+            self.type_name
+        );
+        self.group = Some(group);
+    }
+
+    /// Returns functions assigned by [`Self::set_group`].
+    pub(crate) fn group(&self) -> Option<&UntypedGroupFns> {
+        self.group.as_ref()
+    }
+
+    /// Assigns functions to replicate the component as a different component on clients.
+    pub(super) fn set_view(&mut self, view: UntypedViewFns) {
+        self.view = Some(view);
+    }
+
+    /// Returns functions assigned by [`Self::set_view`].
+    pub(crate) fn view(&self) -> Option<&UntypedViewFns> {
+        self.view.as_ref()
+    }
+
+    /// Returns `true` if the component is a part of a group, but serialized by another group component.
+    pub(crate) fn serialized_by_group(&self) -> bool {
+        self.group_member && self.group.is_none()
+    }
+
+    /// Returns the channel set by [`RuleFns::with_insert_channel`].
+    pub(crate) fn insert_channel(&self) -> ReplicationChannel {
+        self.insert_channel
+    }
+
+    /// Returns the channel set by [`RuleFns::with_mutation_channel`].
+    pub(crate) fn mutation_channel(&self) -> ReplicationChannel {
+        self.mutation_channel
+    }
+
+    /// Returns the timeout set by [`RuleFns::with_mutation_timeout`].
+    pub(crate) fn mutation_timeout(&self) -> Option<Duration> {
+        self.mutation_timeout
+    }
+
+    /// Returns the interval set by [`RuleFns::with_send_interval`].
+    pub(crate) fn send_interval(&self) -> u32 {
+        self.send_interval
+    }
+
+    /// Returns the function set by [`RuleFns::with_condition`].
+    pub(crate) fn condition(&self) -> Option<ConditionFn> {
+        self.condition
+    }
+
+    /// Returns the group set by [`RuleFns::with_client_group`].
+    pub(crate) fn client_group(&self) -> Option<GroupId> {
+        self.client_group
+    }
+
+    /// Returns `true` if [`RuleFns::with_owner_only`] was set.
+    pub(crate) fn owner_only(&self) -> bool {
+        self.owner_only
+    }
+
+    /// Returns `true` if [`RuleFns::with_once`] was set.
+    pub(crate) fn once(&self) -> bool {
+        self.once
+    }
+
+    /// Returns `true` if [`RuleFns::with_delta`] was set.
+    pub(crate) fn has_delta(&self) -> bool {
+        self.delta.is_some()
+    }
+
+    /// Returns `true` if [`RuleFns::with_should_send`] was set.
+    pub(crate) fn has_filter(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    /// Returns `true` if [`RuleFns::with_optional`] was set.
+    pub(crate) fn optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns `true` if updates should be serialized for each client individually.
+    pub(crate) fn per_client(&self) -> bool {
+        self.per_client
+    }
+
+    /// Returns `true` if the functions were created with [`RuleFns::default`] and don't map entities.
+    pub(crate) fn unmapped(&self) -> bool {
+        self.unmapped
+    }
+
+    /// Returns the type ID of the component.
+    pub(crate) fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the type name of the component.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
 }
 
 impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
@@ -57,6 +215,31 @@ impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
             deserialize: unsafe { mem::transmute(value.deserialize) },
             deserialize_in_place: unsafe { mem::transmute(value.deserialize_in_place) },
             consume: unsafe { mem::transmute(value.consume) },
+            intercept: unsafe { mem::transmute(value.intercept) },
+            resolve_conflict: unsafe { mem::transmute(value.resolve_conflict) },
+            delta: value.delta.map(|delta| UntypedDeltaFns {
+                diff: unsafe { mem::transmute(delta.diff) },
+                apply_diff: unsafe { mem::transmute(delta.apply_diff) },
+                clone: unsafe { mem::transmute(delta.clone) },
+            }),
+            filter: value.filter.map(|filter| UntypedFilterFns {
+                should_send: unsafe { mem::transmute(filter.should_send) },
+                clone: unsafe { mem::transmute(filter.clone) },
+            }),
+            condition: value.condition,
+            client_group: value.client_group,
+            insert_channel: value.insert_channel,
+            mutation_channel: value.mutation_channel,
+            mutation_timeout: value.mutation_timeout,
+            send_interval: value.send_interval,
+            owner_only: value.owner_only,
+            once: value.once,
+            optional: value.optional,
+            per_client: value.per_client,
+            group: None,
+            view: None,
+            unmapped: value.unmapped,
+            group_member: value.group_member,
         }
     }
 }
@@ -70,6 +253,23 @@ pub struct RuleFns<C> {
     deserialize: DeserializeFn<C>,
     deserialize_in_place: DeserializeInPlaceFn<C>,
     consume: ConsumeFn<C>,
+    intercept: Option<InterceptFn<C>>,
+    resolve_conflict: Option<ResolveConflictFn<C>>,
+    delta: Option<DeltaFns<C>>,
+    filter: Option<FilterFns<C>>,
+    condition: Option<ConditionFn>,
+    client_group: Option<GroupId>,
+    stable_name: Option<&'static str>,
+    insert_channel: ReplicationChannel,
+    mutation_channel: ReplicationChannel,
+    mutation_timeout: Option<Duration>,
+    send_interval: u32,
+    owner_only: bool,
+    once: bool,
+    optional: bool,
+    per_client: bool,
+    unmapped: bool,
+    group_member: bool,
 }
 
 impl<C: Component> RuleFns<C> {
@@ -82,9 +282,45 @@ impl<C: Component> RuleFns<C> {
             deserialize,
             deserialize_in_place: in_place_as_deserialize::<C>,
             consume: consume_as_deserialize,
+            intercept: None,
+            resolve_conflict: None,
+            delta: None,
+            filter: None,
+            condition: None,
+            client_group: None,
+            stable_name: None,
+            insert_channel: ReplicationChannel::Init,
+            mutation_channel: ReplicationChannel::Update,
+            mutation_timeout: None,
+            send_interval: 1,
+            owner_only: false,
+            once: false,
+            optional: false,
+            per_client: false,
+            unmapped: false,
+            group_member: false,
         }
     }
 
+    /// Creates functions for a component that is serialized together with its group.
+    ///
+    /// Group components are written by [`GroupFns`](super::group_fns::GroupFns) assigned to the first component,
+    /// so these functions only return errors.
+    pub(super) fn group_member() -> Self {
+        Self {
+            group_member: true,
+            ..Self::new(group_member_serialize::<C>, group_member_deserialize::<C>)
+        }
+    }
+
+    /// Creates functions for a component that is replicated as a different component.
+    ///
+    /// The component is written by [`UntypedViewFns`] assigned to its functions,
+    /// so these functions only return errors.
+    pub(super) fn view() -> Self {
+        Self::new(view_serialize::<C>, view_deserialize::<C>)
+    }
+
     /// Replaces default [`in_place_as_deserialize`] with a custom function.
     ///
     /// This function will be called when a component is already present on an entity.
@@ -112,14 +348,574 @@ impl<C: Component> RuleFns<C> {
         self
     }
 
+    /**
+    Sets the channel over which insertions of the component are sent.
+
+    By default, insertions are sent over the reliable [`ReplicationChannel::Init`], so the client always
+    receives the initial value. With [`ReplicationChannel::Update`] insertions into entities that the client
+    already has are sent like changes: unreliably with last-write-wins semantics, and resent until the client
+    acknowledges them. Entities that are new for the client are always sent over [`ReplicationChannel::Init`]
+    with all their components.
+
+    Useful for components that are frequently inserted and removed, when their presence isn't critical.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::{replication_fns::rule_fns::RuleFns, replicon_channels::ReplicationChannel},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with::<Highlighted>(
+        RuleFns::default().with_insert_channel(ReplicationChannel::Update),
+    );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Highlighted;
+    ```
+    **/
+    pub fn with_insert_channel(mut self, channel: ReplicationChannel) -> Self {
+        self.insert_channel = channel;
+        self
+    }
+
+    /**
+    Sets the channel over which changes of the component are sent.
+
+    By default, changes are sent over the unreliable [`ReplicationChannel::Update`] with last-write-wins
+    semantics: a lost change is included again until the client acknowledges a newer one, and stale changes
+    that arrive late are discarded. With [`ReplicationChannel::Init`] every change is sent reliably and applied
+    in order with insertions and removals, at the cost of delivering the whole entity data reliably on change.
+
+    Mutation timeout and delta functions apply only to changes over [`ReplicationChannel::Update`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::{replication_fns::rule_fns::RuleFns, replicon_channels::ReplicationChannel},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with::<Score>(
+        RuleFns::default().with_mutation_channel(ReplicationChannel::Init),
+    );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Score(u32);
+    ```
+    **/
+    pub fn with_mutation_channel(mut self, channel: ReplicationChannel) -> Self {
+        self.mutation_channel = channel;
+        self
+    }
+
+    /// Limits how long changes of the component will be resent to a client until acknowledged.
+    ///
+    /// By default, changes are included into update messages until the client acknowledges them,
+    /// which could be wasteful for frequently changing components, such as transforms, when the
+    /// newer value will arrive soon anyway. With a timeout, a change will be resent only while
+    /// the time since it happened is less than the specified duration.
+    ///
+    /// Since the timeout is measured in time rather than in ticks, it doesn't depend on the tick rate.
+    /// Applies only to changes, insertions are always delivered reliably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::{core::replication_fns::rule_fns::RuleFns, prelude::*};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.replicate_with::<Transform>(
+    ///     RuleFns::default().with_mutation_timeout(Duration::from_millis(200)),
+    /// );
+    /// ```
+    pub fn with_mutation_timeout(mut self, timeout: Duration) -> Self {
+        self.mutation_timeout = Some(timeout);
+        self
+    }
+
+    /**
+    Sends changes of the component only every `interval` ticks.
+
+    Changes between sends are not lost, the client will receive the latest value on the next send.
+    Useful for slowly changing state, such as hunger, that doesn't need to be updated every tick.
+    If [`ServerPlugin::send_groups`](crate::server::ServerPlugin::send_groups) is used,
+    the interval is counted in the client's send turns instead of ticks.
+
+    Applies only to changes, insertions are always sent immediately. Changes will also be sent
+    earlier if another component is inserted into the same entity.
+
+    # Panics
+
+    Panics if `interval` is 0.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::rule_fns::RuleFns, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Health>()
+        .replicate_with::<Hunger>(RuleFns::default().with_send_interval(30));
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Hunger(u32);
+    ```
+    **/
+    pub fn with_send_interval(mut self, interval: u32) -> Self {
+        assert!(interval > 0, "send interval should be greater than 0");
+        self.send_interval = interval;
+        self
+    }
+
+    /// Sends the component only to the client from the entity's [`Owner`](crate::core::Owner).
+    ///
+    /// Other clients won't receive the component at all.
+    /// If an entity has no owner, the component won't be sent to anyone.
+    /// Useful for private data, such as inventory or quest log.
+    ///
+    /// The owner is expected to be assigned on spawn. After changing the owner,
+    /// the new owner will receive the component only after its next change.
+    ///
+    /// See also [`AppRuleExt::replicate_to_owner`](crate::core::replication_rules::AppRuleExt::replicate_to_owner).
+    pub fn with_owner_only(mut self) -> Self {
+        self.owner_only = true;
+        self
+    }
+
+    /// Sends the component only when the client receives it for the first time.
+    ///
+    /// The component will be included on entity spawn, insertion or when the entity becomes visible,
+    /// but its changes won't be sent. Useful for components that never change after spawn, such as
+    /// names or mesh IDs, since the server will skip them during change detection.
+    ///
+    /// See also [`AppRuleExt::replicate_once`](crate::core::replication_rules::AppRuleExt::replicate_once).
+    pub fn with_once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Sends the component only to clients from the specified group.
+    ///
+    /// Groups are managed on server via [`ConnectedClients::groups_mut`](crate::server::connected_clients::ConnectedClients::groups_mut),
+    /// so clients can't opt in on their own. Useful for data that requires a separate trust level,
+    /// such as debug information for developer tools.
+    ///
+    /// Similar to [`Self::with_owner_only`], a client added to the group will receive the component
+    /// only after its next change or when the entity becomes visible again.
+    pub fn with_client_group(mut self, group_id: GroupId) -> Self {
+        self.client_group = Some(group_id);
+        self
+    }
+
+    /// Sends the component only to clients that subscribed to it.
+    ///
+    /// Clients subscribe and unsubscribe at runtime using
+    /// [`ComponentSubscriptions`](crate::component_subscriptions::ComponentSubscriptions).
+    /// Useful for heavy opt-in data, such as debug-only components or spectator extras.
+    ///
+    /// See also [`AppRuleExt::replicate_optional`](crate::core::replication_rules::AppRuleExt::replicate_optional).
+    pub fn with_optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /**
+    Sends the component only while `condition` returns `true` for its entity.
+
+    The condition is evaluated on server for each replicated entity with the component every tick.
+    When it stops holding, clients receive a removal of the component.
+    When it holds again, clients receive the whole component, even if it wasn't changed.
+
+    Useful for data that matters only in a certain state, when removing [`Replicated`](crate::core::Replicated)
+    from the whole entity is too coarse.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::rule_fns::RuleFns, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<InCombat>()
+        .replicate_with::<Health>(
+            RuleFns::default().with_condition(|entity| entity.contains::<InCombat>()),
+        );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct InCombat;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+    ```
+    **/
+    pub fn with_condition(mut self, condition: ConditionFn) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /**
+    Uses [`TypePath::type_path`] instead of [`std::any::type_name`] to calculate the stable ID.
+
+    Unlike type names, type paths are guaranteed to stay the same between compiler versions,
+    so the server and clients built with different compilers will agree on IDs.
+    Should be set on both server and clients.
+
+    See also [`ReplicationFns::stable_id`](super::ReplicationFns::stable_id).
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::rule_fns::RuleFns, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins, StableFnsIdsPlugin));
+    app.replicate_with::<Health>(RuleFns::default().with_type_path());
+
+    #[derive(Component, Deserialize, Serialize, TypePath)]
+    struct Health(u32);
+    ```
+    **/
+    pub fn with_type_path(mut self) -> Self
+    where
+        C: TypePath,
+    {
+        self.stable_name = Some(C::type_path());
+        self
+    }
+
+    /// Returns `true` if [`Self::with_optional`] was set.
+    pub(crate) fn optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns the name used to calculate the stable ID.
+    ///
+    /// The type path if [`Self::with_type_path`] was set or the type name otherwise.
+    pub(crate) fn stable_name(&self) -> &'static str {
+        self.stable_name.unwrap_or_else(any::type_name::<C>)
+    }
+
+    /// Serializes updates of the component for each client individually.
+    ///
+    /// The serialization function will receive the last change tick acknowledged by the client,
+    /// so it can write only the data that the client is missing.
+    pub(crate) fn with_per_client(mut self) -> Self {
+        self.per_client = true;
+        self
+    }
+
+    /// Sets a function that will be called on server before serializing the component.
+    ///
+    /// Useful to sanitize values in one place instead of every gameplay system,
+    /// for example, to round positions or clamp values to the range expected by clients.
+    /// The function should return a replacement value or [`None`] to send the component as is.
+    /// Only the sent value is affected, the component in the server world stays untouched.
+    ///
+    /// Called once per changed component per tick, the result is shared between all clients.
+    ///
+    /// The function receives only the current value. The previously sent serialized value isn't available
+    /// because the server doesn't keep serialized values between ticks. To compare against what clients
+    /// already have, use [`Self::with_should_send`], which receives the last sent value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::{
+    ///     core::replication_fns::{ctx::SerializeCtx, rule_fns::RuleFns},
+    ///     prelude::*,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.replicate_with::<Health>(RuleFns::default().with_intercept(clamp_health));
+    ///
+    /// fn clamp_health(_ctx: &SerializeCtx, health: &Health) -> Option<Health> {
+    ///     (health.0 > Health::MAX).then_some(Health(Health::MAX))
+    /// }
+    ///
+    /// #[derive(Component, Deserialize, Serialize)]
+    /// struct Health(u32);
+    ///
+    /// impl Health {
+    ///     const MAX: u32 = 100;
+    /// }
+    /// ```
+    pub fn with_intercept(mut self, intercept: InterceptFn<C>) -> Self {
+        self.intercept = Some(intercept);
+        self
+    }
+
+    /// Sets a function to resolve conflicts between local changes and received updates on clients.
+    ///
+    /// A conflict occurs when the component was changed on the client outside of replication
+    /// (for example, by prediction) since the last received update. By default the received value
+    /// always overwrites the local one. With this function set, the received value is deserialized
+    /// and passed to `resolve` together with the local value instead.
+    ///
+    /// Use [`server_wins`] or [`client_wins`] or write a custom merge function.
+    ///
+    /// Used only by [`default_write`](super::command_fns::default_write),
+    /// custom writing functions could check for conflicts with
+    /// [`WriteCtx::is_changed_locally`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::{
+    ///     core::replication_fns::{ctx::WriteCtx, rule_fns::RuleFns},
+    ///     prelude::*,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.replicate_with::<Ammo>(RuleFns::default().with_conflict_resolution(keep_spent));
+    ///
+    /// /// Keeps ammo spent by predicted shots that the server doesn't know about yet.
+    /// fn keep_spent(_ctx: &mut WriteCtx, local: &mut Ammo, received: Ammo) {
+    ///     local.0 = local.0.min(received.0);
+    /// }
+    ///
+    /// #[derive(Component, Deserialize, Serialize)]
+    /// struct Ammo(u32);
+    /// ```
+    pub fn with_conflict_resolution(mut self, resolve: ResolveConflictFn<C>) -> Self {
+        self.resolve_conflict = Some(resolve);
+        self
+    }
+
+    /// Returns the function assigned by [`Self::with_conflict_resolution`].
+    pub(super) fn conflict_resolution(&self) -> Option<ResolveConflictFn<C>> {
+        self.resolve_conflict
+    }
+
+    /**
+    Sets functions to send updates as differences against the value acknowledged by the client.
+
+    The server keeps previous values of the component and sends to each client the difference
+    produced by `diff` against the last value the client acknowledged. Clients apply it with `apply_diff`.
+    If there is no such value, for example on insertion, the whole component is sent using regular functions.
+    Useful for large components where only a small part changes at a time, such as inventories.
+
+    The difference is serialized for each client individually and only when the client is guaranteed
+    to hold the base. Since updates are unreliable, values sent after the acknowledged one may still be
+    in flight. So if the component changed more than once since the acknowledged value,
+    the whole component is sent instead until the client acknowledges a newer value.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        prelude::*,
+    };
+    use bincode::{DefaultOptions, Options};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with::<Inventory>(
+        RuleFns::default().with_delta(diff_inventory, apply_inventory_diff),
+    );
+
+    /// Writes only changed slots.
+    fn diff_inventory(
+        _ctx: &SerializeCtx,
+        base: &Inventory,
+        inventory: &Inventory,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        let changed: Vec<_> = inventory
+            .0
+            .iter()
+            .enumerate()
+            .filter(|&(index, item)| base.0.get(index) != Some(item))
+            .collect();
+        DefaultOptions::new().serialize_into(&mut *cursor, &inventory.0.len())?;
+        DefaultOptions::new().serialize_into(cursor, &changed)
+    }
+
+    fn apply_inventory_diff(
+        _ctx: &mut WriteCtx,
+        inventory: &mut Inventory,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        let len: usize = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+        let changed: Vec<(usize, u32)> = DefaultOptions::new().deserialize_from(cursor)?;
+        inventory.0.resize(len, 0);
+        for (index, item) in changed {
+            inventory.0[index] = item;
+        }
+        Ok(())
+    }
+
+    #[derive(Clone, Component, Deserialize, Serialize)]
+    struct Inventory(Vec<u32>);
+    ```
+    **/
+    pub fn with_delta(mut self, diff: DiffFn<C>, apply_diff: ApplyDiffFn<C>) -> Self
+    where
+        C: Clone,
+    {
+        self.delta = Some(DeltaFns {
+            diff,
+            apply_diff,
+            clone: C::clone,
+        });
+        self
+    }
+
+    /**
+    Sets a function that decides on server whether a change of the component should be sent.
+
+    The function receives the current value and the last sent value and is evaluated before serialization.
+    If it returns `false`, the change is suppressed and clients that already have the last sent value won't receive it.
+    Since the comparison is against the last sent value rather than the previous one, small changes accumulate
+    and will be sent once they exceed the threshold.
+
+    Useful to avoid sending insignificant updates, such as sub-millimeter transform jitter from physics.
+    Applies only to changes, insertions are always sent.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::replication_fns::rule_fns::RuleFns, prelude::*};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with::<Transform>(RuleFns::default().with_should_send(moved_enough));
+
+    fn moved_enough(transform: &Transform, last_sent: &Transform) -> bool {
+        transform.translation.distance_squared(last_sent.translation) > 0.001 * 0.001
+            || transform.rotation.angle_between(last_sent.rotation) > 0.001
+            || transform.scale != last_sent.scale
+    }
+    ```
+    **/
+    pub fn with_should_send(mut self, should_send: ShouldSendFn<C>) -> Self
+    where
+        C: Clone,
+    {
+        self.filter = Some(FilterFns {
+            should_send,
+            clone: C::clone,
+        });
+        self
+    }
+
     /// Serializes a component into a cursor.
+    ///
+    /// If [`Self::with_intercept`] was set, serializes the value returned by it instead.
+    /// If [`Self::with_delta`] was set, the value is prefixed with a marker of the whole value.
     pub(super) fn serialize(
         &self,
         ctx: &SerializeCtx,
         component: &C,
         cursor: &mut Cursor<Vec<u8>>,
     ) -> bincode::Result<()> {
-        (self.serialize)(ctx, component, cursor)
+        if self.delta.is_some() {
+            DefaultOptions::new().serialize_into(&mut *cursor, &FULL)?;
+        }
+
+        match self
+            .intercept
+            .and_then(|intercept| (intercept)(ctx, component))
+        {
+            Some(component) => (self.serialize)(ctx, &component, cursor),
+            None => (self.serialize)(ctx, component, cursor),
+        }
+    }
+
+    /// Serializes the difference between `base` and `component` using functions from [`Self::with_delta`].
+    ///
+    /// If [`Self::with_intercept`] was set, it will be applied to both values.
+    pub(super) fn serialize_delta(
+        &self,
+        ctx: &SerializeCtx,
+        base: &C,
+        component: &C,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        let delta = self
+            .delta
+            .as_ref()
+            .expect("delta functions should be set to serialize difference");
+
+        let intercepted_base = self.intercept.and_then(|intercept| (intercept)(ctx, base));
+        let intercepted = self
+            .intercept
+            .and_then(|intercept| (intercept)(ctx, component));
+        let mut diff = Cursor::new(Vec::new());
+        (delta.diff)(
+            ctx,
+            intercepted_base.as_ref().unwrap_or(base),
+            intercepted.as_ref().unwrap_or(component),
+            &mut diff,
+        )?;
+
+        // Prefix with size to be able to skip the difference without applying it.
+        let diff = diff.into_inner();
+        DefaultOptions::new().serialize_into(&mut *cursor, &DELTA)?;
+        DefaultOptions::new().serialize_into(&mut *cursor, &diff.len())?;
+        cursor.write_all(&diff)?;
+
+        Ok(())
+    }
+
+    /// Clones a component to use it as a base for [`Self::serialize_delta`] later
+    /// or to compare it with future changes using the function from [`Self::with_should_send`].
+    ///
+    /// Returns [`None`] if neither [`Self::with_delta`] nor [`Self::with_should_send`] was set.
+    pub(super) fn clone_previous(&self, component: &C) -> Option<C> {
+        let clone = self
+            .delta
+            .map(|delta| delta.clone)
+            .or(self.filter.map(|filter| filter.clone))?;
+        Some(clone(component))
+    }
+
+    /// Returns `true` if the change from `last_sent` to `component` should be sent.
+    ///
+    /// Always returns `true` if [`Self::with_should_send`] wasn't set.
+    pub(super) fn should_send(&self, component: &C, last_sent: &C) -> bool {
+        self.filter
+            .as_ref()
+            .map_or(true, |filter| (filter.should_send)(component, last_sent))
     }
 
     /// Deserializes a component from a cursor.
@@ -130,9 +926,37 @@ impl<C: Component> RuleFns<C> {
         ctx: &mut WriteCtx,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<C> {
+        if self.delta.is_some() {
+            let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            if kind != FULL {
+                return Err(bincode::ErrorKind::Custom(format!(
+                    "received difference for a missing component `{}`",
+                    any::type_name::<C>()
+                ))
+                .into());
+            }
+        }
+
         (self.deserialize)(ctx, cursor)
     }
 
+    /// Deserializes the previously written server value of the component and returns it with its message tick.
+    ///
+    /// Returns [`None`] if the component wasn't written before or none of markers on the entity
+    /// has [`MarkerConfig::need_previous`](crate::core::command_markers::MarkerConfig::need_previous) enabled.
+    /// Can be called only once per write.
+    pub fn deserialize_previous(
+        &self,
+        ctx: &mut WriteCtx,
+    ) -> bincode::Result<Option<(RepliconTick, C)>> {
+        let Some((tick, bytes)) = ctx.previous.take() else {
+            return Ok(None);
+        };
+
+        let component = self.deserialize(ctx, &mut Cursor::new(&*bytes))?;
+        Ok(Some((tick, component)))
+    }
+
     /// Same as [`Self::deserialize`], but instead of returning a component, it updates the passed reference.
     ///
     /// Use this function for updating an existing component.
@@ -142,6 +966,18 @@ impl<C: Component> RuleFns<C> {
         component: &mut C,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
+        if let Some(delta) = &self.delta {
+            let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            match kind {
+                FULL => (),
+                DELTA => {
+                    let mut diff = Cursor::new(read_diff(cursor)?);
+                    return (delta.apply_diff)(ctx, component, &mut diff);
+                }
+                _ => return Err(bincode::ErrorKind::Custom(format!("unknown kind {kind}")).into()),
+            }
+        }
+
         (self.deserialize_in_place)(self.deserialize, ctx, component, cursor)
     }
 
@@ -151,10 +987,88 @@ impl<C: Component> RuleFns<C> {
         ctx: &mut WriteCtx,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
+        if self.delta.is_some() {
+            let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            match kind {
+                FULL => (),
+                DELTA => {
+                    read_diff(cursor)?;
+                    return Ok(());
+                }
+                _ => return Err(bincode::ErrorKind::Custom(format!("unknown kind {kind}")).into()),
+            }
+        }
+
         (self.consume)(self.deserialize, ctx, cursor)
     }
 }
 
+/// Marks the whole value of a component with [`RuleFns::with_delta`].
+const FULL: u8 = 0;
+
+/// Marks the difference for a component with [`RuleFns::with_delta`].
+const DELTA: u8 = 1;
+
+/// Reads the size-prefixed difference written by [`RuleFns::serialize_delta`] and advances the cursor past it.
+fn read_diff<'a>(cursor: &mut Cursor<&'a [u8]>) -> bincode::Result<&'a [u8]> {
+    let len: usize = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let message: &'a [u8] = cursor.get_ref();
+    let start = cursor.position() as usize;
+    let diff = start
+        .checked_add(len)
+        .and_then(|end| message.get(start..end))
+        .ok_or_else(|| {
+            bincode::ErrorKind::Custom(format!("difference size {len} exceeds the message"))
+        })?;
+    cursor.set_position((start + len) as u64);
+
+    Ok(diff)
+}
+
+/// Functions for [`RuleFns::with_delta`].
+struct DeltaFns<C> {
+    diff: DiffFn<C>,
+    apply_diff: ApplyDiffFn<C>,
+    clone: fn(&C) -> C,
+}
+
+impl<C> Clone for DeltaFns<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for DeltaFns<C> {}
+
+/// Type-erased version of [`DeltaFns`].
+#[derive(Clone, Copy)]
+struct UntypedDeltaFns {
+    diff: unsafe fn(),
+    apply_diff: unsafe fn(),
+    clone: unsafe fn(),
+}
+
+/// Functions for [`RuleFns::with_should_send`].
+struct FilterFns<C> {
+    should_send: ShouldSendFn<C>,
+    clone: fn(&C) -> C,
+}
+
+impl<C> Clone for FilterFns<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for FilterFns<C> {}
+
+/// Type-erased version of [`FilterFns`].
+#[derive(Clone, Copy)]
+struct UntypedFilterFns {
+    should_send: unsafe fn(),
+    clone: unsafe fn(),
+}
+
 impl<C: Component + Serialize + DeserializeOwned + MapEntities> RuleFns<C> {
     /// Like [`Self::default`], but uses a special deserialization function to map server
     /// entities inside the component into client entities.
@@ -174,13 +1088,50 @@ impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     ///
     /// See also [`default_serialize`], [`default_deserialize`] and [`in_place_as_deserialize`].
     fn default() -> Self {
-        Self::new(default_serialize::<C>, default_deserialize::<C>)
+        Self {
+            unmapped: true,
+            ..Self::new(default_serialize::<C>, default_deserialize::<C>)
+        }
     }
 }
 
 /// Signature of component serialization functions.
 pub type SerializeFn<C> = fn(&SerializeCtx, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
 
+/// Signature of functions that serialize the difference between a base and the current component value.
+///
+/// See [`RuleFns::with_delta`].
+pub type DiffFn<C> = fn(&SerializeCtx, &C, &C, &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
+
+/// Signature of functions that apply a difference serialized by [`DiffFn`].
+///
+/// See [`RuleFns::with_delta`].
+pub type ApplyDiffFn<C> = fn(&mut WriteCtx, &mut C, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+
+/// Signature of component interception functions.
+///
+/// See [`RuleFns::with_intercept`].
+pub type InterceptFn<C> = fn(&SerializeCtx, &C) -> Option<C>;
+
+/// Signature of functions that resolve conflicts between local and received values.
+///
+/// Receives the local value and the received value.
+///
+/// See [`RuleFns::with_conflict_resolution`].
+pub type ResolveConflictFn<C> = fn(&mut WriteCtx, &mut C, C);
+
+/// Signature of functions that decide whether a change should be sent.
+///
+/// Receives the current value and the last sent value.
+///
+/// See [`RuleFns::with_should_send`].
+pub type ShouldSendFn<C> = fn(&C, &C) -> bool;
+
+/// Signature of replication condition functions.
+///
+/// See [`RuleFns::with_condition`].
+pub type ConditionFn = fn(EntityRef) -> bool;
+
 /// Signature of component deserialization functions.
 pub type DeserializeFn<C> = fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<C>;
 
@@ -192,6 +1143,68 @@ pub type DeserializeInPlaceFn<C> =
 pub type ConsumeFn<C> =
     fn(DeserializeFn<C>, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>;
 
+/// Serialization function for components that are serialized by their group.
+fn group_member_serialize<C: Component>(
+    _ctx: &SerializeCtx,
+    _component: &C,
+    _cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    Err(bincode::ErrorKind::Custom(format!(
+        "`{}` should be serialized by its group",
+        any::type_name::<C>()
+    ))
+    .into())
+}
+
+/// Deserialization function for components that are deserialized by their group.
+fn group_member_deserialize<C: Component>(
+    _ctx: &mut WriteCtx,
+    _cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    Err(bincode::ErrorKind::Custom(format!(
+        "`{}` should be deserialized by its group",
+        any::type_name::<C>()
+    ))
+    .into())
+}
+
+/// Serialization function for components that are serialized by their view.
+fn view_serialize<C: Component>(
+    _ctx: &SerializeCtx,
+    _component: &C,
+    _cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    Err(bincode::ErrorKind::Custom(format!(
+        "`{}` should be serialized by its view",
+        any::type_name::<C>()
+    ))
+    .into())
+}
+
+/// Deserialization function for components that are deserialized by their view.
+fn view_deserialize<C: Component>(
+    _ctx: &mut WriteCtx,
+    _cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    Err(bincode::ErrorKind::Custom(format!(
+        "`{}` should be deserialized by its view",
+        any::type_name::<C>()
+    ))
+    .into())
+}
+
+/// Conflict resolution function that overwrites the local value with the received one.
+///
+/// Same as the behavior without conflict resolution, but could be used inside custom resolution functions.
+pub fn server_wins<C: Component>(_ctx: &mut WriteCtx, local: &mut C, received: C) {
+    *local = received;
+}
+
+/// Conflict resolution function that keeps the local value and discards the received one.
+///
+/// The next received update will overwrite the local value unless it's changed locally again.
+pub fn client_wins<C: Component>(_ctx: &mut WriteCtx, _local: &mut C, _received: C) {}
+
 /// Default component serialization function.
 pub fn default_serialize<C: Component + Serialize>(
     _ctx: &SerializeCtx,
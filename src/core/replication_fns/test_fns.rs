@@ -86,7 +86,7 @@ pub trait TestFnsEntityExt {
     /// See also [`AppMarkerExt`](crate::core::command_markers::AppMarkerExt).
     fn apply_remove(&mut self, fns_info: FnsInfo, message_tick: RepliconTick) -> &mut Self;
 
-    /// Despawns an entity using [`ReplicationFns::despawn`].
+    /// Despawns an entity using [`ReplicationFns::despawn`] or a despawn function of a marker present on it.
     fn apply_despawn(self, message_tick: RepliconTick);
 }
 
@@ -96,7 +96,11 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         let (component_fns, rule_fns) = replication_fns.get(fns_info.fns_id());
         let server_tick = **self.world().resource::<ServerTick>();
         let mut cursor = Cursor::default();
-        let ctx = SerializeCtx { server_tick };
+        let ctx = SerializeCtx::new(
+            server_tick,
+            EntityRef::from(&*self),
+            self.world().read_change_tick(),
+        );
         let ptr = self.get_by_id(fns_info.component_id()).unwrap_or_else(|| {
             let components = self.world().components();
             let component_name = components
@@ -107,7 +111,7 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
 
         unsafe {
             component_fns
-                .serialize(&ctx, rule_fns, ptr, &mut cursor)
+                .serialize(&ctx, rule_fns, &[ptr], &mut cursor)
                 .expect("serialization into memory should never fail");
         }
 
@@ -175,10 +179,10 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
                 let mut queue = CommandQueue::default();
                 let mut commands = Commands::new_from_entities(&mut queue, world_cell.entities());
 
-                let (component_fns, _) = replication_fns.get(fns_info.fns_id());
+                let (component_fns, rule_fns) = replication_fns.get(fns_info.fns_id());
                 let mut ctx = RemoveCtx::new(&mut commands, message_tick);
 
-                component_fns.remove(&mut ctx, &entity_markers, &mut entity);
+                component_fns.remove(&mut ctx, rule_fns, &entity_markers, &mut entity);
 
                 queue.apply(world);
             })
@@ -187,9 +191,14 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         self
     }
 
-    fn apply_despawn(self, message_tick: RepliconTick) {
+    fn apply_despawn(mut self, message_tick: RepliconTick) {
+        let mut entity_markers = self.world_scope(EntityMarkers::from_world);
+        let command_markers = self.world().resource::<CommandMarkers>();
+        entity_markers.read(command_markers, &self);
+
         let replication_fns = self.world().resource::<ReplicationFns>();
+        let despawn = replication_fns.despawn_fn(&entity_markers);
         let ctx = DespawnCtx { message_tick };
-        (replication_fns.despawn)(&ctx, self);
+        (despawn)(&ctx, self);
     }
 }
@@ -0,0 +1,138 @@
+use std::io::Cursor;
+
+use bevy::{prelude::*, ptr::Ptr};
+
+use super::{
+    command_fns::{self, RemoveFn},
+    ctx::{RemoveCtx, SerializeCtx, WriteCtx},
+    rule_fns::{RuleFns, UntypedRuleFns},
+};
+
+/// Functions to replicate a server component as a different component on clients.
+///
+/// Stored inside [`UntypedRuleFns`] of the server component.
+/// See [`AppRuleExt::replicate_as`](crate::core::replication_rules::AppRuleExt::replicate_as).
+pub(crate) struct UntypedViewFns {
+    /// Functions for the client component.
+    rule_fns: UntypedRuleFns,
+
+    serialize: UntypedViewSerializeFn,
+    write: UntypedViewWriteFn,
+    consume: UntypedViewConsumeFn,
+    remove: RemoveFn,
+}
+
+impl UntypedViewFns {
+    /// Creates functions that convert `S` into `C` on server and use `rule_fns` to replicate `C`.
+    pub(super) fn new<S, C>(rule_fns: RuleFns<C>) -> Self
+    where
+        S: Component,
+        C: Component + for<'a> From<&'a S>,
+    {
+        Self {
+            rule_fns: rule_fns.into(),
+            serialize: untyped_serialize::<S, C>,
+            write: untyped_write::<C>,
+            consume: untyped_consume::<C>,
+            remove: command_fns::default_remove::<C>,
+        }
+    }
+
+    /// Converts the server component and serializes the result.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` points to the server component for which this instance was created.
+    pub(crate) unsafe fn serialize(
+        &self,
+        ctx: &SerializeCtx,
+        ptr: Ptr,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        (self.serialize)(&self.rule_fns, ctx, ptr, cursor)
+    }
+
+    /// Deserializes the client component and inserts it into the entity or updates the existing one.
+    pub(crate) fn write(
+        &self,
+        ctx: &mut WriteCtx,
+        entity: &mut EntityMut,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        // SAFETY: functions were created for the same type.
+        unsafe { (self.write)(&self.rule_fns, ctx, entity, cursor) }
+    }
+
+    /// Deserializes the client component and discards it.
+    pub(crate) fn consume(
+        &self,
+        ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        // SAFETY: functions were created for the same type.
+        unsafe { (self.consume)(&self.rule_fns, ctx, cursor) }
+    }
+
+    /// Removes the client component from the entity.
+    pub(crate) fn remove(&self, ctx: &mut RemoveCtx, entity: &mut EntityMut) {
+        (self.remove)(ctx, entity)
+    }
+}
+
+/// Signature of view serialization functions that restore the original types.
+type UntypedViewSerializeFn =
+    unsafe fn(&UntypedRuleFns, &SerializeCtx, Ptr, &mut Cursor<Vec<u8>>) -> bincode::Result<()>;
+
+/// Signature of view writing functions that restore the original type.
+type UntypedViewWriteFn = unsafe fn(
+    &UntypedRuleFns,
+    &mut WriteCtx,
+    &mut EntityMut,
+    &mut Cursor<&[u8]>,
+) -> bincode::Result<()>;
+
+/// Signature of view consuming functions that restore the original type.
+type UntypedViewConsumeFn =
+    unsafe fn(&UntypedRuleFns, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+
+/// Dereferences the server component from a pointer, converts it and serializes the result.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` points to `S` and `rule_fns` were created for `C`.
+unsafe fn untyped_serialize<S: Component, C: Component + for<'a> From<&'a S>>(
+    rule_fns: &UntypedRuleFns,
+    ctx: &SerializeCtx,
+    ptr: Ptr,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let component = C::from(ptr.deref::<S>());
+    rule_fns.typed::<C>().serialize(ctx, &component, cursor)
+}
+
+/// Resolves `rule_fns` to `C` and writes it with [`command_fns::default_write`].
+///
+/// # Safety
+///
+/// The caller must ensure that `rule_fns` were created for `C`.
+unsafe fn untyped_write<C: Component>(
+    rule_fns: &UntypedRuleFns,
+    ctx: &mut WriteCtx,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    command_fns::default_write::<C>(ctx, &rule_fns.typed::<C>(), entity, cursor)
+}
+
+/// Resolves `rule_fns` to `C` and consumes it.
+///
+/// # Safety
+///
+/// The caller must ensure that `rule_fns` were created for `C`.
+unsafe fn untyped_consume<C: Component>(
+    rule_fns: &UntypedRuleFns,
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    rule_fns.typed::<C>().consume(ctx, cursor)
+}
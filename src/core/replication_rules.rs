@@ -7,7 +7,12 @@ use bevy::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::replication_fns::{rule_fns::RuleFns, FnsInfo, ReplicationFns};
+use super::replication_fns::{
+    group_fns::{ComponentGroup, GroupFns},
+    rule_fns::RuleFns,
+    FnsInfo, ReplicationFns,
+};
+use crate::component_subscriptions::SubscriptionPlugin;
 
 /// Replication functions for [`App`].
 pub trait AppRuleExt {
@@ -64,6 +69,117 @@ pub trait AppRuleExt {
         self.replicate_with::<C>(RuleFns::default_mapped())
     }
 
+    /**
+    Same as [`Self::replicate`], but the component will be sent only to the client
+    from the entity's [`Owner`](super::Owner).
+
+    Useful for private components, such as inventory or quest log, that should be visible
+    only to their owner while the entity itself is visible to everyone.
+
+    For components with entities or custom functions use [`RuleFns::with_owner_only`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::Owner, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Player>().replicate_to_owner::<Inventory>();
+
+    # let client_id = ClientId::new(1);
+    app.world.spawn((Replicated, Owner(client_id), Player, Inventory(Vec::new())));
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Player;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Inventory(Vec<u32>);
+    ```
+    **/
+    fn replicate_to_owner<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        self.replicate_with::<C>(RuleFns::default().with_owner_only())
+    }
+
+    /**
+    Same as [`Self::replicate`], but the component will be sent only to clients
+    that subscribed to it using [`ComponentSubscriptions`](crate::component_subscriptions::ComponentSubscriptions).
+
+    Useful for heavy opt-in data, such as debug-only components or spectator extras,
+    without separate builds.
+
+    For components with entities or custom functions use [`RuleFns::with_optional`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.replicate::<Player>()
+        .replicate_optional::<PathDebug>()
+        .add_systems(Startup, enable_debug);
+
+    /// Requests debug data from server on client.
+    fn enable_debug(mut subscriptions: ResMut<ComponentSubscriptions>) {
+        subscriptions.subscribe::<PathDebug>();
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Player;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct PathDebug(Vec<(f32, f32)>);
+    ```
+    **/
+    fn replicate_optional<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        self.replicate_with::<C>(RuleFns::default().with_optional())
+    }
+
+    /**
+    Same as [`Self::replicate`], but the component will be sent only on initialization and never updated.
+
+    The component is included when a client receives the entity or the component for the first time.
+    Later changes on server are ignored and skipped during change detection.
+
+    For components with entities or custom functions use [`RuleFns::with_once`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Player>().replicate_once::<MeshId>();
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Player;
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct MeshId(u32);
+    ```
+    **/
+    fn replicate_once<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned,
+    {
+        self.replicate_with::<C>(RuleFns::default().with_once())
+    }
+
     /**
     Same as [`Self::replicate`], but uses the specified functions for serialization and deserialization.
 
@@ -157,6 +273,112 @@ pub trait AppRuleExt {
     ```
     **/
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate_group`], but serializes all components together using the specified functions.
+
+    Useful for correlated data that can be packed more efficiently as a whole,
+    since the group is written as a single value instead of a value for each component.
+    The group is serialized whenever any of its components changes.
+
+    Components are written on clients directly, so custom writing functions from
+    [`AppMarkerExt`](super::command_markers::AppMarkerExt) are not used for them.
+    Group components shouldn't be replicated by other rules with the same or higher priority.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            group_fns::GroupFns,
+        },
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_group_with(GroupFns::<(Transform, Velocity)>::new(
+        serialize_movement,
+        deserialize_movement,
+    ));
+
+    /// Serializes only `translation` from [`Transform`] and velocity with reduced precision.
+    fn serialize_movement(
+        _ctx: &SerializeCtx,
+        (transform, velocity): (&Transform, &Velocity),
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        let velocity = velocity.0.to_array().map(|value| (value * 100.0) as i16);
+        bincode::serialize_into(cursor, &(transform.translation, velocity))
+    }
+
+    /// Deserializes both components.
+    fn deserialize_movement(
+        _ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<(Transform, Velocity)> {
+        let (translation, velocity): (Vec3, [i16; 3]) = bincode::deserialize_from(cursor)?;
+        Ok((
+            Transform::from_translation(translation),
+            Velocity(Vec3::from_array(velocity.map(|value| value as f32 / 100.0))),
+        ))
+    }
+
+    #[derive(Component)]
+    struct Velocity(Vec3);
+    ```
+    **/
+    fn replicate_group_with<G: ComponentGroup>(&mut self, group_fns: GroupFns<G>) -> &mut Self;
+
+    /**
+    Creates a replication rule for server component `S` that will be replicated as component `C` on clients.
+
+    Useful when clients need only a part of the server data or a different representation of it.
+    On server `S` is converted into `C` using its [`From`] implementation before serialization,
+    so the conversion happens for each send. Clients never receive `S`, only `C`.
+    Removal of `S` removes `C` on clients.
+
+    `C` is written on clients directly, so custom writing functions from
+    [`AppMarkerExt`](super::command_markers::AppMarkerExt) are not used for it.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_as::<Health, HealthBar>();
+
+    /// Exact health, known only to the server.
+    #[derive(Component)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    /// Health ratio for display on clients.
+    #[derive(Component, Deserialize, Serialize)]
+    struct HealthBar(u8);
+
+    impl From<&Health> for HealthBar {
+        fn from(health: &Health) -> Self {
+            Self((health.current * 100 / health.max) as u8)
+        }
+    }
+    ```
+    **/
+    fn replicate_as<S, C>(&mut self) -> &mut Self
+    where
+        S: Component,
+        C: Component + Serialize + DeserializeOwned + for<'a> From<&'a S>;
 }
 
 impl AppRuleExt for App {
@@ -164,29 +386,61 @@ impl AppRuleExt for App {
     where
         C: Component,
     {
-        let rule =
-            self.world_mut()
-                .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
-                    let fns_info = replication_fns.register_rule_fns(world, rule_fns);
-                    ReplicationRule::new(vec![fns_info])
-                });
+        if rule_fns.optional() && !self.is_plugin_added::<SubscriptionPlugin>() {
+            self.add_plugins(SubscriptionPlugin);
+        }
 
         self.world_mut()
-            .resource_mut::<ReplicationRules>()
-            .insert(rule);
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                let fns_info = replication_fns.register_rule_fns(world, rule_fns);
+                let rule = ReplicationRule::new(vec![fns_info]);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(rule, &replication_fns);
+            });
+
         self
     }
 
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self {
-        let rule =
-            self.world_mut()
-                .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
-                    C::register(world, &mut replication_fns)
-                });
+        self.world_mut()
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                let rule = C::register(world, &mut replication_fns);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(rule, &replication_fns);
+            });
 
+        self
+    }
+
+    fn replicate_group_with<G: ComponentGroup>(&mut self, group_fns: GroupFns<G>) -> &mut Self {
         self.world_mut()
-            .resource_mut::<ReplicationRules>()
-            .insert(rule);
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                let components = replication_fns.register_group_fns(world, group_fns);
+                let rule = ReplicationRule::new(components);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(rule, &replication_fns);
+            });
+
+        self
+    }
+
+    fn replicate_as<S, C>(&mut self) -> &mut Self
+    where
+        S: Component,
+        C: Component + Serialize + DeserializeOwned + for<'a> From<&'a S>,
+    {
+        self.world_mut()
+            .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+                let fns_info = replication_fns.register_view_fns::<S, C>(world, RuleFns::default());
+                let rule = ReplicationRule::new(vec![fns_info]);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(rule, &replication_fns);
+            });
+
         self
     }
 }
@@ -197,9 +451,13 @@ pub(crate) struct ReplicationRules(Vec<ReplicationRule>);
 
 impl ReplicationRules {
     /// Inserts a new rule, maintaining sorting by their priority in descending order.
-    fn insert(&mut self, rule: ReplicationRule) {
+    ///
+    /// Rules with the same priority are sorted by stable IDs of their functions
+    /// to make the order independent of the registration order.
+    fn insert(&mut self, rule: ReplicationRule, replication_fns: &ReplicationFns) {
+        let key = rule.sort_key(replication_fns);
         let index = self
-            .binary_search_by_key(&Reverse(rule.priority), |rule| Reverse(rule.priority))
+            .binary_search_by(|other| other.sort_key(replication_fns).cmp(&key))
             .unwrap_or_else(|index| index);
 
         self.0.insert(index, rule);
@@ -227,6 +485,17 @@ impl ReplicationRule {
         }
     }
 
+    /// Returns a key for sorting by priority in descending order and then by stable IDs.
+    fn sort_key(&self, replication_fns: &ReplicationFns) -> (Reverse<usize>, Vec<u64>) {
+        let stable_ids = self
+            .components
+            .iter()
+            .map(|fns_info| replication_fns.stable_id(fns_info.fns_id()))
+            .collect();
+
+        (Reverse(self.priority), stable_ids)
+    }
+
     /// Determines whether an archetype contains all components required by the rule.
     pub(crate) fn matches(&self, archetype: &Archetype) -> bool {
         self.components
@@ -367,6 +636,42 @@ mod tests {
         assert_eq!(priorities, [2, 2, 1, 1, 1, 1]);
     }
 
+    #[test]
+    fn registration_order() {
+        let mut app_a = App::new();
+        app_a
+            .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate::<ComponentA>()
+            .replicate::<ComponentB>()
+            .replicate_group::<(ComponentC, ComponentD)>();
+
+        let mut app_b = App::new();
+        app_b
+            .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationFns>()
+            .replicate_group::<(ComponentC, ComponentD)>()
+            .replicate::<ComponentB>()
+            .replicate::<ComponentA>();
+
+        assert_eq!(stable_rules(&app_a), stable_rules(&app_b));
+    }
+
+    /// Returns stable IDs of rule functions in the order of rules.
+    fn stable_rules(app: &App) -> Vec<Vec<u64>> {
+        let replication_fns = app.world().resource::<ReplicationFns>();
+        app.world()
+            .resource::<ReplicationRules>()
+            .iter()
+            .map(|rule| {
+                rule.components
+                    .iter()
+                    .map(|fns_info| replication_fns.stable_id(fns_info.fns_id()))
+                    .collect()
+            })
+            .collect()
+    }
+
     #[derive(Serialize, Deserialize, Component)]
     struct ComponentA;
 
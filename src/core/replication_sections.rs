@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use super::{replicon_tick::RepliconTick, ClientId};
+
+/**
+Custom sections that will be appended to the init message of each tick.
+
+Intended for third-party crates that need to send data along with replication,
+for example, voice positions or crate-specific state. Sections piggyback on the tick framing
+of init messages, so they are delivered reliably and applied on client right after
+all insertions from the same tick.
+
+On server the write function is called for each connected client during sending
+replication. If it doesn't write anything, the section is skipped for this client.
+On client the apply function is called with the server tick of the message and the written bytes.
+Resources used for receiving, such as [`RepliconClient`](crate::client::replicon_client::RepliconClient)
+or [`ServerEntityMap`](crate::client::server_entity_map::ServerEntityMap), are temporarily
+removed from the world and not available inside apply functions.
+
+Sections are identified by their names, so registration order doesn't affect the protocol.
+Both server and client should register the same sections.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::replicon_tick::RepliconTick, prelude::*};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.world
+    .resource_mut::<ReplicationSections>()
+    .register("weather", write_weather, apply_weather);
+
+fn write_weather(world: &World, _client_id: ClientId, bytes: &mut Vec<u8>) {
+    if let Some(weather) = world.get_resource::<Weather>() {
+        bytes.push(weather.0);
+    }
+}
+
+fn apply_weather(world: &mut World, _tick: RepliconTick, bytes: &[u8]) {
+    if let Some(&value) = bytes.first() {
+        world.insert_resource(Weather(value));
+    }
+}
+
+#[derive(Resource)]
+struct Weather(u8);
+```
+**/
+#[derive(Resource, Default)]
+pub struct ReplicationSections(Vec<ReplicationSection>);
+
+impl ReplicationSections {
+    /// Registers a new section.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a section with the same name is already registered
+    /// or if more than [`u8::MAX`] sections are registered.
+    pub fn register(&mut self, name: &'static str, write: SectionWriteFn, apply: SectionApplyFn) {
+        let index = match self.0.binary_search_by_key(&name, |section| section.name) {
+            Ok(_) => panic!("section `{name}` should be registered only once"),
+            Err(index) => index,
+        };
+        assert!(
+            self.0.len() < u8::MAX as usize,
+            "there should be no more than {} sections",
+            u8::MAX
+        );
+
+        self.0
+            .insert(index, ReplicationSection { name, write, apply });
+    }
+
+    /// Returns `true` if no sections are registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns all sections with their network IDs.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (u8, &ReplicationSection)> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, section)| (index as u8, section))
+    }
+
+    /// Returns a section by its network ID.
+    pub(crate) fn get(&self, id: u8) -> Option<&ReplicationSection> {
+        self.0.get(id as usize)
+    }
+}
+
+/// Functions for a single section.
+///
+/// See [`ReplicationSections`].
+pub(crate) struct ReplicationSection {
+    pub(crate) name: &'static str,
+    pub(crate) write: SectionWriteFn,
+    pub(crate) apply: SectionApplyFn,
+}
+
+/// Signature of section writing functions.
+pub type SectionWriteFn = fn(&World, ClientId, &mut Vec<u8>);
+
+/// Signature of section applying functions.
+pub type SectionApplyFn = fn(&mut World, RepliconTick, &[u8]);
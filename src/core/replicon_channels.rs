@@ -5,6 +5,7 @@ use bevy::prelude::*;
 /// ID of a server replication channel.
 ///
 /// See also [`RepliconChannels`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ReplicationChannel {
     /// For sending messages with entity mappings, inserts, removals and despawns.
@@ -73,6 +74,10 @@ impl RepliconChannels {
 
     /// Creates a new server channel and returns its ID.
     ///
+    /// IDs are assigned in the order of calls. Channels of Replicon plugins and events
+    /// are assigned when plugins are finished in the order of their keys,
+    /// so messaging backends should read channels after that.
+    ///
     /// # Panics
     ///
     /// Panics if the number of events exceeds [`u8::MAX`].
@@ -85,7 +90,7 @@ impl RepliconChannels {
         self.server.len() as u8 - 1
     }
 
-    /// Creates a new client channel and returns its ID.
+    /// Same as [`Self::create_server_channel`], but for client.
     ///
     /// # Panics
     ///
@@ -128,6 +133,141 @@ impl RepliconChannels {
     }
 }
 
+/// Channels registered by stable keys.
+///
+/// IDs are assigned once in [`Plugin::finish`] of [`RepliconCorePlugin`](super::RepliconCorePlugin)
+/// in the order of keys, so they don't depend on the registration order of plugins and events.
+/// If the app is updated without finishing plugins, like in tests, they are assigned in [`PreStartup`].
+#[derive(Resource, Default)]
+pub(crate) struct KeyedChannels {
+    /// Server channels, sorted by keys after assignment.
+    server: Vec<KeyedChannel>,
+
+    /// Same as [`Self::server`], but for client.
+    client: Vec<KeyedChannel>,
+
+    /// Indicates that IDs have been assigned.
+    assigned: bool,
+}
+
+impl KeyedChannels {
+    /// Registers a server channel with a stable key, such as [`TypePath::type_path`] of its message.
+    ///
+    /// `set_id` will be called once with the assigned ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if IDs have already been assigned or a server channel with this key is already registered.
+    pub(crate) fn register_server(
+        world: &mut World,
+        key: &'static str,
+        channel: RepliconChannel,
+        set_id: fn(&mut World, u8),
+    ) {
+        world.init_resource::<Self>();
+        let mut keyed_channels = world.resource_mut::<Self>();
+        keyed_channels.check_registration(key);
+        if keyed_channels.server.iter().any(|keyed| keyed.key == key) {
+            panic!("server channel `{key}` should be registered only once");
+        }
+
+        keyed_channels
+            .server
+            .push(KeyedChannel::new(key, channel, set_id));
+    }
+
+    /// Same as [`Self::register_server`], but for client.
+    pub(crate) fn register_client(
+        world: &mut World,
+        key: &'static str,
+        channel: RepliconChannel,
+        set_id: fn(&mut World, u8),
+    ) {
+        world.init_resource::<Self>();
+        let mut keyed_channels = world.resource_mut::<Self>();
+        keyed_channels.check_registration(key);
+        if keyed_channels.client.iter().any(|keyed| keyed.key == key) {
+            panic!("client channel `{key}` should be registered only once");
+        }
+
+        keyed_channels
+            .client
+            .push(KeyedChannel::new(key, channel, set_id));
+    }
+
+    fn check_registration(&self, key: &str) {
+        if self.assigned {
+            panic!("channel `{key}` should be registered before plugins are finished");
+        }
+    }
+
+    /// Creates registered channels sorted by keys and passes their IDs to `set_id` callbacks.
+    ///
+    /// Does nothing if IDs have already been assigned.
+    pub(crate) fn assign_ids(world: &mut World) {
+        world.init_resource::<Self>();
+        world.resource_scope(|world, mut keyed_channels: Mut<Self>| {
+            if keyed_channels.assigned {
+                return;
+            }
+            keyed_channels.assigned = true;
+
+            keyed_channels
+                .server
+                .sort_unstable_by_key(|keyed| keyed.key);
+            keyed_channels
+                .client
+                .sort_unstable_by_key(|keyed| keyed.key);
+
+            let mut replicon_channels = world.resource_mut::<RepliconChannels>();
+            for keyed in &mut keyed_channels.server {
+                keyed.id = replicon_channels.create_server_channel(keyed.channel.clone());
+            }
+            for keyed in &mut keyed_channels.client {
+                keyed.id = replicon_channels.create_client_channel(keyed.channel.clone());
+            }
+
+            for keyed in keyed_channels.server.iter().chain(&keyed_channels.client) {
+                (keyed.set_id)(world, keyed.id);
+            }
+        });
+    }
+
+    /// Returns the ID of a client channel by its key.
+    pub(crate) fn client_id(&self, key: &str) -> Option<u8> {
+        self.client
+            .iter()
+            .find(|keyed| self.assigned && keyed.key == key)
+            .map(|keyed| keyed.id)
+    }
+
+    /// Returns the key of a client channel by its ID.
+    pub(crate) fn client_key(&self, id: u8) -> Option<&'static str> {
+        self.client
+            .iter()
+            .find(|keyed| self.assigned && keyed.id == id)
+            .map(|keyed| keyed.key)
+    }
+}
+
+struct KeyedChannel {
+    key: &'static str,
+    channel: RepliconChannel,
+    id: u8,
+    set_id: fn(&mut World, u8),
+}
+
+impl KeyedChannel {
+    fn new(key: &'static str, channel: RepliconChannel, set_id: fn(&mut World, u8)) -> Self {
+        Self {
+            key,
+            channel,
+            id: 0,
+            set_id,
+        }
+    }
+}
+
 /// Channel configuration.
 #[derive(Clone)]
 pub struct RepliconChannel {
@@ -167,3 +307,67 @@ impl From<ChannelKind> for RepliconChannel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_ids() {
+        let mut world = World::new();
+        world.init_resource::<RepliconChannels>();
+        KeyedChannels::register_server(&mut world, "b", ChannelKind::Ordered.into(), set_b);
+        KeyedChannels::register_server(&mut world, "a", ChannelKind::Unordered.into(), set_a);
+        KeyedChannels::assign_ids(&mut world);
+
+        let mut reordered_world = World::new();
+        reordered_world.init_resource::<RepliconChannels>();
+        KeyedChannels::register_server(
+            &mut reordered_world,
+            "a",
+            ChannelKind::Unordered.into(),
+            set_a,
+        );
+        KeyedChannels::register_server(
+            &mut reordered_world,
+            "b",
+            ChannelKind::Ordered.into(),
+            set_b,
+        );
+        KeyedChannels::assign_ids(&mut reordered_world);
+
+        for world in [&world, &reordered_world] {
+            assert_eq!(**world.resource::<ChannelA>(), 2);
+            assert_eq!(**world.resource::<ChannelB>(), 3);
+
+            let replicon_channels = world.resource::<RepliconChannels>();
+            assert_eq!(
+                replicon_channels.server_channels()[2].kind,
+                ChannelKind::Unordered
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn registration_after_assignment() {
+        let mut world = World::new();
+        world.init_resource::<RepliconChannels>();
+        KeyedChannels::assign_ids(&mut world);
+        KeyedChannels::register_server(&mut world, "a", ChannelKind::Ordered.into(), set_a);
+    }
+
+    fn set_a(world: &mut World, id: u8) {
+        world.insert_resource(ChannelA(id));
+    }
+
+    fn set_b(world: &mut World, id: u8) {
+        world.insert_resource(ChannelB(id));
+    }
+
+    #[derive(Resource, Deref)]
+    struct ChannelA(u8);
+
+    #[derive(Resource, Deref)]
+    struct ChannelB(u8);
+}
@@ -0,0 +1,72 @@
+use std::io::Cursor;
+
+use bincode::{DefaultOptions, Options};
+
+/// Serializes indices of received update messages into a single acknowledgment message.
+///
+/// The first index is written as is and each next index as a signed difference from the previous one.
+/// Update messages usually arrive in order, so the differences are small and take a single byte
+/// thanks to variable-length encoding. Reordered or wrapped indices are still encoded correctly.
+pub(crate) fn serialize_acks(indices: &[u16]) -> bincode::Result<Vec<u8>> {
+    let mut message = Vec::with_capacity(indices.len() + 2);
+    let mut previous = None;
+    for &index in indices {
+        match previous {
+            None => DefaultOptions::new().serialize_into(&mut message, &index)?,
+            Some(previous) => {
+                let delta = index.wrapping_sub(previous) as i16;
+                DefaultOptions::new().serialize_into(&mut message, &delta)?
+            }
+        }
+        previous = Some(index);
+    }
+
+    Ok(message)
+}
+
+/// Deserializes indices written by [`serialize_acks`].
+pub(crate) fn deserialize_acks(message: &[u8]) -> bincode::Result<Vec<u16>> {
+    let mut cursor = Cursor::new(message);
+    let message_end = message.len() as u64;
+    let mut indices = Vec::new();
+    while cursor.position() < message_end {
+        let index = match indices.last() {
+            None => DefaultOptions::new().deserialize_from(&mut cursor)?,
+            Some(&previous) => {
+                let delta: i16 = DefaultOptions::new().deserialize_from(&mut cursor)?;
+                previous.wrapping_add(delta as u16)
+            }
+        };
+        indices.push(index);
+    }
+
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> bincode::Result<()> {
+        let indices = [5, 6, 7, 9, 8, u16::MAX, 0, 1];
+        let message = serialize_acks(&indices)?;
+        assert_eq!(
+            message.len(),
+            indices.len(),
+            "each index should take a single byte"
+        );
+        assert_eq!(deserialize_acks(&message)?, indices);
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> bincode::Result<()> {
+        let message = serialize_acks(&[])?;
+        assert!(message.is_empty());
+        assert!(deserialize_acks(&message)?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,505 @@
+use std::{any, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, client_just_connected, server_running},
+        replicon_channels::{ChannelKind, KeyedChannels},
+        ClientId,
+    },
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, ServerEvent, ServerPlugin, ServerSet,
+    },
+};
+
+/**
+Runs a sequence of typed handshake stages after each connection.
+
+Stages are registered via [`HandshakeAppExt::add_handshake_stage`] by messaging backends or user plugins
+and executed in the registration order. For each stage the client sends a request,
+and the server validates it and responds. The next stage starts only after the previous one was accepted.
+
+On server, progress for each client is tracked in [`Handshakes`] and results are emitted as [`HandshakeEvent`].
+Until the handshake completes, the client is [held](crate::server::connected_clients::ConnectedClient::is_held):
+it doesn't receive replication or server events. Clients with failed handshakes stay held.
+Since the core doesn't manage connections, disconnecting clients with failed handshakes is up to the messaging
+backend or user code. On client, progress is available via [`ClientHandshake`].
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients
+with the same stages registered in the same order.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, HandshakePlugin::default()))
+    .add_handshake_stage::<VersionStage>()
+    .add_systems(Update, disconnect_rejected.run_if(server_running));
+
+struct VersionStage;
+
+impl HandshakeStage for VersionStage {
+    type Request = String;
+
+    fn request(_world: &mut World) -> Self::Request {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn validate(_world: &mut World, _client_id: ClientId, version: Self::Request) -> Result<(), String> {
+        if version == env!("CARGO_PKG_VERSION") {
+            Ok(())
+        } else {
+            Err(format!("client version {version} doesn't match the server"))
+        }
+    }
+}
+
+fn disconnect_rejected(mut handshake_events: EventReader<HandshakeEvent>, names: Res<ClientNames>) {
+    for event in handshake_events.read() {
+        if let HandshakeEvent::Rejected { client_id, reason } = event {
+            info!("disconnecting {}: {reason}", names.label(*client_id));
+            // Disconnect via the messaging backend.
+        }
+    }
+}
+```
+**/
+pub struct HandshakePlugin {
+    /// Maximum time for a client to complete all stages.
+    ///
+    /// Clients that exceed it will be rejected.
+    pub timeout: Duration,
+}
+
+impl Default for HandshakePlugin {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Plugin for HandshakePlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            HandshakeChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<HandshakeChannels>().server = id,
+        );
+        KeyedChannels::register_client(
+            app.world_mut(),
+            HandshakeChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<HandshakeChannels>().client = id,
+        );
+
+        app.init_resource::<HandshakeStages>()
+            .init_resource::<Handshakes>()
+            .init_resource::<ClientHandshake>()
+            .init_resource::<HandshakeChannels>()
+            .add_event::<HandshakeEvent>()
+            .add_systems(
+                PreUpdate,
+                (
+                    (
+                        Self::start_server,
+                        Self::receive_requests,
+                        Self::check_timeouts(self.timeout),
+                    )
+                        .chain()
+                        .after(ServerPlugin::handle_connections)
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    (
+                        Self::start_client.run_if(client_just_connected),
+                        Self::receive_responses.run_if(client_connected),
+                    )
+                        .chain()
+                        .in_set(ClientSet::Receive),
+                    Self::reset.in_set(ClientSet::Reset),
+                ),
+            );
+    }
+}
+
+impl HandshakePlugin {
+    /// Starts tracking handshakes for newly connected clients and holds them until completion.
+    fn start_server(
+        mut server_events: EventReader<ServerEvent>,
+        mut handshake_events: EventWriter<HandshakeEvent>,
+        mut handshakes: ResMut<Handshakes>,
+        mut connected_clients: ResMut<ConnectedClients>,
+        stages: Res<HandshakeStages>,
+        time: Res<Time<Real>>,
+    ) {
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    if stages.0.is_empty() {
+                        handshakes.insert(client_id, HandshakeStatus::Completed, time.elapsed());
+                        handshake_events.send(HandshakeEvent::Completed { client_id });
+                    } else {
+                        handshakes.insert(
+                            client_id,
+                            HandshakeStatus::Pending { stage: 0 },
+                            time.elapsed(),
+                        );
+                        // Could be already removed if disconnected in the same update.
+                        if let Some(client) = connected_clients.get_client_mut(client_id) {
+                            client.set_held(true);
+                        }
+                    }
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    handshakes.0.remove(&client_id);
+                }
+            }
+        }
+    }
+
+    /// Validates requests from clients and responds to them.
+    fn receive_requests(world: &mut World) {
+        let channels = *world.resource::<HandshakeChannels>();
+        let messages: Vec<_> = world
+            .resource_mut::<RepliconServer>()
+            .receive(channels.client)
+            .collect();
+
+        world.resource_scope(|world, stages: Mut<HandshakeStages>| {
+            for (client_id, message) in messages {
+                let Some(HandshakeStatus::Pending { stage }) =
+                    world.resource::<Handshakes>().status(client_id).cloned()
+                else {
+                    debug!(
+                        "ignoring unexpected handshake request from {}",
+                        world.resource::<ClientNames>().label(client_id)
+                    );
+                    continue;
+                };
+
+                let result = match message.split_first() {
+                    Some((&request_stage, request)) if request_stage == stage => {
+                        let stage_fns = &stages.0[stage as usize];
+                        (stage_fns.validate)(world, client_id, request)
+                    }
+                    _ => Err(format!("expected request for handshake stage {stage}")),
+                };
+
+                let (status, response) = match result {
+                    Ok(()) => {
+                        let status = if stage as usize + 1 == stages.0.len() {
+                            HandshakeStatus::Completed
+                        } else {
+                            HandshakeStatus::Pending { stage: stage + 1 }
+                        };
+                        (status, HandshakeResponse::Accepted { stage })
+                    }
+                    Err(reason) => (
+                        HandshakeStatus::Rejected {
+                            reason: reason.clone(),
+                        },
+                        HandshakeResponse::Rejected { stage, reason },
+                    ),
+                };
+
+                finish_stage(world, channels, client_id, status, response);
+            }
+        });
+    }
+
+    /// Rejects clients that didn't complete all stages in time.
+    fn check_timeouts(timeout: Duration) -> impl FnMut(&mut World) {
+        move |world| {
+            let channels = *world.resource::<HandshakeChannels>();
+            let min_started = world
+                .resource::<Time<Real>>()
+                .elapsed()
+                .saturating_sub(timeout);
+            let expired: Vec<_> = world
+                .resource::<Handshakes>()
+                .0
+                .iter()
+                .filter_map(|(&client_id, handshake)| match handshake.status {
+                    HandshakeStatus::Pending { stage } if handshake.started < min_started => {
+                        Some((client_id, stage))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            for (client_id, stage) in expired {
+                let reason = format!("handshake timed out on stage {stage}");
+                let status = HandshakeStatus::Rejected {
+                    reason: reason.clone(),
+                };
+                let response = HandshakeResponse::Rejected { stage, reason };
+                finish_stage(world, channels, client_id, status, response);
+            }
+        }
+    }
+
+    /// Sends the request for the first stage.
+    fn start_client(world: &mut World) {
+        if world.resource::<HandshakeStages>().0.is_empty() {
+            world.resource_mut::<ClientHandshake>().0 = HandshakeStatus::Completed;
+        } else {
+            send_request(world, 0);
+        }
+    }
+
+    /// Receives responses from the server and sends requests for the next stages.
+    fn receive_responses(world: &mut World) {
+        let channels = *world.resource::<HandshakeChannels>();
+        let messages: Vec<_> = world
+            .resource_mut::<RepliconClient>()
+            .receive(channels.server)
+            .collect();
+
+        for message in messages {
+            let response = match DefaultOptions::new().deserialize(&message) {
+                Ok(response) => response,
+                Err(e) => {
+                    error!("unable to deserialize handshake response: {e}");
+                    continue;
+                }
+            };
+
+            match response {
+                HandshakeResponse::Accepted { stage } => {
+                    debug!("handshake stage {stage} accepted");
+                    if stage as usize + 1 == world.resource::<HandshakeStages>().0.len() {
+                        world.resource_mut::<ClientHandshake>().0 = HandshakeStatus::Completed;
+                    } else {
+                        send_request(world, stage + 1);
+                    }
+                }
+                HandshakeResponse::Rejected { stage, reason } => {
+                    error!("handshake stage {stage} rejected: {reason}");
+                    world.resource_mut::<ClientHandshake>().0 =
+                        HandshakeStatus::Rejected { reason };
+                }
+            }
+        }
+    }
+
+    fn reset(mut handshake: ResMut<ClientHandshake>) {
+        *handshake = Default::default();
+    }
+}
+
+/// Updates the status for a client, notifies it and emits [`HandshakeEvent`] if the handshake is finished.
+///
+/// Releases the client if the handshake is completed.
+fn finish_stage(
+    world: &mut World,
+    channels: HandshakeChannels,
+    client_id: ClientId,
+    status: HandshakeStatus,
+    response: HandshakeResponse,
+) {
+    let message = DefaultOptions::new()
+        .serialize(&response)
+        .expect("handshake response should be serializable");
+    world
+        .resource_mut::<RepliconServer>()
+        .send(client_id, channels.server, message);
+
+    match &status {
+        HandshakeStatus::Pending { .. } => (),
+        HandshakeStatus::Completed => {
+            debug!(
+                "{} completed handshake",
+                world.resource::<ClientNames>().label(client_id)
+            );
+            if let Some(client) = world
+                .resource_mut::<ConnectedClients>()
+                .get_client_mut(client_id)
+            {
+                client.set_held(false);
+            }
+            world.send_event(HandshakeEvent::Completed { client_id });
+        }
+        HandshakeStatus::Rejected { reason } => {
+            debug!(
+                "{} failed handshake: {reason}",
+                world.resource::<ClientNames>().label(client_id)
+            );
+            world.send_event(HandshakeEvent::Rejected {
+                client_id,
+                reason: reason.clone(),
+            });
+        }
+    }
+
+    if let Some(handshake) = world.resource_mut::<Handshakes>().0.get_mut(&client_id) {
+        handshake.status = status;
+    }
+}
+
+/// Creates a request for a stage and sends it to the server.
+fn send_request(world: &mut World, stage: u8) {
+    world.resource_scope(|world, stages: Mut<HandshakeStages>| {
+        let mut message = vec![stage];
+        (stages.0[stage as usize].request)(world, &mut message);
+
+        let channels = *world.resource::<HandshakeChannels>();
+        world
+            .resource_mut::<RepliconClient>()
+            .send(channels.client, message);
+    });
+
+    world.resource_mut::<ClientHandshake>().0 = HandshakeStatus::Pending { stage };
+}
+
+/// An extension trait for [`App`] for registering handshake stages.
+pub trait HandshakeAppExt {
+    /// Appends a stage to the handshake pipeline.
+    ///
+    /// Requires [`HandshakePlugin`]. See its documentation for details.
+    fn add_handshake_stage<S: HandshakeStage>(&mut self) -> &mut Self;
+}
+
+impl HandshakeAppExt for App {
+    fn add_handshake_stage<S: HandshakeStage>(&mut self) -> &mut Self {
+        let mut stages = self.world_mut().resource_mut::<HandshakeStages>();
+        if stages.0.len() == u8::MAX as usize {
+            panic!("number of handshake stages shouldn't exceed `u8::MAX`");
+        }
+
+        stages.0.push(StageFns {
+            request: request::<S>,
+            validate: validate::<S>,
+        });
+
+        self
+    }
+}
+
+/// A single step of the handshake, such as authentication, version check, or join payload.
+///
+/// See [`HandshakePlugin`].
+pub trait HandshakeStage: 'static {
+    /// Data sent from client to server.
+    type Request: Serialize + DeserializeOwned;
+
+    /// Creates a request on client.
+    fn request(world: &mut World) -> Self::Request;
+
+    /// Validates a request on server.
+    ///
+    /// Returned error will be sent to the client as the rejection reason.
+    fn validate(
+        world: &mut World,
+        client_id: ClientId,
+        request: Self::Request,
+    ) -> Result<(), String>;
+}
+
+fn request<S: HandshakeStage>(world: &mut World, message: &mut Vec<u8>) {
+    DefaultOptions::new()
+        .serialize_into(message, &S::request(world))
+        .expect("handshake request should be serializable");
+}
+
+fn validate<S: HandshakeStage>(
+    world: &mut World,
+    client_id: ClientId,
+    message: &[u8],
+) -> Result<(), String> {
+    let request = DefaultOptions::new().deserialize(message).map_err(|e| {
+        format!(
+            "unable to deserialize request for `{}`: {e}",
+            any::type_name::<S>()
+        )
+    })?;
+    S::validate(world, client_id, request)
+}
+
+/// Type-erased functions for a registered [`HandshakeStage`].
+struct StageFns {
+    request: fn(&mut World, &mut Vec<u8>),
+    validate: fn(&mut World, ClientId, &[u8]) -> Result<(), String>,
+}
+
+/// Registered stages in the execution order.
+#[derive(Resource, Default)]
+struct HandshakeStages(Vec<StageFns>);
+
+/// Handshake progress for each connected client on server.
+#[derive(Resource, Default)]
+pub struct Handshakes(HashMap<ClientId, ServerHandshake>);
+
+impl Handshakes {
+    /// Returns the handshake status for a connected client.
+    pub fn status(&self, client_id: ClientId) -> Option<&HandshakeStatus> {
+        self.0.get(&client_id).map(|handshake| &handshake.status)
+    }
+
+    /// Returns `true` if the client completed all stages.
+    pub fn is_completed(&self, client_id: ClientId) -> bool {
+        matches!(self.status(client_id), Some(HandshakeStatus::Completed))
+    }
+
+    fn insert(&mut self, client_id: ClientId, status: HandshakeStatus, started: Duration) {
+        self.0
+            .insert(client_id, ServerHandshake { status, started });
+    }
+}
+
+struct ServerHandshake {
+    status: HandshakeStatus,
+
+    /// Real time when the client connected.
+    started: Duration,
+}
+
+/// Handshake progress on client.
+#[derive(Resource, Default, Deref)]
+pub struct ClientHandshake(HandshakeStatus);
+
+/// Progress of a handshake.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HandshakeStatus {
+    /// Waiting for the stage to be accepted.
+    Pending { stage: u8 },
+    /// All stages were accepted.
+    Completed,
+    /// One of the stages was rejected or timed out.
+    Rejected { reason: String },
+}
+
+impl Default for HandshakeStatus {
+    fn default() -> Self {
+        Self::Pending { stage: 0 }
+    }
+}
+
+/// Emitted on server when a client finishes its handshake.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub enum HandshakeEvent {
+    Completed { client_id: ClientId },
+    Rejected { client_id: ClientId, reason: String },
+}
+
+#[derive(Deserialize, Serialize)]
+enum HandshakeResponse {
+    Accepted { stage: u8 },
+    Rejected { stage: u8, reason: String },
+}
+
+/// Channel IDs, assigned when plugins are finished.
+#[derive(Resource, Clone, Copy, Default, TypePath)]
+struct HandshakeChannels {
+    server: u8,
+    client: u8,
+}
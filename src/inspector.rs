@@ -0,0 +1,202 @@
+use std::io::Cursor;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::ClientSet,
+    core::{
+        common_conditions::{client_connected, has_authority},
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        replication_rules::AppRuleExt,
+        Replicated,
+    },
+    server::{connected_clients::client_groups::GroupId, ServerSet},
+};
+
+/**
+Replicates [`Name`] and hierarchy of replicated entities to trusted inspector clients.
+
+Intended for remote editors and inspectors that show the server's entity tree live from a separate client process.
+Data is sent only to clients from [`Self::group`], so regular players don't receive it.
+Clients can't join the group on their own, the server decides who is trusted.
+
+On server the hierarchy of replicated entities is mirrored into [`InspectedParent`].
+On clients it's applied back to the hierarchy, so any local inspection tool will show the server's tree.
+
+Should be added on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_plugins(InspectorPlugin::default())
+    .add_systems(Update, trust_inspectors);
+
+/// Allows authorized clients to inspect the world.
+fn trust_inspectors(
+    mut server_events: EventReader<ServerEvent>,
+    mut connected_clients: ResMut<ConnectedClients>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            if is_developer(client_id) {
+                connected_clients
+                    .groups_mut()
+                    .insert(InspectorPlugin::DEFAULT_GROUP, client_id);
+            }
+        }
+    }
+}
+
+# fn is_developer(_client_id: ClientId) -> bool { true }
+```
+**/
+pub struct InspectorPlugin {
+    /// Group of clients that receive inspection data.
+    ///
+    /// Managed on server via [`ConnectedClients::groups_mut`](crate::server::connected_clients::ConnectedClients::groups_mut).
+    ///
+    /// By default set to [`Self::DEFAULT_GROUP`].
+    pub group: GroupId,
+}
+
+impl InspectorPlugin {
+    /// Group used by default for inspector clients.
+    pub const DEFAULT_GROUP: GroupId = GroupId(u32::MAX);
+}
+
+impl Default for InspectorPlugin {
+    fn default() -> Self {
+        Self {
+            group: Self::DEFAULT_GROUP,
+        }
+    }
+}
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<InspectedParent>()
+            .replicate_with(
+                RuleFns::new(serialize_name, deserialize_name).with_client_group(self.group),
+            )
+            .replicate_with(
+                RuleFns::<InspectedParent>::default_mapped().with_client_group(self.group),
+            )
+            .add_systems(
+                PreUpdate,
+                apply_hierarchy
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            )
+            .add_systems(
+                PostUpdate,
+                (store_changes, store_removals)
+                    .run_if(has_authority)
+                    .in_set(ServerSet::StoreHierarchy),
+            );
+    }
+}
+
+/// Serializes [`Name`] as a string, since it doesn't implement [`Serialize`] without Bevy's `serialize` feature.
+fn serialize_name(
+    _ctx: &SerializeCtx,
+    name: &Name,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(cursor, name.as_str())
+}
+
+/// Deserializes [`Name`] written by [`serialize_name`].
+fn deserialize_name(_ctx: &mut WriteCtx, cursor: &mut Cursor<&[u8]>) -> bincode::Result<Name> {
+    let name: String = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(Name::new(name))
+}
+
+/// Mirrors parents of replicated entities into [`InspectedParent`].
+///
+/// Also includes entities that just started replicating.
+fn store_changes(
+    mut commands: Commands,
+    mut hierarchy: Query<
+        (Entity, &Parent, Option<&mut InspectedParent>),
+        (With<Replicated>, Or<(Changed<Parent>, Added<Replicated>)>),
+    >,
+) {
+    for (entity, parent, inspected_parent) in &mut hierarchy {
+        match inspected_parent {
+            Some(mut inspected_parent) => inspected_parent.0 = Some(**parent),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(InspectedParent(Some(**parent)));
+            }
+        }
+    }
+}
+
+fn store_removals(
+    mut removed_parents: RemovedComponents<Parent>,
+    mut hierarchy: Query<&mut InspectedParent>,
+) {
+    for entity in removed_parents.read() {
+        if let Ok(mut inspected_parent) = hierarchy.get_mut(entity) {
+            inspected_parent.0 = None;
+        }
+    }
+}
+
+/// Applies received [`InspectedParent`] to the hierarchy on client.
+fn apply_hierarchy(
+    mut commands: Commands,
+    hierarchy: Query<(Entity, &InspectedParent, Option<&Parent>), Changed<InspectedParent>>,
+) {
+    for (entity, inspected_parent, parent) in &hierarchy {
+        if let Some(inspected_entity) = inspected_parent.0 {
+            if parent
+                .filter(|&parent| **parent == inspected_entity)
+                .is_none()
+            {
+                commands.entity(entity).set_parent(inspected_entity);
+            }
+        } else if parent.is_some() {
+            commands.entity(entity).remove_parent();
+        }
+    }
+}
+
+/// Parent of a replicated entity on server.
+///
+/// Inserted automatically by [`InspectorPlugin`] and sent only to inspector clients.
+#[derive(Component, Default, Reflect, Clone, Copy, Debug, Serialize, Deserialize)]
+#[reflect(Component, MapEntities)]
+pub struct InspectedParent(Option<Entity>);
+
+impl InspectedParent {
+    /// Returns the parent entity.
+    pub fn get(&self) -> Option<Entity> {
+        self.0
+    }
+}
+
+impl MapEntities for InspectedParent {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        if let Some(ref mut entity) = self.0 {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_just_stopped, server_running},
+        replicon_channels::{ChannelKind, KeyedChannels},
+        replicon_tick::RepliconTick,
+    },
+    server::{
+        connected_clients::ConnectedClients, replicon_server::RepliconServer, ServerEvent,
+        ServerPlugin, ServerSet,
+    },
+};
+
+/**
+Synchronizes key rotations for payload codecs between the server and clients.
+
+Replicon doesn't encrypt anything by itself. This plugin only distributes key IDs,
+while the key material and encryption are managed by the user's codec.
+
+The flow looks like this:
+1. The server calls [`ServerKeyRotation::rotate`] with a new key ID and a tick at which it becomes active.
+2. The rotation is sent to all connected clients over a reliable channel.
+Newly connected clients receive all known rotations.
+3. Clients receive [`KeyRotationScheduled`] and can prepare the key.
+4. Both sides call `key_id_at` with the tick of a message to pick the key.
+Since the key is selected by tick, both sides switch atomically without additional handshakes.
+
+The rotation should be scheduled far enough ahead to reach clients before the designated tick.
+Key ID 0 is used before the first rotation.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, KeyRotationPlugin))
+    .add_systems(Update, rotate_key.run_if(server_running));
+
+fn rotate_key(mut rotation: ResMut<ServerKeyRotation>, server_tick: Res<ServerTick>) {
+    if server_tick.get() % 10_000 == 0 {
+        let key_id = server_tick.get() / 10_000;
+        // Give clients 100 ticks to receive the rotation.
+        rotation.rotate(key_id, **server_tick + 100);
+    }
+}
+```
+**/
+pub struct KeyRotationPlugin;
+
+impl Plugin for KeyRotationPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            KeyRotationChannel::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.insert_resource(KeyRotationChannel(id)),
+        );
+
+        app.init_resource::<ServerKeyRotation>()
+            .init_resource::<ClientKeyRotation>()
+            .add_event::<KeyRotationScheduled>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::receive_rotations
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::client_reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::announce
+                        .before(ServerPlugin::send_replication)
+                        .in_set(ServerSet::Send)
+                        .run_if(server_running),
+                    Self::server_reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+impl KeyRotationPlugin {
+    /// Sends requested rotations to all clients and the whole schedule to newly connected clients.
+    fn announce(
+        mut server_events: EventReader<ServerEvent>,
+        mut rotation: ResMut<ServerKeyRotation>,
+        mut server: ResMut<RepliconServer>,
+        connected_clients: Res<ConnectedClients>,
+        channel: Res<KeyRotationChannel>,
+    ) {
+        let requested: Vec<_> = rotation.requested.drain(..).collect();
+        for &rotation_entry in &requested {
+            debug!(
+                "rotating to key {} at tick {:?}",
+                rotation_entry.key_id, rotation_entry.tick
+            );
+            rotation.schedule.insert(rotation_entry);
+        }
+
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = event {
+                // Connected clients will receive requested rotations below.
+                let known: Vec<_> = rotation
+                    .schedule
+                    .iter()
+                    .filter(|entry| !requested.contains(entry))
+                    .collect();
+                if !known.is_empty() {
+                    server.send(*client_id, channel.0, serialize(&known));
+                }
+            }
+        }
+
+        if !requested.is_empty() {
+            let message = serialize(&requested);
+            for client_id in connected_clients.iter_client_ids() {
+                server.send(client_id, channel.0, message.clone());
+            }
+        }
+    }
+
+    fn receive_rotations(
+        mut scheduled_events: EventWriter<KeyRotationScheduled>,
+        mut rotation: ResMut<ClientKeyRotation>,
+        mut client: ResMut<RepliconClient>,
+        channel: Res<KeyRotationChannel>,
+    ) {
+        for message in client.receive(channel.0) {
+            match DefaultOptions::new().deserialize::<Vec<KeyRotationScheduled>>(&message) {
+                Ok(entries) => {
+                    for entry in entries {
+                        debug!(
+                            "received rotation to key {} at tick {:?}",
+                            entry.key_id, entry.tick
+                        );
+                        rotation.schedule.insert(entry);
+                        scheduled_events.send(entry);
+                    }
+                }
+                Err(e) => error!("unable to deserialize key rotation: {e}"),
+            }
+        }
+    }
+
+    fn server_reset(mut rotation: ResMut<ServerKeyRotation>) {
+        *rotation = Default::default();
+    }
+
+    fn client_reset(mut rotation: ResMut<ClientKeyRotation>) {
+        *rotation = Default::default();
+    }
+}
+
+fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+    DefaultOptions::new()
+        .serialize(value)
+        .expect("key rotation should be serializable")
+}
+
+/// Server-side schedule of key rotations.
+///
+/// See [`KeyRotationPlugin`].
+#[derive(Resource, Default)]
+pub struct ServerKeyRotation {
+    schedule: KeySchedule,
+
+    /// Rotations from [`Self::rotate`] that will be announced on the next update.
+    requested: Vec<KeyRotationScheduled>,
+}
+
+impl ServerKeyRotation {
+    /// Schedules a switch to a key at the specified tick.
+    ///
+    /// The rotation will be announced to clients in [`PostUpdate`] of the current update.
+    /// Use [`ServerTick`](crate::server::server_tick::ServerTick) to calculate the tick.
+    pub fn rotate(&mut self, key_id: u32, tick: RepliconTick) {
+        self.requested.push(KeyRotationScheduled { key_id, tick });
+    }
+
+    /// Returns the key ID that should be used for a message with the given tick.
+    ///
+    /// Rotations requested in the current update are taken into account only after they are announced.
+    pub fn key_id_at(&self, tick: RepliconTick) -> u32 {
+        self.schedule.key_id_at(tick)
+    }
+}
+
+/// Client-side schedule of key rotations received from the server.
+///
+/// See [`KeyRotationPlugin`].
+#[derive(Resource, Default)]
+pub struct ClientKeyRotation {
+    schedule: KeySchedule,
+}
+
+impl ClientKeyRotation {
+    /// Returns the key ID that should be used for a message with the given tick.
+    pub fn key_id_at(&self, tick: RepliconTick) -> u32 {
+        self.schedule.key_id_at(tick)
+    }
+}
+
+/// Known key switches sorted by tick.
+///
+/// Only the last [`Self::MAX_ENTRIES`] are kept to avoid unbounded growth in long sessions.
+#[derive(Default)]
+struct KeySchedule(Vec<KeyRotationScheduled>);
+
+impl KeySchedule {
+    const MAX_ENTRIES: usize = 8;
+
+    fn insert(&mut self, entry: KeyRotationScheduled) {
+        self.0.retain(|existing| existing.tick != entry.tick);
+        let index = self
+            .0
+            .partition_point(|existing| existing.tick < entry.tick);
+        self.0.insert(index, entry);
+
+        if self.0.len() > Self::MAX_ENTRIES {
+            self.0.remove(0);
+        }
+    }
+
+    fn key_id_at(&self, tick: RepliconTick) -> u32 {
+        self.0
+            .iter()
+            .rev()
+            .find(|entry| entry.tick <= tick)
+            .map(|entry| entry.key_id)
+            .unwrap_or_default()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &KeyRotationScheduled> {
+        self.0.iter()
+    }
+}
+
+/// An event indicating that the server scheduled a switch to a new key.
+///
+/// Emitted only on clients.
+#[derive(Clone, Copy, Debug, Deserialize, Event, PartialEq, Eq, Serialize)]
+pub struct KeyRotationScheduled {
+    pub key_id: u32,
+
+    /// Tick starting from which the key should be used.
+    pub tick: RepliconTick,
+}
+
+/// Server channel ID for key rotations.
+#[derive(Resource, TypePath)]
+struct KeyRotationChannel(u8);
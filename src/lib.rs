@@ -64,7 +64,8 @@ app.add_plugins(
 ```
 
 Depending on the game, you may notice that the lower the interval, the less smooth the game feels.
-To smooth updates, you will need to apply interpolation.
+To smooth updates, you will need to apply interpolation,
+see [`AppInterpolationExt`] and [`InterpolationPlugin`].
 
 ## Server and client creation
 
@@ -280,13 +281,13 @@ fn send_events(mut dummy_events: EventWriter<DummyEvent>) {
 }
 
 /// Receives event on server and single-player.
-fn receive_events(mut dummy_events: EventReader<FromClient<DummyEvent>>) {
-    for FromClient { client_id, event } in dummy_events.read() {
-        info!("received event {event:?} from {client_id:?}");
+fn receive_events(mut dummy_events: EventReader<FromClient<DummyEvent>>, names: Res<ClientNames>) {
+    for FromClient { client_id, event, .. } in dummy_events.read() {
+        info!("received event {event:?} from {}", names.label(*client_id));
     }
 }
 
-#[derive(Debug, Default, Deserialize, Event, Serialize)]
+#[derive(Debug, Default, Deserialize, Event, Serialize, TypePath)]
 struct DummyEvent;
 ```
 
@@ -303,7 +304,7 @@ To do this, use [`ClientEventAppExt::add_mapped_client_event()`] and implement
 # app.add_plugins(RepliconPlugins);
 app.add_mapped_client_event::<MappedEvent>(ChannelKind::Ordered);
 
-#[derive(Debug, Deserialize, Event, Serialize, Clone)]
+#[derive(Debug, Deserialize, Event, Serialize, TypePath)]
 struct MappedEvent(Entity);
 
 impl MapEntities for MappedEvent {
@@ -313,8 +314,6 @@ impl MapEntities for MappedEvent {
 }
 ```
 
-As shown above, mapped client events must also implement [`Clone`].
-
 There is also [`ClientEventAppExt::add_client_event_with()`] to register an event with special sending and receiving functions.
 This could be used for sending events that contain [`Box<dyn Reflect>`], which require access to the [`AppTypeRegistry`] resource.
 Don't forget to validate the contents of every [`Box<dyn Reflect>`] from a client, it could be anything!
@@ -343,6 +342,7 @@ fn send_events(mut dummy_events: EventWriter<ToClients<DummyEvent>>) {
     dummy_events.send(ToClients {
         mode: SendMode::Broadcast,
         event: DummyEvent,
+        apply_tick: None,
     });
 }
 
@@ -353,7 +353,7 @@ fn receive_events(mut dummy_events: EventReader<DummyEvent>) {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Event, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Event, Serialize, TypePath)]
 struct DummyEvent;
 ```
 
@@ -416,6 +416,16 @@ For a higher level API consider using [`bevy_replicon_attributes`](https://docs.
 
 All events, inserts, removals and despawns will be applied to clients in the same order as on the server.
 
+Each replicated component is delivered with two different guarantees. Its insertion is sent over the reliable
+[`ReplicationChannel::Init`](core::replicon_channels::ReplicationChannel::Init), so the client always receives
+the initial value. Later changes are sent over the unreliable
+[`ReplicationChannel::Update`](core::replicon_channels::ReplicationChannel::Update) with last-write-wins semantics:
+a lost change is included again until the client acknowledges a newer one, and stale changes that arrive late are discarded.
+To stop resending changes that became too old, use [`RuleFns::with_mutation_timeout`](core::replication_fns::rule_fns::RuleFns::with_mutation_timeout).
+Channels can be assigned separately for insertions and changes of each rule via
+[`RuleFns::with_insert_channel`](core::replication_fns::rule_fns::RuleFns::with_insert_channel) and
+[`RuleFns::with_mutation_channel`](core::replication_fns::rule_fns::RuleFns::with_mutation_channel).
+
 Entity component updates are grouped by entity, and component groupings may be applied to clients in a different order than on the server.
 For example, if two entities are spawned in tick 1 on the server and their components are updated in tick 2,
 then the client is guaranteed to see the spawns at the same time, but the component updates may appear in different client ticks.
@@ -438,44 +448,139 @@ To reduce packet size there are the following limits per replication update:
 - Up to [`u16::MAX`] entities that were despawned.
 */
 
+pub mod asset_handles;
+pub mod authority_watchdog;
 pub mod client;
+pub mod client_endpoints;
+pub mod component_subscriptions;
 pub mod core;
+pub mod handshake;
+#[cfg(feature = "inspector")]
+pub mod inspector;
+pub mod key_rotation;
+pub mod load_test;
+pub mod map_transition;
+pub mod net_ids;
 pub mod network_event;
 pub mod parent_sync;
+pub mod ping;
+mod protocol;
 pub mod scene;
 pub mod server;
+pub mod single_player;
+pub mod sparse_updates;
+pub mod stable_fns_ids;
+pub mod state_replication;
 pub mod test_app;
+pub mod timing_diagnostics;
+
+// Re-exported for macros, so crates that use them don't need to depend on the same Bevy version.
+#[doc(hidden)]
+pub use bevy;
 
 pub mod prelude {
     #[allow(deprecated)]
     pub use super::core::Replication;
 
+    #[cfg(feature = "inspector")]
+    pub use super::inspector::{InspectedParent, InspectorPlugin};
+
     pub use super::{
+        asset_handles::{AppHandleExt, AssetResolver, HandleComponent},
+        authority_watchdog::AuthorityWatchdogPlugin,
         client::{
+            despawn_prediction::{DespawnPredictionTimeout, PredictedDespawn, PredictedDespawnExt},
             diagnostics::{ClientDiagnosticsPlugin, ClientStats},
+            duplicate_spawns::{DuplicateSpawn, DuplicateSpawnPolicy},
+            entity_map_gc::EntityMapGc,
+            fast_forward::{fast_forwarding, FastForward, FastForwarded},
+            interpolation::{
+                AppInterpolationExt, Extrapolate, ExtrapolationBuffer, ExtrapolationSettings,
+                Interpolate, Interpolated, InterpolationBuffer, InterpolationPlugin,
+            },
+            message_hooks::MessageHooks,
+            obscured::Obscured,
             replicon_client::{RepliconClient, RepliconClientStatus},
+            tick_changes::TickChangesApplied,
+            upstream_budget::UpstreamBudget,
             ClientPlugin, ClientSet,
         },
+        client_endpoints::{ClientEndpoint, ClientEndpointAppExt, FromEndpoint, ToEndpoint},
+        component_subscriptions::ComponentSubscriptions,
         core::{
-            command_markers::AppMarkerExt,
+            command_markers::{AppMarkerExt, MarkerCommandsExt},
             common_conditions::*,
+            protocol_validation::ProtocolValidation,
             replication_rules::AppRuleExt,
+            replication_sections::ReplicationSections,
             replicon_channels::{ChannelKind, RepliconChannel, RepliconChannels},
-            ClientId, Replicated, RepliconCorePlugin,
+            ClientId, Owner, Replicated, RepliconCorePlugin,
+        },
+        handshake::{
+            ClientHandshake, HandshakeAppExt, HandshakeEvent, HandshakePlugin, HandshakeStage,
+            HandshakeStatus, Handshakes,
+        },
+        key_rotation::{
+            ClientKeyRotation, KeyRotationPlugin, KeyRotationScheduled, ServerKeyRotation,
         },
+        load_test::{LoadTestAppExt, LoadTestMetrics, LoadTestPlugin},
+        map_transition::{
+            ClientMapTransition, MapTransitionFinished, MapTransitionPlugin, MapTransitionStarted,
+            MapTransitionState, ServerMapTransition,
+        },
+        net_ids::{NetId, NetIdMap, NetIdPlugin},
         network_event::{
-            client_event::{ClientEventAppExt, FromClient},
-            server_event::{SendMode, ServerEventAppExt, ToClients},
+            bidirectional::{BidirectionalEventAppExt, FromServer},
+            client_event::{
+                AckTimeout, ClientEventAppExt, DroppedClientEvents, EventAck, EventLost,
+                FromClient, WithMetadata,
+            },
+            compression::{
+                CompressionAppExt, CompressionCodec, CompressionCodecs, MaxDecompressedSize,
+            },
+            positional::{
+                InterpolationDelay, Positional, PositionalEventAppExt, RelevancyPositions, ToNearby,
+            },
+            rpc::{
+                PendingRequests, Request, RequestError, RequestId, RequestResult, RequestSender,
+                Response, ResponseSender, RpcAppExt,
+            },
+            server_event::{
+                SendMode, ServerEventAppExt, ServerEventSender, ToClients, UnmappedEventExpired,
+                UnmappedEventTimeout,
+            },
+            subtick::{Subtick, WithSubtick},
+            topics::{EventTopics, TopicAppExt},
+            versioning::{EventVersions, VersionedEventAppExt},
         },
         parent_sync::{ParentSync, ParentSyncPlugin},
+        ping::{ClientNetworkStats, NetworkStats, PingClock, PingPlugin},
         server::{
             client_entity_map::{ClientEntityMap, ClientMapping},
+            client_names::ClientNames,
             connected_clients::{
-                client_visibility::ClientVisibility, ConnectedClient, ConnectedClients,
+                client_groups::{ClientGroups, GroupId},
+                client_priority::ClientPriority,
+                client_visibility::ClientVisibility,
+                ConnectedClient, ConnectedClients,
             },
+            despawn_tombstones::DespawnTombstones,
+            replicated_clone::{CloneVisibility, ReplicatedCloneExt},
+            replication_pause::{ReplicationPause, ResumeMode},
             replicon_server::RepliconServer,
-            ServerEvent, ServerPlugin, ServerSet, TickPolicy, VisibilityPolicy,
+            virtual_clients::VirtualClients,
+            visibility_conditions::{
+                ClientTeams, InvisibleTo, TeamId, VisibleToAll, VisibleToGroup, VisibleToOwner,
+                VisibleToTeam,
+            },
+            visibility_scheduler::{VisibilitySchedule, VisibilityScheduler},
+            RevealPolicy, ServerEvent, ServerPlugin, ServerSet, TickPolicy, VisibilityPolicy,
         },
+        single_player::{SinglePlayerClient, SinglePlayerWithNetworkingPlugin},
+        sparse_updates::{AppSparseExt, Sparse, SparseData},
+        stable_fns_ids::StableFnsIdsPlugin,
+        state_replication::StateReplicationAppExt,
+        timing_diagnostics::TimingDiagnosticsPlugin,
         RepliconPlugins,
     };
 }
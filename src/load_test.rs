@@ -0,0 +1,297 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::Instant};
+use bincode::{DefaultOptions, Options};
+use serde::Serialize;
+
+use crate::{
+    core::{
+        common_conditions::{server_just_stopped, server_running},
+        replicon_channels::RepliconChannels,
+        ClientId,
+    },
+    network_event::client_event::ClientEventChannel,
+    server::{virtual_clients::VirtualClients, ServerSet},
+};
+
+/**
+Connects many simulated clients to the server to validate its performance under load.
+
+Clients are connected via [`VirtualClients`] when the server starts and participate in replication
+like real ones: their update messages are acknowledged and sent messages are received by the server.
+All messages sent to them are discarded, so each client is cheap to simulate.
+Clients can also send scripted events, see [`LoadTestAppExt::add_load_test_event`].
+
+Aggregate metrics are available via [`LoadTestMetrics`].
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on a headless server.
+
+# Examples
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    LoadTestPlugin {
+        clients: 500,
+        ..Default::default()
+    },
+))
+.add_systems(Last, report.run_if(server_running));
+
+fn report(metrics: Res<LoadTestMetrics>) {
+    info!(
+        "average frame time: {:?}, frames over budget: {}",
+        metrics.average_frame_time(),
+        metrics.frames_over_budget,
+    );
+}
+```
+**/
+pub struct LoadTestPlugin {
+    /// Number of simulated clients.
+    pub clients: usize,
+
+    /// Maximum duration of a single server frame.
+    ///
+    /// Frames that take longer are counted in [`LoadTestMetrics::frames_over_budget`].
+    pub budget: Duration,
+}
+
+impl Default for LoadTestPlugin {
+    fn default() -> Self {
+        Self {
+            clients: 100,
+            budget: Duration::from_secs_f64(1.0 / 60.0),
+        }
+    }
+}
+
+impl Plugin for LoadTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadTestClients>()
+            .init_resource::<LoadTestMetrics>()
+            .add_systems(First, Self::begin_frame.run_if(server_running))
+            .add_systems(
+                PreUpdate,
+                Self::connect(self.clients)
+                    .before(ServerSet::SendEvents)
+                    .run_if(server_running),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::discard_received
+                        .after(ServerSet::SendPackets)
+                        .run_if(server_running),
+                    Self::reset.run_if(server_just_stopped),
+                ),
+            )
+            .add_systems(Last, Self::end_frame(self.budget).run_if(server_running));
+    }
+}
+
+impl LoadTestPlugin {
+    fn connect(count: usize) -> impl FnMut(ResMut<LoadTestClients>, ResMut<VirtualClients>) {
+        move |mut clients, mut virtual_clients| {
+            if clients.0.is_empty() {
+                debug!("connecting {count} load test clients");
+                clients.0 = (0..count).map(|_| virtual_clients.connect()).collect();
+            }
+        }
+    }
+
+    /// Drains messages sent to simulated clients and counts them.
+    fn discard_received(
+        mut metrics: ResMut<LoadTestMetrics>,
+        mut virtual_clients: ResMut<VirtualClients>,
+        clients: Res<LoadTestClients>,
+        channels: Res<RepliconChannels>,
+    ) {
+        for &client_id in &clients.0 {
+            for channel_id in 0..channels.server_channels().len() as u8 {
+                for message in virtual_clients.receive(client_id, channel_id) {
+                    metrics.messages_received += 1;
+                    metrics.bytes_received += message.len();
+                }
+            }
+        }
+    }
+
+    fn begin_frame(mut metrics: ResMut<LoadTestMetrics>) {
+        metrics.frame_start = Some(Instant::now());
+    }
+
+    fn end_frame(budget: Duration) -> impl FnMut(ResMut<LoadTestMetrics>) {
+        move |mut metrics| {
+            let Some(frame_start) = metrics.frame_start.take() else {
+                return;
+            };
+
+            let frame_time = frame_start.elapsed();
+            metrics.frames += 1;
+            metrics.total_frame_time += frame_time;
+            metrics.max_frame_time = metrics.max_frame_time.max(frame_time);
+            if frame_time > budget {
+                metrics.frames_over_budget += 1;
+            }
+        }
+    }
+
+    fn reset(mut clients: ResMut<LoadTestClients>) {
+        clients.0.clear();
+    }
+}
+
+/// Scripted events for [`LoadTestPlugin`].
+pub trait LoadTestAppExt {
+    /**
+    Makes all simulated clients send event `E` every `interval`.
+
+    The event is created by `script` from the sending client ID and the number of
+    events of this type the client sent before.
+
+    The event should be registered via [`ClientEventAppExt::add_client_event`](crate::network_event::client_event::ClientEventAppExt::add_client_event)
+    and use the default serialization.
+
+    # Examples
+
+    ```
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins, LoadTestPlugin::default()));
+    app.add_client_event::<Shoot>(ChannelKind::Ordered)
+        .add_load_test_event(Duration::from_millis(500), shoot);
+
+    fn shoot(_client_id: ClientId, index: usize) -> Shoot {
+        Shoot {
+            angle: index as f32 * 0.1,
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct Shoot {
+        angle: f32,
+    }
+    ```
+    **/
+    fn add_load_test_event<E: Event + Serialize>(
+        &mut self,
+        interval: Duration,
+        script: fn(ClientId, usize) -> E,
+    ) -> &mut Self;
+}
+
+impl LoadTestAppExt for App {
+    fn add_load_test_event<E: Event + Serialize>(
+        &mut self,
+        interval: Duration,
+        script: fn(ClientId, usize) -> E,
+    ) -> &mut Self {
+        self.add_systems(
+            PreUpdate,
+            send_events(interval, script)
+                .before(ServerSet::ReceivePackets)
+                .run_if(server_running),
+        )
+    }
+}
+
+/// Sends scripted events from all simulated clients when the interval passes.
+fn send_events<E: Event + Serialize>(
+    interval: Duration,
+    script: fn(ClientId, usize) -> E,
+) -> impl FnMut(
+    Local<Option<Duration>>,
+    Local<usize>,
+    ResMut<LoadTestMetrics>,
+    ResMut<VirtualClients>,
+    Res<LoadTestClients>,
+    Res<ClientEventChannel<E>>,
+    Res<Time<Real>>,
+) {
+    move |mut last_sent, mut index, mut metrics, mut virtual_clients, clients, channel, time| {
+        if clients.0.is_empty() {
+            *last_sent = None;
+            *index = 0;
+            return;
+        }
+
+        let now = time.elapsed();
+        if last_sent.is_some_and(|last_sent| now - last_sent < interval) {
+            return;
+        }
+        *last_sent = Some(now);
+
+        for &client_id in &clients.0 {
+            let event = script(client_id, *index);
+            let message = DefaultOptions::new()
+                .serialize(&event)
+                .expect("load test event should be serializable");
+            virtual_clients.send(client_id, *channel, message);
+            metrics.events_sent += 1;
+        }
+        *index += 1;
+    }
+}
+
+/// Simulated clients connected by [`LoadTestPlugin`].
+#[derive(Resource, Default)]
+struct LoadTestClients(Vec<ClientId>);
+
+/// Aggregate server metrics collected by [`LoadTestPlugin`].
+///
+/// Collected only while the server is running.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LoadTestMetrics {
+    /// Number of measured server frames.
+    pub frames: usize,
+
+    /// Total duration of all measured frames.
+    pub total_frame_time: Duration,
+
+    /// Duration of the longest frame.
+    pub max_frame_time: Duration,
+
+    /// Number of frames that exceeded [`LoadTestPlugin::budget`].
+    pub frames_over_budget: usize,
+
+    /// Number of messages sent to all simulated clients.
+    pub messages_received: usize,
+
+    /// Total size of messages sent to all simulated clients.
+    pub bytes_received: usize,
+
+    /// Number of scripted events sent by all simulated clients.
+    pub events_sent: usize,
+
+    /// Start of the current frame.
+    frame_start: Option<Instant>,
+}
+
+impl LoadTestMetrics {
+    /// Returns the average duration of a server frame.
+    pub fn average_frame_time(&self) -> Duration {
+        if self.frames == 0 {
+            Duration::ZERO
+        } else {
+            self.total_frame_time / self.frames as u32
+        }
+    }
+
+    /// Resets all metrics.
+    pub fn clear(&mut self) {
+        *self = Default::default();
+    }
+}
@@ -0,0 +1,448 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashSet};
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet, ServerInitTick},
+    core::{
+        common_conditions::{client_connected, server_just_stopped, server_running},
+        replicon_channels::{ChannelKind, KeyedChannels},
+        replicon_tick::RepliconTick,
+        ClientId,
+    },
+    server::{
+        client_names::ClientNames,
+        connected_clients::ConnectedClients,
+        replication_pause::{replication_paused, ReplicationPause, ResumeMode},
+        replicon_server::RepliconServer,
+        server_tick::ServerTick,
+        ServerEvent, ServerPlugin, ServerSet,
+    },
+};
+
+/**
+Coordinates map or level changes between the server and clients.
+
+The flow looks like this:
+1. The server calls [`ServerMapTransition::start`] with a level ID, which pauses replication
+and announces the transition to all connected clients.
+2. Clients receive [`MapTransitionStarted`], load the level and call [`ClientMapTransition::ready`].
+3. When all clients are ready or the timeout expires, the server resets all clients (see
+[`RepliconServer::reset_clients`]) and resumes replication, so the new world is streamed as a fresh snapshot.
+The server emits [`MapTransitionFinished`] with clients that didn't report in time.
+Clients switch back to [`MapTransitionState::Idle`] only after applying the reset.
+
+While replication is paused, the server can freely despawn the old level and spawn the new one,
+clients will receive only the final state.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, MapTransitionPlugin::default()))
+    .add_systems(Update, load_level.run_if(client_connected));
+
+fn load_level(
+    mut transition_events: EventReader<MapTransitionStarted>,
+    mut transition: ResMut<ClientMapTransition>,
+) {
+    for event in transition_events.read() {
+        info!("loading level {}", event.level_id);
+        // Load assets for the level...
+        transition.ready();
+    }
+}
+```
+**/
+pub struct MapTransitionPlugin {
+    /// Maximum time to wait for clients to report readiness.
+    ///
+    /// After it expires, the transition will be finished without waiting for the remaining clients.
+    pub timeout: Duration,
+}
+
+impl Default for MapTransitionPlugin {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Plugin for MapTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            TransitionChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<TransitionChannels>().server = id,
+        );
+        KeyedChannels::register_client(
+            app.world_mut(),
+            TransitionChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<TransitionChannels>().client = id,
+        );
+
+        app.insert_resource(ServerMapTransition::new(self.timeout))
+            .init_resource::<ClientMapTransition>()
+            .init_resource::<TransitionChannels>()
+            .add_event::<MapTransitionStarted>()
+            .add_event::<MapTransitionFinished>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::receive_ready
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::receive_announcements
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::apply_finish
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::client_reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::announce
+                        .before(ServerPlugin::send_replication)
+                        .in_set(ServerSet::Send)
+                        .run_if(server_running),
+                    Self::send_finish
+                        .after(ServerPlugin::send_replication)
+                        .in_set(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(not(replication_paused))
+                        .run_if(resource_changed::<ServerTick>),
+                    Self::send_ready
+                        .in_set(ClientSet::Send)
+                        .run_if(client_connected),
+                    Self::server_reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+impl MapTransitionPlugin {
+    /// Pauses replication and announces requested transitions to clients.
+    fn announce(
+        mut transition: ResMut<ServerMapTransition>,
+        mut pause: ResMut<ReplicationPause>,
+        mut server: ResMut<RepliconServer>,
+        connected_clients: Res<ConnectedClients>,
+        channels: Res<TransitionChannels>,
+        time: Res<Time<Real>>,
+    ) {
+        let Some(level_id) = transition.requested.take() else {
+            return;
+        };
+
+        debug!("starting transition to level {level_id}");
+        pause.pause();
+        transition.state = MapTransitionState::Loading { level_id };
+        transition.finished = None;
+        transition.started = time.elapsed();
+        transition.pending = connected_clients.iter_client_ids().collect();
+
+        let message = serialize(&TransitionMessage::Start { level_id });
+        for client_id in connected_clients.iter_client_ids() {
+            server.send(client_id, channels.server, message.clone());
+        }
+    }
+
+    /// Receives readiness from clients and finishes the transition when all are ready or the timeout expired.
+    fn receive_ready(
+        mut server_events: EventReader<ServerEvent>,
+        mut finished_events: EventWriter<MapTransitionFinished>,
+        mut transition: ResMut<ServerMapTransition>,
+        mut pause: ResMut<ReplicationPause>,
+        mut server: ResMut<RepliconServer>,
+        channels: Res<TransitionChannels>,
+        names: Res<ClientNames>,
+        time: Res<Time<Real>>,
+    ) {
+        let messages: Vec<_> = server.receive(channels.client).collect();
+        let MapTransitionState::Loading { level_id } = transition.state else {
+            server_events.clear();
+            return;
+        };
+
+        for (client_id, message) in messages {
+            match DefaultOptions::new().deserialize::<u64>(&message) {
+                Ok(ready_id) if ready_id == level_id => {
+                    debug!("{} is ready for level {level_id}", names.label(client_id));
+                    transition.pending.remove(&client_id);
+                }
+                Ok(ready_id) => {
+                    debug!(
+                        "ignoring readiness of {} for outdated level {ready_id}",
+                        names.label(client_id)
+                    )
+                }
+                Err(e) => debug!(
+                    "unable to deserialize readiness from {}: {e}",
+                    names.label(client_id)
+                ),
+            }
+        }
+
+        // Handle disconnects after receiving because messages from clients
+        // that disconnected in this update could still be received.
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+                transition.pending.remove(client_id);
+            }
+        }
+
+        let timed_out = time.elapsed().saturating_sub(transition.started) >= transition.timeout;
+        if !transition.pending.is_empty() && !timed_out {
+            return;
+        }
+
+        let timed_out: Vec<_> = transition.pending.drain().collect();
+        if !timed_out.is_empty() {
+            warn!("finishing transition to level {level_id} without ready {timed_out:?}");
+        }
+
+        pause.resume(ResumeMode::Resync);
+        server.reset_clients();
+        transition.state = MapTransitionState::Idle;
+        transition.finished = Some(level_id);
+
+        finished_events.send(MapTransitionFinished {
+            level_id,
+            timed_out,
+        });
+    }
+
+    /// Announces finished transitions to clients.
+    ///
+    /// Runs right after the replication that sent the reset and includes its tick,
+    /// so clients can wait for the reset even if the announcement arrives first.
+    fn send_finish(
+        mut transition: ResMut<ServerMapTransition>,
+        mut server: ResMut<RepliconServer>,
+        connected_clients: Res<ConnectedClients>,
+        channels: Res<TransitionChannels>,
+        server_tick: Res<ServerTick>,
+    ) {
+        let Some(level_id) = transition.finished.take() else {
+            return;
+        };
+
+        let message = serialize(&TransitionMessage::Finish {
+            level_id,
+            tick: **server_tick,
+        });
+        for client_id in connected_clients.iter_client_ids() {
+            server.send(client_id, channels.server, message.clone());
+        }
+    }
+
+    fn receive_announcements(
+        mut started_events: EventWriter<MapTransitionStarted>,
+        mut transition: ResMut<ClientMapTransition>,
+        mut client: ResMut<RepliconClient>,
+        channels: Res<TransitionChannels>,
+    ) {
+        for message in client.receive(channels.server) {
+            match DefaultOptions::new().deserialize(&message) {
+                Ok(TransitionMessage::Start { level_id }) => {
+                    debug!("received transition to level {level_id}");
+                    transition.state = MapTransitionState::Loading { level_id };
+                    transition.ready_requested = false;
+                    transition.finish_tick = None;
+                    started_events.send(MapTransitionStarted { level_id });
+                }
+                Ok(TransitionMessage::Finish { level_id, tick }) => {
+                    debug!("received finish of transition to level {level_id} at {tick:?}");
+                    transition.finish_tick = Some(tick);
+                }
+                Err(e) => error!("unable to deserialize transition message: {e}"),
+            }
+        }
+    }
+
+    /// Finishes the transition when the reset from the server is applied.
+    fn apply_finish(mut transition: ResMut<ClientMapTransition>, init_tick: Res<ServerInitTick>) {
+        let Some(finish_tick) = transition.finish_tick else {
+            return;
+        };
+
+        if finish_tick <= **init_tick {
+            debug!("finished transition at {finish_tick:?}");
+            transition.state = MapTransitionState::Idle;
+            transition.finish_tick = None;
+        }
+    }
+
+    fn send_ready(
+        mut transition: ResMut<ClientMapTransition>,
+        mut client: ResMut<RepliconClient>,
+        channels: Res<TransitionChannels>,
+    ) {
+        if !transition.ready_requested {
+            return;
+        }
+        transition.ready_requested = false;
+
+        if let MapTransitionState::Loading { level_id } = transition.state {
+            client.send(channels.client, serialize(&level_id));
+        }
+    }
+
+    fn server_reset(mut transition: ResMut<ServerMapTransition>) {
+        transition.requested = None;
+        transition.finished = None;
+        transition.state = MapTransitionState::Idle;
+        transition.pending.clear();
+    }
+
+    fn client_reset(mut transition: ResMut<ClientMapTransition>) {
+        *transition = Default::default();
+    }
+}
+
+fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+    DefaultOptions::new()
+        .serialize(value)
+        .expect("transition message should be serializable")
+}
+
+/// Server-side control of map transitions.
+///
+/// See [`MapTransitionPlugin`].
+#[derive(Resource)]
+pub struct ServerMapTransition {
+    state: MapTransitionState,
+    timeout: Duration,
+
+    /// Level ID from [`Self::start`] that will be announced on the next update.
+    requested: Option<u64>,
+
+    /// Level ID of a finished transition that will be announced with the reset.
+    finished: Option<u64>,
+
+    /// Clients that haven't reported readiness yet.
+    pending: HashSet<ClientId>,
+
+    /// Real time when the transition was announced.
+    started: Duration,
+}
+
+impl ServerMapTransition {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            state: Default::default(),
+            timeout,
+            requested: None,
+            finished: None,
+            pending: Default::default(),
+            started: Default::default(),
+        }
+    }
+
+    /// Starts a transition to a level.
+    ///
+    /// Replication will be paused in [`PostUpdate`] of the current update before sending any data,
+    /// so the old level could be despawned in the same update.
+    /// If a transition is already in progress, it will be restarted for the new level.
+    pub fn start(&mut self, level_id: u64) {
+        self.requested = Some(level_id);
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> MapTransitionState {
+        self.state
+    }
+
+    /// Returns `true` if the client reported readiness for the current transition.
+    ///
+    /// Always returns `true` when no transition is in progress.
+    pub fn is_ready(&self, client_id: ClientId) -> bool {
+        !self.pending.contains(&client_id)
+    }
+}
+
+/// Client-side progress of map transitions.
+///
+/// See [`MapTransitionPlugin`].
+#[derive(Resource, Default)]
+pub struct ClientMapTransition {
+    state: MapTransitionState,
+
+    /// Indicates that readiness should be sent to the server on the next update.
+    ready_requested: bool,
+
+    /// Tick of the reset after which the current transition is finished.
+    finish_tick: Option<RepliconTick>,
+}
+
+impl ClientMapTransition {
+    /// Reports to the server that the level from [`MapTransitionStarted`] is loaded.
+    ///
+    /// Does nothing if no transition is in progress.
+    pub fn ready(&mut self) {
+        self.ready_requested = true;
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> MapTransitionState {
+        self.state
+    }
+}
+
+/// State of a map transition.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapTransitionState {
+    /// No transition in progress.
+    #[default]
+    Idle,
+    /// Clients are loading a level.
+    Loading {
+        /// Level ID passed to [`ServerMapTransition::start`].
+        level_id: u64,
+    },
+}
+
+/// Emitted on client when the server announces a transition.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapTransitionStarted {
+    /// Level ID that should be loaded.
+    pub level_id: u64,
+}
+
+/// Emitted on server when a transition is finished.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct MapTransitionFinished {
+    /// Level ID of the finished transition.
+    pub level_id: u64,
+
+    /// Clients that didn't report readiness before the timeout.
+    ///
+    /// They still receive the new level, but may not be able to display it properly.
+    pub timed_out: Vec<ClientId>,
+}
+
+#[derive(Deserialize, Serialize)]
+enum TransitionMessage {
+    Start { level_id: u64 },
+    Finish { level_id: u64, tick: RepliconTick },
+}
+
+/// Channel IDs, assigned when plugins are finished.
+#[derive(Resource, Clone, Copy, Default, TypePath)]
+struct TransitionChannels {
+    server: u8,
+    client: u8,
+}
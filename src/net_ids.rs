@@ -0,0 +1,252 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{common_conditions::has_authority, replication_rules::AppRuleExt},
+    server::ServerSet,
+    Replicated,
+};
+
+/**
+Assigns [`NetId`] to replicated entities to reference them independently of [`Entity`].
+
+Entity IDs differ between server and clients and aren't preserved across game sessions.
+With this plugin, the server inserts a unique [`NetId`] on each entity with [`Replicated`]
+and replicates it to clients. Use [`NetIdMap`] to find entities by their IDs on both sides.
+
+Since [`NetId`] is reflected and serializable, it's saved together with entities.
+IDs of loaded entities are kept, and new IDs continue after the largest known one.
+If an entity gets an ID that is already used by another entity, for example, after
+[`clone_replicated`](crate::server::replicated_clone::ReplicatedCloneExt::clone_replicated),
+the server assigns a new ID to it.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, NetIdPlugin))
+    .add_systems(Update, follow_target);
+
+/// Stores the target as a [`NetId`] to keep it valid in save files.
+#[derive(Component)]
+struct Follow(NetId);
+
+fn follow_target(
+    net_ids: Res<NetIdMap>,
+    followers: Query<&Follow>,
+    transforms: Query<&Transform>,
+) {
+    for follow in &followers {
+        if let Some(transform) = net_ids
+            .get(follow.0)
+            .and_then(|entity| transforms.get(entity).ok())
+        {
+            info!("following {:?}", transform.translation);
+        }
+    }
+}
+```
+**/
+pub struct NetIdPlugin;
+
+impl Plugin for NetIdPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<NetId>()
+            .init_resource::<NetIdMap>()
+            .replicate::<NetId>()
+            .add_systems(PreUpdate, Self::update_map.after(ClientSet::Receive))
+            .add_systems(
+                PostUpdate,
+                (Self::update_map, Self::assign_ids.run_if(has_authority))
+                    .chain()
+                    .before(ServerSet::Send),
+            );
+    }
+}
+
+impl NetIdPlugin {
+    /// Inserts new IDs on replicated entities that don't have them.
+    ///
+    /// Runs after [`Self::update_map`] to avoid assigning IDs of loaded entities.
+    fn assign_ids(
+        mut commands: Commands,
+        mut net_ids: ResMut<NetIdMap>,
+        entities: Query<Entity, (With<Replicated>, Without<NetId>)>,
+    ) {
+        for entity in &entities {
+            let net_id = net_ids.next_id();
+            trace!("assigning `{net_id:?}` to `{entity:?}`");
+            commands.entity(entity).insert(net_id);
+            net_ids.insert(entity, net_id);
+        }
+    }
+
+    /// Updates the lookup for changed and removed IDs.
+    ///
+    /// Runs not only on server, but also on clients and after the server state is deserialized.
+    /// With authority, entities whose IDs duplicate IDs of other entities get new IDs.
+    fn update_map(
+        mut commands: Commands,
+        mut removed_ids: RemovedComponents<NetId>,
+        mut net_ids: ResMut<NetIdMap>,
+        client: Option<Res<RepliconClient>>,
+        ids: Query<(Entity, Ref<NetId>)>,
+    ) {
+        for entity in removed_ids.read() {
+            net_ids.remove(entity);
+        }
+
+        let authority = has_authority(client);
+        for (entity, net_id) in ids.iter().filter(|(_, net_id)| net_id.is_changed()) {
+            let net_id = *net_id;
+            let duplicate = net_ids.get(net_id).filter(|&other| {
+                other != entity
+                    && ids
+                        .get(other)
+                        .is_ok_and(|(_, other_id)| *other_id == net_id)
+            });
+            match duplicate {
+                Some(other) if authority => {
+                    let new_id = net_ids.next_id();
+                    debug!("reassigning `{net_id:?}` of `{entity:?}` to `{new_id:?}` since it's used by `{other:?}`");
+                    commands.entity(entity).insert(new_id);
+                    net_ids.insert(entity, new_id);
+                }
+                _ => net_ids.insert(entity, net_id),
+            }
+        }
+    }
+}
+
+/// Unique ID of a replicated entity that is the same on server and clients.
+///
+/// Assigned by [`NetIdPlugin`].
+#[derive(
+    Component,
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Reflect,
+    Serialize,
+)]
+#[reflect(Component)]
+pub struct NetId(u64);
+
+impl NetId {
+    /// Creates a new instance wrapping the given value.
+    ///
+    /// Can be used to restore IDs from custom save formats.
+    #[inline]
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Gets the value of this ID.
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Maps [`NetId`] to entities and back.
+///
+/// Available on both server and clients when [`NetIdPlugin`] is added.
+/// Updated in [`PreUpdate`] after [`ClientSet::Receive`] and in [`PostUpdate`] before [`ServerSet::Send`].
+#[derive(Resource, Default)]
+pub struct NetIdMap {
+    entities: HashMap<NetId, Entity>,
+    net_ids: EntityHashMap<NetId>,
+
+    /// Value for the next assigned ID.
+    next: u64,
+}
+
+impl NetIdMap {
+    /// Returns the entity with the given ID.
+    #[inline]
+    pub fn get(&self, net_id: NetId) -> Option<Entity> {
+        self.entities.get(&net_id).copied()
+    }
+
+    /// Returns the ID of the given entity.
+    #[inline]
+    pub fn net_id(&self, entity: Entity) -> Option<NetId> {
+        self.net_ids.get(&entity).copied()
+    }
+
+    /// Returns an iterator over all IDs with their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (NetId, Entity)> + '_ {
+        self.entities
+            .iter()
+            .map(|(&net_id, &entity)| (net_id, entity))
+    }
+
+    /// Returns the number of mapped entities.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no mapped entities.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn next_id(&mut self) -> NetId {
+        let net_id = NetId(self.next);
+        self.next += 1;
+        net_id
+    }
+
+    fn insert(&mut self, entity: Entity, net_id: NetId) {
+        if let Some(old_id) = self.net_ids.insert(entity, net_id) {
+            self.entities.remove(&old_id);
+        }
+        if let Some(old_entity) = self.entities.insert(net_id, entity) {
+            if old_entity != entity {
+                self.net_ids.remove(&old_entity);
+            }
+        }
+        self.next = self.next.max(net_id.0 + 1);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(net_id) = self.net_ids.remove(&entity) {
+            self.entities.remove(&net_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_after_loaded() {
+        let mut net_ids = NetIdMap::default();
+        net_ids.insert(Entity::PLACEHOLDER, NetId(5));
+        assert_eq!(net_ids.next_id(), NetId(6));
+        assert_eq!(net_ids.get(NetId(5)), Some(Entity::PLACEHOLDER));
+
+        net_ids.remove(Entity::PLACEHOLDER);
+        assert!(net_ids.is_empty());
+        assert_eq!(
+            net_ids.next_id(),
+            NetId(7),
+            "removed IDs shouldn't be reused"
+        );
+    }
+}
@@ -1,5 +1,12 @@
+pub mod bidirectional;
 pub mod client_event;
+pub mod compression;
+pub mod positional;
+pub mod rpc;
 pub mod server_event;
+pub mod subtick;
+pub mod topics;
+pub mod versioning;
 
 use bevy::{ecs::entity::EntityHashMap, prelude::*};
 
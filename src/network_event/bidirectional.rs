@@ -0,0 +1,112 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{client_event::ClientEventAppExt, server_event::ServerEventAppExt};
+use crate::core::replicon_channels::RepliconChannel;
+
+/**
+An extension trait for [`App`] for registering events that flow in both directions.
+
+Registers `T` as a client event and [`FromServer<T>`] as a server event with the same channel configuration.
+Server events are wrapped because otherwise events received from the server would be sent back
+by the client as client events.
+
+[`FromServer<T>`] is serialized exactly like `T`, so both directions share the same wire format.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_bidirectional_event::<Chat>(ChannelKind::Ordered)
+    .add_systems(Update, (send_chat, broadcast_chat, read_chat));
+
+/// Sends a message from client.
+fn send_chat(mut chat_events: EventWriter<Chat>) {
+    chat_events.send(Chat("Hello!".into()));
+}
+
+/// Forwards messages from clients to everyone else on server.
+fn broadcast_chat(
+    mut from_clients: EventReader<FromClient<Chat>>,
+    mut to_clients: EventWriter<ToClients<FromServer<Chat>>>,
+) {
+    for FromClient { client_id, event, .. } in from_clients.read() {
+        to_clients.send(ToClients {
+            mode: SendMode::BroadcastExcept(*client_id),
+            event: FromServer(event.clone()),
+            apply_tick: None,
+        });
+    }
+}
+
+/// Reads messages from server on client.
+fn read_chat(mut chat_events: EventReader<FromServer<Chat>>) {
+    for FromServer(chat) in chat_events.read() {
+        info!("received: {}", chat.0);
+    }
+}
+
+#[derive(Clone, Event, Deserialize, Serialize, TypePath)]
+struct Chat(String);
+```
+**/
+pub trait BidirectionalEventAppExt {
+    /// Registers `T` as a client event and [`FromServer<T>`] as a server event.
+    ///
+    /// See also [`ClientEventAppExt::add_client_event`] and [`ServerEventAppExt::add_server_event`].
+    fn add_bidirectional_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /// Same as [`Self::add_bidirectional_event`], but additionally maps client entities to server inside
+    /// client events and server entities to client inside server events.
+    ///
+    /// See also [`ClientEventAppExt::add_mapped_client_event`] and [`ServerEventAppExt::add_mapped_server_event`].
+    fn add_mapped_bidirectional_event<
+        T: Event + TypePath + Serialize + DeserializeOwned + MapEntities,
+    >(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+}
+
+impl BidirectionalEventAppExt for App {
+    fn add_bidirectional_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        let channel = channel.into();
+        self.add_client_event::<T>(channel.clone())
+            .add_server_event::<FromServer<T>>(channel)
+    }
+
+    fn add_mapped_bidirectional_event<
+        T: Event + TypePath + Serialize + DeserializeOwned + MapEntities,
+    >(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        let channel = channel.into();
+        self.add_mapped_client_event::<T>(channel.clone())
+            .add_mapped_server_event::<FromServer<T>>(channel)
+    }
+}
+
+/// An event `T` received from the server.
+///
+/// See [`BidirectionalEventAppExt`].
+#[derive(Clone, Copy, Debug, Deref, DerefMut, Deserialize, Event, Serialize, TypePath)]
+#[serde(transparent)]
+pub struct FromServer<T>(pub T);
+
+impl<T: MapEntities> MapEntities for FromServer<T> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0.map_entities(entity_mapper);
+    }
+}
@@ -1,30 +1,50 @@
-use std::{any, marker::PhantomData};
+pub mod rate_limit;
+mod reflect;
+pub mod speculation;
+pub mod validation;
+
+use std::{any, io::Cursor, marker::PhantomData, time::Duration};
 
 use bevy::{
-    ecs::{entity::MapEntities, event::Event},
+    ecs::{entity::MapEntities, event::Event, system::SystemParam},
     prelude::*,
+    reflect::GetTypeRegistration,
+    utils::{HashMap, Instant},
 };
 use bincode::{DefaultOptions, Options};
+use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
+use varint_rs::{VarintReader, VarintWriter};
 
 use super::EventMapper;
 use crate::{
     client::{replicon_client::RepliconClient, server_entity_map::ServerEntityMap, ClientSet},
     core::{
         common_conditions::{client_connected, has_authority, server_running},
-        replicon_channels::{RepliconChannel, RepliconChannels},
+        protocol_validation::{EventKind, ProtocolValidation},
+        replicon_channels::{ChannelKind, KeyedChannels, RepliconChannel},
+        replicon_tick::RepliconTick,
         ClientId,
     },
-    server::{replicon_server::RepliconServer, ServerSet},
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, server_tick::ServerTick, ServerSet,
+    },
 };
+use rate_limit::{EventRateLimit, RateLimitExceeded, RateLimiter};
+use speculation::{PendingSpeculations, SpeculateFn, Speculation, SpeculationChannel};
+use validation::{ClientEventRejected, EventValidation, RejectionChannel, ValidateFn};
 
 /// An extension trait for [`App`] for creating client events.
 pub trait ClientEventAppExt {
     /// Registers [`FromClient<T>`] event that will be emitted on server after sending `T` event on client.
     ///
+    /// Channel IDs are assigned when plugins are finished in the order of [`TypePath::type_path`],
+    /// so the registration order doesn't affect the protocol.
+    ///
     /// For usage example see the [corresponding section](../../index.html#from-client-to-server)
     /// in the quick start guide.
-    fn add_client_event<T: Event + Serialize + DeserializeOwned>(
+    fn add_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self;
@@ -32,11 +52,311 @@ pub trait ClientEventAppExt {
     /// Same as [`Self::add_client_event`], but additionally maps client entities to server inside the event before sending.
     ///
     /// Always use it for events that contain entities.
+    /// Events are mapped in place, so they are consumed on sending and can't be read
+    /// by systems that run after [`ClientSet::Send`](crate::client::ClientSet::Send).
     /// For usage example see the [corresponding section](../../index.html#from-client-to-server)
     /// in the quick start guide.
-    fn add_mapped_client_event<T: Event + Serialize + DeserializeOwned + MapEntities + Clone>(
+    fn add_mapped_client_event<T: Event + TypePath + Serialize + DeserializeOwned + MapEntities>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /// Same as [`Self::add_client_event`], but all `T` events sent in one frame
+    /// will be packed into a single message.
+    ///
+    /// Each event inside the message is prefixed with its length.
+    /// Reduces per-message overhead for events that are sent many times per frame,
+    /// such as inputs. Both client and server should register the event in the same way.
+    fn add_batched_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event`], but emits [`FromClient<WithMetadata<T>>`]
+    instead of [`FromClient<T>`] on server.
+
+    Intended for rate accounting and debugging.
+    For local events on listen server the size will be 0.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{network_event::client_event::WithMetadata, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_event_with_metadata::<Chat>(ChannelKind::Ordered)
+        .add_systems(Update, log_chat);
+
+    fn log_chat(mut chat_events: EventReader<FromClient<WithMetadata<Chat>>>, names: Res<ClientNames>) {
+        for FromClient { client_id, event, .. } in chat_events.read() {
+            info!(
+                "received {} bytes from {} over channel {}",
+                event.bytes,
+                names.label(*client_id),
+                event.channel
+            );
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct Chat(String);
+    ```
+    */
+    fn add_client_event_with_metadata<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event`], but all `T` events sent in one frame
+    will be coalesced into a single event according to the policy.
+
+    Intended for continuous inputs where only the final value matters,
+    so intermediate values aren't sent over the network.
+    Local events on listen server are not coalesced.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{network_event::client_event::CoalescePolicy, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_coalesced_client_event::<MoveIntent>(
+        ChannelKind::Unreliable,
+        CoalescePolicy::Latest,
+    )
+    .add_coalesced_client_event::<Scroll>(
+        ChannelKind::Ordered,
+        CoalescePolicy::Merge(|scroll, other| scroll.0 += other.0),
+    );
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct MoveIntent(Vec2);
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct Scroll(f32);
+    ```
+    */
+    fn add_coalesced_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        policy: CoalescePolicy<T>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event`], but `T` is sent as raw bytes without serialization.
+
+    Intended for crates that implement their own protocols on top of Replicon
+    and already have the payload serialized. `T` is converted into [`Bytes`] on client
+    and created from the received [`Bytes`] on server, so the payload is never copied or re-encoded.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use bytes::Bytes;
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_raw_client_event::<CustomPacket>(ChannelKind::Ordered);
+
+    #[derive(Event, TypePath)]
+    struct CustomPacket(Bytes);
+
+    impl From<Bytes> for CustomPacket {
+        fn from(value: Bytes) -> Self {
+            Self(value)
+        }
+    }
+
+    impl From<CustomPacket> for Bytes {
+        fn from(value: CustomPacket) -> Self {
+            value.0
+        }
+    }
+    ```
+    */
+    fn add_raw_client_event<T: Event + TypePath + From<Bytes> + Into<Bytes>>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event`], but serializes `T` using its [`Reflect`] implementation
+    instead of [`Serialize`] and [`DeserializeOwned`].
+
+    Intended for events that are defined in crates that only derive [`Reflect`], such as scripting or editor tooling.
+    The type is registered in [`AppTypeRegistry`] automatically, so all its fields should be registered too.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_reflect_client_event::<Rename>(ChannelKind::Ordered);
+
+    #[derive(Event, Reflect)]
+    struct Rename(String);
+    ```
+    */
+    fn add_reflect_client_event<T: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /// Same as [`Self::add_reflect_client_event`], but additionally maps client entities to server inside the event before sending.
+    ///
+    /// Entities are found by walking the reflected fields of the event, so [`MapEntities`] doesn't need to be implemented.
+    /// [`ReflectMapEntities`](bevy::ecs::reflect::ReflectMapEntities) is not used because it works only with components inside the world.
+    /// Like with [`Self::add_mapped_client_event`], events are consumed on sending.
+    fn add_mapped_reflect_client_event<T: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /// Same as [`Self::add_client_event`], but the server confirms receipt of each event.
+    ///
+    /// After the confirmation arrives, [`EventAck<T>`] with the original event will be emitted on client.
+    /// Useful for UI feedback on high-latency connections, especially with unreliable channels
+    /// where events could be lost. Confirmations are always sent over [`ChannelKind::Unordered`].
+    ///
+    /// If the confirmation doesn't arrive within [`AckTimeout`], the event is considered lost
+    /// and [`EventLost<T>`] will be emitted instead. Events that weren't confirmed before a disconnect will be discarded.
+    fn add_client_event_with_ack<T: Event + TypePath + Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event`], but the server validates each event before emitting [`FromClient<T>`].
+
+    The validation function has read-only access to the [`World`] and runs before the event reaches gameplay systems.
+    Rejected events are discarded. If [`RejectReason::notify`](validation::RejectReason::notify) is used,
+    [`ClientEventRejected<T>`] with the reason will be emitted on the offending client.
+
+    Local events on listen server are not validated.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{network_event::client_event::validation::RejectReason, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_event_with_validation::<BuyItem>(ChannelKind::Ordered, validate_purchase);
+
+    fn validate_purchase(
+        _client_id: &ClientId,
+        event: &BuyItem,
+        world: &World,
+    ) -> Result<(), RejectReason> {
+        let shop = world.resource::<Shop>();
+        if !shop.0.contains(&event.0) {
+            return Err(RejectReason::notify("item is not available"));
+        }
+
+        Ok(())
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct BuyItem(u32);
+
+    #[derive(Resource, Default)]
+    struct Shop(Vec<u32>);
+    # app.init_resource::<Shop>();
+    ```
+    */
+    fn add_client_event_with_validation<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
+        validate: ValidateFn<T>,
+    ) -> &mut Self;
+
+    /**
+    Same as [`Self::add_client_event_with_validation`], but the client applies each event locally before
+    the server decision arrives.
+
+    `apply` is called on client right before sending the event, so obvious local effects, like opening a door,
+    appear without waiting for the round trip. The server validates the event and always reports the decision back.
+    If the event is rejected, `rollback` is called on client to undo the effects of `apply`,
+    and [`ClientEventRejected<T>`] with the reason is emitted.
+
+    Events that weren't decided before a disconnect are discarded without a rollback.
+    Local events on listen server are neither applied speculatively nor validated.
+
+    # Panics
+
+    Panics if `channel` is [`ChannelKind::Unreliable`]. Events are kept until the server decision arrives,
+    so a lost event would never be rolled back.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{network_event::client_event::validation::RejectReason, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_speculative_client_event::<OpenDoor>(
+        ChannelKind::Ordered,
+        validate_door,
+        open_door,
+        close_door,
+    );
+
+    fn validate_door(
+        _client_id: &ClientId,
+        event: &OpenDoor,
+        world: &World,
+    ) -> Result<(), RejectReason> {
+        match world.get::<Door>(event.0) {
+            Some(Door { locked: false, .. }) => Ok(()),
+            _ => Err(RejectReason::notify("door is locked")),
+        }
+    }
+
+    fn open_door(world: &mut World, event: &OpenDoor) {
+        if let Some(mut door) = world.get_mut::<Door>(event.0) {
+            door.open = true;
+        }
+    }
+
+    fn close_door(world: &mut World, event: &OpenDoor) {
+        if let Some(mut door) = world.get_mut::<Door>(event.0) {
+            door.open = false;
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct OpenDoor(Entity);
+
+    #[derive(Component)]
+    struct Door {
+        open: bool,
+        locked: bool,
+    }
+    ```
+    */
+    fn add_speculative_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        validate: ValidateFn<T>,
+        apply: SpeculateFn<T>,
+        rollback: SpeculateFn<T>,
     ) -> &mut Self;
 
     /**
@@ -50,7 +370,10 @@ pub trait ClientEventAppExt {
 
     ```
     use bevy::{prelude::*, reflect::serde::{ReflectSerializer, UntypedReflectDeserializer}};
-    use bevy_replicon::{network_event::client_event::ClientEventChannel, prelude::*};
+    use bevy_replicon::{
+        network_event::client_event::{ClientEventChannel, FromClientTicks},
+        prelude::*,
+    };
     use bincode::{DefaultOptions, Options};
     use serde::de::DeserializeSeed;
 
@@ -84,107 +407,620 @@ pub trait ClientEventAppExt {
         mut server: ResMut<RepliconServer>,
         channel: Res<ClientEventChannel<ReflectEvent>>,
         registry: Res<AppTypeRegistry>,
+        names: Res<ClientNames>,
+        ticks: FromClientTicks,
     ) {
         let registry = registry.read();
         for (client_id, message) in server.receive(*channel) {
             let mut deserializer = bincode::Deserializer::from_slice(&message, DefaultOptions::new());
             match UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer) {
                 Ok(reflect) => {
-                    reflect_events.send(FromClient {
-                        client_id,
-                        event: ReflectEvent(reflect),
-                    });
+                    reflect_events.send(ticks.from_client(client_id, ReflectEvent(reflect)));
                 }
                 Err(e) => {
-                    debug!("unable to deserialize event from {client_id:?}: {e}")
+                    debug!(
+                        "unable to deserialize event from {}: {e}",
+                        names.label(client_id)
+                    )
                 }
             }
         }
     }
 
-    #[derive(Event)]
+    #[derive(Event, TypePath)]
     struct ReflectEvent(Box<dyn Reflect>);
     ```
     */
-    fn add_client_event_with<T: Event, Marker1, Marker2>(
+    fn add_client_event_with<T: Event + TypePath, Marker1, Marker2>(
         &mut self,
         channel: impl Into<RepliconChannel>,
         send_system: impl IntoSystemConfigs<Marker1>,
         receive_system: impl IntoSystemConfigs<Marker2>,
     ) -> &mut Self;
+
+    /**
+    Discards `T` events on server unless the condition is met.
+
+    Intended for gating events by game state, for example, with `in_state(MatchState::Playing)`,
+    but any run condition can be used.
+    Events are discarded before deserialization, so handler systems won't see them
+    and don't need to check the state manually. Local events on listen server are also discarded.
+    Dropped events are counted in [`DroppedClientEvents<T>`].
+
+    Can be called multiple times for the same event, in this case
+    the event will be accepted only if all conditions are met.
+
+    Should be called after the event registration.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_event::<MoveInput>(ChannelKind::Ordered)
+        .gate_client_event::<MoveInput, _>(resource_exists::<Match>);
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct MoveInput(Vec2);
+
+    /// Inserted when match starts.
+    #[derive(Resource)]
+    struct Match;
+    ```
+    */
+    fn gate_client_event<T: Event, M>(&mut self, condition: impl Condition<M> + Clone)
+        -> &mut Self;
+
+    /**
+    Limits the number of `T` messages that the server accepts from each client.
+
+    Messages are checked after receiving, so handler systems will see only accepted events.
+    Excess messages are dropped or buffered depending on [`EventRateLimit::overflow`].
+    [`RateLimitExceeded<T>`] will be emitted for each client that exceeded the limits during the update,
+    which can be used to kick abusive clients.
+
+    Limits apply to messages, so for [`Self::add_batched_client_event`] a message with
+    all events from one client frame is counted once. Local events on listen server are not limited.
+
+    Should be called after the event registration.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        network_event::client_event::rate_limit::{EventRateLimit, RateLimitExceeded},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_event::<Chat>(ChannelKind::Ordered)
+        .limit_client_event::<Chat>(EventRateLimit {
+            max_per_second: Some(5),
+            ..Default::default()
+        })
+        .add_systems(Update, kick_spammers);
+
+    fn kick_spammers(
+        mut exceeded_events: EventReader<RateLimitExceeded<Chat>>,
+        names: Res<ClientNames>,
+    ) {
+        for event in exceeded_events.read() {
+            info!("{} sends chat messages too often", names.label(event.client_id));
+            // Disconnect the client via the messaging backend...
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize, TypePath)]
+    struct Chat(String);
+    ```
+    */
+    fn limit_client_event<T: Event>(&mut self, limit: EventRateLimit) -> &mut Self;
 }
 
 impl ClientEventAppExt for App {
-    fn add_client_event<T: Event + Serialize + DeserializeOwned>(
+    fn add_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self {
+        ProtocolValidation::add_unmapped_event::<T>(self.world_mut(), EventKind::Client);
         self.add_client_event_with::<T, _, _>(channel, send::<T>, receive::<T>)
     }
 
-    fn add_mapped_client_event<T: Event + Serialize + DeserializeOwned + MapEntities + Clone>(
+    fn add_mapped_client_event<T: Event + TypePath + Serialize + DeserializeOwned + MapEntities>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self {
         self.add_client_event_with::<T, _, _>(channel, map_and_send::<T>, receive::<T>)
     }
 
-    fn add_client_event_with<T: Event, Marker1, Marker2>(
+    fn add_batched_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
-        send_system: impl IntoSystemConfigs<Marker1>,
-        receive_system: impl IntoSystemConfigs<Marker2>,
     ) -> &mut Self {
-        let channel_id = self
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel.into());
-
-        self.add_event::<T>()
-            .init_resource::<Events<FromClient<T>>>()
-            .insert_resource(ClientEventChannel::<T>::new(channel_id))
+        self.add_client_event_with::<T, _, _>(channel, send_batched::<T>, receive_batched::<T>)
+    }
+
+    fn add_client_event_with_metadata<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.add_client_event_with::<T, _, _>(channel, send::<T>, receive_with_metadata::<T>)
+            .init_resource::<Events<FromClient<WithMetadata<T>>>>()
+            .add_systems(
+                PostUpdate,
+                wrap_local::<T>
+                    .after(resend_locally::<T>)
+                    .in_set(ClientSet::Send)
+                    .run_if(has_authority),
+            )
+    }
+
+    fn add_coalesced_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        policy: CoalescePolicy<T>,
+    ) -> &mut Self {
+        self.insert_resource(EventCoalescing(policy))
+            .add_client_event_with::<T, _, _>(channel, send_coalesced::<T>, receive::<T>)
+    }
+
+    fn add_raw_client_event<T: Event + TypePath + From<Bytes> + Into<Bytes>>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.add_client_event_with::<T, _, _>(channel, send_raw::<T>, receive_raw::<T>)
+    }
+
+    fn add_reflect_client_event<T: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.register_type::<T>().add_client_event_with::<T, _, _>(
+            channel,
+            reflect::send_reflect::<T>,
+            reflect::receive_reflect::<T>,
+        )
+    }
+
+    fn add_mapped_reflect_client_event<T: Event + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.register_type::<T>().add_client_event_with::<T, _, _>(
+            channel,
+            reflect::map_and_send_reflect::<T>,
+            reflect::receive_reflect::<T>,
+        )
+    }
+
+    fn add_client_event_with_ack<T: Event + TypePath + Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        KeyedChannels::register_server(
+            self.world_mut(),
+            EventAckChannel::<T>::type_path(),
+            ChannelKind::Unordered.into(),
+            |world, channel_id| world.insert_resource(EventAckChannel::<T>::new(channel_id)),
+        );
+
+        self.add_client_event_with::<T, _, _>(channel, send_with_ack::<T>, receive_with_ack::<T>)
+            .add_event::<EventAck<T>>()
+            .add_event::<EventLost<T>>()
+            .init_resource::<AckTimeout>()
+            .init_resource::<PendingAcks<T>>()
             .add_systems(
                 PreUpdate,
                 (
-                    reset::<T>.in_set(ClientSet::ResetEvents),
-                    receive_system
-                        .in_set(ServerSet::Receive)
-                        .run_if(server_running),
+                    reset_acks::<T>.in_set(ClientSet::ResetEvents),
+                    (receive_acks::<T>, expire_acks::<T>)
+                        .chain()
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
                 ),
             )
             .add_systems(
                 PostUpdate,
-                (
-                    send_system.run_if(client_connected),
-                    resend_locally::<T>.run_if(has_authority),
-                )
-                    .chain()
-                    .in_set(ClientSet::Send),
-            );
+                ack_locally::<T>
+                    .after(resend_locally::<T>)
+                    .in_set(ClientSet::Send)
+                    .run_if(has_authority),
+            )
+    }
 
-        self
+    fn add_client_event_with_validation<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        validate: ValidateFn<T>,
+    ) -> &mut Self {
+        KeyedChannels::register_server(
+            self.world_mut(),
+            RejectionChannel::<T>::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, channel_id| world.insert_resource(RejectionChannel::<T>::new(channel_id)),
+        );
+
+        self.add_client_event_with::<T, _, _>(
+            channel,
+            send::<T>,
+            validation::receive_validated::<T>,
+        )
+        .insert_resource(EventValidation(validate))
+        .add_event::<ClientEventRejected<T>>()
+        .add_systems(
+            PreUpdate,
+            validation::receive_rejections::<T>
+                .in_set(ClientSet::Receive)
+                .run_if(client_connected),
+        )
     }
+
+    fn add_speculative_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        validate: ValidateFn<T>,
+        apply: SpeculateFn<T>,
+        rollback: SpeculateFn<T>,
+    ) -> &mut Self {
+        let channel = channel.into();
+        assert_ne!(
+            channel.kind,
+            ChannelKind::Unreliable,
+            "speculative event `{}` should use a reliable channel",
+            any::type_name::<T>()
+        );
+
+        KeyedChannels::register_server(
+            self.world_mut(),
+            SpeculationChannel::<T>::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, channel_id| world.insert_resource(SpeculationChannel::<T>::new(channel_id)),
+        );
+
+        self.add_client_event_with::<T, _, _>(
+            channel,
+            speculation::send_speculative::<T>,
+            speculation::receive_speculative::<T>,
+        )
+        .insert_resource(EventValidation(validate))
+        .insert_resource(Speculation { apply, rollback })
+        .init_resource::<PendingSpeculations<T>>()
+        .add_event::<ClientEventRejected<T>>()
+        .add_systems(
+            PreUpdate,
+            (
+                speculation::reset_speculations::<T>.in_set(ClientSet::ResetEvents),
+                speculation::receive_decisions::<T>
+                    .in_set(ClientSet::Receive)
+                    .run_if(client_connected),
+            ),
+        )
+    }
+
+    fn add_client_event_with<T: Event + TypePath, Marker1, Marker2>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        send_system: impl IntoSystemConfigs<Marker1>,
+        receive_system: impl IntoSystemConfigs<Marker2>,
+    ) -> &mut Self {
+        add_keyed_client_event_with::<T, _, _>(
+            self,
+            ClientEventChannel::<T>::type_path(),
+            channel,
+            send_system,
+            receive_system,
+        )
+    }
+
+    fn gate_client_event<T: Event, M>(
+        &mut self,
+        condition: impl Condition<M> + Clone,
+    ) -> &mut Self {
+        self.init_resource::<DroppedClientEvents<T>>()
+            .add_systems(
+                PreUpdate,
+                discard_received::<T>
+                    .after(ServerSet::ReceivePackets)
+                    .before(ServerSet::Receive)
+                    .run_if(server_running)
+                    .run_if(not(condition.clone())),
+            )
+            .add_systems(
+                PostUpdate,
+                discard_local::<T>
+                    .before(resend_locally::<T>)
+                    .in_set(ClientSet::Send)
+                    .run_if(has_authority)
+                    .run_if(not(condition)),
+            )
+    }
+
+    fn limit_client_event<T: Event>(&mut self, limit: EventRateLimit) -> &mut Self {
+        self.insert_resource(RateLimiter::<T>::new(limit))
+            .add_event::<RateLimitExceeded<T>>()
+            .add_systems(
+                PreUpdate,
+                rate_limit::limit_received::<T>
+                    .after(ServerSet::ReceivePackets)
+                    .before(ServerSet::Receive)
+                    .run_if(server_running),
+            )
+    }
+}
+
+/// Same as [`ClientEventAppExt::add_client_event_with`], but registers the channel under `key`
+/// instead of the type path of [`ClientEventChannel<T>`].
+///
+/// Used for events that should keep their channel when the type is renamed or replaced.
+pub(crate) fn add_keyed_client_event_with<T: Event, Marker1, Marker2>(
+    app: &mut App,
+    key: &'static str,
+    channel: impl Into<RepliconChannel>,
+    send_system: impl IntoSystemConfigs<Marker1>,
+    receive_system: impl IntoSystemConfigs<Marker2>,
+) -> &mut App {
+    KeyedChannels::register_client(app.world_mut(), key, channel.into(), |world, channel_id| {
+        world.insert_resource(ClientEventChannel::<T>::new(channel_id))
+    });
+
+    app.add_event::<T>()
+        .init_resource::<Events<FromClient<T>>>()
+        .add_systems(
+            PreUpdate,
+            (
+                reset::<T>.in_set(ClientSet::ResetEvents),
+                receive_system
+                    .in_set(ServerSet::Receive)
+                    .run_if(server_running),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                send_system.run_if(client_connected),
+                resend_locally::<T>.run_if(has_authority),
+            )
+                .chain()
+                .in_set(ClientSet::Send),
+        )
 }
 
 fn receive<T: Event + DeserializeOwned>(
     mut client_events: EventWriter<FromClient<T>>,
     mut server: ResMut<RepliconServer>,
     channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
 ) {
     for (client_id, message) in server.receive(*channel) {
         match DefaultOptions::new().deserialize(&message) {
             Ok(event) => {
                 trace!(
-                    "applying event `{}` from `{client_id:?}`",
-                    any::type_name::<T>()
+                    "applying event `{}` from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
                 );
-                client_events.send(FromClient { client_id, event });
+                client_events.send(ticks.from_client(client_id, event));
             }
-            Err(e) => debug!("unable to deserialize event from {client_id:?}: {e}"),
+            Err(e) => debug!(
+                "unable to deserialize event from {}: {e}",
+                names.label(client_id)
+            ),
         }
     }
 }
 
+fn receive_with_metadata<T: Event + DeserializeOwned>(
+    mut client_events: EventWriter<FromClient<WithMetadata<T>>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    let received_at = Instant::now();
+    for (client_id, message) in server.receive(*channel) {
+        match DefaultOptions::new().deserialize(&message) {
+            Ok(event) => {
+                trace!(
+                    "applying event `{}` from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                let event = WithMetadata {
+                    event,
+                    bytes: message.len(),
+                    channel: channel.id,
+                    received_at,
+                };
+                client_events.send(ticks.from_client(client_id, event));
+            }
+            Err(e) => debug!(
+                "unable to deserialize event from {}: {e}",
+                names.label(client_id)
+            ),
+        }
+    }
+}
+
+fn receive_raw<T: Event + From<Bytes>>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    for (client_id, message) in server.receive(*channel) {
+        trace!(
+            "applying raw event `{}` from {}",
+            any::type_name::<T>(),
+            names.label(client_id)
+        );
+        client_events.send(ticks.from_client(client_id, message.into()));
+    }
+}
+
+fn receive_batched<T: Event + DeserializeOwned>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    for (client_id, message) in server.receive(*channel) {
+        let mut cursor = Cursor::new(&*message);
+        while cursor.position() < message.len() as u64 {
+            match deserialize_batched(&mut cursor) {
+                Ok(event) => {
+                    trace!(
+                        "applying batched event `{}` from {}",
+                        any::type_name::<T>(),
+                        names.label(client_id)
+                    );
+                    client_events.send(ticks.from_client(client_id, event));
+                }
+                Err(e) => {
+                    debug!(
+                        "unable to deserialize batched event from {}: {e}",
+                        names.label(client_id)
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Reads a length-prefixed event written by [`send_batched`].
+fn deserialize_batched<T: DeserializeOwned>(cursor: &mut Cursor<&[u8]>) -> bincode::Result<T> {
+    let len = cursor.read_u32_varint()? as usize;
+    let start = cursor.position() as usize;
+    let message = *cursor.get_ref();
+    let bytes = start
+        .checked_add(len)
+        .and_then(|end| message.get(start..end))
+        .ok_or_else(|| bincode::ErrorKind::Custom(format!("event length {len} exceeds message")))?;
+    cursor.set_position((start + len) as u64);
+
+    DefaultOptions::new().deserialize(bytes)
+}
+
+/// Receives events with sequence numbers written by [`send_with_ack`] and confirms them.
+fn receive_with_ack<T: Event + DeserializeOwned>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    ack_channel: Res<EventAckChannel<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    let mut acks = HashMap::<ClientId, Vec<u8>>::default();
+    for (client_id, message) in server.receive(*channel) {
+        let mut cursor = Cursor::new(&*message);
+        let result = cursor
+            .read_u32_varint()
+            .map_err(Into::into)
+            .and_then(|sequence| {
+                DefaultOptions::new()
+                    .deserialize_from(&mut cursor)
+                    .map(|event| (sequence, event))
+            });
+        match result {
+            Ok((sequence, event)) => {
+                trace!(
+                    "applying event `{}` with sequence {sequence} from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                client_events.send(ticks.from_client(client_id, event));
+                acks.entry(client_id)
+                    .or_default()
+                    .write_u32_varint(sequence)
+                    .expect("writing into a vector shouldn't fail");
+            }
+            Err(e) => debug!(
+                "unable to deserialize event from {}: {e}",
+                names.label(client_id)
+            ),
+        }
+    }
+
+    for (client_id, message) in acks {
+        server.send(client_id, *ack_channel, message);
+    }
+}
+
+/// Emits [`EventAck<T>`] for events confirmed by the server.
+fn receive_acks<T: Event + Clone>(
+    mut acks: EventWriter<EventAck<T>>,
+    mut client: ResMut<RepliconClient>,
+    mut pending_acks: ResMut<PendingAcks<T>>,
+    ack_channel: Res<EventAckChannel<T>>,
+) {
+    for message in client.receive(*ack_channel) {
+        let mut cursor = Cursor::new(&*message);
+        while cursor.position() < message.len() as u64 {
+            match cursor.read_u32_varint() {
+                Ok(sequence) => {
+                    if let Some(PendingAck { event, .. }) = pending_acks.events.remove(&sequence) {
+                        trace!(
+                            "received ack for event `{}` with sequence {sequence}",
+                            any::type_name::<T>()
+                        );
+                        acks.send(EventAck { event });
+                    }
+                }
+                Err(e) => {
+                    debug!("unable to deserialize ack: {e}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Emits [`EventLost<T>`] for events that weren't confirmed within [`AckTimeout`].
+fn expire_acks<T: Event>(
+    mut lost_events: EventWriter<EventLost<T>>,
+    mut pending_acks: ResMut<PendingAcks<T>>,
+    timeout: Res<AckTimeout>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed();
+    let mut expired: Vec<_> = pending_acks
+        .events
+        .iter()
+        .filter(|(_, pending)| now.saturating_sub(pending.sent_at) >= **timeout)
+        .map(|(&sequence, pending)| (pending.sent_at, sequence))
+        .collect();
+    if expired.is_empty() {
+        return;
+    }
+
+    debug!(
+        "considering {} events `{}` lost after {:?} without confirmation",
+        expired.len(),
+        any::type_name::<T>(),
+        **timeout
+    );
+    expired.sort_unstable();
+    for (_, sequence) in expired {
+        let pending = pending_acks
+            .events
+            .remove(&sequence)
+            .expect("expired sequence should be pending");
+        lost_events.send(EventLost {
+            event: pending.event,
+        });
+    }
+}
+
 fn send<T: Event + Serialize>(
     mut events: EventReader<T>,
     mut client: ResMut<RepliconClient>,
@@ -200,13 +1036,125 @@ fn send<T: Event + Serialize>(
     }
 }
 
-fn map_and_send<T: Event + MapEntities + Serialize + Clone>(
+/// Sends a single event coalesced from all events of this frame.
+///
+/// Drains events since intermediate values are consumed by coalescing.
+fn send_coalesced<T: Event + Serialize>(
+    mut events: ResMut<Events<T>>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+    coalescing: Res<EventCoalescing<T>>,
+) {
+    let mut count = 0;
+    let coalesced = events.drain().reduce(|mut coalesced, event| {
+        count += 1;
+        match coalescing.0 {
+            CoalescePolicy::Latest => event,
+            CoalescePolicy::Merge(merge) => {
+                (merge)(&mut coalesced, event);
+                coalesced
+            }
+        }
+    });
+
+    if let Some(event) = coalesced {
+        let message = DefaultOptions::new()
+            .serialize(&event)
+            .expect("client event should be serializable");
+
+        trace!(
+            "sending event `{}` coalesced from {} events",
+            any::type_name::<T>(),
+            count + 1
+        );
+        client.send(*channel, message);
+    }
+}
+
+/// Drains events because converting them into [`Bytes`] consumes them.
+fn send_raw<T: Event + Into<Bytes>>(
+    mut events: ResMut<Events<T>>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+) {
+    for event in events.drain() {
+        trace!("sending raw event `{}`", any::type_name::<T>());
+        client.send(*channel, event);
+    }
+}
+
+fn send_batched<T: Event + Serialize>(
     mut events: EventReader<T>,
     mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+) {
+    let mut message = Vec::new();
+    let mut count = 0;
+    for event in events.read() {
+        let bytes = DefaultOptions::new()
+            .serialize(&event)
+            .expect("client event should be serializable");
+        message
+            .write_u32_varint(bytes.len() as u32)
+            .expect("writing into a vector shouldn't fail");
+        message.extend_from_slice(&bytes);
+        count += 1;
+    }
+
+    if count > 0 {
+        trace!(
+            "sending {count} events `{}` in a batch",
+            any::type_name::<T>()
+        );
+        client.send(*channel, message);
+    }
+}
+
+/// Sends events prefixed with sequence numbers and keeps them until confirmation.
+fn send_with_ack<T: Event + Serialize + Clone>(
+    mut events: EventReader<T>,
+    mut client: ResMut<RepliconClient>,
+    mut pending_acks: ResMut<PendingAcks<T>>,
+    channel: Res<ClientEventChannel<T>>,
+    time: Res<Time>,
+) {
+    for event in events.read() {
+        let sequence = pending_acks.next_sequence;
+        pending_acks.next_sequence = sequence.wrapping_add(1);
+
+        let mut message = Vec::new();
+        message
+            .write_u32_varint(sequence)
+            .expect("writing into a vector shouldn't fail");
+        DefaultOptions::new()
+            .serialize_into(&mut message, &event)
+            .expect("client event should be serializable");
+
+        trace!(
+            "sending event `{}` with sequence {sequence}",
+            any::type_name::<T>()
+        );
+        client.send(*channel, message);
+        pending_acks.events.insert(
+            sequence,
+            PendingAck {
+                event: event.clone(),
+                sent_at: time.elapsed(),
+            },
+        );
+    }
+}
+
+/// Drains events to map them in place without cloning.
+///
+/// Doesn't conflict with [`resend_locally`] since they can't run at the same time.
+fn map_and_send<T: Event + MapEntities + Serialize>(
+    mut events: ResMut<Events<T>>,
+    mut client: ResMut<RepliconClient>,
     entity_map: Res<ServerEntityMap>,
     channel: Res<ClientEventChannel<T>>,
 ) {
-    for mut event in events.read().cloned() {
+    for mut event in events.drain() {
         event.map_entities(&mut EventMapper(entity_map.to_server()));
         let message = DefaultOptions::new()
             .serialize(&event)
@@ -222,15 +1170,80 @@ fn map_and_send<T: Event + MapEntities + Serialize + Clone>(
 fn resend_locally<T: Event>(
     mut events: ResMut<Events<T>>,
     mut client_events: EventWriter<FromClient<T>>,
+    ticks: FromClientTicks,
 ) {
     for event in events.drain() {
-        client_events.send(FromClient {
-            client_id: ClientId::SERVER,
-            event,
+        client_events.send(ticks.from_client(ClientId::SERVER, event));
+    }
+}
+
+/// Wraps events that were resent locally by [`resend_locally`] into [`WithMetadata`].
+fn wrap_local<T: Event>(
+    mut client_events: ResMut<Events<FromClient<T>>>,
+    mut metadata_events: EventWriter<FromClient<WithMetadata<T>>>,
+    channel: Res<ClientEventChannel<T>>,
+) {
+    let received_at = Instant::now();
+    for event in client_events.drain() {
+        metadata_events.send(FromClient {
+            client_id: event.client_id,
+            event: WithMetadata {
+                event: event.event,
+                bytes: 0,
+                channel: channel.id,
+                received_at,
+            },
+            server_tick: event.server_tick,
+            client_tick: event.client_tick,
         });
     }
 }
 
+/// Confirms events that were resent locally by [`resend_locally`].
+fn ack_locally<T: Event + Clone>(
+    mut client_events: EventReader<FromClient<T>>,
+    mut acks: EventWriter<EventAck<T>>,
+) {
+    for FromClient {
+        client_id, event, ..
+    } in client_events.read()
+    {
+        if *client_id == ClientId::SERVER {
+            acks.send(EventAck {
+                event: event.clone(),
+            });
+        }
+    }
+}
+
+/// Drops received messages for `T` while its gate is closed.
+fn discard_received<T: Event>(
+    mut server: ResMut<RepliconServer>,
+    mut dropped: ResMut<DroppedClientEvents<T>>,
+    channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+) {
+    for (client_id, _) in server.receive(*channel) {
+        trace!(
+            "dropping event `{}` from {} by gate",
+            any::type_name::<T>(),
+            names.label(client_id)
+        );
+        dropped.increment(client_id);
+    }
+}
+
+/// Drops local events for `T` while its gate is closed.
+fn discard_local<T: Event>(
+    mut events: ResMut<Events<T>>,
+    mut dropped: ResMut<DroppedClientEvents<T>>,
+) {
+    for _ in events.drain() {
+        trace!("dropping local event `{}` by gate", any::type_name::<T>());
+        dropped.increment(ClientId::SERVER);
+    }
+}
+
 /// Discards all pending events.
 ///
 /// We discard events while waiting to connect to ensure clean reconnects.
@@ -241,8 +1254,43 @@ fn reset<T: Event>(mut events: ResMut<Events<T>>) {
     }
 }
 
-/// Holds a client's channel ID for `T`.
+/// Discards all events that are waiting for confirmation.
+fn reset_acks<T: Event>(mut pending_acks: ResMut<PendingAcks<T>>) {
+    if !pending_acks.events.is_empty() {
+        debug!(
+            "discarding {} unconfirmed events `{}` due to a disconnect",
+            pending_acks.events.len(),
+            any::type_name::<T>()
+        );
+    }
+    pending_acks.events.clear();
+}
+
+/// How to combine multiple `T` events sent in one frame.
+///
+/// See [`ClientEventAppExt::add_coalesced_client_event`].
+pub enum CoalescePolicy<T> {
+    /// Keep only the last event.
+    Latest,
+
+    /// Merge subsequent events into the first one using the function.
+    Merge(fn(&mut T, T)),
+}
+
+impl<T> Clone for CoalescePolicy<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CoalescePolicy<T> {}
+
+/// Coalescing policy for `T`.
 #[derive(Resource)]
+struct EventCoalescing<T>(CoalescePolicy<T>);
+
+/// Holds a client's channel ID for `T`.
+#[derive(Resource, TypePath)]
 pub struct ClientEventChannel<T> {
     id: u8,
     marker: PhantomData<T>,
@@ -271,10 +1319,201 @@ impl<T> From<ClientEventChannel<T>> for u8 {
     }
 }
 
+/// Holds a server's channel ID for acknowledgments of `T`.
+///
+/// See [`ClientEventAppExt::add_client_event_with_ack`].
+#[derive(Resource, TypePath)]
+pub struct EventAckChannel<T> {
+    id: u8,
+    marker: PhantomData<T>,
+}
+
+impl<T> EventAckChannel<T> {
+    fn new(id: u8) -> Self {
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for EventAckChannel<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EventAckChannel<T> {}
+
+impl<T> From<EventAckChannel<T>> for u8 {
+    fn from(value: EventAckChannel<T>) -> Self {
+        value.id
+    }
+}
+
+/// Sent `T` events that wait for confirmation from the server by their sequence numbers.
+#[derive(Resource)]
+struct PendingAcks<T> {
+    next_sequence: u32,
+    events: HashMap<u32, PendingAck<T>>,
+}
+
+/// An event waiting for confirmation with the time at which it was sent.
+struct PendingAck<T> {
+    event: T,
+    sent_at: Duration,
+}
+
+impl<T> Default for PendingAcks<T> {
+    fn default() -> Self {
+        Self {
+            next_sequence: 0,
+            events: Default::default(),
+        }
+    }
+}
+
+/// Number of `T` events dropped on server by [`ClientEventAppExt::gate_client_event`] for each client.
+#[derive(Resource)]
+pub struct DroppedClientEvents<T> {
+    counts: HashMap<ClientId, usize>,
+    marker: PhantomData<T>,
+}
+
+impl<T> DroppedClientEvents<T> {
+    /// Returns the number of dropped events from a client.
+    pub fn get(&self, client_id: ClientId) -> usize {
+        self.counts.get(&client_id).copied().unwrap_or_default()
+    }
+
+    /// Returns the number of dropped events from all clients.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Resets all counters.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+
+    fn increment(&mut self, client_id: ClientId) {
+        *self.counts.entry(client_id).or_default() += 1;
+    }
+}
+
+impl<T> Default for DroppedClientEvents<T> {
+    fn default() -> Self {
+        Self {
+            counts: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
 /// An event indicating that a message from client was received.
 /// Emited only on server.
 #[derive(Clone, Copy, Event)]
 pub struct FromClient<T> {
     pub client_id: ClientId,
     pub event: T,
+
+    /// Server tick at the moment of receiving.
+    pub server_tick: RepliconTick,
+
+    /// Estimated server tick that the client has seen when sending the event.
+    ///
+    /// Equals to the last tick acknowledged by the client, see [`ConnectedClient::acked_tick`](crate::server::connected_clients::ConnectedClient::acked_tick).
+    /// Useful for lag compensation.
+    /// For local events on listen server it's the same as [`Self::server_tick`].
+    pub client_tick: RepliconTick,
+}
+
+/// Client event with information about the received message.
+///
+/// See [`ClientEventAppExt::add_client_event_with_metadata`].
+#[derive(Clone, Copy, Debug)]
+pub struct WithMetadata<T> {
+    pub event: T,
+
+    /// Size of the message in bytes.
+    pub bytes: usize,
+
+    /// Channel ID over which the message was received.
+    pub channel: u8,
+
+    /// Time at which the message was processed by the server.
+    pub received_at: Instant,
+}
+
+/// Provides ticks for [`FromClient<T>`] in custom receive systems.
+///
+/// See [`ClientEventAppExt::add_client_event_with`] for an example.
+#[derive(SystemParam)]
+pub struct FromClientTicks<'w> {
+    server_tick: Option<Res<'w, ServerTick>>,
+    connected_clients: Option<Res<'w, ConnectedClients>>,
+}
+
+impl FromClientTicks<'_> {
+    /// Creates [`FromClient<T>`] for an event received from a client at the current tick.
+    pub fn from_client<T>(&self, client_id: ClientId, event: T) -> FromClient<T> {
+        let (server_tick, client_tick) = received_ticks(
+            self.server_tick.as_deref(),
+            self.connected_clients.as_deref(),
+            client_id,
+        );
+
+        FromClient {
+            client_id,
+            event,
+            server_tick,
+            client_tick,
+        }
+    }
+}
+
+/// Returns the current server tick and the estimated client tick.
+pub(super) fn received_ticks(
+    server_tick: Option<&ServerTick>,
+    connected_clients: Option<&ConnectedClients>,
+    client_id: ClientId,
+) -> (RepliconTick, RepliconTick) {
+    let server_tick = server_tick.map(|tick| **tick).unwrap_or_default();
+    let client_tick = connected_clients
+        .and_then(|clients| clients.get_client(client_id))
+        .map(|client| client.acked_tick())
+        .unwrap_or(server_tick);
+
+    (server_tick, client_tick)
+}
+
+/// An event indicating that the server received `T` sent by this client.
+/// Emitted only on client for events registered with [`ClientEventAppExt::add_client_event_with_ack`].
+#[derive(Clone, Copy, Event)]
+pub struct EventAck<T> {
+    pub event: T,
+}
+
+/// An event indicating that `T` sent by this client wasn't confirmed within [`AckTimeout`].
+///
+/// The server may still have received the event, only the confirmation is missing.
+/// Emitted only on client for events registered with [`ClientEventAppExt::add_client_event_with_ack`].
+#[derive(Clone, Copy, Event)]
+pub struct EventLost<T> {
+    pub event: T,
+}
+
+/// Time after which an event sent with [`ClientEventAppExt::add_client_event_with_ack`]
+/// is considered lost if the server didn't confirm it.
+///
+/// Bounds the memory used for unconfirmed events on unreliable channels.
+///
+/// By default set to 5 seconds.
+#[derive(Resource, Clone, Copy, Debug, Deref, DerefMut)]
+pub struct AckTimeout(pub Duration);
+
+impl Default for AckTimeout {
+    fn default() -> Self {
+        Self(Duration::from_secs(5))
+    }
 }
@@ -0,0 +1,211 @@
+use std::{any, collections::VecDeque, marker::PhantomData, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::ClientEventChannel;
+use crate::{
+    core::ClientId,
+    server::{client_names::ClientNames, replicon_server::RepliconServer, ServerEvent},
+};
+
+/// Limits for a client event per client.
+///
+/// See [`ClientEventAppExt::limit_client_event`](super::ClientEventAppExt::limit_client_event).
+#[derive(Clone, Copy, Debug)]
+pub struct EventRateLimit {
+    /// Maximum number of messages from a single client per update.
+    ///
+    /// [`None`] disables this limit.
+    pub max_per_update: Option<usize>,
+
+    /// Maximum number of messages from a single client within any one-second window.
+    ///
+    /// Measured in real time, so it's not affected by pausing or scaling the virtual time.
+    ///
+    /// [`None`] disables this limit.
+    pub max_per_second: Option<usize>,
+
+    /// What to do with messages that exceed the limits.
+    pub overflow: RateLimitOverflow,
+}
+
+impl Default for EventRateLimit {
+    fn default() -> Self {
+        Self {
+            max_per_update: None,
+            max_per_second: None,
+            overflow: RateLimitOverflow::Drop,
+        }
+    }
+}
+
+impl EventRateLimit {
+    /// Returns `true` if a client with the given state can send one more message.
+    fn allows(&self, state: &ClientRate) -> bool {
+        self.max_per_update
+            .map_or(true, |max| state.update_count < max)
+            && self
+                .max_per_second
+                .map_or(true, |max| state.accepted.len() < max)
+    }
+}
+
+/// Behavior for messages that exceed [`EventRateLimit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitOverflow {
+    /// Discard excess messages.
+    #[default]
+    Drop,
+
+    /// Keep excess messages and deliver them in later updates when the limits allow.
+    ///
+    /// If the buffer of a client is full, new excess messages will be discarded.
+    Buffer {
+        /// Maximum number of buffered messages per client.
+        max_buffered: usize,
+    },
+}
+
+/// An event indicating that a client exceeded [`EventRateLimit`] for `T` during the update.
+///
+/// Emitted only on server, at most once per client per update.
+/// Can be used to kick abusive clients.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct RateLimitExceeded<T> {
+    pub client_id: ClientId,
+
+    /// Number of excess messages in this update, both dropped and buffered.
+    pub excess: usize,
+
+    /// Number of dropped messages in this update.
+    pub dropped: usize,
+
+    marker: PhantomData<T>,
+}
+
+/// Rate limiting state for `T`.
+#[derive(Resource)]
+pub(super) struct RateLimiter<T> {
+    limit: EventRateLimit,
+    clients: HashMap<ClientId, ClientRate>,
+    marker: PhantomData<T>,
+}
+
+impl<T> RateLimiter<T> {
+    pub(super) fn new(limit: EventRateLimit) -> Self {
+        Self {
+            limit,
+            clients: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ClientRate {
+    /// Number of messages accepted in the current update.
+    update_count: usize,
+
+    /// Times of messages accepted within the last second.
+    accepted: VecDeque<Duration>,
+
+    /// Messages that exceeded the limits with [`RateLimitOverflow::Buffer`].
+    buffered: VecDeque<Bytes>,
+
+    /// Number of excess messages in the current update.
+    excess: usize,
+
+    /// Number of dropped messages in the current update.
+    dropped: usize,
+}
+
+impl ClientRate {
+    fn accept(&mut self, now: Duration) {
+        self.update_count += 1;
+        self.accepted.push_back(now);
+    }
+}
+
+/// Re-inserts only the messages for `T` that fit into the limits.
+///
+/// Should run after receiving packets, but before the receive system of `T`.
+pub(super) fn limit_received<T: Event>(
+    mut server_events: EventReader<ServerEvent>,
+    mut exceeded_events: EventWriter<RateLimitExceeded<T>>,
+    mut server: ResMut<RepliconServer>,
+    mut limiter: ResMut<RateLimiter<T>>,
+    channel: Res<ClientEventChannel<T>>,
+    time: Res<Time<Real>>,
+    names: Res<ClientNames>,
+) {
+    let now = time.elapsed();
+    let limiter = &mut *limiter;
+    for state in limiter.clients.values_mut() {
+        state.update_count = 0;
+        state.excess = 0;
+        state.dropped = 0;
+        while state
+            .accepted
+            .front()
+            .is_some_and(|&time| now.saturating_sub(time) >= Duration::from_secs(1))
+        {
+            state.accepted.pop_front();
+        }
+    }
+
+    let batch = server.take_received(*channel);
+
+    // Buffered messages go first to preserve the order.
+    for (&client_id, state) in &mut limiter.clients {
+        while limiter.limit.allows(state) {
+            let Some(message) = state.buffered.pop_front() else {
+                break;
+            };
+            state.accept(now);
+            server.insert_received(client_id, *channel, message);
+        }
+    }
+
+    for (client_id, message) in batch {
+        let state = limiter.clients.entry(client_id).or_default();
+        if state.buffered.is_empty() && limiter.limit.allows(state) {
+            state.accept(now);
+            server.insert_received(client_id, *channel, message);
+            continue;
+        }
+
+        state.excess += 1;
+        match limiter.limit.overflow {
+            RateLimitOverflow::Buffer { max_buffered } if state.buffered.len() < max_buffered => {
+                state.buffered.push_back(message);
+            }
+            _ => state.dropped += 1,
+        }
+    }
+
+    for (&client_id, state) in &limiter.clients {
+        if state.excess > 0 {
+            debug!(
+                "{} exceeded rate limit for event `{}` by {} messages, {} dropped",
+                names.label(client_id),
+                any::type_name::<T>(),
+                state.excess,
+                state.dropped,
+            );
+            exceeded_events.send(RateLimitExceeded {
+                client_id,
+                excess: state.excess,
+                dropped: state.dropped,
+                marker: PhantomData,
+            });
+        }
+    }
+
+    // Process after receiving to avoid re-adding states with buffered messages of disconnected clients.
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+            limiter.clients.remove(client_id);
+        }
+    }
+}
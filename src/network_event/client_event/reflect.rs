@@ -0,0 +1,173 @@
+use std::any::{self, TypeId};
+
+use bevy::{
+    ecs::entity::EntityMapper,
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        ReflectMut,
+    },
+};
+use bincode::{DefaultOptions, Options};
+use serde::de::DeserializeSeed;
+
+use super::{ClientEventChannel, FromClient, FromClientTicks};
+use crate::{
+    client::{replicon_client::RepliconClient, server_entity_map::ServerEntityMap},
+    network_event::EventMapper,
+    server::{client_names::ClientNames, replicon_server::RepliconServer},
+};
+
+/// Serializes events using their type registration.
+pub(super) fn send_reflect<T: Event + Reflect>(
+    mut events: EventReader<T>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+    registry: Res<AppTypeRegistry>,
+) {
+    let registry = registry.read();
+    for event in events.read() {
+        send(&mut client, *channel, &registry, event);
+    }
+}
+
+/// Same as [`send_reflect`], but maps all entities inside the event to server before sending.
+///
+/// Drains events to map them in place without cloning.
+/// Doesn't conflict with [`resend_locally`](super::resend_locally) since they can't run at the same time.
+pub(super) fn map_and_send_reflect<T: Event + Reflect>(
+    mut events: ResMut<Events<T>>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+    entity_map: Res<ServerEntityMap>,
+    registry: Res<AppTypeRegistry>,
+) {
+    let registry = registry.read();
+    for mut event in events.drain() {
+        map_entities(
+            event.as_reflect_mut(),
+            &mut EventMapper(entity_map.to_server()),
+        );
+        send(&mut client, *channel, &registry, &event);
+    }
+}
+
+fn send<T: Reflect>(
+    client: &mut RepliconClient,
+    channel: ClientEventChannel<T>,
+    registry: &bevy::reflect::TypeRegistry,
+    event: &T,
+) {
+    let serializer = TypedReflectSerializer::new(event.as_reflect(), registry);
+    let message = DefaultOptions::new()
+        .serialize(&serializer)
+        .expect("client event should be serializable");
+
+    trace!("sending reflect event `{}`", any::type_name::<T>());
+    client.send(channel, message);
+}
+
+/// Deserializes events using their type registration.
+pub(super) fn receive_reflect<T: Event + FromReflect>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    registry: Res<AppTypeRegistry>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    let registry = registry.read();
+    let registration = registry
+        .get(TypeId::of::<T>())
+        .unwrap_or_else(|| panic!("`{}` should be registered", any::type_name::<T>()));
+
+    for (client_id, message) in server.receive(*channel) {
+        let mut deserializer = bincode::Deserializer::from_slice(&message, DefaultOptions::new());
+        match TypedReflectDeserializer::new(registration, &registry).deserialize(&mut deserializer)
+        {
+            Ok(reflect) => match T::from_reflect(&*reflect) {
+                Some(event) => {
+                    trace!(
+                        "applying reflect event `{}` from {}",
+                        any::type_name::<T>(),
+                        names.label(client_id)
+                    );
+                    client_events.send(ticks.from_client(client_id, event));
+                }
+                None => debug!(
+                    "unable to convert reflected event from {} into `{}`",
+                    names.label(client_id),
+                    any::type_name::<T>()
+                ),
+            },
+            Err(e) => debug!(
+                "unable to deserialize event from {}: {e}",
+                names.label(client_id)
+            ),
+        }
+    }
+}
+
+/// Recursively maps all [`Entity`] fields inside a reflected value.
+///
+/// [`ReflectMapEntities`](bevy::ecs::reflect::ReflectMapEntities) works only with components inside the world,
+/// so events are mapped by walking their reflected structure instead.
+fn map_entities(value: &mut dyn Reflect, mapper: &mut impl EntityMapper) {
+    if let Some(entity) = value.downcast_mut::<Entity>() {
+        *entity = mapper.map_entity(*entity);
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                if let Some(element) = value.get_mut(index) {
+                    map_entities(element, mapper);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                if let Some(element) = value.get_mut(index) {
+                    map_entities(element, mapper);
+                }
+            }
+        }
+        ReflectMut::Map(value) => {
+            for index in 0..value.len() {
+                if let Some((_, element)) = value.get_at_mut(index) {
+                    map_entities(element, mapper);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::Value(_) => (),
+    }
+}
@@ -0,0 +1,269 @@
+use std::{any, io::Cursor, marker::PhantomData};
+
+use bevy::{ecs::event::ManualEventReader, prelude::*, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    received_ticks,
+    validation::{ClientEventRejected, EventValidation},
+    ClientEventChannel, FromClient,
+};
+use crate::{
+    client::replicon_client::RepliconClient,
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, server_tick::ServerTick,
+    },
+};
+
+/// Signature of functions that apply or roll back local effects of a speculative event.
+///
+/// See [`ClientEventAppExt::add_speculative_client_event`](super::ClientEventAppExt::add_speculative_client_event).
+pub type SpeculateFn<T> = fn(&mut World, &T);
+
+/// Local callbacks for speculative `T` events.
+#[derive(Resource)]
+pub(super) struct Speculation<T> {
+    pub(super) apply: SpeculateFn<T>,
+    pub(super) rollback: SpeculateFn<T>,
+}
+
+/// Serialized `T` events that were applied locally and wait for the server decision by their sequence numbers.
+///
+/// Events are stored serialized to read them from [`Events<T>`] without draining or cloning.
+#[derive(Resource)]
+pub(super) struct PendingSpeculations<T> {
+    next_sequence: u32,
+    events: HashMap<u32, Vec<u8>>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for PendingSpeculations<T> {
+    fn default() -> Self {
+        Self {
+            next_sequence: 0,
+            events: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Holds a server's channel ID for decisions about speculative `T` events.
+#[derive(Resource, TypePath)]
+pub struct SpeculationChannel<T> {
+    id: u8,
+    marker: PhantomData<T>,
+}
+
+impl<T> SpeculationChannel<T> {
+    pub(super) fn new(id: u8) -> Self {
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for SpeculationChannel<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SpeculationChannel<T> {}
+
+impl<T> From<SpeculationChannel<T>> for u8 {
+    fn from(value: SpeculationChannel<T>) -> Self {
+        value.id
+    }
+}
+
+/// Applies events locally and sends them prefixed with sequence numbers.
+///
+/// Exclusive to provide [`World`] access to the apply function.
+/// Events are read without draining, so other client systems can still read them.
+/// They are serialized before calling the apply function, so it can access [`Events<T>`] too.
+pub(super) fn send_speculative<T: Event + Serialize + DeserializeOwned>(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<T>>,
+) {
+    let events = world.resource::<Events<T>>();
+    let events_bytes: Vec<_> = reader
+        .read(events)
+        .map(|event| {
+            DefaultOptions::new()
+                .serialize(event)
+                .expect("client event should be serializable")
+        })
+        .collect();
+
+    let apply = world.resource::<Speculation<T>>().apply;
+    let channel = *world.resource::<ClientEventChannel<T>>();
+    for event_bytes in events_bytes {
+        let event = DefaultOptions::new()
+            .deserialize(&event_bytes)
+            .expect("client event should be deserializable since it was just serialized");
+        (apply)(world, &event);
+
+        let mut pending = world.resource_mut::<PendingSpeculations<T>>();
+        let sequence = pending.next_sequence;
+        pending.next_sequence = sequence.wrapping_add(1);
+
+        let mut message = DefaultOptions::new()
+            .serialize(&sequence)
+            .expect("sequence should be serializable");
+        message.extend_from_slice(&event_bytes);
+        pending.events.insert(sequence, event_bytes);
+
+        trace!(
+            "sending speculative event `{}` with sequence {sequence}",
+            any::type_name::<T>()
+        );
+        world
+            .resource_mut::<RepliconClient>()
+            .send(channel, message);
+    }
+}
+
+/// Receives events written by [`send_speculative`], validates them and sends decisions back.
+///
+/// Exclusive to provide [`World`] access to the validation function.
+pub(super) fn receive_speculative<T: Event + DeserializeOwned>(world: &mut World) {
+    let channel = *world.resource::<ClientEventChannel<T>>();
+    let batch = world
+        .resource_mut::<RepliconServer>()
+        .take_received(channel);
+
+    let validate = world.resource::<EventValidation<T>>().0;
+    let names = world.resource::<ClientNames>();
+    let server_tick = world.get_resource::<ServerTick>();
+    let connected_clients = world.get_resource::<ConnectedClients>();
+    let mut events = Vec::new();
+    let mut decisions = Vec::new();
+    for (client_id, message) in batch {
+        let mut cursor = Cursor::new(&*message);
+        let result = DefaultOptions::new()
+            .deserialize_from::<_, u32>(&mut cursor)
+            .and_then(|sequence| {
+                DefaultOptions::new()
+                    .deserialize_from(&mut cursor)
+                    .map(|event| (sequence, event))
+            });
+        let (sequence, event) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                debug!(
+                    "unable to deserialize speculative event from {}: {e}",
+                    names.label(client_id)
+                );
+                continue;
+            }
+        };
+
+        let rejection = match (validate)(&client_id, &event, world) {
+            Ok(()) => {
+                trace!(
+                    "applying speculative event `{}` with sequence {sequence} from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                let (server_tick, client_tick) =
+                    received_ticks(server_tick, connected_clients, client_id);
+                events.push(FromClient {
+                    client_id,
+                    event,
+                    server_tick,
+                    client_tick,
+                });
+                None
+            }
+            Err(reason) => {
+                debug!(
+                    "rejecting speculative event `{}` with sequence {sequence} from {}: {}",
+                    any::type_name::<T>(),
+                    names.label(client_id),
+                    reason.message
+                );
+                Some(reason.message)
+            }
+        };
+
+        let message = DefaultOptions::new()
+            .serialize(&(sequence, rejection))
+            .expect("speculation decision should be serializable");
+        decisions.push((client_id, message));
+    }
+
+    world
+        .resource_mut::<Events<FromClient<T>>>()
+        .send_batch(events);
+
+    let speculation_channel = *world.resource::<SpeculationChannel<T>>();
+    let mut server = world.resource_mut::<RepliconServer>();
+    for (client_id, message) in decisions {
+        server.send(client_id, speculation_channel, message);
+    }
+}
+
+/// Receives decisions from the server and rolls back rejected events.
+///
+/// Exclusive to provide [`World`] access to the rollback function.
+pub(super) fn receive_decisions<T: Event + DeserializeOwned>(world: &mut World) {
+    let channel = *world.resource::<SpeculationChannel<T>>();
+    let messages: Vec<_> = world
+        .resource_mut::<RepliconClient>()
+        .receive(channel)
+        .collect();
+
+    let rollback = world.resource::<Speculation<T>>().rollback;
+    for message in messages {
+        let (sequence, rejection) =
+            match DefaultOptions::new().deserialize::<(u32, Option<String>)>(&message) {
+                Ok(decision) => decision,
+                Err(e) => {
+                    debug!("unable to deserialize speculation decision: {e}");
+                    continue;
+                }
+            };
+
+        let Some(event_bytes) = world
+            .resource_mut::<PendingSpeculations<T>>()
+            .events
+            .remove(&sequence)
+        else {
+            continue;
+        };
+
+        if let Some(reason) = rejection {
+            let event = DefaultOptions::new()
+                .deserialize(&event_bytes)
+                .expect("pending event should be deserializable since it was serialized by client");
+            trace!(
+                "rolling back speculative event `{}` with sequence {sequence}: {reason}",
+                any::type_name::<T>()
+            );
+            (rollback)(world, &event);
+            world.send_event(ClientEventRejected::<T>::new(reason));
+        } else {
+            trace!(
+                "speculative event `{}` with sequence {sequence} was accepted",
+                any::type_name::<T>()
+            );
+        }
+    }
+}
+
+/// Discards all events that are waiting for the server decision.
+///
+/// Nothing is rolled back since the world is expected to be reset after a disconnect.
+pub(super) fn reset_speculations<T: Event>(mut pending: ResMut<PendingSpeculations<T>>) {
+    if !pending.events.is_empty() {
+        debug!(
+            "discarding {} undecided speculative events `{}` due to a disconnect",
+            pending.events.len(),
+            any::type_name::<T>()
+        );
+    }
+    pending.events.clear();
+}
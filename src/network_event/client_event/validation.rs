@@ -0,0 +1,191 @@
+use std::{any, marker::PhantomData};
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+use serde::de::DeserializeOwned;
+
+use super::{received_ticks, ClientEventChannel, FromClient};
+use crate::{
+    client::replicon_client::RepliconClient,
+    core::ClientId,
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, server_tick::ServerTick,
+    },
+};
+
+/// Signature of client event validation functions.
+///
+/// See [`ClientEventAppExt::add_client_event_with_validation`](super::ClientEventAppExt::add_client_event_with_validation).
+pub type ValidateFn<T> = fn(&ClientId, &T, &World) -> Result<(), RejectReason>;
+
+/// Reason why a client event was rejected by [`ValidateFn`].
+#[derive(Clone, Debug)]
+pub struct RejectReason {
+    /// Human-readable description.
+    pub message: String,
+
+    /// Whether to send [`ClientEventRejected<T>`] to the client.
+    pub notify: bool,
+}
+
+impl RejectReason {
+    /// Creates a reason that will be only logged on server.
+    pub fn silent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            notify: false,
+        }
+    }
+
+    /// Creates a reason that will also be sent back to the client.
+    pub fn notify(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            notify: true,
+        }
+    }
+}
+
+/// An event indicating that the server rejected `T` sent by this client.
+///
+/// Emitted only on client for reasons created with [`RejectReason::notify`].
+#[derive(Clone, Debug, Event)]
+pub struct ClientEventRejected<T> {
+    /// Message from [`RejectReason::message`].
+    pub reason: String,
+    marker: PhantomData<T>,
+}
+
+impl<T> ClientEventRejected<T> {
+    pub(super) fn new(reason: String) -> Self {
+        Self {
+            reason,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Validation function for `T`.
+#[derive(Resource)]
+pub(super) struct EventValidation<T>(pub(super) ValidateFn<T>);
+
+/// Holds a server's channel ID for rejections of `T`.
+#[derive(Resource, TypePath)]
+pub struct RejectionChannel<T> {
+    id: u8,
+    marker: PhantomData<T>,
+}
+
+impl<T> RejectionChannel<T> {
+    pub(super) fn new(id: u8) -> Self {
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for RejectionChannel<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RejectionChannel<T> {}
+
+impl<T> From<RejectionChannel<T>> for u8 {
+    fn from(value: RejectionChannel<T>) -> Self {
+        value.id
+    }
+}
+
+/// Receives events and emits only the ones that passed [`EventValidation<T>`].
+///
+/// Exclusive to provide [`World`] access to the validation function.
+pub(super) fn receive_validated<T: Event + DeserializeOwned>(world: &mut World) {
+    let channel = *world.resource::<ClientEventChannel<T>>();
+    let batch = world
+        .resource_mut::<RepliconServer>()
+        .take_received(channel);
+
+    let validate = world.resource::<EventValidation<T>>().0;
+    let names = world.resource::<ClientNames>();
+    let server_tick = world.get_resource::<ServerTick>();
+    let connected_clients = world.get_resource::<ConnectedClients>();
+    let mut events = Vec::new();
+    let mut rejections = Vec::new();
+    for (client_id, message) in batch {
+        let event = match DefaultOptions::new().deserialize(&message) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!(
+                    "unable to deserialize event from {}: {e}",
+                    names.label(client_id)
+                );
+                continue;
+            }
+        };
+
+        match (validate)(&client_id, &event, world) {
+            Ok(()) => {
+                trace!(
+                    "applying event `{}` from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                let (server_tick, client_tick) =
+                    received_ticks(server_tick, connected_clients, client_id);
+                events.push(FromClient {
+                    client_id,
+                    event,
+                    server_tick,
+                    client_tick,
+                });
+            }
+            Err(reason) => {
+                debug!(
+                    "rejecting event `{}` from {}: {}",
+                    any::type_name::<T>(),
+                    names.label(client_id),
+                    reason.message
+                );
+                if reason.notify {
+                    let message = DefaultOptions::new()
+                        .serialize(&reason.message)
+                        .expect("rejection reason should be serializable");
+                    rejections.push((client_id, message));
+                }
+            }
+        }
+    }
+
+    world
+        .resource_mut::<Events<FromClient<T>>>()
+        .send_batch(events);
+
+    let rejection_channel = *world.resource::<RejectionChannel<T>>();
+    let mut server = world.resource_mut::<RepliconServer>();
+    for (client_id, message) in rejections {
+        server.send(client_id, rejection_channel, message);
+    }
+}
+
+pub(super) fn receive_rejections<T: Event>(
+    mut rejected_events: EventWriter<ClientEventRejected<T>>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<RejectionChannel<T>>,
+) {
+    for message in client.receive(*channel) {
+        match DefaultOptions::new().deserialize::<String>(&message) {
+            Ok(reason) => {
+                trace!(
+                    "received rejection for event `{}`: {reason}",
+                    any::type_name::<T>()
+                );
+                rejected_events.send(ClientEventRejected::new(reason));
+            }
+            Err(e) => debug!("unable to deserialize rejection: {e}"),
+        }
+    }
+}
@@ -0,0 +1,602 @@
+#[cfg(feature = "zstd")]
+use std::io::Read;
+use std::{
+    any,
+    borrow::Cow,
+    io::{self, Write},
+    marker::PhantomData,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    client_event::{ClientEventAppExt, ClientEventChannel, FromClient, FromClientTicks},
+    server_event::{self, ServerEventAppExt, ServerEventChannel, ServerEventQueue, ToClients},
+};
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet, ServerInitTick, ServerUpdateTick},
+    core::{
+        common_conditions::{
+            client_connected, client_just_connected, server_just_stopped, server_running,
+        },
+        replicon_channels::{ChannelKind, KeyedChannels, RepliconChannel},
+        ClientId,
+    },
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, ServerEvent, ServerSet,
+    },
+};
+
+/**
+Extension for [`App`] to register network events with compressed payloads.
+
+Large events, such as level descriptors, can take a whole packet budget.
+Events registered with this extension are compressed if their serialized size exceeds the threshold
+and the compressed payload is smaller than the original one.
+
+The codec is negotiated on connection: the client sends its supported codecs and the server picks
+the preferred one that both sides support. Codecs are enabled with the `lz4` and `zstd` features.
+Until the negotiation completes or if there is no common codec, events are sent uncompressed.
+The negotiated codecs are available in [`CompressionCodecs`].
+
+Compressed payloads that decompress beyond [`MaxDecompressedSize`] or use a codec that wasn't
+negotiated with the sender are discarded.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_compressed_server_event::<LevelDescriptor>(ChannelKind::Ordered, 512)
+    .add_compressed_client_event::<Blueprint>(ChannelKind::Ordered, 512);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct LevelDescriptor {
+    tiles: Vec<u8>,
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Blueprint {
+    blocks: Vec<u8>,
+}
+```
+**/
+pub trait CompressionAppExt {
+    /// Same as [`ClientEventAppExt::add_client_event`], but compresses events
+    /// whose serialized size exceeds `threshold` bytes.
+    fn add_compressed_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        threshold: usize,
+    ) -> &mut Self;
+
+    /// Same as [`ServerEventAppExt::add_server_event`], but compresses events
+    /// whose serialized size exceeds `threshold` bytes.
+    fn add_compressed_server_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        threshold: usize,
+    ) -> &mut Self;
+}
+
+impl CompressionAppExt for App {
+    fn add_compressed_client_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        threshold: usize,
+    ) -> &mut Self {
+        if !self.is_plugin_added::<CompressionPlugin>() {
+            self.add_plugins(CompressionPlugin);
+        }
+
+        self.insert_resource(CompressionThreshold::<T>::new(threshold))
+            .add_client_event_with::<T, _, _>(
+                channel,
+                send_client_event::<T>,
+                receive_client_event::<T>,
+            )
+    }
+
+    fn add_compressed_server_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        threshold: usize,
+    ) -> &mut Self {
+        if !self.is_plugin_added::<CompressionPlugin>() {
+            self.add_plugins(CompressionPlugin);
+        }
+
+        self.insert_resource(CompressionThreshold::<T>::new(threshold))
+            .add_server_event_with::<T, _, _>(
+                channel,
+                send_server_event::<T>,
+                receive_server_event::<T>,
+            )
+    }
+}
+
+/// Negotiates codecs on connection.
+///
+/// Added automatically on the first compressed event registration.
+struct CompressionPlugin;
+
+impl Plugin for CompressionPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            NegotiationChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<NegotiationChannels>().server = id,
+        );
+        KeyedChannels::register_client(
+            app.world_mut(),
+            NegotiationChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<NegotiationChannels>().client = id,
+        );
+
+        app.init_resource::<CompressionCodecs>()
+            .init_resource::<MaxDecompressedSize>()
+            .init_resource::<NegotiationChannels>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_supported
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    receive_negotiated
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    reset_client.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    send_supported
+                        .in_set(ClientSet::Send)
+                        .run_if(client_just_connected),
+                    reset_server.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+/// Sends supported codecs to the server.
+fn send_supported(mut client: ResMut<RepliconClient>, channels: Res<NegotiationChannels>) {
+    let supported: Vec<_> = CompressionCodec::supported().collect();
+    debug!("sending supported codecs {supported:?}");
+    let message = DefaultOptions::new()
+        .serialize(&supported)
+        .expect("codecs should be serializable");
+    client.send(channels.client, message);
+}
+
+/// Picks a codec for each client that sent its supported codecs and removes disconnected clients.
+fn receive_supported(
+    mut server_events: EventReader<ServerEvent>,
+    mut server: ResMut<RepliconServer>,
+    mut codecs: ResMut<CompressionCodecs>,
+    channels: Res<NegotiationChannels>,
+    names: Res<ClientNames>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+            codecs.clients.remove(&client_id);
+        }
+    }
+
+    let messages: Vec<_> = server.receive(channels.client).collect();
+    for (client_id, message) in messages {
+        let client_supported: Vec<CompressionCodec> =
+            match DefaultOptions::new().deserialize(&message) {
+                Ok(supported) => supported,
+                Err(e) => {
+                    debug!(
+                        "unable to deserialize codecs from {}: {e}",
+                        names.label(client_id)
+                    );
+                    continue;
+                }
+            };
+
+        let codec = CompressionCodec::supported().find(|codec| client_supported.contains(codec));
+        debug!("negotiated {codec:?} with {}", names.label(client_id));
+        match codec {
+            Some(codec) => codecs.clients.insert(client_id, codec),
+            None => codecs.clients.remove(&client_id),
+        };
+
+        let message = DefaultOptions::new()
+            .serialize(&codec)
+            .expect("codec should be serializable");
+        server.send(client_id, channels.server, message);
+    }
+}
+
+/// Receives the negotiated codec from the server.
+fn receive_negotiated(
+    mut client: ResMut<RepliconClient>,
+    mut codecs: ResMut<CompressionCodecs>,
+    channels: Res<NegotiationChannels>,
+) {
+    for message in client.receive(channels.server) {
+        match DefaultOptions::new().deserialize(&message) {
+            Ok(codec) => {
+                debug!("negotiated {codec:?} with server");
+                codecs.server = codec;
+            }
+            Err(e) => error!("unable to deserialize negotiated codec from server: {e}"),
+        }
+    }
+}
+
+fn reset_client(mut codecs: ResMut<CompressionCodecs>) {
+    codecs.server = None;
+}
+
+fn reset_server(mut codecs: ResMut<CompressionCodecs>) {
+    codecs.clients.clear();
+}
+
+fn send_client_event<T: Event + Serialize>(
+    mut events: EventReader<T>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+    threshold: Res<CompressionThreshold<T>>,
+    codecs: Res<CompressionCodecs>,
+) {
+    for event in events.read() {
+        let bytes = DefaultOptions::new()
+            .serialize(&event)
+            .expect("client event should be serializable");
+        let message = encode(codecs.server, threshold.bytes, bytes)
+            .expect("client event should be compressible");
+
+        trace!("sending compressed event `{}`", any::type_name::<T>());
+        client.send(*channel, message);
+    }
+}
+
+fn receive_client_event<T: Event + DeserializeOwned>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    names: Res<ClientNames>,
+    codecs: Res<CompressionCodecs>,
+    max_size: Res<MaxDecompressedSize>,
+    ticks: FromClientTicks,
+) {
+    for (client_id, message) in server.receive(*channel) {
+        let event = decode(&message, **max_size, |codec| {
+            codecs.client(client_id) == Some(codec)
+        })
+        .map_err(bincode::Error::from)
+        .and_then(|bytes| DefaultOptions::new().deserialize(&bytes));
+        match event {
+            Ok(event) => {
+                trace!(
+                    "applying compressed event `{}` from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                client_events.send(ticks.from_client(client_id, event));
+            }
+            Err(e) => debug!(
+                "unable to decode event from {}: {e}",
+                names.label(client_id)
+            ),
+        }
+    }
+}
+
+/// Sends events to clients grouped by their negotiated codecs.
+///
+/// This way the event is compressed only once per codec.
+fn send_server_event<T: Event + Serialize>(
+    mut server: ResMut<RepliconServer>,
+    mut server_events: EventReader<ToClients<T>>,
+    connected_clients: Res<ConnectedClients>,
+    channel: Res<ServerEventChannel<T>>,
+    threshold: Res<CompressionThreshold<T>>,
+    codecs: Res<CompressionCodecs>,
+) {
+    for ToClients {
+        event,
+        mode,
+        apply_tick,
+    } in server_events.read()
+    {
+        trace!(
+            "sending compressed event `{}` with `{mode:?}`",
+            any::type_name::<T>()
+        );
+        let bytes = DefaultOptions::new()
+            .serialize(&event)
+            .expect("server event should be serializable");
+        for codec in [None]
+            .into_iter()
+            .chain(CompressionCodec::supported().map(Some))
+        {
+            let message = encode(codec, threshold.bytes, bytes.clone())
+                .expect("server event should be compressible");
+            server_event::send_filtered(
+                &mut server,
+                &connected_clients,
+                *channel,
+                mode,
+                *apply_tick,
+                |client_id| codecs.client(client_id) == codec,
+                |cursor| cursor.write_all(&message).map_err(Into::into),
+            )
+            .expect("server event should be serializable");
+        }
+    }
+}
+
+fn receive_server_event<T: Event + DeserializeOwned>(
+    mut server_events: EventWriter<T>,
+    mut client: ResMut<RepliconClient>,
+    mut event_queue: ResMut<ServerEventQueue<T>>,
+    init_tick: Res<ServerInitTick>,
+    update_tick: Res<ServerUpdateTick>,
+    channel: Res<ServerEventChannel<T>>,
+    max_size: Res<MaxDecompressedSize>,
+) {
+    for message in client.receive(*channel) {
+        let result = server_event::deserialize_with(&message, |cursor| {
+            let position = cursor.position() as usize;
+            let bytes = decode(
+                &cursor.get_ref()[position..],
+                **max_size,
+                CompressionCodec::is_supported,
+            )?;
+            DefaultOptions::new().deserialize(&bytes)
+        });
+        let (tick, event) = match result {
+            Ok(tick_and_event) => tick_and_event,
+            Err(e) => {
+                debug!("unable to decode event from server: {e}");
+                continue;
+            }
+        };
+
+        if tick.is_reached(*init_tick, *update_tick) {
+            trace!(
+                "applying compressed event `{}` with `{tick:?}`",
+                any::type_name::<T>()
+            );
+            server_events.send(event);
+        } else {
+            trace!(
+                "queuing compressed event `{}` with `{tick:?}`",
+                any::type_name::<T>()
+            );
+            event_queue.insert(tick, event);
+        }
+    }
+}
+
+/// Prepends the codec tag and compresses the payload if it exceeds the threshold.
+///
+/// Falls back to the uncompressed payload if compression doesn't reduce its size.
+fn encode(
+    codec: Option<CompressionCodec>,
+    threshold: usize,
+    bytes: Vec<u8>,
+) -> io::Result<Vec<u8>> {
+    if let Some(codec) = codec.filter(|_| bytes.len() > threshold) {
+        let compressed = codec.compress(&bytes)?;
+        if compressed.len() < bytes.len() {
+            let mut message = Vec::with_capacity(compressed.len() + 1);
+            message.push(codec as u8);
+            message.extend(compressed);
+            return Ok(message);
+        }
+    }
+
+    let mut message = Vec::with_capacity(bytes.len() + 1);
+    message.push(UNCOMPRESSED);
+    message.extend(bytes);
+    Ok(message)
+}
+
+/// Reads the codec tag and decompresses the payload if needed.
+///
+/// Returns an error if the codec isn't allowed or the payload decompresses beyond `max_size`.
+fn decode(
+    message: &[u8],
+    max_size: usize,
+    is_allowed: impl Fn(CompressionCodec) -> bool,
+) -> io::Result<Cow<[u8]>> {
+    let (&tag, bytes) = message
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing codec tag"))?;
+
+    if tag == UNCOMPRESSED {
+        return Ok(Cow::Borrowed(bytes));
+    }
+
+    let codec = CompressionCodec::from_tag(tag)
+        .filter(|&codec| is_allowed(codec))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("codec tag {tag} wasn't negotiated"),
+            )
+        })?;
+    codec.decompress(bytes, max_size).map(Cow::Owned)
+}
+
+/// Tag for uncompressed payloads.
+const UNCOMPRESSED: u8 = 0;
+
+/// Compression algorithm for event payloads.
+///
+/// Each codec requires the corresponding feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CompressionCodec {
+    /// Fast compression, requires `lz4` feature.
+    Lz4 = 1,
+    /// Better ratio with slower compression, requires `zstd` feature.
+    Zstd = 2,
+}
+
+impl CompressionCodec {
+    /// Returns codecs enabled in this build in the order of preference.
+    pub fn supported() -> impl Iterator<Item = Self> {
+        [Self::Zstd, Self::Lz4]
+            .into_iter()
+            .filter(|codec| codec.is_supported())
+    }
+
+    /// Returns `true` if the corresponding feature is enabled.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Lz4 => cfg!(feature = "lz4"),
+            Self::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(Self::Lz4),
+            2 => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(lz4_flex::compress_prepend_size(bytes)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::encode_all(bytes, 0),
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Decompresses the payload, returning an error if its size exceeds `max_size`.
+    ///
+    /// The size is checked before allocation, so a malicious payload can't allocate more than `max_size`.
+    #[allow(unused_variables)]
+    fn decompress(self, bytes: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => {
+                let (size, compressed) = bytes.split_first_chunk::<4>().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::UnexpectedEof, "missing lz4 size prefix")
+                })?;
+                let size = u32::from_le_bytes(*size) as usize;
+                if size > max_size {
+                    return Err(too_large(max_size));
+                }
+                lz4_flex::decompress(compressed, size)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd => {
+                let mut decompressed = Vec::new();
+                zstd::stream::read::Decoder::new(bytes)?
+                    .take(max_size as u64 + 1)
+                    .read_to_end(&mut decompressed)?;
+                if decompressed.len() > max_size {
+                    return Err(too_large(max_size));
+                }
+                Ok(decompressed)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn unsupported(self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{self:?} is not enabled in this build"),
+        )
+    }
+}
+
+#[allow(dead_code)]
+fn too_large(max_size: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("decompressed size exceeds {max_size} bytes"),
+    )
+}
+
+/// Maximum size in bytes of a decompressed event payload.
+///
+/// Protects against payloads that are small on the wire, but decompress into huge allocations.
+/// Applies to all events registered with [`CompressionAppExt`], larger payloads are discarded.
+/// Defaults to 1 MiB.
+#[derive(Resource, Deref, DerefMut, Clone, Copy, Debug)]
+pub struct MaxDecompressedSize(pub usize);
+
+impl Default for MaxDecompressedSize {
+    fn default() -> Self {
+        Self(1024 * 1024)
+    }
+}
+
+/// Codecs negotiated on connection.
+///
+/// See [`CompressionAppExt`].
+#[derive(Resource, Default)]
+pub struct CompressionCodecs {
+    /// Codec negotiated with the server on client.
+    server: Option<CompressionCodec>,
+
+    /// Codecs negotiated with each client on server.
+    clients: HashMap<ClientId, CompressionCodec>,
+}
+
+impl CompressionCodecs {
+    /// Returns the codec negotiated with the server.
+    ///
+    /// Used on client.
+    pub fn server(&self) -> Option<CompressionCodec> {
+        self.server
+    }
+
+    /// Returns the codec negotiated with a client.
+    ///
+    /// Used on server.
+    pub fn client(&self, client_id: ClientId) -> Option<CompressionCodec> {
+        self.clients.get(&client_id).copied()
+    }
+}
+
+/// Channels for codec negotiation.
+#[derive(Resource, Default, TypePath)]
+struct NegotiationChannels {
+    client: u8,
+    server: u8,
+}
+
+/// Size in bytes after which events `T` are compressed.
+#[derive(Resource)]
+struct CompressionThreshold<T> {
+    bytes: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T> CompressionThreshold<T> {
+    fn new(bytes: usize) -> Self {
+        Self {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+}
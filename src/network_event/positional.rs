@@ -0,0 +1,386 @@
+use std::{any, collections::VecDeque, time::Duration};
+
+use bevy::{ecs::event::Events, prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::server_event::{SendMode, ServerEventAppExt, ToClients};
+use crate::{
+    client::{self, ClientSet, ServerInitTick, ServerUpdateTick},
+    core::{
+        common_conditions::{client_connected, has_authority, server_running},
+        replicon_channels::RepliconChannel,
+        replicon_tick::RepliconTick,
+        ClientId,
+    },
+    server::{server_tick::ServerTick, ServerEvent, ServerSet},
+};
+
+/**
+Extension for [`App`] to send server events only to clients near a position.
+
+Covers the common "play effect at location" pattern, such as explosions, footsteps or impact sounds.
+Send [`ToNearby<T>`] on server and read [`Positional<T>`] on clients. An event is delivered only to
+clients whose position in [`RelevancyPositions`] is within its radius. Clients without a position
+don't receive positional events.
+
+Like regular server events, each event is received on client after all replication sent before it.
+Then it's additionally delayed by [`InterpolationDelay`] to map it into the interpolation timeline,
+so the effect plays when interpolated entities reach the world state at the moment of sending.
+
+On listen server events are emitted locally without delay if [`ClientId::SERVER`] has a position within the radius.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_positional_event::<Explosion>(ChannelKind::Unreliable)
+    .add_systems(Update, (update_positions, explode, play_explosions));
+
+/// Uses player positions as relevancy positions.
+fn update_positions(mut positions: ResMut<RelevancyPositions>, players: Query<(&Player, &Transform)>) {
+    for (player, transform) in &players {
+        positions.set(player.0, transform.translation);
+    }
+}
+
+fn explode(mut explosions: EventWriter<ToNearby<Explosion>>) {
+    explosions.send(ToNearby {
+        position: Vec3::new(10.0, 0.0, 5.0),
+        radius: 50.0,
+        event: Explosion { power: 2.0 },
+    });
+}
+
+fn play_explosions(mut explosions: EventReader<Positional<Explosion>>) {
+    for explosion in explosions.read() {
+        info!("playing explosion with power {} at {}", explosion.event.power, explosion.position);
+    }
+}
+
+#[derive(Component)]
+struct Player(ClientId);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Explosion {
+    power: f32,
+}
+```
+**/
+pub trait PositionalEventAppExt {
+    /// Registers event `T` that will be emitted on nearby clients as [`Positional<T>`]
+    /// after sending [`ToNearby<T>`] on server.
+    ///
+    /// Internally registers a regular server event to deliver it.
+    fn add_positional_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+}
+
+impl PositionalEventAppExt for App {
+    fn add_positional_event<T: Event + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        if !self.world().contains_resource::<RelevancyPositions>() {
+            self.init_resource::<RelevancyPositions>()
+                .init_resource::<InterpolationDelay>()
+                .add_systems(
+                    PreUpdate,
+                    (
+                        remove_disconnected
+                            .after(ServerSet::Receive)
+                            .run_if(server_running),
+                        InterpolationDelay::update
+                            .after(ClientSet::Receive)
+                            .run_if(client_connected),
+                        InterpolationDelay::reset.in_set(ClientSet::Reset),
+                    ),
+                );
+        }
+
+        self.add_server_event::<PositionalMessage<T>>(channel)
+            .add_event::<ToNearby<T>>()
+            .add_event::<Positional<T>>()
+            .init_resource::<PositionalQueue<T>>()
+            .add_systems(
+                PreUpdate,
+                (
+                    emit_positional::<T>
+                        .after(InterpolationDelay::update)
+                        .run_if(client_connected),
+                    reset_queue::<T>.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    send_nearby::<T>
+                        .before(ServerSet::Send)
+                        .run_if(has_authority),
+                    emit_positional::<T>
+                        .after(ServerSet::Send)
+                        .run_if(has_authority),
+                ),
+            )
+    }
+}
+
+/// Removes positions of disconnected clients.
+fn remove_disconnected(
+    mut server_events: EventReader<ServerEvent>,
+    mut positions: ResMut<RelevancyPositions>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+            positions.remove(*client_id);
+        }
+    }
+}
+
+/// Converts [`ToNearby<T>`] into [`ToClients<PositionalMessage<T>>`] for clients within the radius.
+fn send_nearby<T: Event>(
+    mut nearby_events: ResMut<Events<ToNearby<T>>>,
+    mut server_events: EventWriter<ToClients<PositionalMessage<T>>>,
+    positions: Res<RelevancyPositions>,
+    server_tick: Option<Res<ServerTick>>,
+) {
+    let tick = server_tick.map(|tick| **tick).unwrap_or_default();
+    for ToNearby {
+        position,
+        radius,
+        event,
+    } in nearby_events.drain()
+    {
+        let client_ids: Vec<_> = positions.clients_within(position, radius).collect();
+        if client_ids.is_empty() {
+            trace!(
+                "discarding event `{}` at {position} without nearby clients",
+                any::type_name::<T>()
+            );
+            continue;
+        }
+
+        server_events.send(ToClients {
+            mode: SendMode::Only(client_ids),
+            event: PositionalMessage {
+                position,
+                tick,
+                event,
+            },
+            apply_tick: None,
+        });
+    }
+}
+
+/// Queues received events and emits them as [`Positional<T>`] after [`InterpolationDelay`].
+///
+/// On listen server the delay is always zero, so locally resent events are emitted immediately.
+fn emit_positional<T: Event>(
+    mut messages: ResMut<Events<PositionalMessage<T>>>,
+    mut positional_events: EventWriter<Positional<T>>,
+    mut queue: ResMut<PositionalQueue<T>>,
+    delay: Res<InterpolationDelay>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed();
+    for message in messages.drain() {
+        queue.push_back((now + delay.get(), message));
+    }
+
+    while let Some((emit_time, _)) = queue.front() {
+        if *emit_time > now {
+            break;
+        }
+
+        let (_, message) = queue.pop_front().unwrap();
+        positional_events.send(Positional {
+            position: message.position,
+            tick: message.tick,
+            event: message.event,
+        });
+    }
+}
+
+fn reset_queue<T: Event>(mut queue: ResMut<PositionalQueue<T>>) {
+    queue.clear();
+}
+
+/// Received events with the time when they should be emitted.
+///
+/// Since the delay changes rarely, emit times are usually sorted,
+/// but an event is never emitted before the ones received earlier.
+#[derive(Resource, Deref, DerefMut)]
+struct PositionalQueue<T>(VecDeque<(Duration, PositionalMessage<T>)>);
+
+impl<T> Default for PositionalQueue<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// Estimated delay of interpolated state behind received replication on client.
+///
+/// Components registered with [`AppInterpolationExt::replicate_interpolated`](crate::client::interpolation::AppInterpolationExt::replicate_interpolated)
+/// blend towards each received value over the interval between the last two received values,
+/// so the rendered state lags behind by about one update. This resource tracks the interval between
+/// the last two changes of [`replicated_tick`](client::replicated_tick) to delay [`Positional`] events by it.
+///
+/// Available for apps with events registered via [`PositionalEventAppExt::add_positional_event`].
+#[derive(Resource, Default)]
+pub struct InterpolationDelay {
+    /// Replicated tick from the last update.
+    last_tick: RepliconTick,
+
+    /// Time when [`Self::last_tick`] was received.
+    last_received: Option<Duration>,
+
+    delay: Duration,
+}
+
+impl InterpolationDelay {
+    /// Returns the current delay.
+    pub fn get(&self) -> Duration {
+        self.delay
+    }
+
+    fn update(
+        mut interpolation_delay: ResMut<Self>,
+        init_tick: Res<ServerInitTick>,
+        update_tick: Res<ServerUpdateTick>,
+        time: Res<Time>,
+    ) {
+        let tick = client::replicated_tick(*init_tick, *update_tick);
+        if tick == interpolation_delay.last_tick {
+            return;
+        }
+
+        let now = time.elapsed();
+        if let Some(last_received) = interpolation_delay.last_received {
+            interpolation_delay.delay = now - last_received;
+        }
+        interpolation_delay.last_tick = tick;
+        interpolation_delay.last_received = Some(now);
+    }
+
+    fn reset(mut interpolation_delay: ResMut<Self>) {
+        *interpolation_delay = Default::default();
+    }
+}
+
+/// Positions of clients used to select recipients for [`ToNearby`] events.
+///
+/// Available on server for apps with events registered via [`PositionalEventAppExt::add_positional_event`].
+/// Positions of disconnected clients are removed automatically.
+#[derive(Resource, Default)]
+pub struct RelevancyPositions(HashMap<ClientId, Vec3>);
+
+impl RelevancyPositions {
+    /// Sets the position of a client.
+    pub fn set(&mut self, client_id: ClientId, position: Vec3) {
+        self.0.insert(client_id, position);
+    }
+
+    /// Returns the position of a client.
+    pub fn get(&self, client_id: ClientId) -> Option<Vec3> {
+        self.0.get(&client_id).copied()
+    }
+
+    /// Removes the position of a client, after which it no longer receives positional events.
+    pub fn remove(&mut self, client_id: ClientId) {
+        self.0.remove(&client_id);
+    }
+
+    /// Returns an iterator over clients within `radius` from `position`.
+    pub fn clients_within(
+        &self,
+        position: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = ClientId> + '_ {
+        self.0
+            .iter()
+            .filter(move |(_, client_position)| {
+                client_position.distance_squared(position) <= radius * radius
+            })
+            .map(|(&client_id, _)| client_id)
+    }
+}
+
+/// An event that will be sent to clients near its position.
+///
+/// See [`PositionalEventAppExt`].
+#[derive(Clone, Debug, Event)]
+pub struct ToNearby<T> {
+    /// Position at which the event happened.
+    pub position: Vec3,
+
+    /// Maximum distance from [`Self::position`] to clients that should receive the event.
+    pub radius: f32,
+
+    pub event: T,
+}
+
+/// An event received from server with the position at which it happened.
+///
+/// Emitted after [`InterpolationDelay`], see [`PositionalEventAppExt`].
+#[derive(Clone, Debug, Event)]
+pub struct Positional<T> {
+    /// Position at which the event happened.
+    pub position: Vec3,
+
+    /// Server tick at which the event was sent.
+    pub tick: RepliconTick,
+
+    pub event: T,
+}
+
+/// Network representation of [`Positional<T>`].
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct PositionalMessage<T> {
+    #[serde(with = "vec3")]
+    position: Vec3,
+    tick: RepliconTick,
+    event: T,
+}
+
+/// Serializes [`Vec3`] as an array since `glam` serialization is not enabled.
+mod vec3 {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(value: &Vec3, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_array().serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec3, D::Error> {
+        <[f32; 3]>::deserialize(deserializer).map(Vec3::from_array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_within() {
+        let mut positions = RelevancyPositions::default();
+        positions.set(CLIENT_A, Vec3::ZERO);
+        positions.set(CLIENT_B, Vec3::X * 20.0);
+
+        let client_ids: Vec<_> = positions.clients_within(Vec3::X * 5.0, 10.0).collect();
+        assert_eq!(client_ids, [CLIENT_A]);
+
+        positions.remove(CLIENT_A);
+        assert_eq!(positions.clients_within(Vec3::ZERO, 10.0).count(), 0);
+    }
+
+    const CLIENT_A: ClientId = ClientId::new(1);
+    const CLIENT_B: ClientId = ClientId::new(2);
+}
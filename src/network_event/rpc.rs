@@ -0,0 +1,393 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    time::Duration,
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    client_event::ClientEventAppExt,
+    server_event::{SendMode, ServerEventAppExt, ToClients},
+};
+use crate::{
+    client::ClientSet,
+    core::{
+        replicon_channels::{ChannelKind, RepliconChannel},
+        ClientId,
+    },
+};
+
+/**
+An extension trait for [`App`] for registering request/response pairs on top of network events.
+
+Clients send requests with [`RequestSender`] and receive results as [`RequestResult`] events.
+The server reads requests as [`FromClient<Request<Req>>`](super::client_event::FromClient)
+and replies with [`ResponseSender`], which routes the response back only to the requester.
+
+If the server doesn't reply within the timeout or the client disconnects,
+the request completes with an error. Responses that arrive after that are ignored.
+
+# Examples
+
+```
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_request::<BuyItem, Gold>(ChannelKind::Ordered, Duration::from_secs(5))
+    .add_systems(Update, (buy, sell, read_results));
+
+fn buy(mut requests: RequestSender<BuyItem, Gold>) {
+    let id = requests.send_request(BuyItem { item: 42 });
+    info!("sent {id:?}");
+}
+
+fn sell(mut requests: EventReader<FromClient<Request<BuyItem>>>, mut responses: ResponseSender<Gold>) {
+    for FromClient { client_id, event, .. } in requests.read() {
+        if event.request.item == 42 {
+            responses.respond(*client_id, event.id, Gold(100));
+        } else {
+            responses.reject(*client_id, event.id, "unknown item");
+        }
+    }
+}
+
+fn read_results(mut results: EventReader<RequestResult<Gold>>) {
+    for RequestResult { id, result } in results.read() {
+        match result {
+            Ok(gold) => info!("{id:?} succeeded, remaining gold: {}", gold.0),
+            Err(e) => error!("{id:?} failed: {e}"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, TypePath)]
+struct BuyItem {
+    item: u32,
+}
+
+#[derive(Deserialize, Serialize, TypePath)]
+struct Gold(u32);
+```
+**/
+pub trait RpcAppExt {
+    /// Registers a client event for requests and a server event for responses.
+    ///
+    /// `channel` is used for both requests and responses. Requests that didn't receive a response
+    /// within `timeout` complete with [`RequestError::TimedOut`].
+    ///
+    /// Multiple request types can share a single response type. In this case the response
+    /// event and [`PendingRequests<Resp>`] are registered only once and shared, so request IDs
+    /// are unique across all request types with this response type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `Resp` was already registered with a different timeout or channel kind.
+    fn add_request<Req, Resp>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        timeout: Duration,
+    ) -> &mut Self
+    where
+        Req: TypePath + Serialize + DeserializeOwned + Send + Sync,
+        Resp: TypePath + Serialize + DeserializeOwned + Send + Sync;
+}
+
+impl RpcAppExt for App {
+    fn add_request<Req, Resp>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        timeout: Duration,
+    ) -> &mut Self
+    where
+        Req: TypePath + Serialize + DeserializeOwned + Send + Sync,
+        Resp: TypePath + Serialize + DeserializeOwned + Send + Sync,
+    {
+        let channel = channel.into();
+        if let Some(pending) = self.world().get_resource::<PendingRequests<Resp>>() {
+            assert!(
+                pending.timeout == timeout && pending.kind == channel.kind,
+                "response `{}` is already registered with timeout {:?} and channel {:?}, \
+                but `{}` uses timeout {timeout:?} and channel {:?}",
+                Resp::type_path(),
+                pending.timeout,
+                pending.kind,
+                Req::type_path(),
+                channel.kind,
+            );
+        } else {
+            self.insert_resource(PendingRequests::<Resp>::new(timeout, channel.kind))
+                .add_event::<RequestResult<Resp>>()
+                .add_server_event::<Response<Resp>>(channel.clone())
+                .add_systems(
+                    PreUpdate,
+                    (
+                        receive_responses::<Resp>.after(ClientSet::Receive),
+                        reset::<Resp>.in_set(ClientSet::Reset),
+                    ),
+                );
+        }
+
+        self.add_client_event::<Request<Req>>(channel)
+    }
+}
+
+/// A [`SystemParam`] for sending requests of type `Req` to the server.
+///
+/// Each request is assigned a [`RequestId`] and tracked in [`PendingRequests<Resp>`]
+/// until [`RequestResult<Resp>`] with the same ID is emitted.
+///
+/// See also [`RpcAppExt::add_request`].
+#[derive(SystemParam)]
+pub struct RequestSender<'w, Req, Resp>
+where
+    Req: Send + Sync + 'static,
+    Resp: Send + Sync + 'static,
+{
+    requests: EventWriter<'w, Request<Req>>,
+    pending: ResMut<'w, PendingRequests<Resp>>,
+    time: Res<'w, Time>,
+}
+
+impl<Req, Resp> RequestSender<'_, Req, Resp>
+where
+    Req: Send + Sync + 'static,
+    Resp: Send + Sync + 'static,
+{
+    /// Sends a request and returns its ID to match it with [`RequestResult`] later.
+    ///
+    /// The timeout starts from the current [`Time::elapsed`].
+    pub fn send_request(&mut self, request: Req) -> RequestId {
+        let id = self.pending.insert(self.time.elapsed());
+        self.requests.send(Request { id, request });
+        id
+    }
+}
+
+/// A [`SystemParam`] for replying to requests on server.
+///
+/// Responses are sent only to the client that sent the request over the channel used for registration.
+/// Pass the [`RequestId`] from the received [`Request`] to let the client match the response.
+/// Responses for requests that already timed out on client are ignored.
+///
+/// See also [`RpcAppExt::add_request`].
+#[derive(SystemParam)]
+pub struct ResponseSender<'w, Resp: Send + Sync + 'static> {
+    responses: EventWriter<'w, ToClients<Response<Resp>>>,
+}
+
+impl<Resp: Send + Sync + 'static> ResponseSender<'_, Resp> {
+    /// Sends a successful response for a request from the client.
+    pub fn respond(&mut self, client_id: ClientId, id: RequestId, response: Resp) {
+        self.send(client_id, id, Ok(response));
+    }
+
+    /// Sends an error response for a request from the client.
+    ///
+    /// The client will receive it as [`RequestError::Rejected`].
+    pub fn reject(&mut self, client_id: ClientId, id: RequestId, reason: impl Into<String>) {
+        self.send(client_id, id, Err(reason.into()));
+    }
+
+    fn send(&mut self, client_id: ClientId, id: RequestId, result: Result<Resp, String>) {
+        self.responses.send(ToClients {
+            mode: SendMode::Direct(client_id),
+            event: Response { id, result },
+            apply_tick: None,
+        });
+    }
+}
+
+/// Converts received responses into results and expires timed out requests.
+fn receive_responses<T: Send + Sync + 'static>(
+    mut responses: ResMut<Events<Response<T>>>,
+    mut results: EventWriter<RequestResult<T>>,
+    mut pending: ResMut<PendingRequests<T>>,
+    time: Res<Time>,
+) {
+    for Response { id, result } in responses.drain() {
+        if pending.remove(id) {
+            results.send(RequestResult {
+                id,
+                result: result.map_err(RequestError::Rejected),
+            });
+        } else {
+            debug!("ignoring response for unknown or expired {id:?}");
+        }
+    }
+
+    for id in pending.drain_expired(time.elapsed()) {
+        results.send(RequestResult {
+            id,
+            result: Err(RequestError::TimedOut),
+        });
+    }
+}
+
+/// Completes all pending requests with [`RequestError::Disconnected`].
+fn reset<T: Send + Sync + 'static>(
+    mut results: EventWriter<RequestResult<T>>,
+    mut pending: ResMut<PendingRequests<T>>,
+) {
+    for id in pending.sent.drain().map(|(id, _)| id) {
+        results.send(RequestResult {
+            id,
+            result: Err(RequestError::Disconnected),
+        });
+    }
+}
+
+/// Requests that are waiting for a response of type `T`.
+///
+/// Managed by [`RequestSender`] and completed when the response arrives, times out or the client disconnects.
+#[derive(Resource)]
+pub struct PendingRequests<T> {
+    /// Time after which requests are considered lost.
+    timeout: Duration,
+
+    /// Channel kind used for registration, stored to detect conflicting registrations.
+    kind: ChannelKind,
+
+    /// ID for the next request.
+    next_id: u32,
+
+    /// Sending timestamps of pending requests.
+    sent: HashMap<RequestId, Duration>,
+
+    marker: PhantomData<T>,
+}
+
+impl<T> PendingRequests<T> {
+    fn new(timeout: Duration, kind: ChannelKind) -> Self {
+        Self {
+            timeout,
+            kind,
+            next_id: 0,
+            sent: Default::default(),
+            marker: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the request is still waiting for a response.
+    pub fn contains(&self, id: RequestId) -> bool {
+        self.sent.contains_key(&id)
+    }
+
+    /// Returns the number of pending requests.
+    pub fn len(&self) -> usize {
+        self.sent.len()
+    }
+
+    /// Returns `true` if there are no pending requests.
+    pub fn is_empty(&self) -> bool {
+        self.sent.is_empty()
+    }
+
+    /// Returns the time after which requests complete with [`RequestError::TimedOut`].
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Allocates a new ID and marks it as pending.
+    fn insert(&mut self, now: Duration) -> RequestId {
+        let id = RequestId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.sent.insert(id, now);
+        id
+    }
+
+    /// Removes a request and returns `true` if it was pending.
+    fn remove(&mut self, id: RequestId) -> bool {
+        self.sent.remove(&id).is_some()
+    }
+
+    /// Removes and returns requests that were sent earlier than the timeout.
+    fn drain_expired(&mut self, now: Duration) -> Vec<RequestId> {
+        let mut expired = Vec::new();
+        self.sent.retain(|&id, &mut timestamp| {
+            let alive = now.saturating_sub(timestamp) < self.timeout;
+            if !alive {
+                expired.push(id);
+            }
+            alive
+        });
+        expired
+    }
+}
+
+/// Correlation ID of a request.
+///
+/// Returned by [`RequestSender::send_request`], received on server inside [`Request`]
+/// and passed back to [`ResponseSender`] to match the response on client.
+///
+/// Unique among pending requests with the same response type, but IDs are reused after wrapping around.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct RequestId(u32);
+
+/// A request sent by a client.
+///
+/// Received on server as [`FromClient<Request<T>>`](super::client_event::FromClient).
+/// Reply using [`ResponseSender`] with the same [`RequestId`].
+#[derive(Clone, Copy, Debug, Deserialize, Event, Serialize, TypePath)]
+pub struct Request<T> {
+    /// ID to pass to [`ResponseSender`].
+    pub id: RequestId,
+
+    /// The request itself.
+    pub request: T,
+}
+
+/// A response sent by the server.
+///
+/// Sent with [`ResponseSender`] and converted into [`RequestResult`] on client,
+/// so usually there is no need to use it directly.
+#[derive(Deserialize, Event, Serialize, TypePath)]
+pub struct Response<T> {
+    /// ID of the request this response is for.
+    pub id: RequestId,
+
+    /// The response or the rejection reason.
+    pub result: Result<T, String>,
+}
+
+/// Result of a request sent with [`RequestSender`].
+#[derive(Clone, Debug, Event)]
+pub struct RequestResult<T> {
+    /// ID returned by [`RequestSender::send_request`].
+    pub id: RequestId,
+
+    /// The response or the reason why the request failed.
+    pub result: Result<T, RequestError>,
+}
+
+/// Reason why a request failed.
+///
+/// Each request completes exactly once, either with a response or with one of these errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestError {
+    /// The server replied with an error using [`ResponseSender::reject`].
+    Rejected(String),
+    /// The server didn't reply within the timeout.
+    TimedOut,
+    /// The client disconnected before receiving a response.
+    Disconnected,
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Rejected(reason) => write!(f, "rejected by server: {reason}"),
+            Self::TimedOut => write!(f, "timed out"),
+            Self::Disconnected => write!(f, "disconnected"),
+        }
+    }
+}
+
+impl Error for RequestError {}
@@ -1,31 +1,46 @@
-use std::{any, io::Cursor, marker::PhantomData};
+use std::{
+    any,
+    collections::VecDeque,
+    io::Cursor,
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
 
 use bevy::{
-    ecs::{entity::MapEntities, event::Event},
+    ecs::{
+        entity::{EntityHashMap, MapEntities},
+        event::Event,
+    },
     prelude::*,
 };
 use bincode::{DefaultOptions, Options};
 use bytes::Bytes;
 use ordered_multimap::ListOrderedMultimap;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::EventMapper;
 use crate::{
     client::{
-        replicon_client::RepliconClient, server_entity_map::ServerEntityMap, ClientSet,
-        ServerInitTick,
+        self,
+        fast_forward::{self, fast_forwarding},
+        replicon_client::RepliconClient,
+        server_entity_map::ServerEntityMap,
+        ClientSet, ServerInitTick, ServerUpdateTick,
     },
     core::{
-        common_conditions::{client_connected, has_authority, server_running},
-        replicon_channels::{RepliconChannel, RepliconChannels},
+        common_conditions::{client_connected, has_authority, server_just_stopped, server_running},
+        protocol_validation::{EventKind, ProtocolValidation},
+        replicon_channels::{KeyedChannels, RepliconChannel},
         replicon_tick::RepliconTick,
         ClientId,
     },
-    prelude::{ClientPlugin, ServerPlugin},
+    prelude::ServerPlugin,
     server::{
-        connected_clients::{ConnectedClient, ConnectedClients},
+        client_names::ClientNames,
+        connected_clients::{client_groups::GroupId, ConnectedClient, ConnectedClients},
         replicon_server::RepliconServer,
-        ServerSet,
+        server_tick::ServerTick,
+        ServerEvent, ServerSet,
     },
 };
 
@@ -33,9 +48,12 @@ use crate::{
 pub trait ServerEventAppExt {
     /// Registers event `T` that will be emitted on client after sending [`ToClients<T>`] on server.
     ///
+    /// Channel IDs are assigned when plugins are finished in the order of [`TypePath::type_path`],
+    /// so the registration order doesn't affect the protocol.
+    ///
     /// For usage example see the [corresponding section](../../index.html#from-server-to-client)
     /// in the quick start guide.
-    fn add_server_event<T: Event + Serialize + DeserializeOwned>(
+    fn add_server_event<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self;
@@ -43,9 +61,12 @@ pub trait ServerEventAppExt {
     /// Same as [`Self::add_server_event`], but additionally maps server entities to client inside the event after receiving.
     ///
     /// Always use it for events that contain entities.
+    /// If an event references entities that aren't replicated yet, it will be buffered until
+    /// they appear in [`ServerEntityMap`]. If this doesn't happen within [`UnmappedEventTimeout`],
+    /// the event will be dropped and [`UnmappedEventExpired<T>`] will be emitted.
     /// For usage example see the [corresponding section](../../index.html#from-server-to-client)
     /// in the quick start guide.
-    fn add_mapped_server_event<T: Event + Serialize + DeserializeOwned + MapEntities>(
+    fn add_mapped_server_event<T: Event + TypePath + Serialize + DeserializeOwned + MapEntities>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self;
@@ -65,7 +86,7 @@ pub trait ServerEventAppExt {
         reflect::serde::{ReflectSerializer, UntypedReflectDeserializer},
     };
     use bevy_replicon::{
-        client::ServerInitTick,
+        client::{ServerInitTick, ServerUpdateTick},
         network_event::server_event::{self, ServerEventChannel, ServerEventQueue},
         prelude::*,
     };
@@ -88,11 +109,18 @@ pub trait ServerEventAppExt {
         registry: Res<AppTypeRegistry>,
     ) {
         let registry = registry.read();
-        for ToClients { event, mode } in reflect_events.read() {
-            server_event::send_with(&mut server, &connected_clients, *channel, *mode, |cursor| {
-                let serializer = ReflectSerializer::new(&*event.0, &registry);
-                DefaultOptions::new().serialize_into(cursor, &serializer)
-            })
+        for ToClients { event, mode, apply_tick } in reflect_events.read() {
+            server_event::send_with(
+                &mut server,
+                &connected_clients,
+                *channel,
+                mode,
+                *apply_tick,
+                |cursor| {
+                    let serializer = ReflectSerializer::new(&*event.0, &registry);
+                    DefaultOptions::new().serialize_into(cursor, &serializer)
+                },
+            )
             .expect("server event should be serializable");
         }
     }
@@ -102,6 +130,7 @@ pub trait ServerEventAppExt {
         mut client: ResMut<RepliconClient>,
         mut event_queue: ResMut<ServerEventQueue<ReflectEvent>>,
         init_tick: Res<ServerInitTick>,
+        update_tick: Res<ServerUpdateTick>,
         channel: Res<ServerEventChannel<ReflectEvent>>,
         registry: Res<AppTypeRegistry>,
     ) {
@@ -116,7 +145,7 @@ pub trait ServerEventAppExt {
             .expect("server should send valid events");
 
             // Event should be sent to the queue if replication message with its tick has not yet arrived.
-            if tick <= **init_tick {
+            if tick.is_reached(*init_tick, *update_tick) {
                 reflect_events.send(event);
             } else {
                 event_queue.insert(tick, event);
@@ -124,81 +153,190 @@ pub trait ServerEventAppExt {
         }
     }
 
-    #[derive(Event)]
+    #[derive(Event, TypePath)]
     struct ReflectEvent(Box<dyn Reflect>);
     ```
     */
-    fn add_server_event_with<T: Event, Marker1, Marker2>(
+    fn add_server_event_with<T: Event + TypePath, Marker1, Marker2>(
         &mut self,
         channel: impl Into<RepliconChannel>,
         send_system: impl IntoSystemConfigs<Marker1>,
         receive_system: impl IntoSystemConfigs<Marker2>,
     ) -> &mut Self;
+
+    /// Discards received `T` events on client while [`FastForward`](crate::client::fast_forward::FastForward) is active.
+    ///
+    /// Intended for non-critical events, such as visual effects, that make no sense to play after a long pause.
+    ///
+    /// Should be called after the event registration.
+    fn skip_on_fast_forward<T: Event>(&mut self) -> &mut Self;
+
+    /**
+    Re-sends recent `T` events to newly connected clients.
+
+    Only events sent with [`SendMode::Broadcast`] are remembered. Which of them are kept
+    is configured by the policy. Remembered events are re-sent directly to each new client
+    along with its initial world sync and are applied on client after it.
+
+    Intended for announcements that late joiners would otherwise miss, such as match phase changes.
+    Remembered events are cleared when the server stops.
+
+    Should be called after the event registration.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{network_event::server_event::StickyPolicy, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_server_event::<MatchPhase>(ChannelKind::Ordered)
+        .make_sticky::<MatchPhase>(StickyPolicy::Last(1))
+        .add_server_event::<TeamScore>(ChannelKind::Ordered)
+        .make_sticky::<TeamScore>(StickyPolicy::LatestBy(|score| score.team.into()));
+
+    #[derive(Clone, Event, Deserialize, Serialize, TypePath)]
+    enum MatchPhase {
+        Warmup,
+        Playing,
+        Finished,
+    }
+
+    #[derive(Clone, Event, Deserialize, Serialize, TypePath)]
+    struct TeamScore {
+        team: u8,
+        score: u32,
+    }
+    ```
+    */
+    fn make_sticky<T: Event + Clone>(&mut self, policy: StickyPolicy<T>) -> &mut Self;
 }
 
 impl ServerEventAppExt for App {
-    fn add_server_event<T: Event + Serialize + DeserializeOwned>(
+    fn add_server_event<T: Event + TypePath + Serialize + DeserializeOwned>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self {
+        ProtocolValidation::add_unmapped_event::<T>(self.world_mut(), EventKind::Server);
         self.add_server_event_with::<T, _, _>(channel, send::<T>, receive::<T>)
     }
 
-    fn add_mapped_server_event<T: Event + Serialize + DeserializeOwned + MapEntities>(
+    fn add_mapped_server_event<T: Event + TypePath + Serialize + DeserializeOwned + MapEntities>(
         &mut self,
         channel: impl Into<RepliconChannel>,
     ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(MappedServerEvents::default)
+            .0
+            .push(any::type_name::<T>());
+
         self.add_server_event_with::<T, _, _>(channel, send::<T>, receive_and_map::<T>)
+            .init_resource::<UnmappedEventTimeout>()
+            .init_resource::<UnmappedServerEvents<T>>()
+            .add_event::<UnmappedEventExpired<T>>()
+            .add_systems(
+                PreUpdate,
+                reset_unmapped::<T>.in_set(ClientSet::ResetEvents),
+            )
     }
 
-    fn add_server_event_with<T: Event, Marker1, Marker2>(
+    fn add_server_event_with<T: Event + TypePath, Marker1, Marker2>(
         &mut self,
         channel: impl Into<RepliconChannel>,
         send_system: impl IntoSystemConfigs<Marker1>,
         receive_system: impl IntoSystemConfigs<Marker2>,
     ) -> &mut Self {
-        let channel_id = self
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_server_channel(channel.into());
-
-        self.add_event::<T>()
-            .init_resource::<Events<ToClients<T>>>()
-            .init_resource::<ServerEventQueue<T>>()
-            .insert_resource(ServerEventChannel::<T>::new(channel_id))
-            .add_systems(
-                PreUpdate,
-                (
-                    reset::<T>.in_set(ClientSet::ResetEvents),
-                    (pop_from_queue::<T>, receive_system)
-                        .chain()
-                        .after(ClientPlugin::receive_replication)
-                        .in_set(ClientSet::Receive)
-                        .run_if(client_connected),
-                ),
-            )
-            .add_systems(
-                PostUpdate,
+        add_keyed_server_event_with::<T, _, _>(
+            self,
+            ServerEventChannel::<T>::type_path(),
+            channel,
+            send_system,
+            receive_system,
+        )
+    }
+
+    fn skip_on_fast_forward<T: Event>(&mut self) -> &mut Self {
+        self.add_systems(
+            PreUpdate,
+            fast_forward::discard::<T>
+                .after(ClientSet::Receive)
+                .run_if(fast_forwarding),
+        )
+    }
+
+    fn make_sticky<T: Event + Clone>(&mut self, policy: StickyPolicy<T>) -> &mut Self {
+        self.insert_resource(StickyEvents::new(policy)).add_systems(
+            PostUpdate,
+            (
+                clear_sticky::<T>.run_if(server_just_stopped),
                 (
-                    send_system.run_if(server_running),
-                    resend_locally::<T>.run_if(has_authority),
+                    resend_sticky::<T>.before(flush_sender::<T>),
+                    // Stored after flushing the sender and before local resending drains events.
+                    store_sticky::<T>
+                        .after(flush_sender::<T>)
+                        .before(resend_locally::<T>),
                 )
-                    .chain()
                     .after(ServerPlugin::send_replication)
-                    .in_set(ServerSet::Send),
-            );
-
-        self
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            ),
+        )
     }
 }
 
-/// Applies all queued events if their tick is less or equal to [`RepliconTick`].
+/// Same as [`ServerEventAppExt::add_server_event_with`], but registers the channel under `key`
+/// instead of the type path of [`ServerEventChannel<T>`].
+///
+/// Used for events that should keep their channel when the type is renamed or replaced.
+pub(crate) fn add_keyed_server_event_with<T: Event, Marker1, Marker2>(
+    app: &mut App,
+    key: &'static str,
+    channel: impl Into<RepliconChannel>,
+    send_system: impl IntoSystemConfigs<Marker1>,
+    receive_system: impl IntoSystemConfigs<Marker2>,
+) -> &mut App {
+    KeyedChannels::register_server(app.world_mut(), key, channel.into(), |world, channel_id| {
+        world.insert_resource(ServerEventChannel::<T>::new(channel_id))
+    });
+
+    app.add_event::<T>()
+        .init_resource::<Events<ToClients<T>>>()
+        .init_resource::<ServerEventQueue<T>>()
+        .init_resource::<ServerEventSender<T>>()
+        .add_systems(
+            PreUpdate,
+            (
+                reset::<T>.in_set(ClientSet::ResetEvents),
+                (pop_from_queue::<T>, receive_system)
+                    .chain()
+                    .after(ClientSet::ApplyUpdates)
+                    .in_set(ClientSet::Receive)
+                    .run_if(client_connected),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                flush_sender::<T>,
+                send_system.run_if(server_running),
+                resend_locally::<T>.run_if(has_authority),
+            )
+                .chain()
+                .after(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send),
+        )
+}
+
+/// Applies all queued events whose ticks are reached by replication.
 fn pop_from_queue<T: Event>(
     init_tick: Res<ServerInitTick>,
+    update_tick: Res<ServerUpdateTick>,
     mut server_events: EventWriter<T>,
     mut event_queue: ResMut<ServerEventQueue<T>>,
 ) {
-    while let Some((tick, event)) = event_queue.pop_if_le(**init_tick) {
+    while let Some((tick, event)) = event_queue.pop_if_reached(*init_tick, *update_tick) {
         trace!(
             "applying event `{}` from queue with `{tick:?}`",
             any::type_name::<T>()
@@ -212,6 +350,7 @@ fn receive<T: Event + DeserializeOwned>(
     mut client: ResMut<RepliconClient>,
     mut event_queue: ResMut<ServerEventQueue<T>>,
     init_tick: Res<ServerInitTick>,
+    update_tick: Res<ServerUpdateTick>,
     channel: Res<ServerEventChannel<T>>,
 ) {
     for message in client.receive(*channel) {
@@ -220,7 +359,7 @@ fn receive<T: Event + DeserializeOwned>(
         })
         .expect("server should send valid events");
 
-        if tick <= **init_tick {
+        if tick.is_reached(*init_tick, *update_tick) {
             trace!("applying event `{}` with `{tick:?}`", any::type_name::<T>());
             server_events.send(event);
         } else {
@@ -230,22 +369,67 @@ fn receive<T: Event + DeserializeOwned>(
     }
 }
 
+/// Receives events and maps their entities.
+///
+/// Events that reference entities that aren't replicated yet are buffered
+/// until all entities appear or [`UnmappedEventTimeout`] expires.
+/// Subsequent events are buffered too to preserve the order.
+#[allow(clippy::too_many_arguments)]
 fn receive_and_map<T: Event + MapEntities + DeserializeOwned>(
     mut server_events: EventWriter<T>,
+    mut expired_events: EventWriter<UnmappedEventExpired<T>>,
     mut client: ResMut<RepliconClient>,
     mut event_queue: ResMut<ServerEventQueue<T>>,
+    mut unmapped_events: ResMut<UnmappedServerEvents<T>>,
     init_tick: Res<ServerInitTick>,
+    update_tick: Res<ServerUpdateTick>,
     entity_map: Res<ServerEntityMap>,
     channel: Res<ServerEventChannel<T>>,
+    timeout: Res<UnmappedEventTimeout>,
+    time: Res<Time>,
 ) {
-    for message in client.receive(*channel) {
-        let (tick, mut event): (_, T) = deserialize_with(&message, |cursor| {
+    let now = time.elapsed();
+    unmapped_events
+        .messages
+        .extend(client.receive(*channel).map(|message| UnmappedMessage {
+            message,
+            received_at: now,
+        }));
+
+    while let Some(unmapped) = unmapped_events.messages.front() {
+        let (tick, mut event): (_, T) = deserialize_with(&unmapped.message, |cursor| {
             DefaultOptions::new().deserialize_from(cursor)
         })
         .expect("server should send valid events");
 
-        event.map_entities(&mut EventMapper(entity_map.to_client()));
-        if tick <= **init_tick {
+        let mut mapper = CheckedEventMapper::new(entity_map.to_client());
+        event.map_entities(&mut mapper);
+        if !mapper.missing.is_empty() {
+            if now.saturating_sub(unmapped.received_at) < **timeout {
+                trace!(
+                    "buffering event `{}` for `{tick:?}` until {:?} are replicated",
+                    any::type_name::<T>(),
+                    mapper.missing,
+                );
+                break;
+            }
+
+            debug!(
+                "dropping event `{}` for `{tick:?}` because {:?} weren't replicated in time",
+                any::type_name::<T>(),
+                mapper.missing,
+            );
+            unmapped_events.messages.pop_front();
+            expired_events.send(UnmappedEventExpired {
+                tick: tick.tick(),
+                entities: mapper.missing,
+                marker: PhantomData,
+            });
+            continue;
+        }
+
+        unmapped_events.messages.pop_front();
+        if tick.is_reached(*init_tick, *update_tick) {
             trace!("applying event `{}` for `{tick:?}`", any::type_name::<T>());
             server_events.send(event);
         } else {
@@ -261,40 +445,165 @@ fn send<T: Event + Serialize>(
     connected_clients: Res<ConnectedClients>,
     channel: Res<ServerEventChannel<T>>,
 ) {
-    for ToClients { event, mode } in server_events.read() {
+    for ToClients {
+        event,
+        mode,
+        apply_tick,
+    } in server_events.read()
+    {
         trace!("sending event `{}` with `{mode:?}`", any::type_name::<T>());
-        send_with(&mut server, &connected_clients, *channel, *mode, |cursor| {
-            DefaultOptions::new().serialize_into(cursor, &event)
-        })
+        send_with(
+            &mut server,
+            &connected_clients,
+            *channel,
+            mode,
+            *apply_tick,
+            |cursor| DefaultOptions::new().serialize_into(cursor, &event),
+        )
         .expect("server event should be serializable");
     }
 }
 
+/// Moves events from [`ServerEventSender<T>`] into [`Events<ToClients<T>>`].
+fn flush_sender<T: Event>(
+    sender: Res<ServerEventSender<T>>,
+    mut server_events: EventWriter<ToClients<T>>,
+) {
+    let mut events = sender.lock();
+    if !events.is_empty() {
+        trace!(
+            "flushing {} events `{}` from sender",
+            events.len(),
+            any::type_name::<T>()
+        );
+        server_events.send_batch(events.drain(..));
+    }
+}
+
+/// Sends remembered events directly to newly connected clients.
+///
+/// For [held](ConnectedClient::is_held) clients events are sent after releasing.
+fn resend_sticky<T: Event + Clone>(
+    mut pending_clients: Local<Vec<ClientId>>,
+    mut connect_events: EventReader<ServerEvent>,
+    mut server_events: EventWriter<ToClients<T>>,
+    connected_clients: Res<ConnectedClients>,
+    sticky_events: Res<StickyEvents<T>>,
+    names: Res<ClientNames>,
+) {
+    for event in connect_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            pending_clients.push(client_id);
+        }
+    }
+
+    pending_clients.retain(|&client_id| {
+        let Some(client) = connected_clients.get_client(client_id) else {
+            return false;
+        };
+        if client.is_held() {
+            return true;
+        }
+
+        if !sticky_events.events.is_empty() {
+            debug!(
+                "resending {} sticky events `{}` to {}",
+                sticky_events.events.len(),
+                any::type_name::<T>(),
+                names.label(client_id)
+            );
+        }
+        for (_, event) in &sticky_events.events {
+            server_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: event.clone(),
+                apply_tick: None,
+            });
+        }
+
+        false
+    });
+}
+
+/// Remembers broadcasted events according to [`StickyPolicy`].
+fn store_sticky<T: Event + Clone>(
+    mut server_events: EventReader<ToClients<T>>,
+    mut sticky_events: ResMut<StickyEvents<T>>,
+) {
+    for ToClients { mode, event, .. } in server_events.read() {
+        if matches!(mode, SendMode::Broadcast) {
+            sticky_events.insert(event.clone());
+        }
+    }
+}
+
+fn clear_sticky<T: Event>(mut sticky_events: ResMut<StickyEvents<T>>) {
+    sticky_events.events.clear();
+}
+
 /// Transforms [`ToClients<T>`] events into `T` events to "emulate"
 /// message sending for offline mode or when server is also a player.
+///
+/// Scheduled events are queued until [`ServerTick`] reaches their apply tick.
 fn resend_locally<T: Event>(
     mut server_events: ResMut<Events<ToClients<T>>>,
     mut local_events: EventWriter<T>,
+    mut event_queue: ResMut<ServerEventQueue<T>>,
+    connected_clients: Option<Res<ConnectedClients>>,
+    server_tick: Option<Res<ServerTick>>,
 ) {
-    for ToClients { event, mode } in server_events.drain() {
-        match mode {
-            SendMode::Broadcast => {
-                local_events.send(event);
+    if let Some(server_tick) = &server_tick {
+        while let Some((_, event)) = event_queue.pop_if_le(***server_tick) {
+            local_events.send(event);
+        }
+    }
+
+    for ToClients {
+        event,
+        mode,
+        apply_tick,
+    } in server_events.drain()
+    {
+        let local = match mode {
+            SendMode::Broadcast | SendMode::Visible(_) => true,
+            SendMode::BroadcastExcept(client_id) => client_id != ClientId::SERVER,
+            SendMode::Direct(client_id) => client_id == ClientId::SERVER,
+            SendMode::Only(client_ids) => client_ids.contains(&ClientId::SERVER),
+            SendMode::Except(client_ids) => !client_ids.contains(&ClientId::SERVER),
+            SendMode::Group(group_id) => {
+                connected_clients.as_ref().is_some_and(|connected_clients| {
+                    connected_clients
+                        .groups()
+                        .contains(group_id, ClientId::SERVER)
+                })
             }
-            SendMode::BroadcastExcept(client_id) => {
-                if client_id != ClientId::SERVER {
-                    local_events.send(event);
-                }
+        };
+        if !local {
+            continue;
+        }
+
+        match (apply_tick, &server_tick) {
+            (Some(apply_tick), Some(server_tick)) if apply_tick > ***server_tick => {
+                event_queue.insert(EventTick::Scheduled(apply_tick), event);
             }
-            SendMode::Direct(client_id) => {
-                if client_id == ClientId::SERVER {
-                    local_events.send(event);
-                }
+            _ => {
+                local_events.send(event);
             }
         }
     }
 }
 
+/// Clears buffered events with unmapped entities.
+fn reset_unmapped<T: Event>(mut unmapped_events: ResMut<UnmappedServerEvents<T>>) {
+    if !unmapped_events.messages.is_empty() {
+        debug!(
+            "discarding {} unmapped server events due to a disconnect",
+            unmapped_events.messages.len()
+        );
+    }
+    unmapped_events.messages.clear();
+}
+
 /// Clears queued events.
 ///
 /// We clear events while waiting for a connection to ensure clean reconnects.
@@ -308,44 +617,237 @@ fn reset<T: Event>(mut event_queue: ResMut<ServerEventQueue<T>>) {
     event_queue.0.clear();
 }
 
+/// Time after which a mapped server event whose entities weren't replicated will be dropped.
+///
+/// See also [`ServerEventAppExt::add_mapped_server_event`].
+///
+/// By default set to 5 seconds.
+#[derive(Resource, Clone, Copy, Debug, Deref, DerefMut)]
+pub struct UnmappedEventTimeout(pub Duration);
+
+impl Default for UnmappedEventTimeout {
+    fn default() -> Self {
+        Self(Duration::from_secs(5))
+    }
+}
+
+/// An event indicating that a mapped server event `T` was dropped because its entities weren't replicated in time.
+///
+/// See also [`UnmappedEventTimeout`].
+#[derive(Clone, Debug, Event)]
+pub struct UnmappedEventExpired<T> {
+    /// Tick at which the event was sent.
+    pub tick: RepliconTick,
+
+    /// Server entities that weren't replicated.
+    pub entities: Vec<Entity>,
+
+    marker: PhantomData<T>,
+}
+
+/// Received messages for `T` that wait for their entities to be replicated.
+#[derive(Resource)]
+struct UnmappedServerEvents<T> {
+    messages: VecDeque<UnmappedMessage>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for UnmappedServerEvents<T> {
+    fn default() -> Self {
+        Self {
+            messages: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+struct UnmappedMessage {
+    message: Bytes,
+
+    /// Value of [`Time::elapsed`] when the message was received.
+    received_at: Duration,
+}
+
+/// Maps entities that are known and collects entities that aren't.
+struct CheckedEventMapper<'a> {
+    entity_map: &'a EntityHashMap<Entity>,
+    missing: Vec<Entity>,
+}
+
+impl<'a> CheckedEventMapper<'a> {
+    fn new(entity_map: &'a EntityHashMap<Entity>) -> Self {
+        Self {
+            entity_map,
+            missing: Default::default(),
+        }
+    }
+}
+
+impl EntityMapper for CheckedEventMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        match self.entity_map.get(&entity) {
+            Some(&mapped) => mapped,
+            None => {
+                self.missing.push(entity);
+                entity
+            }
+        }
+    }
+}
+
+/// Which events to re-send to newly connected clients.
+///
+/// See [`ServerEventAppExt::make_sticky`].
+pub enum StickyPolicy<T> {
+    /// Keep the specified number of the most recent events.
+    Last(usize),
+
+    /// Keep only the most recent event for each key returned by the function.
+    LatestBy(fn(&T) -> u64),
+}
+
+impl<T> Clone for StickyPolicy<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StickyPolicy<T> {}
+
+/// Remembered events registered via [`ServerEventAppExt::make_sticky`] with their keys.
+#[derive(Resource)]
+struct StickyEvents<T> {
+    policy: StickyPolicy<T>,
+    events: VecDeque<(u64, T)>,
+}
+
+impl<T> StickyEvents<T> {
+    fn new(policy: StickyPolicy<T>) -> Self {
+        Self {
+            policy,
+            events: Default::default(),
+        }
+    }
+
+    fn insert(&mut self, event: T) {
+        match self.policy {
+            StickyPolicy::Last(count) => {
+                self.events.push_back((0, event));
+                while self.events.len() > count {
+                    self.events.pop_front();
+                }
+            }
+            StickyPolicy::LatestBy(key_fn) => {
+                let key = (key_fn)(&event);
+                self.events.retain(|&(other_key, _)| other_key != key);
+                self.events.push_back((key, event));
+            }
+        }
+    }
+}
+
+/// Type names of events registered via [`ServerEventAppExt::add_mapped_server_event`].
+#[derive(Resource, Default)]
+pub(crate) struct MappedServerEvents(pub(crate) Vec<&'static str>);
+
 /// Helper for custom sending systems.
 ///
-/// See also [`ServerEventAppExt::add_server_event_with`].
+/// Skips [held](ConnectedClient::is_held) clients.
+///
+/// See also [`ServerEventAppExt::add_server_event_with`] and [`ToClients::apply_tick`].
 pub fn send_with<T>(
     server: &mut RepliconServer,
     connected_clients: &ConnectedClients,
     channel: ServerEventChannel<T>,
-    mode: SendMode,
+    mode: &SendMode,
+    apply_tick: Option<RepliconTick>,
     serialize: impl Fn(&mut Cursor<Vec<u8>>) -> bincode::Result<()>,
 ) -> bincode::Result<()> {
+    send_filtered(
+        server,
+        connected_clients,
+        channel,
+        mode,
+        apply_tick,
+        |_| true,
+        serialize,
+    )
+}
+
+/// Same as [`send_with`], but additionally skips clients for which `filter` returns `false`.
+pub(crate) fn send_filtered<T>(
+    server: &mut RepliconServer,
+    connected_clients: &ConnectedClients,
+    channel: ServerEventChannel<T>,
+    mode: &SendMode,
+    apply_tick: Option<RepliconTick>,
+    filter: impl Fn(ClientId) -> bool,
+    serialize: impl Fn(&mut Cursor<Vec<u8>>) -> bincode::Result<()>,
+) -> bincode::Result<()> {
+    let targets = connected_clients
+        .iter()
+        .filter(|client| !client.is_held() && filter(client.id()));
     match mode {
         SendMode::Broadcast => {
             let mut previous_message = None;
-            for client in connected_clients.iter() {
-                let message = serialize_with(client, previous_message, &serialize)?;
+            for client in targets {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
                 server.send(client.id(), channel, message.bytes.clone());
                 previous_message = Some(message);
             }
         }
         SendMode::BroadcastExcept(client_id) => {
             let mut previous_message = None;
-            for client in connected_clients.iter() {
-                if client.id() == client_id {
-                    continue;
-                }
-                let message = serialize_with(client, previous_message, &serialize)?;
+            for client in targets.filter(|client| client.id() != *client_id) {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
                 server.send(client.id(), channel, message.bytes.clone());
                 previous_message = Some(message);
             }
         }
         SendMode::Direct(client_id) => {
-            if client_id != ClientId::SERVER {
-                if let Some(client) = connected_clients.get_client(client_id) {
-                    let message = serialize_with(client, None, &serialize)?;
+            if *client_id != ClientId::SERVER && filter(*client_id) {
+                if let Some(client) = connected_clients
+                    .get_client(*client_id)
+                    .filter(|client| !client.is_held())
+                {
+                    let message = serialize_with(client, apply_tick, None, &serialize)?;
                     server.send(client.id(), channel, message.bytes);
                 }
             }
         }
+        SendMode::Only(client_ids) => {
+            let mut previous_message = None;
+            for client in targets.filter(|client| client_ids.contains(&client.id())) {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
+                server.send(client.id(), channel, message.bytes.clone());
+                previous_message = Some(message);
+            }
+        }
+        SendMode::Except(client_ids) => {
+            let mut previous_message = None;
+            for client in targets.filter(|client| !client_ids.contains(&client.id())) {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
+                server.send(client.id(), channel, message.bytes.clone());
+                previous_message = Some(message);
+            }
+        }
+        SendMode::Group(group_id) => {
+            let groups = connected_clients.groups();
+            let mut previous_message = None;
+            for client in targets.filter(|client| groups.contains(*group_id, client.id())) {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
+                server.send(client.id(), channel, message.bytes.clone());
+                previous_message = Some(message);
+            }
+        }
+        SendMode::Visible(entity) => {
+            let mut previous_message = None;
+            for client in targets.filter(|client| client.visibility().is_visible(*entity)) {
+                let message = serialize_with(client, apply_tick, previous_message, &serialize)?;
+                server.send(client.id(), channel, message.bytes.clone());
+                previous_message = Some(message);
+            }
+        }
     }
 
     Ok(())
@@ -353,25 +855,32 @@ pub fn send_with<T>(
 
 /// Helper for serializing a server event.
 ///
-/// Will prepend the client's change tick to the injected message.
+/// Will prepend [`EventTick`] to the injected message: the apply tick if it's later than the client's change tick,
+/// otherwise the change tick.
 ///
-/// Optimized to avoid reallocations when consecutive clients have the same change tick.
+/// Optimized to avoid reallocations when consecutive clients have the same tick.
 fn serialize_with(
     client: &ConnectedClient,
+    apply_tick: Option<RepliconTick>,
     previous_message: Option<SerializedMessage>,
     serialize: impl Fn(&mut Cursor<Vec<u8>>) -> bincode::Result<()>,
 ) -> bincode::Result<SerializedMessage> {
+    let tick = match apply_tick {
+        Some(apply_tick) if apply_tick > client.change_tick() => EventTick::Scheduled(apply_tick),
+        _ => EventTick::Init(client.change_tick()),
+    };
+
     if let Some(previous_message) = previous_message {
-        if previous_message.tick == client.change_tick() {
+        if previous_message.tick == tick {
             return Ok(previous_message);
         }
 
-        let tick_size = DefaultOptions::new().serialized_size(&client.change_tick())? as usize;
+        let tick_size = DefaultOptions::new().serialized_size(&tick)? as usize;
         let mut bytes = Vec::with_capacity(tick_size + previous_message.event_bytes().len());
-        DefaultOptions::new().serialize_into(&mut bytes, &client.change_tick())?;
+        DefaultOptions::new().serialize_into(&mut bytes, &tick)?;
         bytes.extend_from_slice(previous_message.event_bytes());
         let message = SerializedMessage {
-            tick: client.change_tick(),
+            tick,
             tick_size,
             bytes: bytes.into(),
         };
@@ -379,11 +888,11 @@ fn serialize_with(
         Ok(message)
     } else {
         let mut cursor = Cursor::new(Vec::new());
-        DefaultOptions::new().serialize_into(&mut cursor, &client.change_tick())?;
+        DefaultOptions::new().serialize_into(&mut cursor, &tick)?;
         let tick_size = cursor.get_ref().len();
         (serialize)(&mut cursor)?;
         let message = SerializedMessage {
-            tick: client.change_tick(),
+            tick,
             tick_size,
             bytes: cursor.into_inner().into(),
         };
@@ -394,7 +903,7 @@ fn serialize_with(
 
 /// Cached message for use in [`serialize_with`].
 struct SerializedMessage {
-    tick: RepliconTick,
+    tick: EventTick,
     tick_size: usize,
     bytes: Bytes,
 }
@@ -405,11 +914,11 @@ impl SerializedMessage {
     }
 }
 
-/// Deserializes event change tick first and then calls the specified deserialization function to get the event itself.
+/// Deserializes [`EventTick`] first and then calls the specified deserialization function to get the event itself.
 pub fn deserialize_with<T>(
     message: &[u8],
     deserialize: impl FnOnce(&mut Cursor<&[u8]>) -> bincode::Result<T>,
-) -> bincode::Result<(RepliconTick, T)> {
+) -> bincode::Result<(EventTick, T)> {
     let mut cursor = Cursor::new(message);
     let tick = DefaultOptions::new().deserialize_from(&mut cursor)?;
     let event = (deserialize)(&mut cursor)?;
@@ -417,8 +926,40 @@ pub fn deserialize_with<T>(
     Ok((tick, event))
 }
 
+/// Tick after which a received server event can be applied.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum EventTick {
+    /// Client's change tick at the moment of sending.
+    ///
+    /// The event is applied after the init message for this tick,
+    /// so all entities and components that the event may reference already exist.
+    Init(RepliconTick),
+    /// Tick from [`ToClients::apply_tick`].
+    ///
+    /// The event is applied once replication reaches this tick by any message,
+    /// see [`replicated_tick`](client::replicated_tick).
+    Scheduled(RepliconTick),
+}
+
+impl EventTick {
+    /// Returns the tick value.
+    pub fn tick(self) -> RepliconTick {
+        match self {
+            EventTick::Init(tick) | EventTick::Scheduled(tick) => tick,
+        }
+    }
+
+    /// Returns `true` if replication on client reached this tick and the event can be applied.
+    pub fn is_reached(self, init_tick: ServerInitTick, update_tick: ServerUpdateTick) -> bool {
+        match self {
+            EventTick::Init(tick) => tick <= *init_tick,
+            EventTick::Scheduled(tick) => tick <= client::replicated_tick(init_tick, update_tick),
+        }
+    }
+}
+
 /// Holds a server's channel ID for `T`.
-#[derive(Resource)]
+#[derive(Resource, TypePath)]
 pub struct ServerEventChannel<T> {
     id: u8,
     marker: PhantomData<T>,
@@ -448,18 +989,114 @@ impl<T> From<ServerEventChannel<T>> for u8 {
 }
 
 /// An event that will be send to client(s).
-#[derive(Clone, Copy, Debug, Event)]
+#[derive(Clone, Debug, Event)]
 pub struct ToClients<T> {
     pub mode: SendMode,
     pub event: T,
+
+    /**
+    Server tick at which the event should be applied on clients.
+
+    If set, clients hold the event until replication reaches this tick, so scheduled effects,
+    such as countdowns or explosions, fire consistently relative to world state on all clients.
+    Replication reaches a tick when an init or update message for this or a later tick is applied,
+    see [`replicated_tick`](client::replicated_tick). On listen server local events are held
+    until [`ServerTick`] reaches it.
+
+    If [`None`] or the tick is already reached, the event is applied as soon as all replication
+    sent before it is applied.
+    */
+    pub apply_tick: Option<RepliconTick>,
 }
 
 /// Type of server message sending.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum SendMode {
+    /// Send to all connected clients.
     Broadcast,
+    /// Send to all connected clients except the specified one.
     BroadcastExcept(ClientId),
+    /// Send only to the specified client.
     Direct(ClientId),
+    /// Send only to the specified clients.
+    ///
+    /// Allows sending a single event to an arbitrary subset of clients.
+    Only(Vec<ClientId>),
+    /// Send to all connected clients except the specified ones.
+    Except(Vec<ClientId>),
+    /// Send only to clients for which the entity is visible.
+    ///
+    /// Uses the same visibility as replication, see [`VisibilityPolicy`](crate::server::VisibilityPolicy).
+    /// Always delivered locally since the server sees all entities.
+    Visible(Entity),
+    /// Send to members of the group.
+    ///
+    /// Memberships are managed via [`ConnectedClients::groups_mut`].
+    Group(GroupId),
+}
+
+/**
+Thread-safe handle for sending [`ToClients<T>`] from outside of the ECS.
+
+Can be cloned and moved into async tasks or other threads, for example, database callbacks
+or matchmaking responses. Sent events are emitted as regular [`ToClients<T>`] on the main
+schedule right before sending.
+
+# Examples
+
+```
+use bevy::{prelude::*, tasks::IoTaskPool};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_server_event::<MatchFound>(ChannelKind::Ordered);
+
+fn find_match(sender: Res<ServerEventSender<MatchFound>>) {
+    let sender = sender.clone();
+    IoTaskPool::get()
+        .spawn(async move {
+            // Talk to matchmaking service...
+            sender.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: MatchFound,
+                apply_tick: None,
+            });
+        })
+        .detach();
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct MatchFound;
+```
+**/
+#[derive(Resource)]
+pub struct ServerEventSender<T>(Arc<Mutex<Vec<ToClients<T>>>>);
+
+impl<T> ServerEventSender<T> {
+    /// Enqueues an event to be sent on the next app update.
+    pub fn send(&self, event: ToClients<T>) {
+        self.lock().push(event);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<ToClients<T>>> {
+        self.0
+            .lock()
+            .expect("sender mutex shouldn't be poisoned by pushing events")
+    }
+}
+
+impl<T> Clone for ServerEventSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Default for ServerEventSender<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
 }
 
 /// Stores all received events from server that arrived earlier then replication message with their tick.
@@ -467,20 +1104,38 @@ pub enum SendMode {
 /// Stores data sorted by ticks and maintains order of arrival.
 /// Needed to ensure that when an event is triggered, all the data that it affects or references already exists.
 #[derive(Resource)]
-pub struct ServerEventQueue<T>(ListOrderedMultimap<RepliconTick, T>);
+pub struct ServerEventQueue<T>(ListOrderedMultimap<EventTick, T>);
 
 impl<T> ServerEventQueue<T> {
     /// Inserts a new event.
     ///
-    /// The event will be queued until [`RepliconTick`] is bigger or equal to the tick specified here.
-    pub fn insert(&mut self, tick: RepliconTick, event: T) {
+    /// The event will be queued until replication reaches the tick specified here,
+    /// see [`EventTick::is_reached`].
+    pub fn insert(&mut self, tick: EventTick, event: T) {
         self.0.insert(tick, event);
     }
 
-    /// Pops the next event that is at least as old as the specified replicon tick.
-    fn pop_if_le(&mut self, init_tick: RepliconTick) -> Option<(RepliconTick, T)> {
+    /// Pops the next event if replication on client reached its tick.
+    fn pop_if_reached(
+        &mut self,
+        init_tick: ServerInitTick,
+        update_tick: ServerUpdateTick,
+    ) -> Option<(EventTick, T)> {
+        let (tick, _) = self.0.front()?;
+        if !tick.is_reached(init_tick, update_tick) {
+            return None;
+        }
+        self.0
+            .pop_front()
+            .map(|(tick, event)| (tick.into_owned(), event))
+    }
+
+    /// Pops the next event that is at least as old as the specified server tick.
+    ///
+    /// Used on listen server, where events are held until [`ServerTick`] reaches them.
+    fn pop_if_le(&mut self, server_tick: RepliconTick) -> Option<(EventTick, T)> {
         let (tick, _) = self.0.front()?;
-        if *tick > init_tick {
+        if tick.tick() > server_tick {
             return None;
         }
         self.0
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/**
+Fractional offset inside a tick at which something happened.
+
+Quantized to [`u16`] to keep messages compact, which gives sub-millisecond precision
+even for low tick rates.
+
+Usually sent with client events via [`WithSubtick`] to let the server reconstruct
+the exact moment of input inside a tick, for example for hit registration in fast-paced games.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_client_event::<WithSubtick<Fire>>(ChannelKind::Ordered)
+    .add_systems(Update, (fire, register_hits));
+
+/// Sends input with the time passed since the last fixed step.
+fn fire(mut fire_events: EventWriter<WithSubtick<Fire>>, time: Res<Time<Fixed>>) {
+    fire_events.send(WithSubtick::new(Fire, Subtick::from_overstep(&time)));
+}
+
+/// Reads the offset on server.
+fn register_hits(
+    mut fire_events: EventReader<FromClient<WithSubtick<Fire>>>,
+    names: Res<ClientNames>,
+    time: Res<Time<Fixed>>,
+) {
+    for FromClient { client_id, event, .. } in fire_events.read() {
+        let offset = event.subtick.offset(time.timestep());
+        info!("{} fired {offset:?} after the tick start", names.label(*client_id));
+    }
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Fire;
+```
+**/
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize,
+)]
+pub struct Subtick(u16);
+
+impl Subtick {
+    /// Creates a new instance from fraction of a tick in the `[0.0, 1.0)` range.
+    ///
+    /// Values outside the range are clamped.
+    pub fn from_fraction(fraction: f32) -> Self {
+        let value = (fraction.clamp(0.0, 1.0) * Self::SCALE).round();
+        Self(value.min(u16::MAX as f32) as u16)
+    }
+
+    /// Creates a new instance from the current overstep of the fixed timestep.
+    ///
+    /// Use it in systems that run outside of [`FixedMain`] to get the time passed since the last fixed step.
+    pub fn from_overstep(time: &Time<Fixed>) -> Self {
+        Self::from_fraction(time.overstep_fraction())
+    }
+
+    /// Returns fraction of a tick in the `[0.0, 1.0)` range.
+    pub fn fraction(self) -> f32 {
+        self.0 as f32 / Self::SCALE
+    }
+
+    /// Returns time passed since the tick start for the given tick duration.
+    pub fn offset(self, tick_duration: Duration) -> Duration {
+        tick_duration.mul_f32(self.fraction())
+    }
+
+    const SCALE: f32 = u16::MAX as f32 + 1.0;
+}
+
+/// Wraps an event to attach [`Subtick`] to it.
+///
+/// Register it as a regular client event, see [`Subtick`] for an example.
+#[derive(Clone, Copy, Debug, Event, Deserialize, Serialize, TypePath)]
+pub struct WithSubtick<T> {
+    pub subtick: Subtick,
+    pub event: T,
+}
+
+impl<T> WithSubtick<T> {
+    pub fn new(event: T, subtick: Subtick) -> Self {
+        Self { subtick, event }
+    }
+}
+
+impl<T: MapEntities> MapEntities for WithSubtick<T> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.event.map_entities(entity_mapper);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraction() {
+        assert_eq!(Subtick::from_fraction(0.0).fraction(), 0.0);
+        assert_eq!(Subtick::from_fraction(0.5).fraction(), 0.5);
+        assert_eq!(Subtick::from_fraction(-1.0), Subtick::from_fraction(0.0));
+        assert_eq!(Subtick::from_fraction(2.0), Subtick(u16::MAX));
+        assert!(Subtick::from_fraction(1.0).fraction() < 1.0);
+    }
+
+    #[test]
+    fn offset() {
+        let subtick = Subtick::from_fraction(0.25);
+        assert_eq!(
+            subtick.offset(Duration::from_millis(100)),
+            Duration::from_millis(25)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::any::{self, TypeId};
+
+use bevy::{prelude::*, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use bytes::Bytes;
+use serde::Serialize;
+
+use super::client_event::FromClient;
+use crate::{client::ClientSet, core::ClientId, server::ServerSet};
+
+/**
+Extension for [`App`] to expose network events under string topics.
+
+Third-party event-routing crates usually route messages by name and don't know about Replicon types.
+This extension assigns a topic name to a registered event and re-emits received events of this type
+as untyped [`TopicMessage`]s while the topic is subscribed in [`EventTopics`].
+This way hybrid stacks can forward Replicon messages into their routers without per-event glue.
+
+The payload is serialized with [`DefaultOptions`], the same as regular events.
+Typed events are not consumed and still available for other systems.
+Topics are not subscribed by default.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{network_event::topics::TopicMessage, prelude::*};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_client_event::<Chat>(ChannelKind::Ordered)
+    .add_client_event_topic::<Chat>("chat")
+    .add_systems(Startup, subscribe)
+    .add_systems(Update, forward);
+
+fn subscribe(mut topics: ResMut<EventTopics>) {
+    topics.subscribe("chat");
+}
+
+fn forward(mut messages: EventReader<TopicMessage>) {
+    for message in messages.read() {
+        // Pass the message to your router...
+        info!("received {} bytes on topic `{}`", message.payload.len(), message.topic);
+    }
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Chat(String);
+```
+**/
+pub trait TopicAppExt {
+    /// Exposes received client events `T` on server under the topic.
+    ///
+    /// `T` should be registered as a client event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the topic or the type is already registered.
+    fn add_client_event_topic<T: Event + Serialize>(&mut self, topic: &'static str) -> &mut Self;
+
+    /// Exposes received server events `T` on client under the topic.
+    ///
+    /// `T` should be registered as a server event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the topic or the type is already registered.
+    fn add_server_event_topic<T: Event + Serialize>(&mut self, topic: &'static str) -> &mut Self;
+}
+
+impl TopicAppExt for App {
+    fn add_client_event_topic<T: Event + Serialize>(&mut self, topic: &'static str) -> &mut Self {
+        self.add_event::<TopicMessage>()
+            .init_resource::<EventTopics>()
+            .world_mut()
+            .resource_mut::<EventTopics>()
+            .register::<T>(topic);
+
+        self.add_systems(
+            PreUpdate,
+            expose_client_event::<T>.after(ServerSet::Receive),
+        )
+    }
+
+    fn add_server_event_topic<T: Event + Serialize>(&mut self, topic: &'static str) -> &mut Self {
+        self.add_event::<TopicMessage>()
+            .init_resource::<EventTopics>()
+            .world_mut()
+            .resource_mut::<EventTopics>()
+            .register::<T>(topic);
+
+        self.add_systems(
+            PreUpdate,
+            expose_server_event::<T>.after(ClientSet::Receive),
+        )
+    }
+}
+
+/// Re-emits `T` from clients as [`TopicMessage`] if its topic is subscribed.
+fn expose_client_event<T: Event + Serialize>(
+    mut client_events: EventReader<FromClient<T>>,
+    mut messages: EventWriter<TopicMessage>,
+    topics: Res<EventTopics>,
+) {
+    let topic = topics.name::<T>().expect("topic should be registered");
+    if !topics.is_subscribed(topic) {
+        client_events.clear();
+        return;
+    }
+
+    for FromClient {
+        client_id, event, ..
+    } in client_events.read()
+    {
+        messages.send(TopicMessage::new::<T>(topic, *client_id, event));
+    }
+}
+
+/// Re-emits `T` from server as [`TopicMessage`] if its topic is subscribed.
+fn expose_server_event<T: Event + Serialize>(
+    mut server_events: EventReader<T>,
+    mut messages: EventWriter<TopicMessage>,
+    topics: Res<EventTopics>,
+) {
+    let topic = topics.name::<T>().expect("topic should be registered");
+    if !topics.is_subscribed(topic) {
+        server_events.clear();
+        return;
+    }
+
+    for event in server_events.read() {
+        messages.send(TopicMessage::new::<T>(topic, ClientId::SERVER, event));
+    }
+}
+
+/// Registered topics and their subscriptions.
+///
+/// See [`TopicAppExt`].
+#[derive(Resource, Default)]
+pub struct EventTopics {
+    /// Topics by their names.
+    topics: HashMap<&'static str, EventTopic>,
+
+    /// Topic names by event types.
+    names: HashMap<TypeId, &'static str>,
+}
+
+impl EventTopics {
+    fn register<T: 'static>(&mut self, topic: &'static str) {
+        let type_name = any::type_name::<T>();
+        assert!(
+            !self.topics.contains_key(topic),
+            "topic `{topic}` should be registered only once"
+        );
+        if let Some(name) = self.names.insert(TypeId::of::<T>(), topic) {
+            panic!(
+                "`{type_name}` should have only one topic, but it's already registered as `{name}`"
+            );
+        }
+
+        self.topics.insert(
+            topic,
+            EventTopic {
+                type_name,
+                subscribed: false,
+            },
+        );
+    }
+
+    /// Returns the topic name of `T`.
+    pub fn name<T: 'static>(&self) -> Option<&'static str> {
+        self.names.get(&TypeId::of::<T>()).copied()
+    }
+
+    /// Starts emitting [`TopicMessage`]s for the topic.
+    ///
+    /// Returns `false` if the topic is not registered.
+    pub fn subscribe(&mut self, topic: &str) -> bool {
+        self.set_subscribed(topic, true)
+    }
+
+    /// Stops emitting [`TopicMessage`]s for the topic.
+    ///
+    /// Returns `false` if the topic is not registered.
+    pub fn unsubscribe(&mut self, topic: &str) -> bool {
+        self.set_subscribed(topic, false)
+    }
+
+    fn set_subscribed(&mut self, topic: &str, subscribed: bool) -> bool {
+        let Some(event_topic) = self.topics.get_mut(topic) else {
+            return false;
+        };
+
+        event_topic.subscribed = subscribed;
+        true
+    }
+
+    /// Returns `true` if the topic is registered and subscribed.
+    pub fn is_subscribed(&self, topic: &str) -> bool {
+        self.topics
+            .get(topic)
+            .is_some_and(|event_topic| event_topic.subscribed)
+    }
+
+    /// Returns all registered topic names with type names of their events.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+        self.topics
+            .iter()
+            .map(|(&topic, event_topic)| (topic, event_topic.type_name))
+    }
+}
+
+struct EventTopic {
+    type_name: &'static str,
+    subscribed: bool,
+}
+
+/// An untyped network event received on a subscribed topic.
+///
+/// See [`TopicAppExt`].
+#[derive(Clone, Debug, Event)]
+pub struct TopicMessage {
+    /// Name of the topic.
+    pub topic: &'static str,
+
+    /// Sender of the event.
+    ///
+    /// [`ClientId::SERVER`] for server events.
+    pub client_id: ClientId,
+
+    /// Serialized event.
+    pub payload: Bytes,
+}
+
+impl TopicMessage {
+    fn new<T: Serialize>(topic: &'static str, client_id: ClientId, event: &T) -> Self {
+        let payload = DefaultOptions::new()
+            .serialize(event)
+            .unwrap_or_else(|e| panic!("`{}` should be serializable: {e}", any::type_name::<T>()));
+
+        Self {
+            topic,
+            client_id,
+            payload: payload.into(),
+        }
+    }
+}
@@ -0,0 +1,639 @@
+use std::{any, io::Cursor};
+
+use bevy::{prelude::*, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    client_event::{self, ClientEventChannel, FromClient, FromClientTicks},
+    server_event::{self, ServerEventChannel, ServerEventQueue, ToClients},
+};
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet, ServerInitTick, ServerUpdateTick},
+    core::{
+        common_conditions::{
+            client_connected, client_just_connected, server_just_stopped, server_running,
+        },
+        replicon_channels::{ChannelKind, KeyedChannels, RepliconChannel},
+        ClientId,
+    },
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, ServerEvent, ServerSet,
+    },
+};
+
+/**
+Extension for [`App`] to register network events with multiple wire versions.
+
+Each message is prefixed with its version. On connection the client sends its supported versions
+of each versioned event, the server picks the highest shared version and both sides send events
+in this version from then on. Older versions are converted into the current event type on receive.
+This way servers can keep talking to clients one release behind.
+
+Events are identified by a user-provided key, which is also used to assign the event channel instead of the type path.
+So the event type can be renamed or replaced between releases as long as the key stays the same.
+Keys should be unique among all versioned events and registered on both sides.
+Until negotiation completes, events are sent in the current version.
+The negotiated versions are available in [`EventVersions`].
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_versioned_client_event::<Chat>(ChannelKind::Ordered, "chat", 2)
+    .add_client_event_version::<Chat, ChatV1>(
+        1,
+        |chat| Chat {
+            text: chat.0,
+            channel: 0,
+        },
+        |chat| ChatV1(chat.text.clone()),
+    );
+
+#[derive(Event, Deserialize, Serialize)]
+struct Chat {
+    text: String,
+    channel: u8,
+}
+
+/// Chat message from the previous release.
+#[derive(Deserialize, Serialize)]
+struct ChatV1(String);
+```
+**/
+pub trait VersionedEventAppExt {
+    /// Same as [`ClientEventAppExt::add_client_event`](super::client_event::ClientEventAppExt::add_client_event),
+    /// but prefixes messages with the version.
+    ///
+    /// `key` identifies the event during negotiation and `version` is the current version of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key or `T` is already registered as a versioned event.
+    fn add_versioned_client_event<T: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        key: &'static str,
+        version: u16,
+    ) -> &mut Self;
+
+    /// Registers an additional wire version `V` for a versioned client event `T`.
+    ///
+    /// `upgrade` converts received events into `T` and `downgrade` converts `T` for sending
+    /// when this version is negotiated.
+    ///
+    /// Should be called after [`Self::add_versioned_client_event`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version is already registered.
+    fn add_client_event_version<T: Event, V: Serialize + DeserializeOwned>(
+        &mut self,
+        version: u16,
+        upgrade: fn(V) -> T,
+        downgrade: fn(&T) -> V,
+    ) -> &mut Self;
+
+    /// Same as [`ServerEventAppExt::add_server_event`](super::server_event::ServerEventAppExt::add_server_event),
+    /// but prefixes messages with the version negotiated with each client.
+    ///
+    /// `key` identifies the event during negotiation and `version` is the current version of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key or `T` is already registered as a versioned event.
+    fn add_versioned_server_event<T: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        key: &'static str,
+        version: u16,
+    ) -> &mut Self;
+
+    /// Registers an additional wire version `V` for a versioned server event `T`.
+    ///
+    /// Works like [`Self::add_client_event_version`].
+    ///
+    /// Should be called after [`Self::add_versioned_server_event`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version is already registered.
+    fn add_server_event_version<T: Event, V: Serialize + DeserializeOwned>(
+        &mut self,
+        version: u16,
+        upgrade: fn(V) -> T,
+        downgrade: fn(&T) -> V,
+    ) -> &mut Self;
+}
+
+impl VersionedEventAppExt for App {
+    fn add_versioned_client_event<T: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        key: &'static str,
+        version: u16,
+    ) -> &mut Self {
+        init_versioned_event::<T>(self, key, version);
+        client_event::add_keyed_client_event_with::<T, _, _>(
+            self,
+            key,
+            channel,
+            send_client::<T>,
+            receive_client::<T>,
+        )
+    }
+
+    fn add_client_event_version<T: Event, V: Serialize + DeserializeOwned>(
+        &mut self,
+        version: u16,
+        upgrade: fn(V) -> T,
+        downgrade: fn(&T) -> V,
+    ) -> &mut Self {
+        add_version(self, version, upgrade, downgrade);
+        self
+    }
+
+    fn add_versioned_server_event<T: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        key: &'static str,
+        version: u16,
+    ) -> &mut Self {
+        init_versioned_event::<T>(self, key, version);
+        server_event::add_keyed_server_event_with::<T, _, _>(
+            self,
+            key,
+            channel,
+            send_server::<T>,
+            receive_server::<T>,
+        )
+    }
+
+    fn add_server_event_version<T: Event, V: Serialize + DeserializeOwned>(
+        &mut self,
+        version: u16,
+        upgrade: fn(V) -> T,
+        downgrade: fn(&T) -> V,
+    ) -> &mut Self {
+        add_version(self, version, upgrade, downgrade);
+        self
+    }
+}
+
+/// Registers the current version of `T` under the key.
+fn init_versioned_event<T: Event + Serialize + DeserializeOwned>(
+    app: &mut App,
+    key: &'static str,
+    version: u16,
+) {
+    if !app.is_plugin_added::<VersioningPlugin>() {
+        app.add_plugins(VersioningPlugin);
+    }
+
+    assert!(
+        !app.world().contains_resource::<VersionedEvent<T>>(),
+        "`{}` should be registered as a versioned event only once",
+        any::type_name::<T>()
+    );
+    let mut versions = app.world_mut().resource_mut::<EventVersions>();
+    assert!(
+        !versions.supported.contains_key(key),
+        "key `{key}` is already used by another versioned event"
+    );
+    versions.supported.insert(key, vec![version]);
+
+    let mut versioned_event = VersionedEvent::<T>::new(key, version);
+    versioned_event.add(VersionFns {
+        version,
+        serialize: Box::new(|event, cursor| DefaultOptions::new().serialize_into(cursor, event)),
+        deserialize: Box::new(|cursor| DefaultOptions::new().deserialize_from(cursor)),
+    });
+    app.insert_resource(versioned_event);
+}
+
+/// Registers an additional version of an already registered `T`.
+fn add_version<T: Event, V: Serialize + DeserializeOwned>(
+    app: &mut App,
+    version: u16,
+    upgrade: fn(V) -> T,
+    downgrade: fn(&T) -> V,
+) {
+    let mut versioned_event = app
+        .world_mut()
+        .get_resource_mut::<VersionedEvent<T>>()
+        .expect("event should be registered as versioned");
+    versioned_event.add(VersionFns {
+        version,
+        serialize: Box::new(move |event, cursor| {
+            DefaultOptions::new().serialize_into(cursor, &(downgrade)(event))
+        }),
+        deserialize: Box::new(move |cursor| {
+            DefaultOptions::new().deserialize_from(cursor).map(upgrade)
+        }),
+    });
+    let key = versioned_event.key;
+
+    app.world_mut()
+        .resource_mut::<EventVersions>()
+        .supported
+        .get_mut(key)
+        .expect("versioned event should have supported versions")
+        .push(version);
+}
+
+/// Negotiates event versions on connection.
+///
+/// Added automatically on the first versioned event registration.
+struct VersioningPlugin;
+
+impl Plugin for VersioningPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            NegotiationChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<NegotiationChannels>().server = id,
+        );
+        KeyedChannels::register_client(
+            app.world_mut(),
+            NegotiationChannels::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.resource_mut::<NegotiationChannels>().client = id,
+        );
+
+        app.init_resource::<EventVersions>()
+            .init_resource::<NegotiationChannels>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_supported
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    receive_negotiated
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    reset_client.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    send_supported
+                        .in_set(ClientSet::Send)
+                        .run_if(client_just_connected),
+                    reset_server.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+/// Sends supported versions of each versioned event to the server.
+fn send_supported(
+    mut client: ResMut<RepliconClient>,
+    channels: Res<NegotiationChannels>,
+    versions: Res<EventVersions>,
+) {
+    let supported: Vec<_> = versions.supported.iter().collect();
+    let message = DefaultOptions::new()
+        .serialize(&supported)
+        .expect("versions should be serializable");
+    client.send(channels.client, message);
+}
+
+/// Picks the highest shared version of each event for clients that sent their supported versions
+/// and removes disconnected clients.
+fn receive_supported(
+    mut server_events: EventReader<ServerEvent>,
+    mut server: ResMut<RepliconServer>,
+    mut versions: ResMut<EventVersions>,
+    channels: Res<NegotiationChannels>,
+    names: Res<ClientNames>,
+) {
+    let messages: Vec<_> = server.receive(channels.client).collect();
+    for (client_id, message) in messages {
+        let client_supported: Vec<(String, Vec<u16>)> =
+            match DefaultOptions::new().deserialize(&message) {
+                Ok(supported) => supported,
+                Err(e) => {
+                    debug!(
+                        "unable to deserialize event versions from {}: {e}",
+                        names.label(client_id)
+                    );
+                    continue;
+                }
+            };
+
+        let mut negotiated = HashMap::default();
+        for (key, client_versions) in client_supported {
+            let Some((&key, server_versions)) = versions.supported.get_key_value(&*key) else {
+                debug!(
+                    "{} sent versions for unknown event `{key}`",
+                    names.label(client_id)
+                );
+                continue;
+            };
+
+            match server_versions
+                .iter()
+                .filter(|version| client_versions.contains(version))
+                .max()
+            {
+                Some(&version) => {
+                    negotiated.insert(key, version);
+                }
+                None => warn!(
+                    "{} has no shared versions for event `{key}`: server supports {server_versions:?}, client supports {client_versions:?}",
+                    names.label(client_id)
+                ),
+            }
+        }
+
+        debug!(
+            "negotiated event versions {negotiated:?} with {}",
+            names.label(client_id)
+        );
+        let message = DefaultOptions::new()
+            .serialize(&negotiated)
+            .expect("versions should be serializable");
+        server.send(client_id, channels.server, message);
+        versions.clients.insert(client_id, negotiated);
+    }
+
+    // Process after receiving to avoid re-adding versions from messages of disconnected clients.
+    for event in server_events.read() {
+        if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+            versions.clients.remove(&client_id);
+        }
+    }
+}
+
+/// Receives negotiated versions from the server.
+fn receive_negotiated(
+    mut client: ResMut<RepliconClient>,
+    mut versions: ResMut<EventVersions>,
+    channels: Res<NegotiationChannels>,
+) {
+    for message in client.receive(channels.server) {
+        let negotiated: HashMap<String, u16> = match DefaultOptions::new().deserialize(&message) {
+            Ok(negotiated) => negotiated,
+            Err(e) => {
+                error!("unable to deserialize negotiated event versions from server: {e}");
+                continue;
+            }
+        };
+
+        debug!("negotiated event versions {negotiated:?} with server");
+        versions.server.clear();
+        for (key, version) in negotiated {
+            let Some((&key, _)) = versions.supported.get_key_value(&*key) else {
+                error!("server negotiated version {version} for unknown event `{key}`");
+                continue;
+            };
+            versions.server.insert(key, version);
+        }
+    }
+}
+
+fn reset_client(mut versions: ResMut<EventVersions>) {
+    versions.server.clear();
+}
+
+fn reset_server(mut versions: ResMut<EventVersions>) {
+    versions.clients.clear();
+}
+
+fn send_client<T: Event>(
+    mut events: EventReader<T>,
+    mut client: ResMut<RepliconClient>,
+    channel: Res<ClientEventChannel<T>>,
+    versioned_event: Res<VersionedEvent<T>>,
+    versions: Res<EventVersions>,
+) {
+    let version = versions
+        .server(versioned_event.key)
+        .unwrap_or(versioned_event.current);
+    let version_fns = versioned_event
+        .get(version)
+        .expect("negotiated version should be registered");
+
+    for event in events.read() {
+        let mut cursor = Cursor::default();
+        version_fns
+            .serialize_with_version(event, &mut cursor)
+            .expect("client event should be serializable");
+
+        trace!(
+            "sending event `{}` with version {version}",
+            any::type_name::<T>()
+        );
+        client.send(*channel, cursor.into_inner());
+    }
+}
+
+fn receive_client<T: Event>(
+    mut client_events: EventWriter<FromClient<T>>,
+    mut server: ResMut<RepliconServer>,
+    channel: Res<ClientEventChannel<T>>,
+    versioned_event: Res<VersionedEvent<T>>,
+    names: Res<ClientNames>,
+    ticks: FromClientTicks,
+) {
+    for (client_id, message) in server.receive(*channel) {
+        let mut cursor = Cursor::new(&*message);
+        match versioned_event.deserialize(&mut cursor) {
+            Ok(event) => {
+                trace!(
+                    "applying versioned event `{}` from {}",
+                    any::type_name::<T>(),
+                    names.label(client_id)
+                );
+                client_events.send(ticks.from_client(client_id, event));
+            }
+            Err(e) => debug!(
+                "unable to deserialize versioned event from {}: {e}",
+                names.label(client_id)
+            ),
+        }
+    }
+}
+
+/// Sends each event to clients in the version negotiated with them.
+fn send_server<T: Event>(
+    mut server: ResMut<RepliconServer>,
+    mut server_events: EventReader<ToClients<T>>,
+    connected_clients: Res<ConnectedClients>,
+    channel: Res<ServerEventChannel<T>>,
+    versioned_event: Res<VersionedEvent<T>>,
+    versions: Res<EventVersions>,
+) {
+    for ToClients {
+        event,
+        mode,
+        apply_tick,
+    } in server_events.read()
+    {
+        trace!("sending event `{}` with `{mode:?}`", any::type_name::<T>());
+        for version_fns in &versioned_event.versions {
+            server_event::send_filtered(
+                &mut server,
+                &connected_clients,
+                *channel,
+                mode,
+                *apply_tick,
+                |client_id| {
+                    let version = versions
+                        .client(client_id, versioned_event.key)
+                        .unwrap_or(versioned_event.current);
+                    version == version_fns.version
+                },
+                |cursor| version_fns.serialize_with_version(event, cursor),
+            )
+            .expect("server event should be serializable");
+        }
+    }
+}
+
+fn receive_server<T: Event>(
+    mut server_events: EventWriter<T>,
+    mut client: ResMut<RepliconClient>,
+    mut event_queue: ResMut<ServerEventQueue<T>>,
+    init_tick: Res<ServerInitTick>,
+    update_tick: Res<ServerUpdateTick>,
+    channel: Res<ServerEventChannel<T>>,
+    versioned_event: Res<VersionedEvent<T>>,
+) {
+    for message in client.receive(*channel) {
+        let (tick, event) = match server_event::deserialize_with(&message, |cursor| {
+            versioned_event.deserialize(cursor)
+        }) {
+            Ok(result) => result,
+            Err(e) => {
+                error!("unable to deserialize versioned event from server: {e}");
+                continue;
+            }
+        };
+
+        if tick.is_reached(*init_tick, *update_tick) {
+            trace!(
+                "applying versioned event `{}` with `{tick:?}`",
+                any::type_name::<T>()
+            );
+            server_events.send(event);
+        } else {
+            trace!(
+                "queuing versioned event `{}` with `{tick:?}`",
+                any::type_name::<T>()
+            );
+            event_queue.insert(tick, event);
+        }
+    }
+}
+
+/// Supported and negotiated versions of versioned events.
+///
+/// See [`VersionedEventAppExt`].
+#[derive(Resource, Default)]
+pub struct EventVersions {
+    /// Supported versions by event keys.
+    supported: HashMap<&'static str, Vec<u16>>,
+
+    /// Versions negotiated with the server by event keys on client.
+    server: HashMap<&'static str, u16>,
+
+    /// Versions negotiated with each client by event keys on server.
+    clients: HashMap<ClientId, HashMap<&'static str, u16>>,
+}
+
+impl EventVersions {
+    /// Returns the version negotiated with the server for an event key.
+    ///
+    /// Used on client.
+    pub fn server(&self, key: &str) -> Option<u16> {
+        self.server.get(key).copied()
+    }
+
+    /// Returns the version negotiated with a client for an event key.
+    ///
+    /// Used on server.
+    pub fn client(&self, client_id: ClientId, key: &str) -> Option<u16> {
+        self.clients
+            .get(&client_id)
+            .and_then(|versions| versions.get(key))
+            .copied()
+    }
+}
+
+/// Channels for version negotiation.
+#[derive(Resource, Default, TypePath)]
+struct NegotiationChannels {
+    client: u8,
+    server: u8,
+}
+
+/// Registered versions of `T`.
+#[derive(Resource)]
+struct VersionedEvent<T> {
+    key: &'static str,
+    current: u16,
+    versions: Vec<VersionFns<T>>,
+}
+
+impl<T> VersionedEvent<T> {
+    fn new(key: &'static str, current: u16) -> Self {
+        Self {
+            key,
+            current,
+            versions: Default::default(),
+        }
+    }
+
+    fn add(&mut self, version_fns: VersionFns<T>) {
+        assert!(
+            self.get(version_fns.version).is_none(),
+            "version {} of `{}` should be registered only once",
+            version_fns.version,
+            any::type_name::<T>(),
+        );
+        self.versions.push(version_fns);
+    }
+
+    fn get(&self, version: u16) -> Option<&VersionFns<T>> {
+        self.versions
+            .iter()
+            .find(|version_fns| version_fns.version == version)
+    }
+
+    /// Reads the version prefix and deserializes the event with the matching conversion.
+    fn deserialize(&self, cursor: &mut Cursor<&[u8]>) -> bincode::Result<T> {
+        let version: u16 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+        let version_fns = self.get(version).ok_or_else(|| {
+            bincode::ErrorKind::Custom(format!("version {version} is not registered"))
+        })?;
+        (version_fns.deserialize)(cursor)
+    }
+}
+
+/// Conversion functions for a single version of `T`.
+struct VersionFns<T> {
+    version: u16,
+    serialize: Box<dyn Fn(&T, &mut Cursor<Vec<u8>>) -> bincode::Result<()> + Send + Sync>,
+    deserialize: Box<dyn Fn(&mut Cursor<&[u8]>) -> bincode::Result<T> + Send + Sync>,
+}
+
+impl<T> VersionFns<T> {
+    /// Serializes the event prefixed with this version.
+    fn serialize_with_version(
+        &self,
+        event: &T,
+        cursor: &mut Cursor<Vec<u8>>,
+    ) -> bincode::Result<()> {
+        DefaultOptions::new().serialize_into(&mut *cursor, &self.version)?;
+        (self.serialize)(event, cursor)
+    }
+}
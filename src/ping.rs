@@ -0,0 +1,439 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_real_timer, utils::HashMap};
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_just_stopped, server_running},
+        replicon_channels::{ChannelKind, KeyedChannels},
+        ClientId,
+    },
+    server::{
+        client_names::{ClientLabel, ClientNames},
+        connected_clients::ConnectedClients,
+        replicon_server::RepliconServer,
+        ServerEvent, ServerSet,
+    },
+};
+
+/**
+Measures latency between the server and clients over a dedicated unreliable channel.
+
+Both sides periodically send pings with their local time, which are echoed back by the other side.
+Results are smoothed and available via [`NetworkStats`] on client and [`ClientNetworkStats`] on server.
+Since measurement doesn't rely on the messaging backend, all backends get uniform latency data.
+
+Pings are timestamped with [`PingClock`], which can be backed by a custom time source via [`Self::clock`].
+The clock never goes backward and detects coarse timers, such as reduced precision of
+`performance.now()` in browsers, to avoid zero samples when the latency is below the timer resolution.
+
+Long pauses between frames, such as throttled background tabs in browsers, are detected
+using [`Self::pause_threshold`]. After such pause smoothing restarts and pings
+that were in flight during the pause are discarded, so stale samples don't inflate the estimate.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, PingPlugin::default()))
+    .add_systems(Update, show_ping.run_if(client_connected));
+
+fn show_ping(stats: Res<NetworkStats>) {
+    info!("ping: {:?}", stats.rtt);
+}
+```
+**/
+pub struct PingPlugin {
+    /// Time between pings.
+    pub interval: Duration,
+
+    /// Minimum time between frames that is considered a pause.
+    ///
+    /// Browsers throttle timers of background tabs and desktop apps could be suspended,
+    /// so pings sent before the pause would produce samples unrelated to the actual latency.
+    pub pause_threshold: Duration,
+
+    /// Source of local time for [`PingClock`].
+    ///
+    /// By default uses [`Time<Real>`], which is backed by `performance.now()` on WASM.
+    pub clock: ClockFn,
+}
+
+impl Default for PingPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            pause_threshold: Duration::from_secs(1),
+            clock: Time::<Real>::elapsed,
+        }
+    }
+}
+
+impl Plugin for PingPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            PingChannels::type_path(),
+            ChannelKind::Unreliable.into(),
+            |world, id| world.resource_mut::<PingChannels>().server = id,
+        );
+        KeyedChannels::register_client(
+            app.world_mut(),
+            PingChannels::type_path(),
+            ChannelKind::Unreliable.into(),
+            |world, id| world.resource_mut::<PingChannels>().client = id,
+        );
+
+        app.init_resource::<NetworkStats>()
+            .init_resource::<ClientNetworkStats>()
+            .init_resource::<PingChannels>()
+            .insert_resource(PingClock::new(self.clock, self.pause_threshold))
+            .add_systems(
+                PreUpdate,
+                (
+                    PingClock::update
+                        .before(ServerSet::Receive)
+                        .before(ClientSet::Receive),
+                    Self::server_receive
+                        .in_set(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::client_receive
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::client_reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::server_send
+                        .in_set(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(on_real_timer(self.interval)),
+                    Self::client_send
+                        .in_set(ClientSet::Send)
+                        .run_if(client_connected)
+                        .run_if(on_real_timer(self.interval)),
+                    Self::server_reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+impl PingPlugin {
+    fn server_receive(
+        mut server_events: EventReader<ServerEvent>,
+        mut server: ResMut<RepliconServer>,
+        mut client_stats: ResMut<ClientNetworkStats>,
+        clock: Res<PingClock>,
+        channels: Res<PingChannels>,
+        names: Res<ClientNames>,
+    ) {
+        if clock.is_resumed() {
+            debug!("restarting latency measurement after a pause");
+            client_stats.0.clear();
+        }
+
+        let messages: Vec<_> = server.receive(channels.client).collect();
+        for (client_id, message) in messages {
+            match DefaultOptions::new().deserialize(&message) {
+                Ok(PingMessage::Ping(sent_at)) => {
+                    // Echoing pings that waited during the pause would report it as latency to the client.
+                    if !clock.is_resumed() {
+                        server.send(
+                            client_id,
+                            channels.server,
+                            serialize(PingMessage::Pong(sent_at)),
+                        );
+                    }
+                }
+                Ok(PingMessage::Pong(sent_at)) => {
+                    if !clock.is_stale(sent_at) {
+                        client_stats
+                            .0
+                            .entry(client_id)
+                            .or_default()
+                            .add_sample(clock.elapsed_since(sent_at))
+                    }
+                }
+                Err(e) => debug!(
+                    "unable to deserialize ping from {}: {e}",
+                    names.label(client_id)
+                ),
+            }
+        }
+
+        // Process after receiving to avoid re-adding stats from messages of disconnected clients.
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = event {
+                client_stats.0.remove(client_id);
+            }
+        }
+    }
+
+    fn server_send(
+        mut server: ResMut<RepliconServer>,
+        connected_clients: Res<ConnectedClients>,
+        clock: Res<PingClock>,
+        channels: Res<PingChannels>,
+    ) {
+        let message = serialize(PingMessage::Ping(clock.now()));
+        for client_id in connected_clients.iter_client_ids() {
+            server.send(client_id, channels.server, message.clone());
+        }
+    }
+
+    fn client_receive(
+        mut client: ResMut<RepliconClient>,
+        mut stats: ResMut<NetworkStats>,
+        clock: Res<PingClock>,
+        channels: Res<PingChannels>,
+    ) {
+        if clock.is_resumed() {
+            debug!("restarting latency measurement after a pause");
+            *stats = Default::default();
+        }
+
+        let messages: Vec<_> = client.receive(channels.server).collect();
+        for message in messages {
+            match DefaultOptions::new().deserialize(&message) {
+                Ok(PingMessage::Ping(sent_at)) => {
+                    if !clock.is_resumed() {
+                        client.send(channels.client, serialize(PingMessage::Pong(sent_at)));
+                    }
+                }
+                Ok(PingMessage::Pong(sent_at)) => {
+                    if !clock.is_stale(sent_at) {
+                        stats.add_sample(clock.elapsed_since(sent_at))
+                    }
+                }
+                Err(e) => debug!("unable to deserialize ping from server: {e}"),
+            }
+        }
+    }
+
+    fn client_send(
+        mut client: ResMut<RepliconClient>,
+        clock: Res<PingClock>,
+        channels: Res<PingChannels>,
+    ) {
+        client.send(channels.client, serialize(PingMessage::Ping(clock.now())));
+    }
+
+    fn server_reset(mut client_stats: ResMut<ClientNetworkStats>) {
+        client_stats.0.clear();
+    }
+
+    fn client_reset(mut stats: ResMut<NetworkStats>) {
+        *stats = Default::default();
+    }
+}
+
+fn serialize(message: PingMessage) -> Vec<u8> {
+    DefaultOptions::new()
+        .serialize(&message)
+        .expect("ping message should be serializable")
+}
+
+/// Latency measured by [`PingPlugin`].
+///
+/// Available as a resource on client and per-client on server via [`ClientNetworkStats`].
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetworkStats {
+    /// Smoothed round-trip time.
+    pub rtt: Duration,
+
+    /// Smoothed deviation of round-trip time samples.
+    pub jitter: Duration,
+
+    /// Number of received samples.
+    ///
+    /// Other values are meaningless until at least one sample is received.
+    pub samples: usize,
+}
+
+impl NetworkStats {
+    /// Returns an estimate for the time it takes for a message to reach the other side.
+    ///
+    /// Since clocks aren't synchronized, assumes symmetric latency.
+    pub fn one_way_delay(&self) -> Duration {
+        self.rtt / 2
+    }
+
+    /// Updates smoothed values with a new round-trip time sample.
+    ///
+    /// Uses the same weights as TCP.
+    fn add_sample(&mut self, rtt: Duration) {
+        if self.samples == 0 {
+            self.rtt = rtt;
+            self.jitter = rtt / 2;
+        } else {
+            let deviation = self.rtt.abs_diff(rtt);
+            self.jitter = (self.jitter * 3 + deviation) / 4;
+            self.rtt = (self.rtt * 7 + rtt) / 8;
+        }
+        self.samples += 1;
+    }
+}
+
+/// Latency for each connected client on server measured by [`PingPlugin`].
+#[derive(Resource, Default)]
+pub struct ClientNetworkStats(HashMap<ClientId, NetworkStats>);
+
+impl ClientNetworkStats {
+    /// Returns stats for a client if at least one sample was received from it.
+    pub fn get(&self, client_id: ClientId) -> Option<&NetworkStats> {
+        self.0.get(&client_id)
+    }
+
+    /// Returns an iterator over clients with their stats.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &NetworkStats)> + '_ {
+        self.0.iter().map(|(&client_id, stats)| (client_id, stats))
+    }
+
+    /// Returns an iterator over client labels from [`ClientNames`] with their stats.
+    ///
+    /// Useful to display stats with client names instead of raw [`ClientId`]s.
+    pub fn iter_labeled<'a>(
+        &'a self,
+        names: &'a ClientNames,
+    ) -> impl Iterator<Item = (ClientLabel<'a>, &'a NetworkStats)> + 'a {
+        self.0
+            .iter()
+            .map(|(&client_id, stats)| (names.label(client_id), stats))
+    }
+}
+
+/// Local time of the sender.
+#[derive(Deserialize, Serialize)]
+enum PingMessage {
+    Ping(Duration),
+    Pong(Duration),
+}
+
+/// Channel IDs, assigned when plugins are finished.
+#[derive(Resource, Clone, Copy, Default, TypePath)]
+struct PingChannels {
+    server: u8,
+    client: u8,
+}
+
+/// Returns the current local time from the given real time.
+///
+/// See [`PingPlugin::clock`].
+pub type ClockFn = fn(&Time<Real>) -> Duration;
+
+/// Local clock used by [`PingPlugin`] to timestamp pings.
+///
+/// Updated once per frame before receiving messages.
+#[derive(Resource)]
+pub struct PingClock {
+    source: ClockFn,
+
+    /// See [`PingPlugin::pause_threshold`].
+    pause_threshold: Duration,
+
+    /// Current local time.
+    ///
+    /// Never decreases, even if the source does.
+    now: Duration,
+
+    /// Set after the first update, when steps between frames become meaningful.
+    started: bool,
+
+    /// Estimated timer resolution.
+    ///
+    /// Zero if the timer is precise enough to advance on every frame.
+    resolution: Duration,
+
+    /// Set if the timer didn't advance on at least one frame.
+    coarse: bool,
+
+    /// Local time of the first frame after the last pause.
+    resumed_at: Option<Duration>,
+
+    /// Set if the current frame is the first one after a pause.
+    resumed: bool,
+}
+
+impl PingClock {
+    fn new(source: ClockFn, pause_threshold: Duration) -> Self {
+        Self {
+            source,
+            pause_threshold,
+            now: Duration::ZERO,
+            started: false,
+            resolution: Duration::ZERO,
+            coarse: false,
+            resumed_at: None,
+            resumed: false,
+        }
+    }
+
+    /// Returns the current local time.
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Returns the estimated timer resolution.
+    ///
+    /// Coarse timers are detected when time doesn't advance between frames.
+    /// In this case the smallest observed step is used as the resolution.
+    /// Returns [`Duration::ZERO`] for precise timers.
+    pub fn resolution(&self) -> Duration {
+        self.resolution
+    }
+
+    fn update(mut clock: ResMut<Self>, time: Res<Time<Real>>) {
+        let now = (clock.source)(&time).max(clock.now);
+        let step = now - clock.now;
+        clock.now = now;
+
+        if !clock.started {
+            clock.started = true;
+        } else if step.is_zero() {
+            clock.coarse = true;
+        } else if clock.coarse && (clock.resolution.is_zero() || step < clock.resolution) {
+            clock.resolution = step;
+        }
+
+        // Check frame delta instead of the clock step since a coarse clock may advance in steps
+        // that exceed the threshold.
+        clock.resumed = time.delta() >= clock.pause_threshold;
+        if clock.resumed {
+            clock.resumed_at = Some(now);
+        }
+    }
+
+    /// Returns `true` if the current frame is the first one after a pause.
+    fn is_resumed(&self) -> bool {
+        self.resumed
+    }
+
+    /// Returns `true` if the ping with this local time was sent before the last pause.
+    fn is_stale(&self, sent_at: Duration) -> bool {
+        self.resumed_at
+            .is_some_and(|resumed_at| sent_at < resumed_at)
+    }
+
+    /// Returns a round-trip time sample for a ping sent at the given local time.
+    ///
+    /// With a coarse timer a ping could return before the time advances.
+    /// Since the actual value is somewhere below the resolution, half of it is used.
+    fn elapsed_since(&self, sent_at: Duration) -> Duration {
+        let elapsed = self.now.saturating_sub(sent_at);
+        if elapsed.is_zero() {
+            self.resolution / 2
+        } else {
+            elapsed
+        }
+    }
+}
@@ -0,0 +1,104 @@
+/**
+Declares a plugin that registers replicated components and network events.
+
+Intended to be placed in a crate shared between separately-built server and client binaries,
+so both register identical protocols from a single description.
+
+Each section is optional, but sections should be declared in the order shown below.
+Channels are specified with the side that sends them and created in the order of declaration,
+so their IDs match on server and client if no other plugin creates channels before the protocol,
+see [`RepliconChannels::create_server_channel`](crate::core::replicon_channels::RepliconChannels::create_server_channel).
+Events are specified with their channels.
+Mapped events use [`ClientEventAppExt::add_mapped_client_event`](crate::network_event::client_event::ClientEventAppExt::add_mapped_client_event)
+and [`ServerEventAppExt::add_mapped_server_event`](crate::network_event::server_event::ServerEventAppExt::add_mapped_server_event).
+
+The plugin should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::{prelude::*, replicon_protocol};
+use serde::{Deserialize, Serialize};
+
+replicon_protocol! {
+    /// Protocol shared between server and client.
+    pub struct GameProtocol;
+    channels: [server => ChannelKind::Unreliable, client => ChannelKind::Ordered],
+    components: [Player, Health],
+    client_events: [MoveInput => ChannelKind::Unreliable],
+    mapped_client_events: [Attack => ChannelKind::Ordered],
+    server_events: [Chat => ChannelKind::Ordered],
+}
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, GameProtocol));
+
+#[derive(Component, Deserialize, Serialize)]
+struct Player;
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct MoveInput(Vec2);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Attack(Entity);
+
+impl MapEntities for Attack {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Chat(String);
+```
+**/
+#[macro_export]
+macro_rules! replicon_protocol {
+    (@channel $app:ident, server, $channel:expr) => {
+        $app.world_mut()
+            .resource_mut::<$crate::core::replicon_channels::RepliconChannels>()
+            .create_server_channel($channel.into());
+    };
+    (@channel $app:ident, client, $channel:expr) => {
+        $app.world_mut()
+            .resource_mut::<$crate::core::replicon_channels::RepliconChannels>()
+            .create_client_channel($channel.into());
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident;
+        $(channels: [$($channel_side:ident => $channel:expr),* $(,)?] $(,)?)?
+        $(components: [$($component:ty),* $(,)?] $(,)?)?
+        $(client_events: [$($client_event:ty => $client_channel:expr),* $(,)?] $(,)?)?
+        $(mapped_client_events: [$($mapped_client_event:ty => $mapped_client_channel:expr),* $(,)?] $(,)?)?
+        $(server_events: [$($server_event:ty => $server_channel:expr),* $(,)?] $(,)?)?
+        $(mapped_server_events: [$($mapped_server_event:ty => $mapped_server_channel:expr),* $(,)?] $(,)?)?
+    ) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        impl $crate::bevy::app::Plugin for $name {
+            #[allow(unused_variables)]
+            fn build(&self, app: &mut $crate::bevy::app::App) {
+                #[allow(unused_imports)]
+                use $crate::{
+                    core::replication_rules::AppRuleExt,
+                    network_event::{
+                        client_event::ClientEventAppExt, server_event::ServerEventAppExt,
+                    },
+                };
+
+                $($($crate::replicon_protocol!(@channel app, $channel_side, $channel);)*)?
+                $($(app.replicate::<$component>();)*)?
+                $($(app.add_client_event::<$client_event>($client_channel);)*)?
+                $($(app.add_mapped_client_event::<$mapped_client_event>($mapped_client_channel);)*)?
+                $($(app.add_server_event::<$server_event>($server_channel);)*)?
+                $($(app.add_mapped_server_event::<$mapped_server_event>($mapped_server_channel);)*)?
+            }
+        }
+    };
+}
@@ -1,13 +1,24 @@
 pub mod client_entity_map;
+pub mod client_names;
 pub mod connected_clients;
 pub(super) mod despawn_buffer;
+pub mod despawn_tombstones;
+pub mod flow_trace;
+pub(super) mod previous_values;
 pub(super) mod removal_buffer;
 pub(super) mod replicated_archetypes;
+pub mod replicated_clone;
 pub(super) mod replication_messages;
+pub mod replication_pause;
 pub mod replicon_server;
+pub(super) mod rule_conditions;
 pub mod server_tick;
+pub(super) mod tick_timestamps;
+pub mod virtual_clients;
+pub mod visibility_conditions;
+pub mod visibility_scheduler;
 
-use std::{io::Cursor, mem, time::Duration};
+use std::{mem, time::Duration};
 
 use bevy::{
     ecs::{
@@ -20,25 +31,38 @@ use bevy::{
     ptr::Ptr,
     time::common_conditions::on_timer,
 };
+use bytes::Bytes;
 
 use crate::core::{
     common_conditions::{server_just_stopped, server_running},
+    replay_log::ReplayLog,
     replication_fns::{ctx::SerializeCtx, ReplicationFns},
     replication_rules::ReplicationRules,
+    replication_sections::ReplicationSections,
     replicon_channels::{ReplicationChannel, RepliconChannels},
     replicon_tick::RepliconTick,
-    ClientId,
+    update_acks, ClientId, Owner,
 };
 use client_entity_map::ClientEntityMap;
+use client_names::ClientNames;
 use connected_clients::{
     client_visibility::Visibility, ClientBuffers, ConnectedClient, ConnectedClients,
 };
 use despawn_buffer::{DespawnBuffer, DespawnBufferPlugin};
+use despawn_tombstones::DespawnTombstones;
+use flow_trace::FlowTracePlugin;
+use previous_values::PreviousValues;
 use removal_buffer::{RemovalBuffer, RemovalBufferPlugin};
 use replicated_archetypes::ReplicatedArchetypes;
 use replication_messages::ReplicationMessages;
+use replication_pause::{replication_paused, ReplicationPause, ResumeMode};
 use replicon_server::RepliconServer;
+use rule_conditions::RuleConditions;
 use server_tick::ServerTick;
+use tick_timestamps::TickTimestamps;
+use virtual_clients::VirtualClientsPlugin;
+use visibility_conditions::VisibilityConditionsPlugin;
+use visibility_scheduler::VisibilitySchedulerPlugin;
 
 pub struct ServerPlugin {
     /// Tick configuration.
@@ -47,10 +71,29 @@ pub struct ServerPlugin {
     /// Visibility configuration.
     pub visibility_policy: VisibilityPolicy,
 
+    /// Configures how entities are sent to clients after regaining visibility.
+    pub reveal_policy: RevealPolicy,
+
     /// The time after which updates will be considered lost if an acknowledgment is not received for them.
     ///
     /// In practice updates will live at least `update_timeout`, and at most `2*update_timeout`.
     pub update_timeout: Duration,
+
+    /// Number of ticks for which despawned entities are kept in [`DespawnTombstones`].
+    pub despawn_retention: u32,
+
+    /// Number of groups into which clients are split to receive updates on different ticks.
+    ///
+    /// Serializing updates for all clients on the same tick may cause CPU spikes when many clients are connected.
+    /// With multiple groups, connected clients are assigned to the least populated group and each group receives
+    /// updates only on every N-th tick, so the work is spread across ticks.
+    /// Skipped changes are sent on the next tick of the group.
+    ///
+    /// Only updates are staggered, insertions, removals and despawns are sent to all clients every tick.
+    /// If an entity gets an insertion, its changes are included to keep the entity state consistent.
+    ///
+    /// Values 0 and 1 send updates to all clients every tick.
+    pub send_groups: u8,
 }
 
 impl Default for ServerPlugin {
@@ -58,7 +101,10 @@ impl Default for ServerPlugin {
         Self {
             tick_policy: TickPolicy::MaxTickRate(30),
             visibility_policy: Default::default(),
+            reveal_policy: Default::default(),
             update_timeout: Duration::from_secs(10),
+            despawn_retention: 64,
+            send_groups: 1,
         }
     }
 }
@@ -66,11 +112,23 @@ impl Default for ServerPlugin {
 impl Plugin for ServerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin))
+            .add_plugins(VisibilityConditionsPlugin)
+            .add_plugins(VisibilitySchedulerPlugin)
+            .add_plugins(FlowTracePlugin)
+            .add_plugins(VirtualClientsPlugin)
             .init_resource::<RepliconServer>()
             .init_resource::<ServerTick>()
             .init_resource::<ClientBuffers>()
             .init_resource::<ClientEntityMap>()
-            .insert_resource(ConnectedClients::new(self.visibility_policy))
+            .init_resource::<ClientNames>()
+            .init_resource::<PreviousValues>()
+            .init_resource::<ReplicationPause>()
+            .insert_resource(DespawnTombstones::new(self.despawn_retention))
+            .insert_resource(ConnectedClients::new(
+                self.visibility_policy,
+                self.reveal_policy,
+                self.send_groups,
+            ))
             .add_event::<ServerEvent>()
             .configure_sets(
                 PreUpdate,
@@ -109,6 +167,7 @@ impl Plugin for ServerPlugin {
                         .map(Result::unwrap)
                         .in_set(ServerSet::Send)
                         .run_if(server_running)
+                        .run_if(not(replication_paused))
                         .run_if(resource_changed::<ServerTick>),
                     Self::reset.run_if(server_just_stopped),
                 ),
@@ -149,21 +208,25 @@ impl ServerPlugin {
         trace!("incremented {server_tick:?}");
     }
 
-    fn handle_connections(
+    pub(super) fn handle_connections(
         mut server_events: EventReader<ServerEvent>,
         mut entity_map: ResMut<ClientEntityMap>,
         mut connected_clients: ResMut<ConnectedClients>,
         mut server: ResMut<RepliconServer>,
         mut client_buffers: ResMut<ClientBuffers>,
+        mut names: ResMut<ClientNames>,
     ) {
         for event in server_events.read() {
             match *event {
                 ServerEvent::ClientDisconnected { client_id, .. } => {
+                    debug!("removing disconnected {}", names.label(client_id));
                     entity_map.0.remove(&client_id);
                     connected_clients.remove(&mut client_buffers, client_id);
                     server.remove_client(client_id);
+                    names.remove(client_id);
                 }
                 ServerEvent::ClientConnected { client_id } => {
+                    debug!("adding connected {}", names.label(client_id));
                     connected_clients.add(&mut client_buffers, client_id);
                 }
             }
@@ -188,22 +251,28 @@ impl ServerPlugin {
         mut server: ResMut<RepliconServer>,
         mut connected_clients: ResMut<ConnectedClients>,
         mut client_buffers: ResMut<ClientBuffers>,
+        names: Res<ClientNames>,
     ) {
         for (client_id, message) in server.receive(ReplicationChannel::Init) {
-            let mut cursor = Cursor::new(&*message);
-            let message_end = message.len() as u64;
-            while cursor.position() < message_end {
-                match bincode::deserialize_from(&mut cursor) {
-                    Ok(update_index) => {
-                        let client = connected_clients.client_mut(client_id);
-                        client.acknowledge(
-                            &mut client_buffers,
-                            change_tick.this_run(),
-                            update_index,
-                        );
-                    }
-                    Err(e) => debug!("unable to deserialize update index from {client_id:?}: {e}"),
+            let update_indices = match update_acks::deserialize_acks(&message) {
+                Ok(update_indices) => update_indices,
+                Err(e) => {
+                    debug!(
+                        "unable to deserialize update indices from {}: {e}",
+                        names.label(client_id)
+                    );
+                    continue;
                 }
+            };
+
+            let client = connected_clients.client_mut(client_id);
+            for update_index in update_indices {
+                client.acknowledge(
+                    &mut client_buffers,
+                    change_tick.this_run(),
+                    update_index,
+                    names.label(client_id),
+                );
             }
         }
     }
@@ -212,15 +281,28 @@ impl ServerPlugin {
     pub(super) fn send_replication(
         mut messages: Local<ReplicationMessages>,
         mut replicated_archetypes: Local<ReplicatedArchetypes>,
+        mut replay_buffer: Local<ReplayLog>,
+        mut tick_timestamps: Local<TickTimestamps>,
+        mut rule_conditions: Local<RuleConditions>,
+        mut section_buffer: Local<Vec<u8>>,
         change_tick: SystemChangeTick,
         mut set: ParamSet<(
             &World,
             ResMut<ConnectedClients>,
             ResMut<ClientEntityMap>,
-            ResMut<DespawnBuffer>,
+            (
+                ResMut<DespawnBuffer>,
+                ResMut<DespawnTombstones>,
+                ResMut<PreviousValues>,
+            ),
             ResMut<RemovalBuffer>,
             ResMut<ClientBuffers>,
             ResMut<RepliconServer>,
+            (
+                ResMut<ReplicationPause>,
+                Option<ResMut<ReplayLog>>,
+                ResMut<ClientNames>,
+            ),
         )>,
         replication_fns: Res<ReplicationFns>,
         rules: Res<ReplicationRules>,
@@ -229,25 +311,91 @@ impl ServerPlugin {
     ) -> bincode::Result<()> {
         replicated_archetypes.update(set.p0(), &rules);
 
+        let resume_mode = set.p7().0.take_resume_mode();
         let connected_clients = mem::take(&mut *set.p1()); // Take ownership to avoid borrowing issues.
+        let mut previous_values = mem::take(&mut *set.p3().2);
+        let names = mem::take(&mut *set.p7().2);
+        let send_groups = connected_clients.send_groups();
         messages.prepare(connected_clients);
 
+        for (_, _, client) in messages.iter_mut_with_clients() {
+            client.priority_mut().start_tick(time.elapsed());
+        }
+
+        if resume_mode == Some(ResumeMode::Discard) {
+            for (_, _, client) in messages.iter_mut_with_clients() {
+                client.set_all_change_limits(change_tick.this_run());
+            }
+        }
+
+        let reset = set.p6().take_reset_request();
+        if reset {
+            send_resets(&mut messages, &mut set.p6(), &names, **server_tick)?;
+        }
+
         collect_mappings(&mut messages, &mut set.p2())?;
-        collect_despawns(&mut messages, &mut set.p3())?;
-        collect_removals(&mut messages, &mut set.p4(), change_tick.this_run())?;
+        let (mut despawn_buffer, mut tombstones, _) = set.p3();
+        collect_despawns(
+            &mut messages,
+            &mut despawn_buffer,
+            &mut tombstones,
+            &mut previous_values,
+            &mut rule_conditions,
+            **server_tick,
+        )?;
+        rule_conditions.update(
+            set.p0(),
+            &replicated_archetypes,
+            &replication_fns,
+            &change_tick,
+        );
+        for (entity, fns_info) in rule_conditions.removed() {
+            set.p4().insert(entity, fns_info);
+        }
+        collect_removals(
+            &mut messages,
+            &mut set.p4(),
+            &mut previous_values,
+            &replication_fns,
+            change_tick.this_run(),
+        )?;
+        match replication_fns.max_mutation_timeout() {
+            Some(max_timeout) => {
+                tick_timestamps.record(change_tick.this_run(), time.elapsed(), max_timeout)
+            }
+            None => tick_timestamps.clear(),
+        }
+        let record_replay = set.p0().contains_resource::<ReplayLog>();
         collect_changes(
             &mut messages,
             &replicated_archetypes,
             &replication_fns,
             set.p0(),
             &change_tick,
+            &tick_timestamps,
+            &mut previous_values,
+            &rule_conditions,
+            time.elapsed(),
             **server_tick,
+            send_groups,
+            reset || resume_mode == Some(ResumeMode::Resync),
+            record_replay.then_some(&mut *replay_buffer),
         )?;
+        for (_, _, client) in messages.iter_mut_with_clients() {
+            client.clear_new_subscriptions();
+        }
+        collect_hidden(&mut messages)?;
+        collect_sections(&mut messages, set.p0(), &names, &mut section_buffer)?;
+
+        if let Some(mut replay_log) = set.p7().1 {
+            replay_log.append(&mut replay_buffer);
+        }
 
         let mut client_buffers = mem::take(&mut *set.p5());
         let connected_clients = messages.send(
             &mut set.p6(),
             &mut client_buffers,
+            &names,
             **server_tick,
             change_tick.this_run(),
             time.elapsed(),
@@ -256,6 +404,8 @@ impl ServerPlugin {
         // Return borrowed data back.
         *set.p1() = connected_clients;
         *set.p5() = client_buffers;
+        *set.p3().2 = previous_values;
+        *set.p7().2 = names;
 
         Ok(())
     }
@@ -265,13 +415,41 @@ impl ServerPlugin {
         mut entity_map: ResMut<ClientEntityMap>,
         mut connected_clients: ResMut<ConnectedClients>,
         mut client_buffers: ResMut<ClientBuffers>,
+        mut tombstones: ResMut<DespawnTombstones>,
+        mut previous_values: ResMut<PreviousValues>,
     ) {
         *server_tick = Default::default();
         entity_map.0.clear();
         connected_clients.clear(&mut client_buffers);
+        tombstones.clear();
+        previous_values.clear();
     }
 }
 
+/// Sends a reset requested via [`RepliconServer::reset_clients`] to each connected client.
+///
+/// The reset is an init message that contains only the current tick, clients recognize it by the absence of data
+/// and despawn all replicated entities and clear their mappings. Entities hidden with incremental reveal
+/// are forgotten too since clients no longer have them.
+///
+/// Sent on [`ReplicationChannel::Init`] before regular replication messages of the same tick,
+/// so clients apply it first.
+fn send_resets(
+    messages: &mut ReplicationMessages,
+    server: &mut RepliconServer,
+    names: &ClientNames,
+    server_tick: RepliconTick,
+) -> bincode::Result<()> {
+    let message = Bytes::from(bincode::serialize(&server_tick)?);
+    for (_, _, client) in messages.iter_mut_with_clients() {
+        trace!("sending reset to {}", names.label(client.id()));
+        client.clear_hidden();
+        server.send(client.id(), ReplicationChannel::Init, message.clone());
+    }
+
+    Ok(())
+}
+
 /// Collects and writes any new entity mappings that happened in this tick.
 ///
 /// On deserialization mappings should be processed first, so all referenced entities after it will behave correctly.
@@ -295,18 +473,45 @@ fn collect_mappings(
 
 /// Collects component insertions from this tick into init messages, and changes into update messages
 /// since the last entity tick.
+///
+/// Insertions and changes are written into messages for channels selected by their rules,
+/// see [`RuleFns::with_insert_channel`](crate::core::replication_fns::rule_fns::RuleFns::with_insert_channel)
+/// and [`RuleFns::with_mutation_channel`](crate::core::replication_fns::rule_fns::RuleFns::with_mutation_channel).
+/// Changes that exceeded their mutation timeout are not resent.
+/// Updates of entities that don't fit into the client's budget are skipped until later ticks,
+/// see [`ClientPriority`](connected_clients::client_priority::ClientPriority).
+/// Updates for clients outside of the current send group are skipped unless the entity got an insertion,
+/// see [`ServerPlugin::send_groups`].
+/// Changes of components with send intervals are skipped outside of their interval in the same way.
+/// Updates of components with delta functions are written as differences against the value acknowledged
+/// by the client when such a value is stored in `previous_values`.
+/// Changes of components with send filters are considered only if they passed the filter,
+/// the last passed value is also stored in `previous_values`.
+/// If `resync` is set, all entities will be written into init messages as newly visible.
+/// If `replay_log` is set, all written component values will be recorded into it.
+/// Values serialized for each client individually, such as deltas, are recorded once per client.
 fn collect_changes(
     messages: &mut ReplicationMessages,
     replicated_archetypes: &ReplicatedArchetypes,
     replication_fns: &ReplicationFns,
     world: &World,
     change_tick: &SystemChangeTick,
+    tick_timestamps: &TickTimestamps,
+    previous_values: &mut PreviousValues,
+    rule_conditions: &RuleConditions,
+    now: Duration,
     server_tick: RepliconTick,
+    send_groups: u8,
+    resync: bool,
+    mut replay_log: Option<&mut ReplayLog>,
 ) -> bincode::Result<()> {
     for (init_message, _) in messages.iter_mut() {
         init_message.start_array();
     }
 
+    // Buffers to collect data of all components of a group.
+    let mut ptrs = Vec::new();
+    let mut ticks_buffer = Vec::new();
     for replicated_archetype in replicated_archetypes.iter() {
         // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
         let archetype = unsafe {
@@ -324,11 +529,34 @@ fn collect_changes(
                 .unwrap_unchecked()
         };
 
+        let has_delta = replicated_archetype
+            .components
+            .iter()
+            .any(|replicated_component| {
+                let (_, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                rule_fns.has_delta()
+            });
+        let has_interval = replicated_archetype
+            .components
+            .iter()
+            .any(|replicated_component| {
+                let (_, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                rule_fns.send_interval() > 1
+            });
+        let has_once = replicated_archetype
+            .components
+            .iter()
+            .any(|replicated_component| {
+                let (_, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                rule_fns.once()
+            });
+
         for entity in archetype.entities() {
             for (init_message, update_message, client) in messages.iter_mut_with_clients() {
                 init_message.start_entity_data(entity.id());
                 update_message.start_entity_data(entity.id());
                 client.visibility_mut().cache_visibility(entity.id());
+                client.cache_reveal(entity.id());
             }
 
             // SAFETY: all replicated archetypes have marker component with table storage.
@@ -347,9 +575,47 @@ fn collect_changes(
             let marker_added =
                 marker_ticks.is_added(change_tick.last_run(), change_tick.this_run());
 
+            // Updates for clients outside of the current send group or components outside of their send interval
+            // can be skipped only if the entity has no insertions, otherwise they will be merged into the init message
+            // and the change limit will be bumped.
+            let any_added = (send_groups > 1 || has_interval)
+                && replicated_archetype
+                    .components
+                    .iter()
+                    .any(|replicated_component| {
+                        // SAFETY: component and storage were obtained from this archetype.
+                        let (_, ticks) = unsafe {
+                            get_component_unchecked(
+                                table,
+                                &world.storages().sparse_sets,
+                                entity,
+                                replicated_component.storage_type,
+                                replicated_component.component_id,
+                            )
+                        };
+                        ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                    });
+
+            // Replicate-once components need to be written only if any client initializes the entity.
+            let any_new = has_once
+                && (resync
+                    || marker_added
+                    || messages.iter_mut_with_clients().any(|(_, _, client)| {
+                        client.visibility().cached_visibility() == Visibility::Gained
+                    }));
+
+            let owner = world.get::<Owner>(entity.id()).map(|owner| **owner);
+            let entity_ref = world.entity(entity.id());
             for replicated_component in &replicated_archetype.components {
+                let (component_fns, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                if rule_fns.serialized_by_group()
+                    || rule_conditions.is_unmet(entity.id(), replicated_component.fns_id)
+                {
+                    continue;
+                }
+
                 // SAFETY: component and storage were obtained from this archetype.
-                let (component, ticks) = unsafe {
+                let (component, component_ticks) = unsafe {
                     get_component_unchecked(
                         table,
                         &world.storages().sparse_sets,
@@ -358,43 +624,223 @@ fn collect_changes(
                         replicated_component.component_id,
                     )
                 };
+                ptrs.clear();
+                ptrs.push(component);
+                ticks_buffer.clear();
+                ticks_buffer.push(component_ticks);
+                if let Some(group) = rule_fns.group() {
+                    for &component_id in group.other_ids() {
+                        // SAFETY: the group rule matched this archetype, so it contains all group components.
+                        let (component, component_ticks) = unsafe {
+                            let storage_type =
+                                archetype.get_storage_type(component_id).unwrap_unchecked();
+                            get_component_unchecked(
+                                table,
+                                &world.storages().sparse_sets,
+                                entity,
+                                storage_type,
+                                component_id,
+                            )
+                        };
+                        ptrs.push(component);
+                        ticks_buffer.push(component_ticks);
+                    }
+                }
+                let ticks = ReplicatedTicks(&ticks_buffer);
 
-                let (component_fns, rule_fns) = replication_fns.get(replicated_component.fns_id);
-                let ctx = SerializeCtx { server_tick };
+                if rule_fns.once()
+                    && !any_new
+                    && !ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                    && !rule_conditions.is_restored(entity.id(), replicated_component.fns_id)
+                    && !(rule_fns.optional()
+                        && messages.iter_mut_with_clients().any(|(_, _, client)| {
+                            client.is_new_subscription(replicated_component.fns_id)
+                        }))
+                {
+                    continue;
+                }
+
+                let expired = rule_fns
+                    .mutation_timeout()
+                    .and_then(|timeout| tick_timestamps.expiration_tick(timeout, now))
+                    .is_some_and(|tick| !ticks.is_changed(tick, change_tick.this_run()));
+                let ctx = SerializeCtx::new(server_tick, entity_ref, change_tick.this_run());
+                let network_id = replication_fns.network_id(replicated_component.fns_id);
+                if rule_fns.has_delta() {
+                    // SAFETY: component and functions were obtained for the same component.
+                    unsafe {
+                        previous_values.record_base(
+                            entity.id(),
+                            replicated_component.fns_id,
+                            (component_fns, rule_fns),
+                            component,
+                            |tick| ticks.is_changed(tick, change_tick.this_run()),
+                            change_tick.this_run(),
+                        );
+                    }
+                }
+                let sent_tick = rule_fns.has_filter().then(|| {
+                    // SAFETY: component and functions were obtained for the same component.
+                    unsafe {
+                        previous_values.update_sent(
+                            entity.id(),
+                            replicated_component.fns_id,
+                            (component_fns, rule_fns),
+                            component,
+                            ticks.is_added(change_tick.last_run(), change_tick.this_run()),
+                            |tick| ticks.is_changed(tick, change_tick.this_run()),
+                            change_tick.this_run(),
+                        )
+                    }
+                });
+                let insert_reliably = rule_fns.insert_channel() == ReplicationChannel::Init;
+                let mutate_reliably = rule_fns.mutation_channel() == ReplicationChannel::Init;
+                // Counted in send turns to not skip all turns of some clients when send groups are used.
+                let interval_turn =
+                    (server_tick.get() / send_groups.max(1) as u32) % rule_fns.send_interval() == 0;
                 let mut shared_bytes = None;
-                for (init_message, update_message, client) in messages.iter_mut_with_clients() {
+                let (clients, groups) = messages.iter_mut_with_clients_and_groups();
+                for (init_message, update_message, client) in clients {
                     let visibility = client.visibility().cached_visibility();
-                    if visibility == Visibility::Hidden {
+                    if visibility == Visibility::Hidden
+                        || (rule_fns.owner_only() && owner != Some(client.id()))
+                        || rule_fns
+                            .client_group()
+                            .is_some_and(|group_id| !groups.contains(group_id, client.id()))
+                        || (rule_fns.optional()
+                            && !client.is_subscribed(replicated_component.fns_id))
+                    {
                         continue;
                     }
 
-                    let new_entity = marker_added || visibility == Visibility::Gained;
-                    if new_entity || ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                    let reveal_tick = client.cached_reveal_tick();
+                    let new_entity = resync
+                        || marker_added
+                        || (visibility == Visibility::Gained && reveal_tick.is_none());
+                    let added = ticks.is_added(change_tick.last_run(), change_tick.this_run());
+                    if new_entity
+                        || (added && (insert_reliably || rule_fns.once()))
+                        || client.is_new_subscription(replicated_component.fns_id)
+                        || rule_conditions.is_restored(entity.id(), replicated_component.fns_id)
                     {
                         init_message.write_component(
                             &mut shared_bytes,
                             rule_fns,
                             component_fns,
                             &ctx,
-                            replicated_component.fns_id,
-                            component,
+                            network_id,
+                            &ptrs,
                         )?;
-                    } else {
+                    } else if rule_fns.once() {
+                        // Changes of replicate-once components are never sent.
+                        continue;
+                    } else if let Some(tick) = reveal_tick {
+                        // Include only changes that happened while the entity was hidden.
+                        if ticks.is_changed(tick, change_tick.this_run()) {
+                            init_message.write_component(
+                                &mut shared_bytes,
+                                rule_fns,
+                                component_fns,
+                                &ctx,
+                                network_id,
+                                &ptrs,
+                            )?;
+                        }
+                    } else if added {
+                        // Insertion over the update channel, resent as a change until acknowledged.
+                        update_message.write_component(
+                            &mut shared_bytes,
+                            rule_fns,
+                            component_fns,
+                            &ctx,
+                            network_id,
+                            &ptrs,
+                        )?;
+                    } else if any_added
+                        || (interval_turn && client.is_send_turn(server_tick, send_groups))
+                    {
                         let tick = client
                             .get_change_limit(entity.id())
                             .expect("entity should be present after adding component");
-                        if ticks.is_changed(tick, change_tick.this_run()) {
-                            update_message.write_component(
+                        let changed = match sent_tick {
+                            Some(sent_tick) => {
+                                sent_tick.is_newer_than(tick, change_tick.this_run())
+                            }
+                            None => ticks.is_changed(tick, change_tick.this_run()),
+                        };
+                        if changed && mutate_reliably {
+                            init_message.write_component(
                                 &mut shared_bytes,
                                 rule_fns,
                                 component_fns,
                                 &ctx,
-                                replicated_component.fns_id,
-                                component,
+                                network_id,
+                                &ptrs,
                             )?;
+                        } else if !expired && changed {
+                            let base = rule_fns
+                                .has_delta()
+                                .then(|| {
+                                    previous_values.base(
+                                        entity.id(),
+                                        replicated_component.fns_id,
+                                        tick,
+                                        change_tick.this_run(),
+                                    )
+                                })
+                                .flatten();
+                            if let Some(base) = base {
+                                let ctx = SerializeCtx {
+                                    change_limit: Some(tick),
+                                    ..ctx
+                                };
+                                let bytes = update_message.write_component_delta(
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    network_id,
+                                    base,
+                                    component,
+                                )?;
+                                if let Some(replay_log) = &mut replay_log {
+                                    replay_log.record(server_tick, entity.id(), network_id, bytes);
+                                }
+                            } else if rule_fns.per_client() {
+                                let ctx = SerializeCtx {
+                                    change_limit: Some(tick),
+                                    ..ctx
+                                };
+                                let mut client_bytes = None;
+                                update_message.write_component(
+                                    &mut client_bytes,
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    network_id,
+                                    &ptrs,
+                                )?;
+                                if let (Some(replay_log), Some(bytes)) =
+                                    (&mut replay_log, client_bytes)
+                                {
+                                    replay_log.record(server_tick, entity.id(), network_id, bytes);
+                                }
+                            } else {
+                                update_message.write_component(
+                                    &mut shared_bytes,
+                                    rule_fns,
+                                    component_fns,
+                                    &ctx,
+                                    network_id,
+                                    &ptrs,
+                                )?;
+                            }
                         }
                     }
                 }
+
+                if let (Some(replay_log), Some(bytes)) = (&mut replay_log, shared_bytes) {
+                    replay_log.record(server_tick, entity.id(), network_id, bytes);
+                }
             }
 
             for (init_message, update_message, client) in messages.iter_mut_with_clients() {
@@ -403,17 +849,43 @@ fn collect_changes(
                     continue;
                 }
 
-                let new_entity = marker_added || visibility == Visibility::Gained;
-                if new_entity || init_message.entity_data_size() != 0 {
+                let reveal_tick = client.cached_reveal_tick();
+                let new_entity = resync
+                    || marker_added
+                    || (visibility == Visibility::Gained && reveal_tick.is_none());
+                // Revealed entity should be written even without changes to notify the client.
+                let save_empty = new_entity || reveal_tick.is_some();
+                if save_empty || init_message.entity_data_size() != 0 {
                     // If there is any insertion or we must initialize, include all updates into init message
                     // and bump the last acknowledged tick to keep entity updates atomic.
                     init_message.take_entity_data(update_message)?;
                     client.set_change_limit(entity.id(), change_tick.this_run());
-                } else {
+                } else if update_message.entity_data_size() == 0
+                    || client
+                        .priority_mut()
+                        .should_send(entity.id(), update_message.entity_data_size())
+                {
                     update_message.end_entity_data()?;
+                } else {
+                    update_message.discard_entity_data();
                 }
 
-                init_message.end_entity_data(new_entity)?;
+                init_message.end_entity_data(save_empty)?;
+            }
+
+            if has_delta {
+                // Keep only values that could be used as bases for acknowledged changes.
+                let min_tick = messages
+                    .iter_mut_with_clients()
+                    .filter_map(|(_, _, client)| client.get_change_limit(entity.id()))
+                    .reduce(|min_tick, tick| {
+                        if min_tick.is_newer_than(tick, change_tick.this_run()) {
+                            tick
+                        } else {
+                            min_tick
+                        }
+                    });
+                previous_values.prune_bases(entity.id(), min_tick, change_tick.this_run());
             }
         }
     }
@@ -425,6 +897,25 @@ fn collect_changes(
     Ok(())
 }
 
+/// Change ticks of a replicated component or all components of a group.
+///
+/// A group is considered added or changed if any of its components is.
+struct ReplicatedTicks<'a>(&'a [ComponentTicks]);
+
+impl ReplicatedTicks<'_> {
+    fn is_added(&self, last_run: Tick, this_run: Tick) -> bool {
+        self.0
+            .iter()
+            .any(|ticks| ticks.is_added(last_run, this_run))
+    }
+
+    fn is_changed(&self, last_run: Tick, this_run: Tick) -> bool {
+        self.0
+            .iter()
+            .any(|ticks| ticks.is_changed(last_run, this_run))
+    }
+}
+
 /// Extracts component in form of [`Ptr`] and its ticks from table or sparse set based on its storage type.
 ///
 /// # Safety
@@ -456,25 +947,94 @@ unsafe fn get_component_unchecked<'w>(
 }
 
 /// Collect entity despawns from this tick into init messages.
+///
+/// Despawned entities are also recorded into [`DespawnTombstones`].
 fn collect_despawns(
     messages: &mut ReplicationMessages,
     despawn_buffer: &mut DespawnBuffer,
+    tombstones: &mut DespawnTombstones,
+    previous_values: &mut PreviousValues,
+    rule_conditions: &mut RuleConditions,
+    server_tick: RepliconTick,
 ) -> bincode::Result<()> {
     for (message, _) in messages.iter_mut() {
         message.start_array();
     }
 
+    tombstones.prune(server_tick);
     for entity in despawn_buffer.drain(..) {
+        tombstones.insert(entity, server_tick);
+        previous_values.remove_entity(entity);
+        rule_conditions.remove_despawned(entity);
         let mut shared_bytes = None;
         for (message, _, client) in messages.iter_mut_with_clients() {
             client.remove_despawned(entity);
             message.write_entity(&mut shared_bytes, entity)?;
         }
+        for client in messages.iter_held_clients_mut() {
+            client.remove_despawned(entity);
+        }
     }
 
     for (message, _, client) in messages.iter_mut_with_clients() {
-        for entity in client.drain_lost_visibility() {
-            message.write_entity(&mut None, entity)?;
+        if client.reveal_policy() == RevealPolicy::Spawn {
+            for entity in client.drain_lost_visibility() {
+                message.write_entity(&mut None, entity)?;
+            }
+        }
+
+        message.end_array()?;
+    }
+
+    Ok(())
+}
+
+/// Collects entities for which visibility was lost during this tick into init messages
+/// for clients with [`RevealPolicy::Incremental`].
+///
+/// For other clients such entities are written as despawns.
+fn collect_hidden(messages: &mut ReplicationMessages) -> bincode::Result<()> {
+    for (message, _, client) in messages.iter_mut_with_clients() {
+        message.start_array();
+
+        if client.reveal_policy() == RevealPolicy::Incremental {
+            for entity in client.drain_lost_visibility() {
+                message.write_entity(&mut None, entity)?;
+            }
+        }
+
+        message.end_array()?;
+    }
+
+    Ok(())
+}
+
+/// Collects custom sections from [`ReplicationSections`] into init messages.
+fn collect_sections(
+    messages: &mut ReplicationMessages,
+    world: &World,
+    names: &ClientNames,
+    buffer: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    let sections = world.resource::<ReplicationSections>();
+    if sections.is_empty() {
+        return Ok(());
+    }
+
+    for (message, _, client) in messages.iter_mut_with_clients() {
+        message.start_array();
+
+        for (id, section) in sections.iter() {
+            buffer.clear();
+            (section.write)(world, client.id(), buffer);
+            if !buffer.is_empty() {
+                trace!(
+                    "writing section `{}` for {}",
+                    section.name,
+                    names.label(client.id())
+                );
+                message.write_section(id, buffer)?;
+            }
         }
 
         message.end_array()?;
@@ -484,9 +1044,13 @@ fn collect_despawns(
 }
 
 /// Collects component removals from this tick into init messages.
+///
+/// Previous values of removed components are dropped since the next insertion will be sent in full.
 fn collect_removals(
     messages: &mut ReplicationMessages,
     removal_buffer: &mut RemovalBuffer,
+    previous_values: &mut PreviousValues,
+    replication_fns: &ReplicationFns,
     tick: Tick,
 ) -> bincode::Result<()> {
     for (message, _) in messages.iter_mut() {
@@ -494,11 +1058,14 @@ fn collect_removals(
     }
 
     for (entity, remove_ids) in removal_buffer.iter() {
+        for fns_info in remove_ids {
+            previous_values.remove_component(entity, fns_info.fns_id());
+        }
         for (message, _, client) in messages.iter_mut_with_clients() {
             message.start_entity_data(entity);
             for fns_info in remove_ids {
                 client.set_change_limit(entity, tick);
-                message.write_fns_id(fns_info.fns_id())?;
+                message.write_fns_id(replication_fns.network_id(fns_info.fns_id()))?;
             }
             message.end_entity_data(false)?;
         }
@@ -584,6 +1151,20 @@ pub enum VisibilityPolicy {
     Whitelist,
 }
 
+/// Controls what will be sent to a client when an entity becomes visible again.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealPolicy {
+    /// Entities are despawned on client after losing visibility and spawned again with all components
+    /// after regaining it.
+    #[default]
+    Spawn,
+    /// Entities stay on client with [`Obscured`](crate::client::obscured::Obscured) after losing visibility.
+    ///
+    /// After regaining visibility, only components changed since the client last saw the entity are sent.
+    /// Useful for entities that frequently leave and re-enter relevance.
+    Incremental,
+}
+
 /// Connection and disconnection events on the server.
 ///
 /// The messaging backend is responsible for emitting these in [`ServerSet::SendEvents`].
@@ -47,7 +47,7 @@ fn confirm_bullet(
     mut bullet_events: EventReader<FromClient<SpawnBullet>>,
     mut entity_map: ResMut<ClientEntityMap>,
 ) {
-    for FromClient { client_id, event } in bullet_events.read() {
+    for FromClient { client_id, event, .. } in bullet_events.read() {
         let server_entity = commands.spawn(Bullet).id(); // You can insert more components, they will be sent to the client's entity correctly.
 
         entity_map.insert(
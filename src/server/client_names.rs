@@ -0,0 +1,102 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::core::ClientId;
+
+/// Display names for connected clients.
+///
+/// Names could be assigned by the messaging backend or by user code and are used in replicon's logs
+/// instead of raw [`ClientId`]s to make them easier to read.
+///
+/// Names are removed automatically when clients disconnect.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+///
+/// fn name_clients(mut server_events: EventReader<ServerEvent>, mut names: ResMut<ClientNames>) {
+///     for event in server_events.read() {
+///         if let ServerEvent::ClientConnected { client_id } = event {
+///             names.insert(*client_id, format!("Player {}", client_id.get()));
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct ClientNames(HashMap<ClientId, String>);
+
+impl ClientNames {
+    /// Assigns a name for a client, replacing the previous one.
+    pub fn insert(&mut self, client_id: ClientId, name: impl Into<String>) {
+        self.0.insert(client_id, name.into());
+    }
+
+    /// Removes name of a client.
+    pub fn remove(&mut self, client_id: ClientId) -> Option<String> {
+        self.0.remove(&client_id)
+    }
+
+    /// Returns name of a client if it was assigned.
+    pub fn get(&self, client_id: ClientId) -> Option<&str> {
+        self.0.get(&client_id).map(String::as_str)
+    }
+
+    /// Returns a label that displays the client name with its ID or just the ID if no name was assigned.
+    pub fn label(&self, client_id: ClientId) -> ClientLabel<'_> {
+        ClientLabel {
+            client_id,
+            name: self.get(client_id),
+        }
+    }
+}
+
+/// Client representation for logging.
+///
+/// See also [`ClientNames::label`].
+#[derive(Clone, Copy, Debug)]
+pub struct ClientLabel<'a> {
+    client_id: ClientId,
+    name: Option<&'a str>,
+}
+
+/// Creates a label without a name.
+///
+/// Useful for clients that aren't connected, since names are assigned only to connected clients.
+impl From<ClientId> for ClientLabel<'_> {
+    fn from(client_id: ClientId) -> Self {
+        Self {
+            client_id,
+            name: None,
+        }
+    }
+}
+
+impl Display for ClientLabel<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.name {
+            Some(name) => write!(f, "`{name}` ({:?})", self.client_id),
+            None => write!(f, "`{:?}`", self.client_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label() {
+        let mut names = ClientNames::default();
+        let client_id = ClientId::new(1);
+        assert_eq!(names.label(client_id).to_string(), "`ClientId(1)`");
+
+        names.insert(client_id, "Alice");
+        assert_eq!(names.label(client_id).to_string(), "`Alice` (ClientId(1))");
+
+        names.remove(client_id);
+        assert_eq!(names.label(client_id).to_string(), "`ClientId(1)`");
+    }
+}
@@ -1,3 +1,5 @@
+pub mod client_groups;
+pub mod client_priority;
 pub mod client_visibility;
 
 use std::mem;
@@ -5,27 +7,45 @@ use std::mem;
 use bevy::{
     ecs::{component::Tick, entity::EntityHashMap},
     prelude::*,
-    utils::{Duration, HashMap},
+    utils::{Duration, HashMap, HashSet},
 };
 
 use crate::{
-    core::{replicon_tick::RepliconTick, ClientId},
-    server::VisibilityPolicy,
+    core::{replication_fns::FnsId, replicon_tick::RepliconTick, ClientId},
+    server::{client_names::ClientLabel, RevealPolicy, VisibilityPolicy},
 };
-use client_visibility::ClientVisibility;
+use client_groups::ClientGroups;
+use client_priority::ClientPriority;
+use client_visibility::{ClientVisibility, Visibility};
 
 /// Stores information about connected clients.
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct ConnectedClients {
     clients: Vec<ConnectedClient>,
+    groups: ClientGroups,
     policy: VisibilityPolicy,
+    reveal_policy: RevealPolicy,
+    send_groups: u8,
+}
+
+impl Default for ConnectedClients {
+    fn default() -> Self {
+        Self::new(Default::default(), Default::default(), 1)
+    }
 }
 
 impl ConnectedClients {
-    pub(super) fn new(policy: VisibilityPolicy) -> Self {
+    pub(super) fn new(
+        policy: VisibilityPolicy,
+        reveal_policy: RevealPolicy,
+        send_groups: u8,
+    ) -> Self {
         Self {
             clients: Default::default(),
+            groups: Default::default(),
             policy,
+            reveal_policy,
+            send_groups: send_groups.max(1),
         }
     }
 
@@ -34,6 +54,28 @@ impl ConnectedClients {
         self.policy
     }
 
+    /// Returns the configured [`RevealPolicy`].
+    pub fn reveal_policy(&self) -> RevealPolicy {
+        self.reveal_policy
+    }
+
+    /// Returns the number of groups into which clients are split to receive updates on different ticks.
+    ///
+    /// See [`ServerPlugin::send_groups`](super::ServerPlugin::send_groups).
+    pub fn send_groups(&self) -> u8 {
+        self.send_groups
+    }
+
+    /// Returns a reference to the group memberships.
+    pub fn groups(&self) -> &ClientGroups {
+        &self.groups
+    }
+
+    /// Returns a mutable reference to the group memberships.
+    pub fn groups_mut(&mut self) -> &mut ClientGroups {
+        &mut self.groups
+    }
+
     /// Returns a reference to a connected client.
     ///
     /// This operation is *O*(*n*).
@@ -93,6 +135,24 @@ impl ConnectedClients {
         self.clients.iter_mut()
     }
 
+    /// Same as [`Self::iter_mut`], but also returns client groups.
+    pub(super) fn iter_mut_with_groups(
+        &mut self,
+    ) -> (impl Iterator<Item = &mut ConnectedClient>, &ClientGroups) {
+        (self.clients.iter_mut(), &self.groups)
+    }
+
+    /// Returns an iterator over clients to which the entity is currently replicated.
+    ///
+    /// Includes only clients that received the entity and for which it's still visible.
+    /// Useful for decisions like playing server-side effects only if at least one client can see them.
+    pub fn replication_targets(&self, entity: Entity) -> impl Iterator<Item = ClientId> + '_ {
+        self.clients
+            .iter()
+            .filter(move |client| client.is_replicated(entity))
+            .map(|client| client.id())
+    }
+
     /// Returns the number of connected clients.
     pub fn len(&self) -> usize {
         self.clients.len()
@@ -107,15 +167,25 @@ impl ConnectedClients {
     ///
     /// Reuses the memory from the buffers if available.
     pub(super) fn add(&mut self, client_buffers: &mut ClientBuffers, client_id: ClientId) {
-        debug!("adding connected `{client_id:?}`");
-
-        let client = if let Some(mut client) = client_buffers.clients.pop() {
+        let mut client = if let Some(mut client) = client_buffers.clients.pop() {
             client.reset(client_id);
             client
         } else {
-            ConnectedClient::new(client_id, self.policy)
+            ConnectedClient::new(client_id, self.policy, self.reveal_policy)
         };
 
+        // Assign to the least populated group to keep them balanced after disconnects.
+        let mut group_sizes = vec![0; self.send_groups.into()];
+        for client in &self.clients {
+            group_sizes[client.send_group as usize] += 1;
+        }
+        let (send_group, _) = group_sizes
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &size)| size)
+            .expect("there should be at least one send group");
+        client.send_group = send_group as u8;
+
         self.clients.push(client);
     }
 
@@ -123,14 +193,13 @@ impl ConnectedClients {
     ///
     /// Keeps allocated memory in the buffers for reuse.
     pub(super) fn remove(&mut self, client_buffers: &mut ClientBuffers, client_id: ClientId) {
-        debug!("removing disconnected `{client_id:?}`");
-
         let index = self
             .clients
             .iter()
             .position(|client| client.id == client_id)
             .unwrap_or_else(|| panic!("{client_id:?} should be added before removal"));
         let mut client = self.clients.remove(index);
+        self.groups.remove_client(client_id);
         client_buffers.entities.extend(client.drain_entities());
         client_buffers.clients.push(client);
     }
@@ -139,6 +208,7 @@ impl ConnectedClients {
     ///
     /// Keeps allocated memory in the buffers for reuse.
     pub(super) fn clear(&mut self, client_buffers: &mut ClientBuffers) {
+        self.groups.clear();
         for mut client in self.clients.drain(..) {
             client_buffers.entities.extend(client.drain_entities());
             client_buffers.clients.push(client);
@@ -156,6 +226,41 @@ pub struct ConnectedClient {
     /// Entity visibility settings.
     visibility: ClientVisibility,
 
+    /// Update prioritization settings.
+    priority: ClientPriority,
+
+    reveal_policy: RevealPolicy,
+
+    /// Indicates that replication and server events are held for this client.
+    ///
+    /// See [`Self::is_held`].
+    held: bool,
+
+    /// Group in which the client receives updates.
+    ///
+    /// See [`ServerPlugin::send_groups`](super::ServerPlugin::send_groups).
+    send_group: u8,
+
+    /// Optional components to which the client is subscribed.
+    ///
+    /// See [`RuleFns::with_optional`](crate::core::replication_fns::rule_fns::RuleFns::with_optional).
+    subscriptions: HashSet<FnsId>,
+
+    /// Subscriptions added since the last replication.
+    ///
+    /// Components from them are sent as insertions to deliver their current values.
+    new_subscriptions: HashSet<FnsId>,
+
+    /// Change limits for entities that lost visibility.
+    ///
+    /// Filled only for [`RevealPolicy::Incremental`].
+    hidden_ticks: EntityHashMap<Tick>,
+
+    /// Change limit of the entity that regained visibility from the last call of [`Self::cache_reveal`].
+    ///
+    /// Used as an optimization by server replication.
+    cached_reveal_tick: Option<Tick>,
+
     /// The last tick in which a replicated entity was spawned, despawned, or gained/lost a component from the
     /// perspective of the client.
     ///
@@ -163,6 +268,14 @@ pub struct ConnectedClient {
     /// message to arrive.
     change_tick: RepliconTick,
 
+    /// The most recent server tick from acknowledged updates.
+    acked_tick: RepliconTick,
+
+    /// Server tick of the oldest update sent after [`Self::acked_tick`].
+    ///
+    /// [`None`] if the client acknowledged all sent updates.
+    oldest_unacked_tick: Option<RepliconTick>,
+
     /// Update message indexes mapped to their info.
     updates: HashMap<u16, UpdateInfo>,
 
@@ -173,12 +286,22 @@ pub struct ConnectedClient {
 }
 
 impl ConnectedClient {
-    fn new(id: ClientId, policy: VisibilityPolicy) -> Self {
+    fn new(id: ClientId, policy: VisibilityPolicy, reveal_policy: RevealPolicy) -> Self {
         Self {
             id,
             ticks: Default::default(),
             visibility: ClientVisibility::new(policy),
+            priority: Default::default(),
+            reveal_policy,
+            held: false,
+            send_group: 0,
+            subscriptions: Default::default(),
+            new_subscriptions: Default::default(),
+            hidden_ticks: Default::default(),
+            cached_reveal_tick: Default::default(),
             change_tick: Default::default(),
+            acked_tick: Default::default(),
+            oldest_unacked_tick: Default::default(),
             updates: Default::default(),
             next_update_index: Default::default(),
         }
@@ -189,6 +312,42 @@ impl ConnectedClient {
         self.id
     }
 
+    /// Returns the group in which the client receives updates.
+    ///
+    /// See [`ServerPlugin::send_groups`](super::ServerPlugin::send_groups).
+    pub fn send_group(&self) -> u8 {
+        self.send_group
+    }
+
+    /// Returns `true` if updates should be sent to the client on this tick.
+    pub(super) fn is_send_turn(&self, server_tick: RepliconTick, send_groups: u8) -> bool {
+        server_tick.get() % send_groups as u32 == self.send_group as u32
+    }
+
+    /// Replaces subscriptions to optional components.
+    pub(crate) fn set_subscriptions(&mut self, subscriptions: HashSet<FnsId>) {
+        self.new_subscriptions
+            .extend(subscriptions.difference(&self.subscriptions));
+        self.new_subscriptions
+            .retain(|fns_id| subscriptions.contains(fns_id));
+        self.subscriptions = subscriptions;
+    }
+
+    /// Returns `true` if the client is subscribed to the optional component with these functions.
+    pub(super) fn is_subscribed(&self, fns_id: FnsId) -> bool {
+        self.subscriptions.contains(&fns_id)
+    }
+
+    /// Returns `true` if the client subscribed to the optional component since the last replication.
+    pub(super) fn is_new_subscription(&self, fns_id: FnsId) -> bool {
+        self.new_subscriptions.contains(&fns_id)
+    }
+
+    /// Clears subscriptions added since the last replication.
+    pub(super) fn clear_new_subscriptions(&mut self) {
+        self.new_subscriptions.clear();
+    }
+
     /// Returns a reference to the client's visibility settings.
     pub fn visibility(&self) -> &ClientVisibility {
         &self.visibility
@@ -199,6 +358,33 @@ impl ConnectedClient {
         &mut self.visibility
     }
 
+    /// Returns a reference to the client's update prioritization settings.
+    pub fn priority(&self) -> &ClientPriority {
+        &self.priority
+    }
+
+    /// Returns a mutable reference to the client's update prioritization settings.
+    pub fn priority_mut(&mut self) -> &mut ClientPriority {
+        &mut self.priority
+    }
+
+    /// Returns `true` if replication and server events are held for this client.
+    ///
+    /// Held clients are skipped by replication and server events, so they don't receive any world state.
+    /// After releasing, the client receives all visible entities as if it just connected.
+    /// Clients are held by [`HandshakePlugin`](crate::handshake::HandshakePlugin) until the handshake completes.
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// Holds or releases replication and server events for this client.
+    ///
+    /// Should be called only on connection and after it, since despawns during the hold are not tracked
+    /// for the client. See [`Self::is_held`].
+    pub(crate) fn set_held(&mut self, held: bool) {
+        self.held = held;
+    }
+
     /// Sets the client's change tick.
     pub(super) fn set_change_tick(&mut self, tick: RepliconTick) {
         self.change_tick = tick;
@@ -210,6 +396,43 @@ impl ConnectedClient {
         self.change_tick
     }
 
+    /// Returns the most recent server tick acknowledged by the client.
+    ///
+    /// Can be used as an estimate of the tick the client sees.
+    /// Updated only when the client acknowledges update messages,
+    /// so it may lag behind if no components change.
+    pub fn acked_tick(&self) -> RepliconTick {
+        self.acked_tick
+    }
+
+    /// Returns how many ticks the client's acknowledgments lag behind `server_tick`.
+    ///
+    /// Counted from the oldest update that was sent after [`Self::acked_tick`],
+    /// so it stays zero while the client acknowledges everything or no components change.
+    /// Growing values indicate connection problems before the client actually times out.
+    pub fn ticks_behind(&self, server_tick: RepliconTick) -> u32 {
+        self.oldest_unacked_tick
+            .map_or(0, |oldest_tick| server_tick - oldest_tick)
+    }
+
+    /// Returns the number of sent update messages that the client hasn't acknowledged yet.
+    ///
+    /// Updates are forgotten after [`ServerPlugin::update_timeout`](super::ServerPlugin::update_timeout)
+    /// and their changes are resent, so they are not counted.
+    pub fn unacked_updates(&self) -> usize {
+        self.updates.len()
+    }
+
+    /// Returns the size in bytes of sent update messages that the client hasn't acknowledged yet.
+    ///
+    /// See also [`Self::unacked_updates`].
+    pub fn unacked_bytes(&self) -> usize {
+        self.updates
+            .values()
+            .map(|update_info| update_info.size)
+            .sum()
+    }
+
     /// Clears all entities for unacknowledged updates, returning them as an iterator.
     ///
     /// Keeps the allocated memory for reuse.
@@ -224,13 +447,20 @@ impl ConnectedClient {
     /// Keeps the allocated memory for reuse.
     fn reset(&mut self, id: ClientId) {
         self.id = id;
+        self.held = false;
         self.visibility.clear();
+        self.priority.clear();
+        self.subscriptions.clear();
+        self.new_subscriptions.clear();
         self.ticks.clear();
+        self.hidden_ticks.clear();
         self.updates.clear();
+        self.acked_tick = Default::default();
+        self.oldest_unacked_tick = None;
         self.next_update_index = 0;
     }
 
-    /// Registers update at specified `tick` and `timestamp` and returns its index with entities to fill.
+    /// Registers update at specified `tick`, `server_tick` and `timestamp` and returns its index with entities and message size to fill.
     ///
     /// Used later to acknowledge updated entities.
     #[must_use]
@@ -238,17 +468,21 @@ impl ConnectedClient {
         &mut self,
         client_buffers: &mut ClientBuffers,
         tick: Tick,
+        server_tick: RepliconTick,
         timestamp: Duration,
-    ) -> (u16, &mut Vec<Entity>) {
+    ) -> (u16, &mut Vec<Entity>, &mut usize) {
         let update_index = self.next_update_index;
         self.next_update_index = self.next_update_index.overflowing_add(1).0;
+        self.oldest_unacked_tick.get_or_insert(server_tick);
 
         let mut entities = client_buffers.entities.pop().unwrap_or_default();
         entities.clear();
         let update_info = UpdateInfo {
             tick,
+            server_tick,
             timestamp,
             entities,
+            size: 0,
         };
         let update_info = self
             .updates
@@ -256,7 +490,11 @@ impl ConnectedClient {
             .insert(update_info)
             .into_mut();
 
-        (update_index, &mut update_info.entities)
+        (
+            update_index,
+            &mut update_info.entities,
+            &mut update_info.size,
+        )
     }
 
     /// Sets the change limit for an entity that is replicated to this client.
@@ -267,6 +505,22 @@ impl ConnectedClient {
         self.ticks.insert(entity, tick);
     }
 
+    /// Sets the change limit for all entities replicated to this client.
+    ///
+    /// Used to discard changes that happened while replication was paused.
+    pub(super) fn set_all_change_limits(&mut self, tick: Tick) {
+        for last_tick in self.ticks.values_mut() {
+            *last_tick = tick;
+        }
+    }
+
+    /// Returns `true` if the entity was sent to this client and is still visible for it.
+    ///
+    /// See also [`ConnectedClients::replication_targets`].
+    pub fn is_replicated(&self, entity: Entity) -> bool {
+        self.ticks.contains_key(&entity) && self.visibility.is_visible(entity)
+    }
+
     /// Gets the change limit for an entity that is replicated to this client.
     pub fn get_change_limit(&mut self, entity: Entity) -> Option<Tick> {
         self.ticks.get(&entity).copied()
@@ -282,12 +536,10 @@ impl ConnectedClient {
         client_buffers: &mut ClientBuffers,
         tick: Tick,
         update_index: u16,
+        label: ClientLabel<'_>,
     ) {
         let Some(update_info) = self.updates.remove(&update_index) else {
-            debug!(
-                "received unknown update index {update_index} from {:?}",
-                self.id
-            );
+            debug!("received unknown update index {update_index} from {label}");
             return;
         };
 
@@ -303,19 +555,32 @@ impl ConnectedClient {
                 *last_tick = update_info.tick;
             }
         }
+        if update_info.server_tick > self.acked_tick {
+            self.acked_tick = update_info.server_tick;
+        }
+        self.oldest_unacked_tick = self
+            .updates
+            .values()
+            .map(|update_info| update_info.server_tick)
+            .filter(|&server_tick| server_tick > self.acked_tick)
+            .reduce(|oldest, server_tick| {
+                if server_tick < oldest {
+                    server_tick
+                } else {
+                    oldest
+                }
+            });
         client_buffers.entities.push(update_info.entities);
 
-        trace!(
-            "{:?} acknowledged an update with {:?}",
-            self.id,
-            update_info.tick,
-        );
+        trace!("{label} acknowledged an update with {:?}", update_info.tick);
     }
 
     /// Removes a despawned entity tracked by this client.
     pub fn remove_despawned(&mut self, entity: Entity) {
         self.ticks.remove(&entity);
+        self.hidden_ticks.remove(&entity);
         self.visibility.remove_despawned(entity);
+        self.priority.remove_despawned(entity);
         // We don't clean up `self.updates` for efficiency reasons.
         // `Self::acknowledge()` will properly ignore despawned entities.
     }
@@ -325,10 +590,44 @@ impl ConnectedClient {
     /// Internal cleanup happens lazily during the iteration.
     pub(super) fn drain_lost_visibility(&mut self) -> impl Iterator<Item = Entity> + '_ {
         self.visibility.drain_lost_visibility().inspect(|entity| {
-            self.ticks.remove(entity);
+            if let Some(tick) = self.ticks.remove(entity) {
+                if self.reveal_policy == RevealPolicy::Incremental {
+                    self.hidden_ticks.insert(*entity, tick);
+                }
+            }
         })
     }
 
+    /// Forgets entities hidden with [`RevealPolicy::Incremental`].
+    ///
+    /// Used after [`RepliconServer::reset_clients`](super::replicon_server::RepliconServer::reset_clients)
+    /// because the client no longer has them.
+    pub(super) fn clear_hidden(&mut self) {
+        self.hidden_ticks.clear();
+    }
+
+    /// Returns the configured [`RevealPolicy`].
+    pub(super) fn reveal_policy(&self) -> RevealPolicy {
+        self.reveal_policy
+    }
+
+    /// Caches the change limit from the moment the entity lost visibility if it was just regained.
+    ///
+    /// Should be called after caching its visibility.
+    /// See also [`Self::cached_reveal_tick`].
+    pub(super) fn cache_reveal(&mut self, entity: Entity) {
+        self.cached_reveal_tick = if self.visibility.cached_visibility() == Visibility::Gained {
+            self.hidden_ticks.remove(&entity)
+        } else {
+            None
+        };
+    }
+
+    /// Returns the change limit cached by [`Self::cache_reveal`].
+    pub(super) fn cached_reveal_tick(&self) -> Option<Tick> {
+        self.cached_reveal_tick
+    }
+
     /// Removes all updates older then `min_timestamp`.
     ///
     /// Keeps allocated memory in the buffers for reuse.
@@ -366,6 +665,8 @@ pub(crate) struct ClientBuffers {
 
 struct UpdateInfo {
     tick: Tick,
+    server_tick: RepliconTick,
     timestamp: Duration,
     entities: Vec<Entity>,
+    size: usize,
 }
@@ -0,0 +1,127 @@
+use std::mem;
+
+use bevy::utils::{HashMap, HashSet};
+
+use crate::core::ClientId;
+
+/**
+Group memberships for targeting server events with
+[`SendMode::Group`](crate::network_event::server_event::SendMode::Group).
+
+Stored inside [`ConnectedClients`](super::ConnectedClients). Clients are removed
+from all groups on disconnect. [`ClientId::SERVER`] can also be added to receive
+events locally on listen server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const LOBBY: GroupId = GroupId(0);
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_server_event::<Chat>(ChannelKind::Ordered)
+    .add_systems(Update, (join_lobby, greet));
+
+fn join_lobby(mut server_events: EventReader<ServerEvent>, mut connected_clients: ResMut<ConnectedClients>) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            connected_clients.groups_mut().insert(LOBBY, client_id);
+        }
+    }
+}
+
+fn greet(mut chat_events: EventWriter<ToClients<Chat>>) {
+    chat_events.send(ToClients {
+        mode: SendMode::Group(LOBBY),
+        event: Chat("Welcome to the lobby!".into()),
+        apply_tick: None,
+    });
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Chat(String);
+```
+**/
+#[derive(Default, Debug)]
+pub struct ClientGroups {
+    groups: HashMap<GroupId, HashSet<ClientId>>,
+
+    /// Indicates that memberships were changed since the last [`Self::take_changed`].
+    changed: bool,
+}
+
+impl ClientGroups {
+    /// Adds a client to a group.
+    ///
+    /// Returns `false` if the client is already a member.
+    pub fn insert(&mut self, group_id: GroupId, client_id: ClientId) -> bool {
+        let inserted = self.groups.entry(group_id).or_default().insert(client_id);
+        self.changed |= inserted;
+        inserted
+    }
+
+    /// Removes a client from a group.
+    ///
+    /// Returns `false` if the client wasn't a member.
+    pub fn remove(&mut self, group_id: GroupId, client_id: ClientId) -> bool {
+        let Some(members) = self.groups.get_mut(&group_id) else {
+            return false;
+        };
+
+        let removed = members.remove(&client_id);
+        if members.is_empty() {
+            self.groups.remove(&group_id);
+        }
+        self.changed |= removed;
+
+        removed
+    }
+
+    /// Removes a client from all groups.
+    pub fn remove_client(&mut self, client_id: ClientId) {
+        let mut removed = false;
+        self.groups.retain(|_, members| {
+            removed |= members.remove(&client_id);
+            !members.is_empty()
+        });
+        self.changed |= removed;
+    }
+
+    /// Removes a group with all its members.
+    pub fn remove_group(&mut self, group_id: GroupId) {
+        self.changed |= self.groups.remove(&group_id).is_some();
+    }
+
+    /// Returns `true` if the client is a member of the group.
+    pub fn contains(&self, group_id: GroupId, client_id: ClientId) -> bool {
+        self.groups
+            .get(&group_id)
+            .is_some_and(|members| members.contains(&client_id))
+    }
+
+    /// Returns an iterator over members of a group.
+    pub fn iter_members(&self, group_id: GroupId) -> impl Iterator<Item = ClientId> + '_ {
+        self.groups.get(&group_id).into_iter().flatten().copied()
+    }
+
+    /// Removes all groups.
+    pub(crate) fn clear(&mut self) {
+        self.changed |= !self.groups.is_empty();
+        self.groups.clear();
+    }
+
+    /// Returns `true` if memberships were changed since the last call and resets the flag.
+    ///
+    /// Used to re-evaluate [`VisibleToGroup`](crate::server::visibility_conditions::VisibleToGroup).
+    pub(crate) fn take_changed(&mut self) -> bool {
+        mem::take(&mut self.changed)
+    }
+}
+
+/// Group identifier for [`ClientGroups`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GroupId(pub u32);
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+/// Update prioritization settings for a client.
+///
+/// Without an update budget all changes are sent every tick.
+/// With a budget, changed entities accumulate their priority each tick and
+/// their updates are sent only when the accumulated value reaches a threshold.
+/// The threshold adapts to keep the size of updates per tick close to the budget,
+/// while skipped entities are guaranteed to be sent eventually.
+///
+/// Priority of all entities is 1 by default and can be temporarily increased with [`Self::boost`],
+/// for example, for entities that the player is aiming at or interacting with.
+///
+/// Only updates are limited, insertions and removals are always sent.
+#[derive(Default)]
+pub struct ClientPriority {
+    /// Maximum size of updates in bytes per tick.
+    update_budget: Option<usize>,
+
+    /// Active boosts for entities.
+    boosts: EntityHashMap<PriorityBoost>,
+
+    /// Priority accumulated by entities since their last sent update.
+    accumulated: EntityHashMap<f32>,
+
+    /// Minimum accumulated priority required to send an update.
+    threshold: f32,
+
+    /// Size of updates written in the current tick.
+    written: usize,
+
+    /// Time of the last call of [`Self::start_tick`].
+    last_tick: Option<Duration>,
+}
+
+impl ClientPriority {
+    /// Sets the maximum size of updates in bytes per tick.
+    ///
+    /// [`None`] disables the limit.
+    pub fn set_update_budget(&mut self, update_budget: Option<usize>) {
+        self.update_budget = update_budget;
+    }
+
+    /// Returns the maximum size of updates in bytes per tick.
+    pub fn update_budget(&self) -> Option<usize> {
+        self.update_budget
+    }
+
+    /// Increases priority of an entity by `amount` for this client.
+    ///
+    /// The boost linearly decays to zero over `duration`.
+    /// Replaces the previous boost for this entity.
+    pub fn boost(&mut self, entity: Entity, amount: f32, duration: Duration) {
+        if duration.is_zero() {
+            return;
+        }
+
+        self.boosts.insert(
+            entity,
+            PriorityBoost {
+                amount,
+                duration,
+                remaining: duration,
+            },
+        );
+    }
+
+    /// Returns the current priority of an entity for this client.
+    pub fn priority(&self, entity: Entity) -> f32 {
+        1.0 + self
+            .boosts
+            .get(&entity)
+            .map(PriorityBoost::current)
+            .unwrap_or_default()
+    }
+
+    /// Decays boosts and adjusts the threshold based on the size of updates from the previous tick.
+    ///
+    /// Should be called before collecting changes.
+    pub(crate) fn start_tick(&mut self, now: Duration) {
+        let delta = self
+            .last_tick
+            .map(|last_tick| now.saturating_sub(last_tick))
+            .unwrap_or_default();
+        self.last_tick = Some(now);
+
+        self.boosts.retain(|_, boost| {
+            boost.remaining = boost.remaining.saturating_sub(delta);
+            !boost.remaining.is_zero()
+        });
+
+        if let Some(update_budget) = self.update_budget {
+            if self.written > update_budget {
+                self.threshold = self.threshold.max(1.0) * 2.0;
+            } else if self.threshold > 1.0 {
+                self.threshold /= 2.0;
+            } else {
+                self.threshold = 0.0;
+            }
+        }
+        self.written = 0;
+    }
+
+    /// Accumulates priority for a changed entity and returns `true` if its update of `size` bytes should be sent.
+    pub(crate) fn should_send(&mut self, entity: Entity, size: usize) -> bool {
+        if self.update_budget.is_none() {
+            return true;
+        }
+
+        let priority = self.priority(entity);
+        let accumulated = self.accumulated.entry(entity).or_default();
+        *accumulated += priority;
+        if *accumulated < self.threshold {
+            return false;
+        }
+
+        self.accumulated.remove(&entity);
+        self.written += size;
+
+        true
+    }
+
+    /// Removes a despawned entity.
+    pub(super) fn remove_despawned(&mut self, entity: Entity) {
+        self.boosts.remove(&entity);
+        self.accumulated.remove(&entity);
+    }
+
+    /// Resets all data.
+    ///
+    /// Keeps the allocated memory for reuse.
+    pub(super) fn clear(&mut self) {
+        self.update_budget = None;
+        self.boosts.clear();
+        self.accumulated.clear();
+        self.threshold = 0.0;
+        self.written = 0;
+        self.last_tick = None;
+    }
+}
+
+struct PriorityBoost {
+    amount: f32,
+    duration: Duration,
+    remaining: Duration,
+}
+
+impl PriorityBoost {
+    fn current(&self) -> f32 {
+        self.amount * self.remaining.as_secs_f32() / self.duration.as_secs_f32()
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/**
+Recently despawned replicated entities with ticks of their despawns.
+
+Allows late events or debugging tools to distinguish entities that never existed
+from entities that were recently despawned.
+
+Entities are kept for [`Self::retention`] ticks after despawn.
+The initial value is taken from [`ServerPlugin::despawn_retention`](super::ServerPlugin::despawn_retention).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+fn log_late_target(In(entity): In<Entity>, tombstones: Res<DespawnTombstones>) {
+    match tombstones.was_replicated(entity) {
+        Some(tick) => info!("`{entity:?}` was despawned at `{tick:?}`"),
+        None => info!("`{entity:?}` wasn't despawned recently"),
+    }
+}
+```
+**/
+#[derive(Resource)]
+pub struct DespawnTombstones {
+    /// Number of ticks to keep despawned entities.
+    retention: u32,
+
+    /// Despawned entities in the order of despawn.
+    order: VecDeque<(Entity, RepliconTick)>,
+
+    /// Despawn ticks for fast lookup.
+    ticks: EntityHashMap<RepliconTick>,
+}
+
+impl DespawnTombstones {
+    pub(super) fn new(retention: u32) -> Self {
+        Self {
+            retention,
+            order: Default::default(),
+            ticks: Default::default(),
+        }
+    }
+
+    /// Returns the number of ticks for which despawned entities are kept.
+    pub fn retention(&self) -> u32 {
+        self.retention
+    }
+
+    /// Sets the number of ticks for which despawned entities are kept.
+    ///
+    /// Takes effect on the next replication tick.
+    pub fn set_retention(&mut self, retention: u32) {
+        self.retention = retention;
+    }
+
+    /// Returns the despawn tick if the entity was replicated and despawned within the retention window.
+    pub fn was_replicated(&self, entity: Entity) -> Option<RepliconTick> {
+        self.ticks.get(&entity).copied()
+    }
+
+    /// Returns an iterator over despawned entities with their despawn ticks in the order of despawn.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, RepliconTick)> + '_ {
+        self.order.iter().copied()
+    }
+
+    /// Returns the number of stored entities.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns `true` if no entities are stored.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Records despawn of an entity.
+    pub(super) fn insert(&mut self, entity: Entity, tick: RepliconTick) {
+        self.order.push_back((entity, tick));
+        self.ticks.insert(entity, tick);
+    }
+
+    /// Removes entities that were despawned more than [`Self::retention`] ticks ago.
+    pub(super) fn prune(&mut self, tick: RepliconTick) {
+        while let Some(&(entity, despawn_tick)) = self.order.front() {
+            if tick - despawn_tick <= self.retention {
+                break;
+            }
+
+            self.order.pop_front();
+            self.ticks.remove(&entity);
+        }
+    }
+
+    /// Removes all entities.
+    pub(super) fn clear(&mut self) {
+        self.order.clear();
+        self.ticks.clear();
+    }
+}
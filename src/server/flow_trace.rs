@@ -0,0 +1,251 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, Write},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bytes::Bytes;
+use serde::Serialize;
+
+use super::{replicon_server::RepliconServer, server_tick::ServerTick, ServerSet};
+use crate::core::{
+    common_conditions::server_running, replicon_channels::ReplicationChannel,
+    replicon_tick::RepliconTick, update_acks, ClientId,
+};
+
+/// Records messages exchanged with the client selected in [`FlowTrace`].
+pub(super) struct FlowTracePlugin;
+
+impl Plugin for FlowTracePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            record_received
+                .after(ServerSet::ReceivePackets)
+                .before(ServerSet::Receive)
+                .run_if(server_running)
+                .run_if(resource_exists::<FlowTrace>),
+        )
+        .add_systems(
+            PostUpdate,
+            record_sent
+                .after(ServerSet::Send)
+                .before(ServerSet::SendPackets)
+                .run_if(server_running)
+                .run_if(resource_exists::<FlowTrace>),
+        );
+    }
+}
+
+/// Records all messages received from the traced client before they are consumed.
+fn record_received(
+    mut trace: ResMut<FlowTrace>,
+    server: Res<RepliconServer>,
+    server_tick: Res<ServerTick>,
+    time: Res<Time<Real>>,
+) {
+    let client_id = trace.client_id;
+    for (channel_id, message) in server
+        .iter_all_received()
+        .filter(|&(_, sender_id, _)| sender_id == client_id)
+        .map(|(channel_id, _, message)| (channel_id, message))
+    {
+        trace.push(
+            time.elapsed(),
+            FlowDirection::Received,
+            **server_tick,
+            channel_id,
+            message,
+        );
+    }
+}
+
+/// Records all messages sent to the traced client before they are passed to the messaging backend.
+pub(super) fn record_sent(
+    mut trace: ResMut<FlowTrace>,
+    server: Res<RepliconServer>,
+    server_tick: Res<ServerTick>,
+    time: Res<Time<Real>>,
+) {
+    let client_id = trace.client_id;
+    for (_, channel_id, message) in server
+        .iter_sent()
+        .filter(|&&(receiver_id, ..)| receiver_id == client_id)
+    {
+        trace.push(
+            time.elapsed(),
+            FlowDirection::Sent,
+            **server_tick,
+            *channel_id,
+            message,
+        );
+    }
+}
+
+/**
+Records every message exchanged with a single client.
+
+Intended for debugging reported issues: each record contains the time, server tick,
+channel, size and a short decoded summary. Replication messages and acknowledgments
+are decoded, other messages are recorded only with their size.
+
+Recording is enabled by inserting this resource on server.
+The trace can be written as a tab-separated log via [`Self::write_log`] or serialized
+to any other format.
+
+# Examples
+
+```
+use std::fs::File;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::flow_trace::FlowTrace};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.insert_resource(FlowTrace::new(ClientId::new(1)));
+
+// ...
+
+fn save_trace(trace: Res<FlowTrace>) {
+    let file = File::create("flow_trace.tsv").expect("file should be writable");
+    trace.write_log(file).expect("trace should be written");
+}
+```
+**/
+#[derive(Resource, Debug, Serialize)]
+pub struct FlowTrace {
+    client_id: ClientId,
+    records: Vec<FlowRecord>,
+}
+
+impl FlowTrace {
+    /// Creates an empty trace for a client.
+    pub fn new(client_id: ClientId) -> Self {
+        Self {
+            client_id,
+            records: Default::default(),
+        }
+    }
+
+    /// Returns the traced client.
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+
+    /// Returns all records in the order of their processing.
+    pub fn records(&self) -> &[FlowRecord] {
+        &self.records
+    }
+
+    /// Removes all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Writes all records as tab-separated lines with a header.
+    pub fn write_log(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "elapsed\tdirection\ttick\tchannel\tbytes\tsummary")?;
+        for record in &self.records {
+            writeln!(writer, "{record}")?;
+        }
+
+        Ok(())
+    }
+
+    fn push(
+        &mut self,
+        elapsed: Duration,
+        direction: FlowDirection,
+        server_tick: RepliconTick,
+        channel_id: u8,
+        message: &Bytes,
+    ) {
+        self.records.push(FlowRecord {
+            elapsed,
+            direction,
+            server_tick,
+            channel_id,
+            bytes: message.len(),
+            summary: summarize(direction, channel_id, message),
+        });
+    }
+}
+
+/// Decodes headers of replication messages and acknowledgments.
+fn summarize(direction: FlowDirection, channel_id: u8, message: &[u8]) -> String {
+    let init_channel = u8::from(ReplicationChannel::Init);
+    let update_channel = u8::from(ReplicationChannel::Update);
+    match direction {
+        FlowDirection::Sent if channel_id == init_channel => {
+            match bincode::deserialize::<RepliconTick>(message) {
+                Ok(tick) => format!("init message for {tick:?}"),
+                Err(e) => format!("invalid init message: {e}"),
+            }
+        }
+        FlowDirection::Sent if channel_id == update_channel => {
+            match bincode::deserialize::<(RepliconTick, RepliconTick, u16)>(message) {
+                Ok((init_tick, message_tick, update_index)) => format!(
+                    "update message {update_index} for {message_tick:?} after {init_tick:?}"
+                ),
+                Err(e) => format!("invalid update message: {e}"),
+            }
+        }
+        FlowDirection::Received if channel_id == init_channel => {
+            match update_acks::deserialize_acks(message) {
+                Ok(indices) => format!("acks for update messages {indices:?}"),
+                Err(e) => format!("invalid acks: {e}"),
+            }
+        }
+        _ => "event or custom message".into(),
+    }
+}
+
+/// Single message inside [`FlowTrace`].
+#[derive(Clone, Debug, Serialize)]
+pub struct FlowRecord {
+    /// Real time since app startup.
+    pub elapsed: Duration,
+    pub direction: FlowDirection,
+    /// Server tick at the moment of processing.
+    pub server_tick: RepliconTick,
+    pub channel_id: u8,
+    /// Message size in bytes.
+    pub bytes: usize,
+    /// Decoded message header.
+    pub summary: String,
+}
+
+impl Display for FlowRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.6}\t{}\t{}\t{}\t{}\t{}",
+            self.elapsed.as_secs_f64(),
+            self.direction,
+            self.server_tick.get(),
+            self.channel_id,
+            self.bytes,
+            self.summary
+        )
+    }
+}
+
+/// Direction of a message inside [`FlowRecord`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum FlowDirection {
+    /// From server to client.
+    Sent,
+    /// From client to server.
+    Received,
+}
+
+impl Display for FlowDirection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FlowDirection::Sent => f.write_str("server -> client"),
+            FlowDirection::Received => f.write_str("client -> server"),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::{any::Any, sync::Arc};
+
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashMap},
+    prelude::*,
+    ptr::Ptr,
+    utils::HashMap,
+};
+
+use crate::core::replication_fns::{component_fns::ComponentFns, rule_fns::UntypedRuleFns, FnsId};
+
+/// Previous values of components with delta functions or send filters.
+///
+/// Stores bases to serialize differences against values acknowledged by clients,
+/// see [`RuleFns::with_delta`](crate::core::replication_fns::rule_fns::RuleFns::with_delta),
+/// and last sent values to suppress insignificant changes,
+/// see [`RuleFns::with_should_send`](crate::core::replication_fns::rule_fns::RuleFns::with_should_send).
+/// A component with both is cloned only once per change.
+///
+/// Cleared when the server stops.
+#[derive(Default, Resource)]
+pub(crate) struct PreviousValues(EntityHashMap<HashMap<FnsId, ComponentValues>>);
+
+type PreviousValue = Arc<dyn Any + Send + Sync>;
+
+impl PreviousValues {
+    /// Stores a copy of the component as a delta base if it changed since the last stored base.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as `component_fns`.
+    pub(super) unsafe fn record_base(
+        &mut self,
+        entity: Entity,
+        fns_id: FnsId,
+        fns: (&ComponentFns, &UntypedRuleFns),
+        ptr: Ptr,
+        changed_since: impl FnOnce(Tick) -> bool,
+        this_run: Tick,
+    ) {
+        let values = self.0.entry(entity).or_default().entry(fns_id).or_default();
+        if values
+            .bases
+            .last()
+            .is_some_and(|&(last_tick, _)| !changed_since(last_tick))
+        {
+            return;
+        }
+
+        if let Some(base) = values.clone_component(fns, ptr, this_run) {
+            values.bases.push((this_run, base));
+        }
+    }
+
+    /// Returns the newest base stored on or before `tick` if the client is guaranteed to hold it.
+    ///
+    /// Returns [`None`] if the component also changed after `tick` before this run.
+    /// These values could be sent in updates that aren't acknowledged yet, so the client
+    /// may hold a newer value than the base and the whole component should be sent instead.
+    pub(super) fn base(
+        &self,
+        entity: Entity,
+        fns_id: FnsId,
+        tick: Tick,
+        this_run: Tick,
+    ) -> Option<&(dyn Any + Send + Sync)> {
+        let bases = &self.0.get(&entity)?.get(&fns_id)?.bases;
+        if bases
+            .iter()
+            .rev()
+            .take_while(|(base_tick, _)| base_tick.is_newer_than(tick, this_run))
+            .any(|&(base_tick, _)| base_tick != this_run)
+        {
+            return None;
+        }
+
+        bases
+            .iter()
+            .rev()
+            .find(|(base_tick, _)| !base_tick.is_newer_than(tick, this_run))
+            .map(|(_, base)| &**base)
+    }
+
+    /// Removes bases that are older than the base needed for `min_tick`.
+    ///
+    /// If `min_tick` is [`None`], keeps only the latest base.
+    pub(super) fn prune_bases(&mut self, entity: Entity, min_tick: Option<Tick>, this_run: Tick) {
+        let Some(components) = self.0.get_mut(&entity) else {
+            return;
+        };
+
+        for values in components.values_mut() {
+            let keep_from = match min_tick {
+                Some(min_tick) => values
+                    .bases
+                    .iter()
+                    .rposition(|(base_tick, _)| !base_tick.is_newer_than(min_tick, this_run))
+                    .unwrap_or_default(),
+                None => values.bases.len().saturating_sub(1),
+            };
+            values.bases.drain(..keep_from);
+        }
+    }
+
+    /// Stores the component as sent if it passes the filter and returns the tick of the last sent change.
+    ///
+    /// Changes that don't pass the filter don't affect the returned tick, so they won't be considered
+    /// as changes for clients. On insertion the value is always stored.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as `component_fns`.
+    pub(super) unsafe fn update_sent(
+        &mut self,
+        entity: Entity,
+        fns_id: FnsId,
+        (component_fns, rule_fns): (&ComponentFns, &UntypedRuleFns),
+        ptr: Ptr,
+        added: bool,
+        changed_since: impl FnOnce(Tick) -> bool,
+        this_run: Tick,
+    ) -> Tick {
+        let values = self.0.entry(entity).or_default().entry(fns_id).or_default();
+        if let Some((sent_tick, last_sent)) = &values.sent {
+            if !added
+                && !(changed_since(*sent_tick)
+                    && component_fns.should_send(rule_fns, &**last_sent, ptr))
+            {
+                return *sent_tick;
+            }
+        }
+
+        if let Some(value) = values.clone_component((component_fns, rule_fns), ptr, this_run) {
+            values.sent = Some((this_run, value));
+        }
+        this_run
+    }
+
+    /// Removes values of a component that was removed from an entity.
+    pub(super) fn remove_component(&mut self, entity: Entity, fns_id: FnsId) {
+        if let Some(components) = self.0.get_mut(&entity) {
+            components.remove(&fns_id);
+        }
+    }
+
+    /// Removes all values for an entity that was despawned or lost [`Replicated`](crate::core::Replicated).
+    pub(super) fn remove_entity(&mut self, entity: Entity) {
+        self.0.remove(&entity);
+    }
+
+    /// Removes all values.
+    pub(super) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Previous values of a single component.
+#[derive(Default)]
+struct ComponentValues {
+    /// Delta bases with ticks at which they were stored, from oldest to newest.
+    bases: Vec<(Tick, PreviousValue)>,
+
+    /// The last value that passed the send filter with the tick at which it was stored.
+    sent: Option<(Tick, PreviousValue)>,
+}
+
+impl ComponentValues {
+    /// Returns a copy of the component, reusing a value already stored during this run.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as `component_fns`.
+    unsafe fn clone_component(
+        &self,
+        (component_fns, rule_fns): (&ComponentFns, &UntypedRuleFns),
+        ptr: Ptr,
+        this_run: Tick,
+    ) -> Option<PreviousValue> {
+        let stored = self
+            .bases
+            .last()
+            .into_iter()
+            .chain(&self.sent)
+            .find(|&&(tick, _)| tick == this_run);
+        if let Some((_, value)) = stored {
+            return Some(value.clone());
+        }
+
+        component_fns.clone_previous(rule_fns, ptr)
+    }
+}
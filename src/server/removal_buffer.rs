@@ -159,6 +159,28 @@ impl RemovalBuffer {
             .map(|(entity, remove_ids)| (*entity, &**remove_ids))
     }
 
+    /// Registers a single component removal for an entity.
+    ///
+    /// Used to remove components whose replication conditions are no longer met.
+    pub(super) fn insert(&mut self, entity: Entity, fns_info: FnsInfo) {
+        if let Some((_, removed_ids)) = self
+            .removals
+            .iter_mut()
+            .find(|&&mut (removed_entity, _)| removed_entity == entity)
+        {
+            if removed_ids
+                .iter()
+                .all(|removed_info| removed_info.component_id() != fns_info.component_id())
+            {
+                removed_ids.push(fns_info);
+            }
+        } else {
+            let mut removed_ids = self.ids_buffer.pop().unwrap_or_default();
+            removed_ids.push(fns_info);
+            self.removals.push((entity, removed_ids));
+        }
+    }
+
     /// Registers component removals that match replication rules for an entity.
     fn update(
         &mut self,
@@ -0,0 +1,168 @@
+use bevy::{
+    ecs::{component::ComponentId, system::EntityCommands, world::Command},
+    prelude::*,
+};
+
+use super::connected_clients::ConnectedClients;
+use crate::{core::replication_rules::ReplicationRules, server::VisibilityPolicy, Replicated};
+
+/// Extension for [`Commands`] to duplicate replicated entities on server.
+pub trait ReplicatedCloneExt {
+    /**
+    Spawns a copy of a replicated entity.
+
+    Only [`Replicated`] and components from matching replication rules are cloned via reflection.
+    The copy is a new entity from the replication perspective: all its components have fresh
+    change ticks, so it will be sent to clients as a regular spawn.
+    Visibility for connected clients is configured according to [`CloneVisibility`].
+
+    If the source entity doesn't exist, the spawned entity will be despawned.
+
+    # Panics
+
+    Panics if any cloned component is not registered using [`App::register_type`]
+    or `#[reflect(Component)]` is missing.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.register_type::<Health>()
+        .replicate::<Health>()
+        .add_systems(Update, split);
+
+    /// Duplicates all splitting slimes.
+    fn split(mut commands: Commands, slimes: Query<Entity, (With<Health>, With<Splitting>)>) {
+        for entity in &slimes {
+            commands
+                .clone_replicated(entity, CloneVisibility::Copy)
+                .insert(Transform::from_xyz(1.0, 0.0, 0.0));
+            commands.entity(entity).remove::<Splitting>();
+        }
+    }
+
+    #[derive(Component, Deserialize, Reflect, Serialize)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Splitting;
+    ```
+    **/
+    fn clone_replicated(&mut self, entity: Entity, visibility: CloneVisibility) -> EntityCommands;
+}
+
+impl ReplicatedCloneExt for Commands<'_, '_> {
+    fn clone_replicated(&mut self, entity: Entity, visibility: CloneVisibility) -> EntityCommands {
+        let target = self.spawn_empty().id();
+        self.add(CloneReplicated {
+            source: entity,
+            target,
+            visibility,
+        });
+
+        self.entity(target)
+    }
+}
+
+/// Configures visibility of cloned entities.
+///
+/// See [`ReplicatedCloneExt::clone_replicated`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CloneVisibility {
+    /// Copy visibility of the source entity for each connected client.
+    #[default]
+    Copy,
+    /// Use the default visibility for the current [`VisibilityPolicy`].
+    Reset,
+}
+
+/// Command that clones replicated components from one entity to another.
+struct CloneReplicated {
+    source: Entity,
+    target: Entity,
+    visibility: CloneVisibility,
+}
+
+impl Command for CloneReplicated {
+    fn apply(self, world: &mut World) {
+        let Some(source) = world.get_entity(self.source) else {
+            debug!("ignoring clone of missing {:?}", self.source);
+            if let Some(target) = world.get_entity_mut(self.target) {
+                target.despawn();
+            }
+            return;
+        };
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let rules = world.resource::<ReplicationRules>();
+        let mut cloned_ids: Vec<ComponentId> = Vec::new();
+        let mut components = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.matches(source.archetype())) {
+            for component_id in rule
+                .components
+                .iter()
+                .map(|fns_info| fns_info.component_id())
+            {
+                if cloned_ids.contains(&component_id) {
+                    continue;
+                }
+                cloned_ids.push(component_id);
+
+                // SAFETY: replication rules can be registered only with valid component IDs.
+                let replicated_component =
+                    unsafe { world.components().get_info_unchecked(component_id) };
+                let type_name = replicated_component.name();
+                let type_id = replicated_component
+                    .type_id()
+                    .unwrap_or_else(|| panic!("{type_name} should have registered TypeId"));
+                let registration = registry
+                    .get(type_id)
+                    .unwrap_or_else(|| panic!("{type_name} should be registered"));
+                let reflect_component = registration
+                    .data::<ReflectComponent>()
+                    .unwrap_or_else(|| panic!("{type_name} should have reflect(Component)"));
+
+                let component = reflect_component
+                    .reflect(source)
+                    .unwrap_or_else(|| panic!("entity should have {type_name}"));
+
+                components.push((reflect_component.clone(), component.clone_value()));
+            }
+        }
+
+        let mut target = world.entity_mut(self.target);
+        target.insert(Replicated);
+        for (reflect_component, component) in components {
+            reflect_component.insert(&mut target, &*component, &registry);
+        }
+
+        debug!(
+            "cloned {:?} into {:?} with {:?} visibility",
+            self.source, self.target, self.visibility
+        );
+
+        if self.visibility == CloneVisibility::Copy {
+            if let Some(mut connected_clients) = world.get_resource_mut::<ConnectedClients>() {
+                let default_visible = match connected_clients.visibility_policy() {
+                    VisibilityPolicy::All => return,
+                    VisibilityPolicy::Blacklist => true,
+                    VisibilityPolicy::Whitelist => false,
+                };
+
+                for client in connected_clients.iter_mut() {
+                    let visible = client.visibility().is_visible(self.source);
+                    if visible != default_visible {
+                        client.visibility_mut().set_visibility(self.target, visible);
+                    }
+                }
+            }
+        }
+    }
+}
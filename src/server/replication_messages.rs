@@ -1,4 +1,5 @@
 use std::{
+    any::Any,
     io::{Cursor, Write},
     mem,
     time::Duration,
@@ -11,7 +12,8 @@ use varint_rs::VarintWriter;
 
 use super::{
     client_entity_map::ClientMapping,
-    connected_clients::{ClientBuffers, ConnectedClients},
+    client_names::{ClientLabel, ClientNames},
+    connected_clients::{client_groups::ClientGroups, ClientBuffers, ConnectedClients},
     replicon_server::RepliconServer,
     ConnectedClient,
 };
@@ -59,8 +61,14 @@ impl ReplicationMessages {
     }
 
     /// Returns iterator over messages for each client.
+    ///
+    /// Skips [held](ConnectedClient::is_held) clients.
     pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut (InitMessage, UpdateMessage)> {
-        self.data.iter_mut().take(self.connected_clients.len())
+        self.data
+            .iter_mut()
+            .zip(self.connected_clients.iter())
+            .filter(|(_, client)| !client.is_held())
+            .map(|(messages, _)| messages)
     }
 
     /// Same as [`Self::iter_mut`], but also includes [`ConnectedClient`].
@@ -70,11 +78,41 @@ impl ReplicationMessages {
         self.data
             .iter_mut()
             .zip(self.connected_clients.iter_mut())
+            .filter(|(_, client)| !client.is_held())
             .map(|((init_message, update_message), client)| (init_message, update_message, client))
     }
 
+    /// Returns iterator over [held](ConnectedClient::is_held) clients.
+    ///
+    /// Used to update their state when it's necessary even without replication.
+    pub(super) fn iter_held_clients_mut(&mut self) -> impl Iterator<Item = &mut ConnectedClient> {
+        self.connected_clients
+            .iter_mut()
+            .filter(|client| client.is_held())
+    }
+
+    /// Same as [`Self::iter_mut_with_clients`], but also returns client groups.
+    pub(super) fn iter_mut_with_clients_and_groups(
+        &mut self,
+    ) -> (
+        impl Iterator<Item = (&mut InitMessage, &mut UpdateMessage, &mut ConnectedClient)>,
+        &ClientGroups,
+    ) {
+        let (clients, groups) = self.connected_clients.iter_mut_with_groups();
+        let iter = self
+            .data
+            .iter_mut()
+            .zip(clients)
+            .filter(|(_, client)| !client.is_held())
+            .map(|((init_message, update_message), client)| (init_message, update_message, client));
+
+        (iter, groups)
+    }
+
     /// Sends cached messages to clients specified in the last [`Self::prepare`] call.
     ///
+    /// Skips [held](ConnectedClient::is_held) clients.
+    ///
     /// The change tick of each client with an init message is updated to equal the latest replicon tick.
     /// messages were sent to clients. If only update messages were sent (or no messages at all) then
     /// it will equal the input `last_change_tick`.
@@ -82,18 +120,24 @@ impl ReplicationMessages {
         &mut self,
         server: &mut RepliconServer,
         client_buffers: &mut ClientBuffers,
+        names: &ClientNames,
         replicon_tick: RepliconTick,
         tick: Tick,
         timestamp: Duration,
     ) -> bincode::Result<ConnectedClients> {
-        for ((init_message, update_message), client) in
-            self.data.iter_mut().zip(self.connected_clients.iter_mut())
+        for ((init_message, update_message), client) in self
+            .data
+            .iter_mut()
+            .zip(self.connected_clients.iter_mut())
+            .filter(|(_, client)| !client.is_held())
         {
-            init_message.send(server, client, replicon_tick)?;
+            let label = names.label(client.id());
+            init_message.send(server, client, label, replicon_tick)?;
             update_message.send(
                 server,
                 client_buffers,
                 client,
+                label,
                 replicon_tick,
                 tick,
                 timestamp,
@@ -109,7 +153,7 @@ impl ReplicationMessages {
 
 /// A reusable message with replicated data.
 ///
-/// Contains tick and mappings, insertions, removals and despawns that
+/// Contains tick and mappings, insertions, removals, despawns and hidden entities that
 /// happened on this tick.
 /// Sent over [`ReplicationChannel::Init`] channel.
 ///
@@ -296,7 +340,7 @@ impl InitMessage {
         component_fns: &ComponentFns,
         ctx: &SerializeCtx,
         fns_id: FnsId,
-        ptr: Ptr,
+        ptrs: &[Ptr],
     ) -> bincode::Result<()> {
         if self.entity_data_size == 0 {
             self.write_data_entity()?;
@@ -304,8 +348,8 @@ impl InitMessage {
 
         let size = write_with(shared_bytes, &mut self.cursor, |cursor| {
             DefaultOptions::new().serialize_into(&mut *cursor, &fns_id)?;
-            // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
-            unsafe { component_fns.serialize(ctx, rule_fns, ptr, cursor) }
+            // SAFETY: `component_fns`, `ptrs` and `rule_fns` were created for the same component type.
+            unsafe { component_fns.serialize(ctx, rule_fns, ptrs, cursor) }
         })?;
 
         self.entity_data_size = self
@@ -337,6 +381,22 @@ impl InitMessage {
         Ok(())
     }
 
+    /// Serializes custom section data with its ID as an array element.
+    ///
+    /// Should be called only inside an array and increases its length by 1.
+    /// See also [`Self::start_array`].
+    pub(super) fn write_section(&mut self, id: u8, bytes: &[u8]) -> bincode::Result<()> {
+        bincode::serialize_into(&mut self.cursor, &id)?;
+        DefaultOptions::new().serialize_into(&mut self.cursor, &(bytes.len() as u64))?;
+        self.cursor.write_all(bytes)?;
+        self.array_len = self
+            .array_len
+            .checked_add(1)
+            .ok_or(bincode::ErrorKind::SizeLimit)?;
+
+        Ok(())
+    }
+
     /// Removes entity data elements from update message and copies it.
     ///
     /// Ends entity data for the update message.
@@ -381,6 +441,7 @@ impl InitMessage {
         &self,
         server: &mut RepliconServer,
         client: &mut ConnectedClient,
+        label: ClientLabel<'_>,
         replicon_tick: RepliconTick,
     ) -> bincode::Result<()> {
         debug_assert_eq!(self.array_len, 0);
@@ -388,7 +449,7 @@ impl InitMessage {
 
         let slice = self.as_slice();
         if slice.is_empty() {
-            trace!("no init data to send for {:?}", client.id());
+            trace!("no init data to send for {label}");
             return Ok(());
         }
 
@@ -397,7 +458,7 @@ impl InitMessage {
         let mut header = [0; mem::size_of::<RepliconTick>()];
         bincode::serialize_into(&mut header[..], &replicon_tick)?;
 
-        trace!("sending init message to {:?}", client.id());
+        trace!("sending init message to {label}");
         server.send(
             client.id(),
             ReplicationChannel::Init,
@@ -513,6 +574,23 @@ impl UpdateMessage {
         Ok(())
     }
 
+    /// Discards the data of the current entity.
+    ///
+    /// Used to skip entities that don't fit into the client's update budget.
+    pub(super) fn discard_entity_data(&mut self) {
+        self.cursor.set_position(self.entity_data_pos);
+        self.entity_data_size = 0;
+    }
+
+    /// Returns size of the current entity data including its header.
+    pub(super) fn entity_data_size(&self) -> usize {
+        if self.entity_data_size == 0 {
+            return 0;
+        }
+
+        (self.cursor.position() - self.entity_data_pos) as usize
+    }
+
     /// Serializes component and its replication functions ID as an element of entity data.
     ///
     /// Reuses previously shared bytes if they exist, or updates them.
@@ -525,7 +603,7 @@ impl UpdateMessage {
         component_fns: &ComponentFns,
         ctx: &SerializeCtx,
         fns_id: FnsId,
-        ptr: Ptr,
+        ptrs: &[Ptr],
     ) -> bincode::Result<()> {
         if self.entity_data_size == 0 {
             self.write_data_entity()?;
@@ -533,8 +611,8 @@ impl UpdateMessage {
 
         let size = write_with(shared_bytes, &mut self.cursor, |cursor| {
             DefaultOptions::new().serialize_into(&mut *cursor, &fns_id)?;
-            // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
-            unsafe { component_fns.serialize(ctx, rule_fns, ptr, cursor) }
+            // SAFETY: `component_fns`, `ptrs` and `rule_fns` were created for the same component type.
+            unsafe { component_fns.serialize(ctx, rule_fns, ptrs, cursor) }
         })?;
 
         self.entity_data_size = self
@@ -545,6 +623,38 @@ impl UpdateMessage {
         Ok(())
     }
 
+    /// Same as [`Self::write_component`], but serializes the difference against `base`.
+    ///
+    /// The difference is specific to the client, so bytes are never shared.
+    /// Returns the written bytes.
+    pub(super) fn write_component_delta(
+        &mut self,
+        rule_fns: &UntypedRuleFns,
+        component_fns: &ComponentFns,
+        ctx: &SerializeCtx,
+        fns_id: FnsId,
+        base: &(dyn Any + Send + Sync),
+        ptr: Ptr,
+    ) -> bincode::Result<&[u8]> {
+        if self.entity_data_size == 0 {
+            self.write_data_entity()?;
+        }
+
+        let mut bytes = None;
+        let size = write_with(&mut bytes, &mut self.cursor, |cursor| {
+            DefaultOptions::new().serialize_into(&mut *cursor, &fns_id)?;
+            // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
+            unsafe { component_fns.serialize_delta(ctx, rule_fns, base, ptr, cursor) }
+        })?;
+
+        self.entity_data_size = self
+            .entity_data_size
+            .checked_add(size)
+            .ok_or(bincode::ErrorKind::SizeLimit)?;
+
+        Ok(bytes.unwrap_or_default())
+    }
+
     /// Returns the serialized data as a byte array.
     fn as_slice(&self) -> &[u8] {
         let slice = self.cursor.get_ref();
@@ -560,6 +670,7 @@ impl UpdateMessage {
         server: &mut RepliconServer,
         client_buffers: &mut ClientBuffers,
         client: &mut ConnectedClient,
+        label: ClientLabel<'_>,
         replicon_tick: RepliconTick,
         tick: Tick,
         timestamp: Duration,
@@ -568,19 +679,19 @@ impl UpdateMessage {
 
         let mut slice = self.as_slice();
         if slice.is_empty() {
-            trace!("no updates to send for {:?}", client.id());
+            trace!("no updates to send for {label}");
             return Ok(());
         }
 
-        trace!("sending update message(s) to {:?}", client.id());
+        trace!("sending update message(s) to {label}");
         const TICKS_SIZE: usize = 2 * mem::size_of::<RepliconTick>();
         let mut header = [0; TICKS_SIZE + mem::size_of::<u16>()];
         bincode::serialize_into(&mut header[..], &(client.change_tick(), replicon_tick))?;
 
         let mut message_size = 0;
         let client_id = client.id();
-        let (mut update_index, mut entities) =
-            client.register_update(client_buffers, tick, timestamp);
+        let (mut update_index, mut entities, mut update_size) =
+            client.register_update(client_buffers, tick, replicon_tick, timestamp);
         for &(entity, data_size) in &self.entities {
             // Try to pack back first, then try to pack forward.
             if message_size == 0
@@ -595,6 +706,7 @@ impl UpdateMessage {
                 message_size = data_size;
 
                 bincode::serialize_into(&mut header[TICKS_SIZE..], &update_index)?;
+                *update_size = header.len() + message.len();
 
                 server.send(
                     client_id,
@@ -603,14 +715,15 @@ impl UpdateMessage {
                 );
 
                 if !slice.is_empty() {
-                    (update_index, entities) =
-                        client.register_update(client_buffers, tick, timestamp);
+                    (update_index, entities, update_size) =
+                        client.register_update(client_buffers, tick, replicon_tick, timestamp);
                 }
             }
         }
 
         if !slice.is_empty() {
             bincode::serialize_into(&mut header[TICKS_SIZE..], &update_index)?;
+            *update_size = header.len() + slice.len();
 
             server.send(
                 client_id,
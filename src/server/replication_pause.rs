@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+/**
+Pauses collection of replicated changes on server.
+
+While paused, the server doesn't collect or send any replication data, but it still
+buffers despawns, removals and client mappings to send them after resuming.
+Component changes that happened during the pause are handled according to [`ResumeMode`].
+
+Useful for server-side pauses or cinematics, when changes are irrelevant for clients,
+so unpausing won't result in a burst of accumulated updates.
+
+# Examples
+
+Pause replication while a cinematic is playing:
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_systems(
+    Update,
+    (
+        pause.run_if(resource_added::<Cinematic>),
+        resume.run_if(resource_removed::<Cinematic>()),
+    ),
+);
+
+fn pause(mut pause: ResMut<ReplicationPause>) {
+    pause.pause();
+}
+
+fn resume(mut pause: ResMut<ReplicationPause>) {
+    pause.resume(ResumeMode::Resync);
+}
+
+#[derive(Resource)]
+struct Cinematic;
+```
+**/
+#[derive(Resource, Default, Debug)]
+pub struct ReplicationPause {
+    paused: bool,
+
+    /// Mode that will be applied on the next replication after resuming.
+    resume_mode: Option<ResumeMode>,
+}
+
+impl ReplicationPause {
+    /// Pauses replication until [`Self::resume`] is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.resume_mode = None;
+    }
+
+    /// Resumes paused replication.
+    ///
+    /// Does nothing if replication isn't paused.
+    pub fn resume(&mut self, mode: ResumeMode) {
+        if self.paused {
+            self.paused = false;
+            self.resume_mode = Some(mode);
+        }
+    }
+
+    /// Returns `true` if replication is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Takes the mode that should be applied in this replication.
+    pub(super) fn take_resume_mode(&mut self) -> Option<ResumeMode> {
+        self.resume_mode.take()
+    }
+}
+
+/// Defines how component changes from a pause will be handled after [`ReplicationPause::resume`].
+///
+/// Spawns, despawns, insertions and removals are always replicated.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ResumeMode {
+    /// Component changes made before the first replication after resuming won't be sent.
+    ///
+    /// Clients will keep their previous values until the components change again.
+    #[default]
+    Discard,
+    /// All replicated entities will be sent again with all their components over the reliable channel.
+    ///
+    /// Use it after long pauses to bring clients into a consistent state.
+    Resync,
+}
+
+/// Returns `true` if replication is paused via [`ReplicationPause`].
+pub fn replication_paused(pause: Res<ReplicationPause>) -> bool {
+    pause.is_paused()
+}
@@ -1,4 +1,6 @@
-use bevy::prelude::*;
+use std::mem;
+
+use bevy::{prelude::*, utils::HashSet};
 use bytes::Bytes;
 
 use crate::core::ClientId;
@@ -26,6 +28,17 @@ pub struct RepliconServer {
 
     /// List of sent messages for each channel since the last tick.
     sent_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// Indicates that [`Self::reset_clients`] was called since the last replication.
+    reset_requested: bool,
+
+    /// Function to pass messages to the messaging backend from [`Self::flush_now`].
+    flush_fn: Option<ServerFlushFn>,
+
+    /// Clients connected in-process, such as virtual clients or the single-player client.
+    ///
+    /// Their messages are delivered without the messaging backend, so they are never flushed.
+    local_clients: HashSet<ClientId>,
 }
 
 impl RepliconServer {
@@ -41,6 +54,14 @@ impl RepliconServer {
         }
         self.sent_messages
             .retain(|&(sender_id, ..)| sender_id != client_id);
+        self.local_clients.remove(&client_id);
+    }
+
+    /// Marks a client as connected in-process.
+    ///
+    /// Messages for it will be skipped by [`Self::flush_now`].
+    pub(crate) fn insert_local_client(&mut self, client_id: ClientId) {
+        self.local_clients.insert(client_id);
     }
 
     /// Receives all available messages from clients over a channel.
@@ -64,6 +85,73 @@ impl RepliconServer {
         receive_channel.drain(..)
     }
 
+    /**
+    Takes all available messages from clients over a channel as an owned batch.
+
+    Unlike [`Self::receive`], the batch doesn't borrow the server, so it can be moved into a task
+    or processed by a parallel iterator while the server is used by other systems.
+    The channel keeps capacity for the same number of messages, so taking batches every tick
+    doesn't cause reallocations when new messages arrive.
+
+    # Examples
+
+    Validate messages in parallel:
+
+    ```
+    use bevy::{prelude::*, tasks::{ComputeTaskPool, ParallelSlice}};
+    use bevy_replicon::prelude::*;
+
+    fn validate_inputs(mut server: ResMut<RepliconServer>) {
+        let batch = server.take_received(0);
+        let valid = batch.messages().par_chunk_map(
+            ComputeTaskPool::get(),
+            256,
+            |_, messages| messages.iter().filter(|(_, message)| !message.is_empty()).count(),
+        );
+        // ...
+    }
+    ```
+    */
+    pub fn take_received<I: Into<u8>>(&mut self, channel_id: I) -> ReceivedBatch {
+        if !self.running {
+            warn!("trying to receive a message when the server is not running");
+        }
+
+        let channel_id = channel_id.into();
+        let receive_channel = self
+            .received_messages
+            .get_mut(channel_id as usize)
+            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
+
+        let capacity = receive_channel.len();
+        ReceivedBatch {
+            channel_id,
+            messages: mem::replace(receive_channel, Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Takes all available messages from clients, split into owned batches by channel.
+    ///
+    /// Channels without messages are skipped.
+    /// See also [`Self::take_received`].
+    pub fn split_received(&mut self) -> impl Iterator<Item = ReceivedBatch> + '_ {
+        if !self.running {
+            warn!("trying to receive a message when the server is not running");
+        }
+
+        self.received_messages
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, messages)| !messages.is_empty())
+            .map(|(channel_id, messages)| {
+                let capacity = messages.len();
+                ReceivedBatch {
+                    channel_id: channel_id as u8,
+                    messages: mem::replace(messages, Vec::with_capacity(capacity)),
+                }
+            })
+    }
+
     /// Sends a message to a client over a channel.
     pub fn send<I: Into<u8>, B: Into<Bytes>>(
         &mut self,
@@ -91,17 +179,141 @@ impl RepliconServer {
                 receive_channel.clear();
             }
             self.sent_messages.clear();
+            self.local_clients.clear();
+            self.reset_requested = false;
         }
 
         self.running = running;
     }
 
+    /// Sets a function that passes a message to the messaging backend immediately.
+    ///
+    /// Should be called only from the messaging backend. The function usually
+    /// forwards messages into a queue or socket that the backend owns, for example, via a channel sender.
+    /// Without it, [`Self::flush_now`] does nothing.
+    pub fn set_flush_fn(
+        &mut self,
+        flush_fn: impl FnMut(ClientId, u8, Bytes) + Send + Sync + 'static,
+    ) {
+        self.flush_fn = Some(Box::new(flush_fn));
+    }
+
+    /**
+    Hands all messages sent so far to the messaging backend immediately instead of waiting for
+    [`ServerSet::SendPackets`](super::ServerSet::SendPackets).
+
+    Useful for latency-critical moments, such as hit confirmations.
+    Only messages already passed to [`Self::send`] are flushed. Server events are serialized in
+    [`ServerSet::Send`](super::ServerSet::Send), so to flush an event mid-frame, send it via
+    [`send_with`](crate::network_event::server_event::send_with) first.
+    Flushed messages aren't visible for systems that inspect sent messages before sending packets.
+    Messages for clients connected in-process, such as [`VirtualClients`](super::virtual_clients::VirtualClients),
+    aren't flushed and delivered as usual.
+
+    Does nothing if the messaging backend didn't set a function via [`Self::set_flush_fn`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    fn confirm_hit(mut server: ResMut<RepliconServer>) {
+        # let (client_id, channel_id, message) = (ClientId::new(1), 0, vec![1]);
+        server.send(client_id, channel_id, message);
+        server.flush_now();
+    }
+    ```
+    */
+    pub fn flush_now(&mut self) {
+        let Some(flush_fn) = &mut self.flush_fn else {
+            trace!("ignoring flush because the messaging backend doesn't support it");
+            return;
+        };
+
+        let (local_messages, messages): (Vec<_>, Vec<_>) = mem::take(&mut self.sent_messages)
+            .into_iter()
+            .partition(|(client_id, ..)| self.local_clients.contains(client_id));
+        self.sent_messages = local_messages;
+
+        trace!("flushing {} messages", messages.len());
+        for (client_id, channel_id, message) in messages {
+            (flush_fn)(client_id, channel_id, message);
+        }
+    }
+
+    /**
+    Requests all clients to despawn their replicated entities and reset mappings.
+
+    Will be sent on the next replication tick, ordered with other replication messages.
+    All entities that are still replicated on the server will be sent again as newly spawned
+    after the reset, so despawning them individually is not required.
+
+    Useful when the entire world changes, such as on match end or map change.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    fn change_map(mut commands: Commands, mut server: ResMut<RepliconServer>, entities: Query<Entity, With<Replicated>>) {
+        for entity in &entities {
+            commands.entity(entity).despawn_recursive();
+        }
+        server.reset_clients();
+        // Spawn entities for the new map...
+    }
+    ```
+    **/
+    pub fn reset_clients(&mut self) {
+        debug!("requesting clients reset");
+        self.reset_requested = true;
+    }
+
+    /// Returns `true` if [`Self::reset_clients`] was called since the last call of this function.
+    pub(super) fn take_reset_request(&mut self) -> bool {
+        mem::take(&mut self.reset_requested)
+    }
+
     /// Returns `true` if the server is running.
     #[inline]
     pub fn is_running(&self) -> bool {
         self.running
     }
 
+    /// Returns an iterator over received messages for a channel without removing them.
+    ///
+    /// Used for testing.
+    #[cfg(feature = "test_utils")]
+    pub(crate) fn iter_received<I: Into<u8>>(
+        &self,
+        channel_id: I,
+    ) -> impl Iterator<Item = &(ClientId, Bytes)> {
+        let channel_id = channel_id.into();
+        self.received_messages
+            .get(channel_id as usize)
+            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"))
+            .iter()
+    }
+
+    /// Returns an iterator over received messages for all channels without removing them.
+    pub(super) fn iter_all_received(&self) -> impl Iterator<Item = (u8, ClientId, &Bytes)> {
+        self.received_messages
+            .iter()
+            .enumerate()
+            .flat_map(|(channel_id, messages)| {
+                messages
+                    .iter()
+                    .map(move |(client_id, message)| (channel_id as u8, *client_id, message))
+            })
+    }
+
+    /// Returns an iterator over sent messages without removing them.
+    pub(super) fn iter_sent(&self) -> impl Iterator<Item = &(ClientId, u8, Bytes)> {
+        self.sent_messages.iter()
+    }
+
     /// Retains only the messages specified by the predicate.
     ///
     /// Used for testing.
@@ -142,3 +354,50 @@ impl RepliconServer {
         receive_channel.push((client_id, message.into()));
     }
 }
+
+/// Messages received over a single channel, detached from [`RepliconServer`].
+///
+/// Returned by [`RepliconServer::take_received`] and [`RepliconServer::split_received`].
+#[derive(Clone, Debug, Default)]
+pub struct ReceivedBatch {
+    channel_id: u8,
+    messages: Vec<(ClientId, Bytes)>,
+}
+
+impl ReceivedBatch {
+    /// Returns the channel over which the messages were received.
+    pub fn channel_id(&self) -> u8 {
+        self.channel_id
+    }
+
+    /// Returns messages with their senders in the receiving order.
+    ///
+    /// Can be processed in parallel via [`ParallelSlice`](bevy::tasks::ParallelSlice).
+    pub fn messages(&self) -> &[(ClientId, Bytes)] {
+        &self.messages
+    }
+
+    /// Returns the number of messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if the batch contains no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl IntoIterator for ReceivedBatch {
+    type Item = (ClientId, Bytes);
+    type IntoIter = std::vec::IntoIter<(ClientId, Bytes)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.messages.into_iter()
+    }
+}
+
+/// Signature of functions that pass messages to the messaging backend on server.
+///
+/// See [`RepliconServer::set_flush_fn`].
+pub type ServerFlushFn = Box<dyn FnMut(ClientId, u8, Bytes) + Send + Sync>;
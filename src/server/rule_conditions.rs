@@ -0,0 +1,120 @@
+use bevy::{
+    ecs::{entity::EntityHashMap, system::SystemChangeTick},
+    prelude::*,
+    utils::HashSet,
+};
+
+use super::replicated_archetypes::ReplicatedArchetypes;
+use crate::core::{
+    replication_fns::{FnsId, FnsInfo, ReplicationFns},
+    Replicated,
+};
+
+/// Tracks components whose replication conditions aren't met.
+///
+/// See [`RuleFns::with_condition`](crate::core::replication_fns::rule_fns::RuleFns::with_condition).
+#[derive(Default)]
+pub(crate) struct RuleConditions {
+    /// Components that shouldn't be replicated, grouped by entity.
+    unmet: EntityHashMap<HashSet<FnsId>>,
+
+    /// Components whose conditions became met during this tick.
+    restored: HashSet<(Entity, FnsId)>,
+
+    /// Components whose conditions stopped being met during this tick.
+    ///
+    /// Should be sent as removals.
+    removed: Vec<(Entity, FnsInfo)>,
+}
+
+impl RuleConditions {
+    /// Evaluates conditions for all replicated components that have them.
+    ///
+    /// Results of the previous call are cleared.
+    pub(super) fn update(
+        &mut self,
+        world: &World,
+        replicated_archetypes: &ReplicatedArchetypes,
+        replication_fns: &ReplicationFns,
+        change_tick: &SystemChangeTick,
+    ) {
+        self.restored.clear();
+        self.removed.clear();
+
+        for replicated_archetype in replicated_archetypes.iter() {
+            if !replicated_archetype
+                .components
+                .iter()
+                .any(|replicated_component| {
+                    let (_, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                    rule_fns.condition().is_some()
+                })
+            {
+                continue;
+            }
+
+            // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+            let archetype = unsafe {
+                world
+                    .archetypes()
+                    .get(replicated_archetype.id)
+                    .unwrap_unchecked()
+            };
+
+            for entity in archetype.entities() {
+                let entity = world.entity(entity.id());
+                // The client hasn't received anything yet for just replicated entities.
+                let new_entity = entity
+                    .get_change_ticks::<Replicated>()
+                    .is_some_and(|ticks| {
+                        ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                    });
+
+                for replicated_component in &replicated_archetype.components {
+                    let (_, rule_fns) = replication_fns.get(replicated_component.fns_id);
+                    let Some(condition) = rule_fns.condition() else {
+                        continue;
+                    };
+
+                    let fns_id = replicated_component.fns_id;
+                    if (condition)(entity) {
+                        let restored = self
+                            .unmet
+                            .get_mut(&entity.id())
+                            .is_some_and(|fns_ids| fns_ids.remove(&fns_id));
+                        if restored && !new_entity {
+                            self.restored.insert((entity.id(), fns_id));
+                        }
+                    } else if self.unmet.entry(entity.id()).or_default().insert(fns_id)
+                        && !new_entity
+                    {
+                        let fns_info = FnsInfo::new(replicated_component.component_id, fns_id);
+                        self.removed.push((entity.id(), fns_info));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the component condition isn't met for the entity.
+    pub(super) fn is_unmet(&self, entity: Entity, fns_id: FnsId) -> bool {
+        self.unmet
+            .get(&entity)
+            .is_some_and(|fns_ids| fns_ids.contains(&fns_id))
+    }
+
+    /// Returns `true` if the component condition became met for the entity during this tick.
+    pub(super) fn is_restored(&self, entity: Entity, fns_id: FnsId) -> bool {
+        self.restored.contains(&(entity, fns_id))
+    }
+
+    /// Returns an iterator over components whose conditions stopped being met during this tick.
+    pub(super) fn removed(&self) -> impl Iterator<Item = (Entity, FnsInfo)> + '_ {
+        self.removed.iter().copied()
+    }
+
+    /// Removes all data for a despawned entity.
+    pub(super) fn remove_despawned(&mut self, entity: Entity) {
+        self.unmet.remove(&entity);
+    }
+}
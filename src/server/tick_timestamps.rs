@@ -0,0 +1,71 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::ecs::component::Tick;
+
+/// Timestamps of recent replication runs.
+///
+/// Used to translate time-based mutation timeouts into system ticks
+/// (see [`RuleFns::with_mutation_timeout`](crate::core::replication_fns::rule_fns::RuleFns::with_mutation_timeout)).
+#[derive(Default)]
+pub(crate) struct TickTimestamps(VecDeque<(Tick, Duration)>);
+
+impl TickTimestamps {
+    /// Records the time of a replication run.
+    ///
+    /// Keeps only the entries needed to resolve `max_timeout`.
+    pub(super) fn record(&mut self, tick: Tick, timestamp: Duration, max_timeout: Duration) {
+        self.0.push_back((tick, timestamp));
+
+        let min_timestamp = timestamp.saturating_sub(max_timeout);
+        while self
+            .0
+            .get(1)
+            .is_some_and(|&(_, timestamp)| timestamp <= min_timestamp)
+        {
+            self.0.pop_front();
+        }
+    }
+
+    /// Returns the last tick that happened at least `timeout` before `now`.
+    ///
+    /// Changes that happened on or before this tick exceed the timeout.
+    pub(super) fn expiration_tick(&self, timeout: Duration, now: Duration) -> Option<Tick> {
+        let min_timestamp = now.checked_sub(timeout)?;
+        let index = self
+            .0
+            .partition_point(|&(_, timestamp)| timestamp <= min_timestamp);
+        index.checked_sub(1).map(|index| self.0[index].0)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiration() {
+        let mut timestamps = TickTimestamps::default();
+        let timeout = Duration::from_secs(2);
+        for index in 0..5 {
+            timestamps.record(Tick::new(index), Duration::from_secs(index.into()), timeout);
+        }
+
+        assert_eq!(timestamps.0.len(), 3, "older entries should be removed");
+        assert_eq!(
+            timestamps.expiration_tick(timeout, Duration::from_secs(4)),
+            Some(Tick::new(2))
+        );
+        assert_eq!(
+            timestamps.expiration_tick(Duration::from_secs(1), Duration::from_secs(4)),
+            Some(Tick::new(3))
+        );
+        assert_eq!(
+            timestamps.expiration_tick(Duration::from_secs(10), Duration::from_secs(4)),
+            None
+        );
+    }
+}
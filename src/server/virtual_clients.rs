@@ -0,0 +1,278 @@
+use std::mem;
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::{
+    client_names::{ClientLabel, ClientNames},
+    flow_trace,
+    replicon_server::RepliconServer,
+    ServerEvent, ServerSet,
+};
+use crate::core::{
+    common_conditions::{server_just_stopped, server_running},
+    replicon_channels::ReplicationChannel,
+    replicon_tick::RepliconTick,
+    update_acks, ClientId,
+};
+
+/// Delivers messages between the server and [`VirtualClients`].
+pub(super) struct VirtualClientsPlugin;
+
+impl Plugin for VirtualClientsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VirtualClients>()
+            .add_systems(
+                PreUpdate,
+                (
+                    send_events.in_set(ServerSet::SendEvents),
+                    receive_sent.in_set(ServerSet::ReceivePackets),
+                )
+                    .run_if(server_running),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    deliver_sent
+                        .after(ServerSet::Send)
+                        .after(flow_trace::record_sent)
+                        .before(ServerSet::SendPackets)
+                        .run_if(server_running),
+                    reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+/// Emits connection events for virtual clients and marks connected ones as local for [`RepliconServer`].
+fn send_events(
+    mut virtual_clients: ResMut<VirtualClients>,
+    mut server_events: EventWriter<ServerEvent>,
+    mut server: ResMut<RepliconServer>,
+    names: Res<ClientNames>,
+) {
+    for event in &virtual_clients.events {
+        match *event {
+            ServerEvent::ClientConnected { client_id } => {
+                debug!("connecting virtual {}", names.label(client_id));
+                server.insert_local_client(client_id);
+            }
+            ServerEvent::ClientDisconnected { client_id, .. } => {
+                debug!("disconnecting virtual {}", names.label(client_id));
+            }
+        }
+    }
+    server_events.send_batch(virtual_clients.events.drain(..));
+}
+
+/// Passes messages sent by virtual clients to [`RepliconServer`] as received.
+fn receive_sent(mut virtual_clients: ResMut<VirtualClients>, mut server: ResMut<RepliconServer>) {
+    for (&client_id, client) in &mut virtual_clients.clients {
+        for (channel_id, message) in client.sent.drain(..) {
+            server.insert_received(client_id, channel_id, message);
+        }
+    }
+}
+
+/// Moves messages for virtual clients from [`RepliconServer`] before they reach the messaging backend.
+///
+/// Update messages are acknowledged automatically.
+fn deliver_sent(
+    mut virtual_clients: ResMut<VirtualClients>,
+    mut server: ResMut<RepliconServer>,
+    names: Res<ClientNames>,
+) {
+    if virtual_clients.clients.is_empty() {
+        return;
+    }
+
+    let update_channel = u8::from(ReplicationChannel::Update);
+    server.retain_sent(|(client_id, channel_id, message)| {
+        let Some(client) = virtual_clients.clients.get_mut(client_id) else {
+            return true;
+        };
+
+        if *channel_id == update_channel {
+            match bincode::deserialize::<(RepliconTick, RepliconTick, u16)>(message) {
+                Ok((_, _, update_index)) => {
+                    let ack = update_acks::serialize_acks(&[update_index])
+                        .expect("update index should be serializable");
+                    client
+                        .sent
+                        .push((ReplicationChannel::Init.into(), ack.into()));
+                }
+                Err(e) => error!(
+                    "unable to read update index for {}: {e}",
+                    names.label(*client_id)
+                ),
+            }
+        }
+
+        client
+            .received
+            .entry(*channel_id)
+            .or_default()
+            .push(message.clone());
+
+        false
+    });
+}
+
+fn reset(mut virtual_clients: ResMut<VirtualClients>) {
+    virtual_clients.clients.clear();
+    virtual_clients.events.clear();
+}
+
+/**
+In-process clients that don't have a connection, such as server-side bots.
+
+Virtual clients have their own [`ClientId`] and are added to
+[`ConnectedClients`](super::connected_clients::ConnectedClients) like real clients,
+so they participate in visibility and all other per-client logic.
+Messages sent to them are not passed to the messaging backend and available via [`Self::receive`]
+for in-process AI systems instead. Messages from them can be sent via [`Self::send`].
+
+Update messages are acknowledged automatically.
+
+All virtual clients are disconnected when the server stops.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{network_event::server_event::ServerEventChannel, prelude::*};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_server_event::<Taunt>(ChannelKind::Ordered)
+    .add_systems(Update, react_to_taunts);
+
+/// Reads taunts sent to bots.
+fn react_to_taunts(
+    mut virtual_clients: ResMut<VirtualClients>,
+    channel: Res<ServerEventChannel<Taunt>>,
+) {
+    let client_ids: Vec<_> = virtual_clients.iter_client_ids().collect();
+    for client_id in client_ids {
+        for message in virtual_clients.receive(client_id, *channel) {
+            // Deserialize and react to the message.
+        }
+    }
+}
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct Taunt;
+```
+**/
+#[derive(Resource)]
+pub struct VirtualClients {
+    clients: HashMap<ClientId, VirtualClient>,
+
+    /// Connection events that will be emitted in [`ServerSet::SendEvents`].
+    events: Vec<ServerEvent>,
+
+    /// Value for the next allocated [`ClientId`].
+    ///
+    /// Allocated from the end of the range to avoid collisions with IDs from messaging backends.
+    next_id: u64,
+}
+
+impl VirtualClients {
+    /// Connects a new virtual client and returns its ID.
+    ///
+    /// The client will be added to [`ConnectedClients`](super::connected_clients::ConnectedClients)
+    /// on the next update, just like a real client.
+    pub fn connect(&mut self) -> ClientId {
+        let client_id = ClientId::new(self.next_id);
+        self.next_id -= 1;
+
+        self.clients.insert(client_id, Default::default());
+        self.events.push(ServerEvent::ClientConnected { client_id });
+
+        client_id
+    }
+
+    /// Disconnects a virtual client.
+    ///
+    /// Pending messages for it are discarded.
+    pub fn disconnect(&mut self, client_id: ClientId) {
+        if self.clients.remove(&client_id).is_none() {
+            warn!(
+                "trying to disconnect unknown virtual {}",
+                ClientLabel::from(client_id)
+            );
+            return;
+        }
+
+        self.events.push(ServerEvent::ClientDisconnected {
+            client_id,
+            reason: "disconnected virtual client".into(),
+        });
+    }
+
+    /// Returns `true` if the client is virtual.
+    pub fn is_virtual(&self, client_id: ClientId) -> bool {
+        self.clients.contains_key(&client_id)
+    }
+
+    /// Returns an iterator over IDs of connected virtual clients.
+    pub fn iter_client_ids(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.clients.keys().copied()
+    }
+
+    /// Receives all messages sent from the server to a virtual client over a channel.
+    ///
+    /// All messages will be drained.
+    pub fn receive<I: Into<u8>>(
+        &mut self,
+        client_id: ClientId,
+        channel_id: I,
+    ) -> impl Iterator<Item = Bytes> + '_ {
+        self.clients
+            .get_mut(&client_id)
+            .and_then(|client| client.received.get_mut(&channel_id.into()))
+            .map(mem::take)
+            .unwrap_or_default()
+            .into_iter()
+    }
+
+    /// Sends a message from a virtual client to the server over a channel.
+    ///
+    /// The message will be received by the server on the next update.
+    pub fn send<I: Into<u8>, B: Into<Bytes>>(
+        &mut self,
+        client_id: ClientId,
+        channel_id: I,
+        message: B,
+    ) {
+        let Some(client) = self.clients.get_mut(&client_id) else {
+            warn!(
+                "trying to send a message from unknown virtual {}",
+                ClientLabel::from(client_id)
+            );
+            return;
+        };
+
+        client.sent.push((channel_id.into(), message.into()));
+    }
+}
+
+impl Default for VirtualClients {
+    fn default() -> Self {
+        Self {
+            clients: Default::default(),
+            events: Default::default(),
+            next_id: u64::MAX,
+        }
+    }
+}
+
+#[derive(Default)]
+struct VirtualClient {
+    /// Messages from the server for each channel.
+    received: HashMap<u8, Vec<Bytes>>,
+
+    /// Messages to the server with their channels.
+    sent: Vec<(u8, Bytes)>,
+}
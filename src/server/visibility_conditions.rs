@@ -0,0 +1,252 @@
+use bevy::{ecs::entity::Entities, prelude::*, utils::HashMap};
+
+use super::{
+    connected_clients::{
+        client_groups::{ClientGroups, GroupId},
+        ConnectedClients,
+    },
+    ServerEvent, ServerPlugin, ServerSet, VisibilityPolicy,
+};
+use crate::core::{common_conditions::server_running, ClientId};
+
+/// Translates visibility condition components into [`ClientVisibility`](super::connected_clients::client_visibility::ClientVisibility).
+///
+/// Conditions are ignored with [`VisibilityPolicy::All`].
+pub(super) struct VisibilityConditionsPlugin;
+
+impl Plugin for VisibilityConditionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClientTeams>().add_systems(
+            PostUpdate,
+            update_visibility
+                .before(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+/// Applies conditions to all clients.
+///
+/// Recalculates visibility for all entities with conditions on client connection, [`ClientTeams`] change
+/// or [`ClientGroups`] change.
+/// Otherwise only entities with changed or removed conditions are processed.
+fn update_visibility(
+    mut server_events: EventReader<ServerEvent>,
+    mut connected_clients: ResMut<ConnectedClients>,
+    teams: Res<ClientTeams>,
+    entities: &Entities,
+    conditions: Query<ConditionsData, ConditionsFilter>,
+    changed_conditions: Query<
+        Entity,
+        Or<(
+            Changed<VisibleToAll>,
+            Changed<VisibleToOwner>,
+            Changed<VisibleToTeam>,
+            Changed<VisibleToGroup>,
+            Changed<InvisibleTo>,
+        )>,
+    >,
+    mut removed_all: RemovedComponents<VisibleToAll>,
+    mut removed_owner: RemovedComponents<VisibleToOwner>,
+    mut removed_team: RemovedComponents<VisibleToTeam>,
+    mut removed_group: RemovedComponents<VisibleToGroup>,
+    mut removed_invisible: RemovedComponents<InvisibleTo>,
+) {
+    let client_connected = server_events
+        .read()
+        .filter(|event| matches!(event, ServerEvent::ClientConnected { .. }))
+        .count()
+        > 0;
+    let groups_changed = connected_clients.groups_mut().take_changed();
+    if matches!(connected_clients.visibility_policy(), VisibilityPolicy::All) {
+        return;
+    }
+
+    let default_visible = matches!(
+        connected_clients.visibility_policy(),
+        VisibilityPolicy::Blacklist
+    );
+    let mut apply = |entity: Entity| {
+        let data = conditions.get(entity).ok();
+        let (clients, groups) = connected_clients.iter_mut_with_groups();
+        for client in clients {
+            let visible = match data {
+                Some(data) => is_visible(data, client.id(), &teams, groups),
+                None => default_visible,
+            };
+            client.visibility_mut().set_visibility(entity, visible);
+        }
+    };
+
+    if client_connected || teams.is_changed() || groups_changed {
+        for (entity, ..) in &conditions {
+            apply(entity);
+        }
+    } else {
+        for entity in &changed_conditions {
+            apply(entity);
+        }
+    }
+
+    for entity in removed_all
+        .read()
+        .chain(removed_owner.read())
+        .chain(removed_team.read())
+        .chain(removed_group.read())
+        .chain(removed_invisible.read())
+    {
+        // Despawns are handled by replication itself.
+        if entities.contains(entity) {
+            apply(entity);
+        }
+    }
+}
+
+type ConditionsData = (
+    Entity,
+    Has<VisibleToAll>,
+    Option<&'static VisibleToOwner>,
+    Option<&'static VisibleToTeam>,
+    Option<&'static VisibleToGroup>,
+    Option<&'static InvisibleTo>,
+);
+
+type ConditionsFilter = Or<(
+    With<VisibleToAll>,
+    With<VisibleToOwner>,
+    With<VisibleToTeam>,
+    With<VisibleToGroup>,
+    With<InvisibleTo>,
+)>;
+
+/// Evaluates conditions for a specific client.
+fn is_visible(
+    (_, all, owner, team, group, invisible): (
+        Entity,
+        bool,
+        Option<&VisibleToOwner>,
+        Option<&VisibleToTeam>,
+        Option<&VisibleToGroup>,
+        Option<&InvisibleTo>,
+    ),
+    client_id: ClientId,
+    teams: &ClientTeams,
+    groups: &ClientGroups,
+) -> bool {
+    if invisible.is_some_and(|invisible| invisible.0 == client_id) {
+        return false;
+    }
+
+    if !all && owner.is_none() && team.is_none() && group.is_none() {
+        // Only negative conditions are present.
+        return true;
+    }
+
+    all || owner.is_some_and(|owner| owner.0 == client_id)
+        || team.is_some_and(|team| teams.get(client_id) == Some(team.0))
+        || group.is_some_and(|group| groups.contains(group.0, client_id))
+}
+
+/**
+Makes an entity visible to all clients.
+
+Visibility condition components are declarative alternative to
+[`ClientVisibility::set_visibility`](super::connected_clients::client_visibility::ClientVisibility::set_visibility)
+and evaluated automatically before each replication.
+
+An entity with conditions is visible to a client if it's not hidden via [`InvisibleTo`] and at least
+one of [`VisibleToAll`], [`VisibleToOwner`], [`VisibleToTeam`] or [`VisibleToGroup`] matches the client.
+If only [`InvisibleTo`] is present, the entity is visible to all other clients.
+If all conditions are removed, the visibility is reset to the default for the current
+[`VisibilityPolicy`].
+
+Conditions are ignored with [`VisibilityPolicy::All`].
+Avoid mixing them with manual visibility changes for the same entity, since conditions will
+override them.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins((
+#     MinimalPlugins,
+#     RepliconPlugins.set(ServerPlugin {
+#         visibility_policy: VisibilityPolicy::Whitelist,
+#         ..Default::default()
+#     }),
+# ));
+# let client_id = ClientId::new(1);
+// Visible only to the player who controls it.
+app.world.spawn((Replicated, VisibleToOwner(client_id)));
+
+// Visible only to the specified team.
+app.world.resource_mut::<ClientTeams>().insert(client_id, TeamId(1));
+app.world.spawn((Replicated, VisibleToTeam(TeamId(1))));
+```
+**/
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct VisibleToAll;
+
+/// Makes an entity visible to a client that owns it.
+///
+/// See [`VisibleToAll`] for details about visibility conditions.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct VisibleToOwner(pub ClientId);
+
+/// Makes an entity visible to clients from a team.
+///
+/// Client teams are assigned via [`ClientTeams`].
+///
+/// See [`VisibleToAll`] for details about visibility conditions.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct VisibleToTeam(pub TeamId);
+
+/// Makes an entity visible to members of a client group.
+///
+/// Unlike teams from [`ClientTeams`], a client can be a member of multiple groups,
+/// which is useful for parties or squads inside a team. Memberships are managed via
+/// [`ConnectedClients::groups_mut`] and the same groups can be used to send events with
+/// [`SendMode::Group`](crate::network_event::server_event::SendMode::Group).
+///
+/// See [`VisibleToAll`] for details about visibility conditions.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct VisibleToGroup(pub GroupId);
+
+/// Hides an entity from a client.
+///
+/// Takes priority over other conditions.
+///
+/// See [`VisibleToAll`] for details about visibility conditions.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct InvisibleTo(pub ClientId);
+
+/// Team identifier for [`VisibleToTeam`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TeamId(pub u32);
+
+/// Team assignments for clients used by [`VisibleToTeam`].
+///
+/// Assignments are not removed on disconnect.
+#[derive(Resource, Default, Debug)]
+pub struct ClientTeams(HashMap<ClientId, TeamId>);
+
+impl ClientTeams {
+    /// Assigns a team for a client, replacing the previous one.
+    pub fn insert(&mut self, client_id: ClientId, team_id: TeamId) {
+        self.0.insert(client_id, team_id);
+    }
+
+    /// Removes a client from its team.
+    pub fn remove(&mut self, client_id: ClientId) -> Option<TeamId> {
+        self.0.remove(&client_id)
+    }
+
+    /// Returns team of a client.
+    pub fn get(&self, client_id: ClientId) -> Option<TeamId> {
+        self.0.get(&client_id).copied()
+    }
+}
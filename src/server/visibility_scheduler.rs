@@ -0,0 +1,369 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{server_tick::ServerTick, ServerEvent, ServerSet};
+use crate::core::{common_conditions::server_running, replicon_tick::RepliconTick, ClientId};
+
+/// Updates [`VisibilityScheduler`] every frame.
+pub(super) struct VisibilitySchedulerPlugin;
+
+impl Plugin for VisibilitySchedulerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VisibilityScheduler>().add_systems(
+            PreUpdate,
+            schedule_clients
+                .after(ServerSet::Receive)
+                .run_if(server_running),
+        );
+    }
+}
+
+/// Adds connected clients and selects due clients for the current tick.
+fn schedule_clients(
+    mut server_events: EventReader<ServerEvent>,
+    mut scheduler: ResMut<VisibilityScheduler>,
+    server_tick: Res<ServerTick>,
+) {
+    for event in server_events.read() {
+        match event {
+            ServerEvent::ClientConnected { client_id } => scheduler.add_client(*client_id),
+            ServerEvent::ClientDisconnected { client_id, .. } => {
+                scheduler.remove_client(*client_id)
+            }
+        }
+    }
+
+    scheduler.advance(**server_tick);
+}
+
+/**
+Decides for which clients custom visibility should be recomputed.
+
+Recomputing visibility for every client on each tick scales poorly with many clients.
+This resource spreads the work: clients are queued according to [`Self::schedule`] and at most
+[`Self::clients_per_tick`] of them are returned from [`Self::due`] on each tick.
+Newly connected clients are always queued.
+
+Updated in [`PreUpdate`] after [`ServerSet::Receive`], so systems in [`Update`] can read due clients.
+Visibility conditions from [`visibility_conditions`](super::visibility_conditions) are
+evaluated on changes and don't use this resource.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+let mut scheduler = app.world.resource_mut::<VisibilityScheduler>();
+scheduler.schedule = VisibilitySchedule::Movement(10.0);
+scheduler.clients_per_tick = Some(32);
+
+app.add_systems(Update, (report_positions, update_visibility).chain());
+
+fn report_positions(mut scheduler: ResMut<VisibilityScheduler>, players: Query<(&Player, &Transform)>) {
+    for (player, transform) in &players {
+        scheduler.set_position(player.0, transform.translation);
+    }
+}
+
+fn update_visibility(
+    scheduler: Res<VisibilityScheduler>,
+    mut connected_clients: ResMut<ConnectedClients>,
+    players: Query<(&Player, &Transform)>,
+    items: Query<(Entity, &Transform), With<Item>>,
+) {
+    for &client_id in scheduler.due() {
+        let Some((_, player_transform)) = players.iter().find(|(player, _)| player.0 == client_id) else {
+            continue;
+        };
+        let Some(client) = connected_clients.get_client_mut(client_id) else {
+            continue;
+        };
+        for (entity, item_transform) in &items {
+            let distance = player_transform.translation.distance(item_transform.translation);
+            client.visibility_mut().set_visibility(entity, distance < 100.0);
+        }
+    }
+}
+
+#[derive(Component)]
+struct Player(ClientId);
+
+#[derive(Component)]
+struct Item;
+```
+**/
+#[derive(Resource)]
+pub struct VisibilityScheduler {
+    /// When clients are queued for recomputation.
+    ///
+    /// By default set to [`VisibilitySchedule::EveryTick`].
+    pub schedule: VisibilitySchedule,
+
+    /// Maximum number of clients returned from [`Self::due`] per tick.
+    ///
+    /// Remaining clients stay in the queue and will be returned on the next ticks.
+    ///
+    /// By default set to [`None`], which means no limit.
+    pub clients_per_tick: Option<usize>,
+
+    clients: HashMap<ClientId, ScheduledClient>,
+
+    /// Clients waiting for recomputation in order of queueing.
+    queue: VecDeque<ClientId>,
+
+    /// Clients selected for the current tick.
+    due: Vec<ClientId>,
+
+    /// Tick for which [`Self::due`] was selected.
+    last_tick: Option<RepliconTick>,
+}
+
+impl VisibilityScheduler {
+    /// Returns clients for which visibility should be recomputed on this tick.
+    ///
+    /// Empty on frames without a new server tick.
+    pub fn due(&self) -> &[ClientId] {
+        &self.due
+    }
+
+    /// Returns `true` if the client is in [`Self::due`].
+    pub fn is_due(&self, client_id: ClientId) -> bool {
+        self.due.contains(&client_id)
+    }
+
+    /// Returns the number of clients waiting for recomputation on the next ticks.
+    pub fn queued_len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Queues a client for recomputation regardless of [`Self::schedule`].
+    ///
+    /// Does nothing if the client is not connected or already queued.
+    pub fn request(&mut self, client_id: ClientId) {
+        if let Some(client) = self.clients.get_mut(&client_id) {
+            if !client.queued {
+                client.queued = true;
+                self.queue.push_back(client_id);
+            }
+        }
+    }
+
+    /// Queues all connected clients for recomputation.
+    pub fn request_all(&mut self) {
+        for (&client_id, client) in &mut self.clients {
+            if !client.queued {
+                client.queued = true;
+                self.queue.push_back(client_id);
+            }
+        }
+    }
+
+    /// Updates the position of a client for [`VisibilitySchedule::Movement`].
+    ///
+    /// Does nothing if the client is not connected.
+    pub fn set_position(&mut self, client_id: ClientId, position: Vec3) {
+        if let Some(client) = self.clients.get_mut(&client_id) {
+            client.position = Some(position);
+        }
+    }
+
+    fn add_client(&mut self, client_id: ClientId) {
+        self.clients.insert(client_id, Default::default());
+        self.request(client_id);
+    }
+
+    fn remove_client(&mut self, client_id: ClientId) {
+        if let Some(client) = self.clients.remove(&client_id) {
+            if client.queued {
+                self.queue.retain(|&queued_id| queued_id != client_id);
+            }
+        }
+    }
+
+    /// Queues clients according to the schedule and selects due clients if the tick changed.
+    fn advance(&mut self, tick: RepliconTick) {
+        self.due.clear();
+        if self.last_tick == Some(tick) {
+            return;
+        }
+        self.last_tick = Some(tick);
+
+        match self.schedule {
+            VisibilitySchedule::EveryTick => self.request_all(),
+            VisibilitySchedule::Interval(ticks) => {
+                if tick.get() % ticks.max(1) == 0 {
+                    self.request_all();
+                }
+            }
+            VisibilitySchedule::Movement(distance) => {
+                for (&client_id, client) in &mut self.clients {
+                    if !client.queued && client.moved(distance) {
+                        client.queued = true;
+                        self.queue.push_back(client_id);
+                    }
+                }
+            }
+            VisibilitySchedule::Manual => (),
+        }
+
+        let count = self
+            .clients_per_tick
+            .unwrap_or(usize::MAX)
+            .min(self.queue.len());
+        for client_id in self.queue.drain(..count) {
+            let client = self
+                .clients
+                .get_mut(&client_id)
+                .expect("queued clients should be connected");
+            client.queued = false;
+            client.computed_position = client.position;
+            self.due.push(client_id);
+        }
+    }
+}
+
+impl Default for VisibilityScheduler {
+    fn default() -> Self {
+        Self {
+            schedule: VisibilitySchedule::EveryTick,
+            clients_per_tick: None,
+            clients: Default::default(),
+            queue: Default::default(),
+            due: Default::default(),
+            last_tick: None,
+        }
+    }
+}
+
+/// Scheduling state of a connected client.
+#[derive(Default)]
+struct ScheduledClient {
+    queued: bool,
+
+    /// Last position from [`VisibilityScheduler::set_position`].
+    position: Option<Vec3>,
+
+    /// Position at the moment of the last recomputation.
+    computed_position: Option<Vec3>,
+}
+
+impl ScheduledClient {
+    /// Returns `true` if the client moved further than `distance` since the last recomputation.
+    fn moved(&self, distance: f32) -> bool {
+        match (self.position, self.computed_position) {
+            (Some(position), Some(computed_position)) => {
+                position.distance_squared(computed_position) > distance * distance
+            }
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Configures when clients are queued in [`VisibilityScheduler`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VisibilitySchedule {
+    /// Queue all clients on each tick.
+    EveryTick,
+    /// Queue all clients once per the specified number of ticks.
+    Interval(u32),
+    /// Queue clients that moved further than the specified distance since their last recomputation.
+    ///
+    /// Positions are provided via [`VisibilityScheduler::set_position`].
+    Movement(f32),
+    /// Queue clients only via [`VisibilityScheduler::request`] or [`VisibilityScheduler::request_all`].
+    Manual,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval() {
+        let mut scheduler = VisibilityScheduler {
+            schedule: VisibilitySchedule::Interval(2),
+            ..Default::default()
+        };
+        scheduler.add_client(CLIENT_A);
+
+        scheduler.advance(RepliconTick::new(1));
+        assert_eq!(scheduler.due(), [CLIENT_A], "new client should be due");
+
+        scheduler.advance(RepliconTick::new(1));
+        assert!(scheduler.due().is_empty(), "tick didn't change");
+
+        scheduler.advance(RepliconTick::new(3));
+        assert!(scheduler.due().is_empty());
+
+        scheduler.advance(RepliconTick::new(4));
+        assert_eq!(scheduler.due(), [CLIENT_A]);
+    }
+
+    #[test]
+    fn budget() {
+        let mut scheduler = VisibilityScheduler {
+            schedule: VisibilitySchedule::Manual,
+            clients_per_tick: Some(1),
+            ..Default::default()
+        };
+        scheduler.add_client(CLIENT_A);
+        scheduler.add_client(CLIENT_B);
+        assert_eq!(scheduler.queued_len(), 2);
+
+        scheduler.advance(RepliconTick::new(1));
+        assert_eq!(scheduler.due(), [CLIENT_A]);
+
+        scheduler.advance(RepliconTick::new(2));
+        assert_eq!(scheduler.due(), [CLIENT_B]);
+
+        scheduler.advance(RepliconTick::new(3));
+        assert!(scheduler.due().is_empty());
+
+        scheduler.request(CLIENT_B);
+        scheduler.advance(RepliconTick::new(4));
+        assert_eq!(scheduler.due(), [CLIENT_B]);
+    }
+
+    #[test]
+    fn movement() {
+        let mut scheduler = VisibilityScheduler {
+            schedule: VisibilitySchedule::Movement(1.0),
+            ..Default::default()
+        };
+        scheduler.add_client(CLIENT_A);
+        scheduler.set_position(CLIENT_A, Vec3::ZERO);
+
+        scheduler.advance(RepliconTick::new(1));
+        assert_eq!(scheduler.due(), [CLIENT_A]);
+
+        scheduler.set_position(CLIENT_A, Vec3::X * 0.5);
+        scheduler.advance(RepliconTick::new(2));
+        assert!(
+            scheduler.due().is_empty(),
+            "movement is below the threshold"
+        );
+
+        scheduler.set_position(CLIENT_A, Vec3::X * 1.5);
+        scheduler.advance(RepliconTick::new(3));
+        assert_eq!(scheduler.due(), [CLIENT_A]);
+    }
+
+    #[test]
+    fn disconnect() {
+        let mut scheduler = VisibilityScheduler::default();
+        scheduler.add_client(CLIENT_A);
+        scheduler.remove_client(CLIENT_A);
+        assert_eq!(scheduler.queued_len(), 0);
+
+        scheduler.advance(RepliconTick::new(1));
+        assert!(scheduler.due().is_empty());
+    }
+
+    const CLIENT_A: ClientId = ClientId::new(1);
+    const CLIENT_B: ClientId = ClientId::new(2);
+}
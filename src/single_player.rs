@@ -0,0 +1,124 @@
+use std::mem;
+
+use bevy::{app::AppLabel, prelude::*, time::TimePlugin};
+
+use crate::{
+    client::replicon_client::{RepliconClient, RepliconClientStatus},
+    core::ClientId,
+    server::{replicon_server::RepliconServer, ServerEvent},
+    RepliconPlugins,
+};
+
+/**
+Runs a client in a separate world inside the same app to exercise real replication in single-player.
+
+All messages are passed through serialization, just like with a messaging backend,
+so replication bugs could be caught without running a separate client.
+
+The client world is stored in a [`SubApp`] with the [`SinglePlayerClient`] label and updated after the main world.
+The main world acts as a listen server with a single connected client with [`Self::CLIENT_ID`].
+
+Since the client and server need identical registration of replication rules, events and channels,
+the plugin accepts a setup function that will be called for both the main app and the client app.
+For the client app the plugin also adds [`TimePlugin`] and [`RepliconPlugins`] before calling the setup.
+
+Should be added after [`RepliconPlugins`] and without a messaging backend.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    SinglePlayerWithNetworkingPlugin::new(shared_setup),
+));
+
+fn shared_setup(app: &mut App) {
+    app.replicate::<Health>();
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
+```
+**/
+pub struct SinglePlayerWithNetworkingPlugin {
+    setup: fn(&mut App),
+}
+
+impl SinglePlayerWithNetworkingPlugin {
+    /// ID of the client that runs in the sub-app.
+    pub const CLIENT_ID: ClientId = ClientId::new(1);
+
+    /// Creates the plugin with a function that registers everything shared between the server and the client.
+    pub fn new(setup: fn(&mut App)) -> Self {
+        Self { setup }
+    }
+}
+
+impl Plugin for SinglePlayerWithNetworkingPlugin {
+    fn build(&self, app: &mut App) {
+        (self.setup)(app);
+
+        let mut sub_app = client_sub_app(self.setup);
+        sub_app
+            .world_mut()
+            .resource_mut::<RepliconClient>()
+            .set_status(RepliconClientStatus::Connected {
+                client_id: Some(Self::CLIENT_ID),
+            });
+        sub_app.set_extract(exchange_messages);
+        app.insert_sub_app(SinglePlayerClient, sub_app);
+
+        let mut server = app.world_mut().resource_mut::<RepliconServer>();
+        server.set_running(true);
+        server.insert_local_client(Self::CLIENT_ID);
+        app.world_mut().send_event(ServerEvent::ClientConnected {
+            client_id: Self::CLIENT_ID,
+        });
+    }
+}
+
+/// Builds an app with [`TimePlugin`], [`RepliconPlugins`] and `setup` and returns its main [`SubApp`].
+///
+/// Used to run clients in separate worlds of the same app.
+pub(crate) fn client_sub_app(setup: fn(&mut App)) -> SubApp {
+    let mut client_app = App::new();
+    client_app.add_plugins((TimePlugin, RepliconPlugins));
+    (setup)(&mut client_app);
+    client_app.finish();
+    client_app.cleanup();
+
+    mem::replace(client_app.main_mut(), SubApp::new())
+}
+
+/// Passes messages between the server in the main world and the client world.
+///
+/// Called by Bevy after each update of the main world and before updating the client.
+fn exchange_messages(main_world: &mut World, client_world: &mut World) {
+    let mut server = main_world.resource_mut::<RepliconServer>();
+    let mut client = client_world.resource_mut::<RepliconClient>();
+
+    for (channel_id, message) in client.drain_sent() {
+        server.insert_received(
+            SinglePlayerWithNetworkingPlugin::CLIENT_ID,
+            channel_id,
+            message,
+        );
+    }
+
+    for (client_id, channel_id, message) in server.drain_sent() {
+        debug_assert_eq!(client_id, SinglePlayerWithNetworkingPlugin::CLIENT_ID);
+        client.insert_received(channel_id, message);
+    }
+}
+
+/// Label for the client [`SubApp`] added by [`SinglePlayerWithNetworkingPlugin`].
+///
+/// Can be used to access the client world via [`App::sub_app`].
+#[derive(AppLabel, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SinglePlayerClient;
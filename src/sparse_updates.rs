@@ -0,0 +1,261 @@
+use std::{collections::BTreeMap, io::Cursor};
+
+use bevy::{
+    ecs::{component::Tick, system::SystemChangeTick},
+    prelude::*,
+};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    core::{
+        common_conditions::server_running,
+        replication_fns::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::{DeserializeFn, RuleFns},
+        },
+        replication_rules::AppRuleExt,
+    },
+    server::ServerPlugin,
+};
+
+/// An extension trait for [`App`] for replicating large array-like components with patches.
+pub trait AppSparseExt {
+    /**
+    Creates a replication rule for [`Sparse<T>`].
+
+    By default, the whole component is serialized on each change, which is wasteful for
+    large arrays or grids, such as tilemaps or voxel chunks, where only a few elements change at a time.
+    With this rule, the server tracks changed elements using [`SparseData::diff`] and sends
+    to each client only elements that changed since its last acknowledged tick.
+    Clients apply them using [`SparseData::apply`].
+
+    The whole value is still sent on insertion or when the entity becomes visible.
+
+    The server keeps the previous value and the latest changed elements to produce patches,
+    so the memory usage of the component is roughly tripled on server.
+    Entities inside such components are not mapped.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_sparse::<Tiles>();
+
+    fn spawn_map(mut commands: Commands) {
+        commands.spawn((Replicated, Sparse::new(Tiles(vec![0; 64 * 64]))));
+    }
+
+    fn dig(mut maps: Query<&mut Sparse<Tiles>>) {
+        for mut tiles in &mut maps {
+            tiles.0[42] = 1; // Only this tile will be sent.
+        }
+    }
+
+    #[derive(Clone, Deserialize, Serialize)]
+    struct Tiles(Vec<u8>);
+
+    impl SparseData for Tiles {
+        type Item = u8;
+
+        fn diff(&self, previous: &Self) -> Vec<(usize, u8)> {
+            self.0
+                .iter()
+                .zip(&previous.0)
+                .enumerate()
+                .filter(|(_, (tile, previous))| tile != previous)
+                .map(|(index, (&tile, _))| (index, tile))
+                .collect()
+        }
+
+        fn apply(&mut self, index: usize, tile: u8) {
+            self.0[index] = tile;
+        }
+    }
+    ```
+    **/
+    fn replicate_sparse<T: SparseData>(&mut self) -> &mut Self;
+}
+
+impl AppSparseExt for App {
+    fn replicate_sparse<T: SparseData>(&mut self) -> &mut Self {
+        self.add_systems(
+            PostUpdate,
+            record_changes::<T>
+                .before(ServerPlugin::send_replication)
+                .run_if(server_running),
+        )
+        .replicate_with::<Sparse<T>>(
+            RuleFns::new(serialize::<T>, deserialize::<T>)
+                .with_in_place(deserialize_in_place::<T>)
+                .with_consume(consume::<T>)
+                .with_per_client(),
+        )
+    }
+}
+
+/// Array-like data that can be replicated with patches.
+///
+/// See [`AppSparseExt::replicate_sparse`].
+pub trait SparseData: Clone + Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Value of a single element.
+    type Item: Serialize + DeserializeOwned + Send + Sync + 'static;
+
+    /// Returns indices and new values of elements that differ from the previous value.
+    fn diff(&self, previous: &Self) -> Vec<(usize, Self::Item)>;
+
+    /// Assigns a new value to an element.
+    fn apply(&mut self, index: usize, item: Self::Item);
+}
+
+/// Component that replicates [`SparseData`] with patches.
+///
+/// Dereferences into the wrapped data.
+/// See [`AppSparseExt::replicate_sparse`].
+#[derive(Component, Deref, DerefMut)]
+pub struct Sparse<T: SparseData> {
+    #[deref]
+    data: T,
+
+    /// Value at the last recording.
+    ///
+    /// Used only on server.
+    previous: Option<T>,
+
+    /// The latest values of changed elements with their change ticks.
+    ///
+    /// Used only on server.
+    changes: BTreeMap<usize, (Tick, T::Item)>,
+}
+
+impl<T: SparseData> Sparse<T> {
+    /// Creates a new instance with the specified data.
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            previous: None,
+            changes: Default::default(),
+        }
+    }
+
+    /// Returns the wrapped data.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+/// Records changed elements of modified components.
+fn record_changes<T: SparseData>(
+    mut components: Query<&mut Sparse<T>, Changed<Sparse<T>>>,
+    change_tick: SystemChangeTick,
+) {
+    for mut sparse in &mut components {
+        let sparse = sparse.bypass_change_detection();
+        match &mut sparse.previous {
+            Some(previous) => {
+                for (index, item) in sparse.data.diff(previous) {
+                    sparse.changes.insert(index, (change_tick.this_run(), item));
+                }
+                previous.clone_from(&sparse.data);
+            }
+            None => sparse.previous = Some(sparse.data.clone()),
+        }
+    }
+}
+
+/// Marks the whole value.
+const FULL: u8 = 0;
+
+/// Marks changed elements.
+const PATCH: u8 = 1;
+
+/// Serializes elements changed since the client's last acknowledged tick or the whole value.
+fn serialize<T: SparseData>(
+    ctx: &SerializeCtx,
+    sparse: &Sparse<T>,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    match ctx.change_limit {
+        Some(change_limit) => {
+            let patch: Vec<_> = sparse
+                .changes
+                .iter()
+                .filter(|(_, (tick, _))| tick.is_newer_than(change_limit, ctx.this_run))
+                .map(|(index, (_, item))| (index, item))
+                .collect();
+
+            DefaultOptions::new().serialize_into(&mut *cursor, &PATCH)?;
+            DefaultOptions::new().serialize_into(cursor, &patch)
+        }
+        None => {
+            DefaultOptions::new().serialize_into(&mut *cursor, &FULL)?;
+            DefaultOptions::new().serialize_into(cursor, &sparse.data)
+        }
+    }
+}
+
+/// Deserializes the whole value.
+///
+/// Patches are sent only for existing components, so receiving them here is an error.
+fn deserialize<T: SparseData>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<Sparse<T>> {
+    let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    if kind != FULL {
+        return Err(bincode::ErrorKind::Custom(format!(
+            "received patch kind {kind} for a missing component"
+        ))
+        .into());
+    }
+
+    let data = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(Sparse::new(data))
+}
+
+/// Replaces the whole value or applies changed elements.
+fn deserialize_in_place<T: SparseData>(
+    _deserialize: DeserializeFn<Sparse<T>>,
+    _ctx: &mut WriteCtx,
+    sparse: &mut Sparse<T>,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    match kind {
+        FULL => sparse.data = DefaultOptions::new().deserialize_from(cursor)?,
+        PATCH => {
+            let patch: Vec<(usize, T::Item)> = DefaultOptions::new().deserialize_from(cursor)?;
+            for (index, item) in patch {
+                sparse.data.apply(index, item);
+            }
+        }
+        _ => return Err(bincode::ErrorKind::Custom(format!("unknown patch kind {kind}")).into()),
+    }
+
+    Ok(())
+}
+
+/// Reads the value or changed elements without applying them.
+fn consume<T: SparseData>(
+    _deserialize: DeserializeFn<Sparse<T>>,
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let kind: u8 = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    match kind {
+        FULL => {
+            DefaultOptions::new().deserialize_from::<_, T>(cursor)?;
+        }
+        PATCH => {
+            DefaultOptions::new().deserialize_from::<_, Vec<(usize, T::Item)>>(cursor)?;
+        }
+        _ => return Err(bincode::ErrorKind::Custom(format!("unknown patch kind {kind}")).into()),
+    }
+
+    Ok(())
+}
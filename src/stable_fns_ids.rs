@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_running},
+        replication_fns::{FnsIdRemap, ReplicationFns},
+        replicon_channels::{ChannelKind, KeyedChannels},
+    },
+    server::{replicon_server::RepliconServer, ServerEvent, ServerPlugin, ServerSet},
+};
+
+/**
+Makes replication independent of the component registration order.
+
+By default, components are identified by their registration index, so the server and clients
+need to register replication rules in the same order. This could be hard to guarantee when
+apps are built with different feature flags.
+
+With this plugin, the server sends stable IDs (see [`ReplicationFns::stable_id`]) to each client on connection.
+Clients translate server IDs into their own and start applying replication only after receiving them.
+Components that replicated from the server, but not registered on the client will result in an error.
+
+Not added by default. Should be added after [`RepliconPlugins`](crate::RepliconPlugins) on both server and clients.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, StableFnsIdsPlugin));
+```
+**/
+pub struct StableFnsIdsPlugin;
+
+impl Plugin for StableFnsIdsPlugin {
+    fn build(&self, app: &mut App) {
+        KeyedChannels::register_server(
+            app.world_mut(),
+            StableIdsChannel::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.insert_resource(StableIdsChannel(id)),
+        );
+
+        app.init_resource::<FnsIdRemap>()
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::receive_ids
+                        .before(ClientSet::ApplyDespawns)
+                        .in_set(ClientSet::Receive)
+                        .run_if(client_connected),
+                    Self::reset.in_set(ClientSet::Reset),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                Self::send_ids
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
+    }
+}
+
+impl StableFnsIdsPlugin {
+    /// Sends stable IDs to all newly connected clients.
+    fn send_ids(
+        mut server_events: EventReader<ServerEvent>,
+        mut server: ResMut<RepliconServer>,
+        channel: Res<StableIdsChannel>,
+        replication_fns: Res<ReplicationFns>,
+    ) {
+        let mut message = None;
+        for event in server_events.read() {
+            if let ServerEvent::ClientConnected { client_id } = *event {
+                let message = message.get_or_insert_with(|| {
+                    DefaultOptions::new()
+                        .serialize(&replication_fns.stable_ids())
+                        .expect("stable IDs should be serializable")
+                });
+                server.send(client_id, channel.0, message.clone());
+            }
+        }
+    }
+
+    /// Receives stable IDs from the server and maps them into local IDs.
+    fn receive_ids(
+        mut client: ResMut<RepliconClient>,
+        mut remap: ResMut<FnsIdRemap>,
+        channel: Res<StableIdsChannel>,
+        replication_fns: Res<ReplicationFns>,
+    ) {
+        for message in client.receive(channel.0) {
+            let stable_ids: Vec<u64> = match DefaultOptions::new().deserialize(&message) {
+                Ok(stable_ids) => stable_ids,
+                Err(e) => {
+                    error!("unable to deserialize stable IDs from server: {e}");
+                    continue;
+                }
+            };
+
+            let mapping = replication_fns.map_stable_ids(&stable_ids);
+            let missing = mapping.iter().filter(|fns_id| fns_id.is_none()).count();
+            if missing > 0 {
+                warn!("{missing} server replication functions have no matching registrations on client");
+            }
+
+            remap.set(mapping);
+        }
+    }
+
+    fn reset(mut remap: ResMut<FnsIdRemap>) {
+        remap.clear();
+    }
+}
+
+/// ID of the server channel for sending stable IDs.
+#[derive(Resource, TypePath)]
+struct StableIdsChannel(u8);
@@ -0,0 +1,175 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    client::{replicon_client::RepliconClient, ClientSet},
+    core::{
+        common_conditions::{client_connected, server_running},
+        replicon_channels::{ChannelKind, KeyedChannels},
+        ClientId,
+    },
+    server::{
+        client_names::ClientNames, connected_clients::ConnectedClients,
+        replicon_server::RepliconServer, ServerEvent, ServerSet,
+    },
+};
+
+/**
+An extension trait for [`App`] for replicating [`States`] from server to clients.
+
+The server sends the state to all clients on each transition and to newly connected clients on connection.
+[Held](crate::server::connected_clients::ConnectedClient::is_held) clients receive the state after releasing.
+Clients apply received states via [`NextState`], so regular transition schedules like [`OnEnter`] run on them.
+
+The state should be initialized on both server and clients, for example with [`App::init_state`].
+Clients shouldn't change the state on their own while connected, since it will be overwritten
+on the next transition on server.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.init_state::<GameState>()
+    .replicate_state::<GameState>()
+    .add_systems(OnEnter(GameState::InGame), spawn_hud);
+
+fn spawn_hud() {
+    info!("match started");
+}
+
+#[derive(States, Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, TypePath)]
+enum GameState {
+    #[default]
+    Lobby,
+    InGame,
+}
+```
+**/
+pub trait StateReplicationAppExt {
+    /// Replicates state `S` from server to clients over a dedicated ordered channel.
+    fn replicate_state<S: States + TypePath + Serialize + DeserializeOwned>(&mut self)
+        -> &mut Self;
+}
+
+impl StateReplicationAppExt for App {
+    fn replicate_state<S: States + TypePath + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut Self {
+        KeyedChannels::register_server(
+            self.world_mut(),
+            StateChannel::<S>::type_path(),
+            ChannelKind::Ordered.into(),
+            |world, id| world.insert_resource(StateChannel::<S>::new(id)),
+        );
+
+        self.add_systems(
+            PreUpdate,
+            receive_state::<S>
+                .in_set(ClientSet::Receive)
+                .run_if(client_connected),
+        )
+        .add_systems(
+            PostUpdate,
+            send_state::<S>
+                .in_set(ServerSet::Send)
+                .run_if(server_running),
+        )
+    }
+}
+
+/// Sends the state to all clients after transitions and to newly connected clients.
+fn send_state<S: States + Serialize>(
+    mut pending_clients: Local<Vec<ClientId>>,
+    mut server_events: EventReader<ServerEvent>,
+    mut server: ResMut<RepliconServer>,
+    connected_clients: Res<ConnectedClients>,
+    channel: Res<StateChannel<S>>,
+    state: Res<State<S>>,
+    names: Res<ClientNames>,
+) {
+    for event in server_events.read() {
+        if let ServerEvent::ClientConnected { client_id } = *event {
+            pending_clients.push(client_id);
+        }
+    }
+
+    let changed = state.is_changed();
+    if !changed && pending_clients.is_empty() {
+        return;
+    }
+
+    let message = DefaultOptions::new()
+        .serialize(state.get())
+        .expect("state should be serializable");
+
+    if changed {
+        debug!("sending state `{:?}` to all clients", state.get());
+        for client in connected_clients.iter().filter(|client| !client.is_held()) {
+            server.send(client.id(), channel.id, message.clone());
+        }
+    }
+
+    pending_clients.retain(|&client_id| {
+        let Some(client) = connected_clients.get_client(client_id) else {
+            return false;
+        };
+        if client.is_held() {
+            return true;
+        }
+
+        if !changed {
+            debug!(
+                "sending state `{:?}` to connected {}",
+                state.get(),
+                names.label(client_id)
+            );
+            server.send(client_id, channel.id, message.clone());
+        }
+
+        false
+    });
+}
+
+/// Applies states received from the server via [`NextState`].
+fn receive_state<S: States + DeserializeOwned>(
+    mut client: ResMut<RepliconClient>,
+    mut next_state: ResMut<NextState<S>>,
+    channel: Res<StateChannel<S>>,
+) {
+    // Only the last state matters if multiple were received.
+    let Some(message) = client.receive(channel.id).last() else {
+        return;
+    };
+
+    match DefaultOptions::new().deserialize::<S>(&message) {
+        Ok(state) => {
+            debug!("received state `{state:?}` from server");
+            next_state.set(state);
+        }
+        Err(e) => error!("unable to deserialize state from server: {e}"),
+    }
+}
+
+/// ID of the server channel for state `S`.
+#[derive(Resource, TypePath)]
+struct StateChannel<S> {
+    id: u8,
+    marker: PhantomData<S>,
+}
+
+impl<S> StateChannel<S> {
+    fn new(id: u8) -> Self {
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
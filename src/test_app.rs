@@ -1,5 +1,14 @@
+#[cfg(feature = "test_utils")]
+use std::any;
+
 use bevy::prelude::*;
+#[cfg(feature = "test_utils")]
+use bincode::{DefaultOptions, Options};
+#[cfg(feature = "test_utils")]
+use serde::de::DeserializeOwned;
 
+#[cfg(feature = "test_utils")]
+use crate::network_event::client_event::ClientEventChannel;
 use crate::{
     client::replicon_client::{RepliconClient, RepliconClientStatus},
     core::ClientId,
@@ -151,3 +160,102 @@ impl ServerTestAppExt for App {
         })
     }
 }
+
+/**
+Extension for [`App`] to inspect client events passing through the test loopback.
+
+Messages are only inspected, so they will still be delivered by [`ServerTestAppExt::exchange_with_client`].
+Events are expected to be serialized with the default serialization used by
+[`ClientEventAppExt::add_client_event`](crate::network_event::client_event::ClientEventAppExt::add_client_event).
+
+Available only with `test_utils` feature.
+
+# Example
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::{EventTestAppExt, ServerTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+let mut server_app = App::new();
+let mut client_app = App::new();
+for app in [&mut server_app, &mut client_app] {
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+}
+
+server_app.connect_client(&mut client_app);
+let client_id = client_app
+    .world
+    .resource::<RepliconClient>()
+    .id()
+    .unwrap();
+
+client_app.world.send_event(DummyEvent(1));
+client_app.update();
+assert_eq!(client_app.client_sent::<DummyEvent>(), [DummyEvent(1)]);
+
+server_app.exchange_with_client(&mut client_app);
+assert_eq!(
+    server_app.received_from::<DummyEvent>(client_id),
+    [DummyEvent(1)]
+);
+
+#[derive(Debug, Deserialize, Event, PartialEq, Serialize, TypePath)]
+struct DummyEvent(u32);
+```
+**/
+#[cfg(feature = "test_utils")]
+pub trait EventTestAppExt {
+    /// Returns all `T` events sent by the client app that haven't been exchanged yet.
+    ///
+    /// Should be called on a client app after its update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` wasn't registered as a client event or can't be deserialized.
+    fn client_sent<T: Event + DeserializeOwned>(&self) -> Vec<T>;
+
+    /// Returns all `T` events received from a client that haven't been processed yet.
+    ///
+    /// Should be called on a server app after [`ServerTestAppExt::exchange_with_client`]
+    /// and before its update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` wasn't registered as a client event or can't be deserialized.
+    fn received_from<T: Event + DeserializeOwned>(&self, client_id: ClientId) -> Vec<T>;
+}
+
+#[cfg(feature = "test_utils")]
+impl EventTestAppExt for App {
+    fn client_sent<T: Event + DeserializeOwned>(&self) -> Vec<T> {
+        let channel_id: u8 = (*self.world().resource::<ClientEventChannel<T>>()).into();
+        self.world()
+            .resource::<RepliconClient>()
+            .iter_sent()
+            .filter(|(sent_channel_id, _)| *sent_channel_id == channel_id)
+            .map(|(_, message)| deserialize_event(message))
+            .collect()
+    }
+
+    fn received_from<T: Event + DeserializeOwned>(&self, client_id: ClientId) -> Vec<T> {
+        let channel = *self.world().resource::<ClientEventChannel<T>>();
+        self.world()
+            .resource::<RepliconServer>()
+            .iter_received(channel)
+            .filter(|(sender_id, _)| *sender_id == client_id)
+            .map(|(_, message)| deserialize_event(message))
+            .collect()
+    }
+}
+
+#[cfg(feature = "test_utils")]
+fn deserialize_event<T: DeserializeOwned>(message: &[u8]) -> T {
+    DefaultOptions::new()
+        .deserialize(message)
+        .unwrap_or_else(|e| panic!("`{}` should be deserializable: {e}", any::type_name::<T>()))
+}
@@ -0,0 +1,160 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::Instant,
+};
+
+use crate::{
+    client::ClientSet,
+    core::common_conditions::{client_connected, server_running},
+    server::ServerSet,
+};
+
+/**
+Plugin to write time spent in replicon systems into Diagnostics every frame.
+
+Each measurement covers a whole system set in milliseconds, so it's possible to tell whether networking
+or user systems cause frame spikes without enabling tracing. Since measurements are taken around the sets,
+they also include user systems added into them.
+
+Server measurements are taken only while the server is running, client measurements only while connected.
+
+Not added by default.
+
+# Examples
+
+```
+use bevy::{diagnostic::DiagnosticsStore, prelude::*};
+use bevy_replicon::prelude::*;
+
+# let mut app = App::new();
+# app.add_plugins((MinimalPlugins, RepliconPlugins));
+app.add_plugins(TimingDiagnosticsPlugin)
+    .add_systems(Update, report_spikes);
+
+fn report_spikes(diagnostics: Res<DiagnosticsStore>) {
+    let send_time = diagnostics
+        .get(&TimingDiagnosticsPlugin::SERVER_SEND)
+        .and_then(|diagnostic| diagnostic.value());
+    if let Some(send_time) = send_time.filter(|&time| time > 5.0) {
+        warn!("replication took {send_time:.1} ms");
+    }
+}
+```
+**/
+pub struct TimingDiagnosticsPlugin;
+
+impl Plugin for TimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpanStarts>();
+        for path in Self::SPANS {
+            app.register_diagnostic(
+                Diagnostic::new(path)
+                    .with_suffix("ms")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            );
+        }
+
+        app.add_systems(
+            PreUpdate,
+            (
+                (
+                    start_span::<SERVER_RECEIVE>
+                        .after(ServerSet::SendEvents)
+                        .before(ServerSet::Receive),
+                    end_span::<SERVER_RECEIVE>.after(ServerSet::Receive),
+                )
+                    .run_if(server_running),
+                (
+                    start_span::<CLIENT_RECEIVE>
+                        .after(ClientSet::ReceivePackets)
+                        .before(ClientSet::Receive),
+                    end_span::<CLIENT_RECEIVE>.after(ClientSet::Receive),
+                    start_span::<CLIENT_APPLY>
+                        .before(ClientSet::ApplyDespawns)
+                        .in_set(ClientSet::Receive),
+                    end_span::<CLIENT_APPLY>
+                        .after(ClientSet::ApplyUpdates)
+                        .in_set(ClientSet::Receive),
+                )
+                    .run_if(client_connected),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                (
+                    start_span::<SERVER_SEND>
+                        .after(ServerSet::StoreHierarchy)
+                        .before(ServerSet::Send),
+                    end_span::<SERVER_SEND>
+                        .after(ServerSet::Send)
+                        .before(ServerSet::SendPackets),
+                )
+                    .run_if(server_running),
+                (
+                    start_span::<CLIENT_SEND>.before(ClientSet::Send),
+                    end_span::<CLIENT_SEND>
+                        .after(ClientSet::Send)
+                        .before(ClientSet::SendPackets),
+                )
+                    .run_if(client_connected),
+            ),
+        );
+    }
+}
+
+impl TimingDiagnosticsPlugin {
+    /// Time spent in [`ServerSet::Receive`] to receive client events and acknowledgments.
+    pub const SERVER_RECEIVE: DiagnosticPath =
+        DiagnosticPath::const_new("replication.timing.server_receive");
+    /// Time spent in [`ServerSet::Send`] to collect, serialize and send replication and server events.
+    pub const SERVER_SEND: DiagnosticPath =
+        DiagnosticPath::const_new("replication.timing.server_send");
+    /// Time spent in [`ClientSet::Receive`] to receive and apply data from the server.
+    pub const CLIENT_RECEIVE: DiagnosticPath =
+        DiagnosticPath::const_new("replication.timing.client_receive");
+    /// Time spent in apply sets from [`ClientSet::ApplyDespawns`] to [`ClientSet::ApplyUpdates`].
+    ///
+    /// Included into [`Self::CLIENT_RECEIVE`].
+    pub const CLIENT_APPLY: DiagnosticPath =
+        DiagnosticPath::const_new("replication.timing.client_apply");
+    /// Time spent in [`ClientSet::Send`] to send client events.
+    pub const CLIENT_SEND: DiagnosticPath =
+        DiagnosticPath::const_new("replication.timing.client_send");
+
+    /// Max diagnostic history length.
+    pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
+
+    /// All measured spans, indexed by their span constants.
+    const SPANS: [DiagnosticPath; SPANS_COUNT] = [
+        Self::SERVER_RECEIVE,
+        Self::SERVER_SEND,
+        Self::CLIENT_RECEIVE,
+        Self::CLIENT_APPLY,
+        Self::CLIENT_SEND,
+    ];
+}
+
+const SERVER_RECEIVE: usize = 0;
+const SERVER_SEND: usize = 1;
+const CLIENT_RECEIVE: usize = 2;
+const CLIENT_APPLY: usize = 3;
+const CLIENT_SEND: usize = 4;
+const SPANS_COUNT: usize = 5;
+
+/// Start times of spans that are currently measured.
+#[derive(Resource, Default)]
+struct SpanStarts([Option<Instant>; SPANS_COUNT]);
+
+fn start_span<const SPAN: usize>(mut starts: ResMut<SpanStarts>) {
+    starts.0[SPAN] = Some(Instant::now());
+}
+
+fn end_span<const SPAN: usize>(mut starts: ResMut<SpanStarts>, mut diagnostics: Diagnostics) {
+    if let Some(start) = starts.0[SPAN].take() {
+        diagnostics.add_measurement(&TimingDiagnosticsPlugin::SPANS[SPAN], || {
+            start.elapsed().as_secs_f64() * 1000.0
+        });
+    }
+}
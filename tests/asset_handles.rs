@@ -0,0 +1,157 @@
+use bevy::{asset::AssetId, prelude::*, utils::HashMap};
+use bevy_replicon::{
+    asset_handles::{AssetKey, AssetResolver},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn resolved() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .init_resource::<TestHandles>()
+        .replicate_handle::<AssetComponent, TestResolver>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let handle = Handle::weak_from_u128(HANDLE_ID);
+    client_app
+        .world
+        .resource_mut::<TestHandles>()
+        .0
+        .insert(HANDLE_ID, handle.clone());
+
+    server_app.world.spawn((
+        Replicated,
+        AssetComponent {
+            handle: handle.clone(),
+            value: 1,
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&AssetComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.handle, handle);
+    assert_eq!(component.value, 1);
+}
+
+#[test]
+fn pending() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .init_resource::<TestHandles>()
+        .replicate_handle::<AssetComponent, TestResolver>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let handle = Handle::weak_from_u128(HANDLE_ID);
+    server_app.world.spawn((
+        Replicated,
+        AssetComponent {
+            handle: handle.clone(),
+            value: 1,
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    assert!(
+        !client_app
+            .world
+            .entity(client_entity)
+            .contains::<AssetComponent>(),
+        "component shouldn't be inserted until the handle is resolved"
+    );
+
+    client_app
+        .world
+        .resource_mut::<TestHandles>()
+        .0
+        .insert(HANDLE_ID, handle.clone());
+
+    client_app.update();
+
+    let component = client_app
+        .world
+        .get::<AssetComponent>(client_entity)
+        .unwrap();
+    assert_eq!(component.handle, handle);
+    assert_eq!(component.value, 1);
+}
+
+const HANDLE_ID: u128 = 42;
+
+struct TestResolver;
+
+impl AssetResolver<TestAsset> for TestResolver {
+    fn key(handle: &Handle<TestAsset>) -> Option<AssetKey> {
+        match handle.id() {
+            AssetId::Uuid { uuid } => Some(AssetKey::Id(uuid.as_u128())),
+            AssetId::Index { .. } => None,
+        }
+    }
+
+    fn resolve(key: &AssetKey, world: &mut World) -> Option<Handle<TestAsset>> {
+        let AssetKey::Id(id) = *key else {
+            return None;
+        };
+        world.resource::<TestHandles>().0.get(&id).cloned()
+    }
+}
+
+/// Handles available on client.
+#[derive(Resource, Default)]
+struct TestHandles(HashMap<u128, Handle<TestAsset>>);
+
+#[derive(Asset, TypePath)]
+struct TestAsset;
+
+#[derive(Component, Deserialize, Serialize)]
+struct AssetComponent {
+    #[serde(skip)]
+    handle: Handle<TestAsset>,
+    value: u8,
+}
+
+impl HandleComponent for AssetComponent {
+    type Asset = TestAsset;
+
+    fn handle(&self) -> &Handle<TestAsset> {
+        &self.handle
+    }
+
+    fn handle_mut(&mut self) -> &mut Handle<TestAsset> {
+        &mut self.handle
+    }
+}
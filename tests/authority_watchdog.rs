@@ -0,0 +1,163 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_replicon::{
+    authority_watchdog::AuthorityWatchdog, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn client_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            AuthorityWatchdogPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component_id = client_app.world.init_component::<DummyComponent>();
+    let watchdog = client_app.world.resource::<AuthorityWatchdog>();
+    assert!(
+        !watchdog.is_reported(component_id),
+        "insertion from server shouldn't be reported"
+    );
+
+    let mut component = client_app
+        .world
+        .query::<&mut DummyComponent>()
+        .single_mut(&mut client_app.world);
+    component.0 = 1;
+
+    client_app.update();
+
+    let watchdog = client_app.world.resource::<AuthorityWatchdog>();
+    assert!(watchdog.is_reported(component_id));
+}
+
+#[test]
+fn server_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            AuthorityWatchdogPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&DummyComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 1);
+
+    let component_id = client_app.world.init_component::<DummyComponent>();
+    let watchdog = client_app.world.resource::<AuthorityWatchdog>();
+    assert!(!watchdog.is_reported(component_id));
+}
+
+#[test]
+fn interpolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            AuthorityWatchdogPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .replicate_interpolated::<Position>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, Position(0.0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    for _ in 0..3 {
+        client_app.update();
+    }
+
+    let position = client_app
+        .world
+        .query::<&Position>()
+        .single(&client_app.world);
+    assert_eq!(position.0, 10.0);
+
+    let component_id = client_app.world.init_component::<Position>();
+    let watchdog = client_app.world.resource::<AuthorityWatchdog>();
+    assert!(
+        !watchdog.is_reported(component_id),
+        "interpolated values shouldn't be reported"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
+
+#[derive(Component, Clone, Deserialize, Serialize)]
+struct Position(f32);
+
+impl Interpolate for Position {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self(self.0.interpolate(&other.0, t))
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_bidirectional_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: FromServer(DummyEvent),
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_events = client_app
+        .world
+        .resource::<Events<FromServer<DummyEvent>>>();
+    assert_eq!(server_events.len(), 1);
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert!(
+        client_events.is_empty(),
+        "received server events shouldn't be sent back"
+    );
+}
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
@@ -1,11 +1,19 @@
 use std::io::Cursor;
 
-use bevy::{ecs::entity::MapEntities, prelude::*, utils::Duration};
+use bevy::{ecs::entity::MapEntities, prelude::*, time::TimeUpdateStrategy, utils::Duration};
 use bevy_replicon::{
     client::{confirmed::Confirmed, server_entity_map::ServerEntityMap, ServerInitTick},
     core::{
         command_markers::MarkerConfig,
-        replication_fns::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+        replay_log::ReplayLog,
+        replication_fns::{
+            command_fns,
+            ctx::{SerializeCtx, WriteCtx},
+            group_fns::GroupFns,
+            rule_fns::{self, RuleFns},
+        },
+        replicon_channels::ReplicationChannel,
+        replicon_tick::RepliconTick,
     },
     prelude::*,
     server::server_tick::ServerTick,
@@ -58,6 +66,156 @@ fn small_component() {
     assert!(component.0, "changed value should be updated on client");
 }
 
+#[test]
+fn priority_boost() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let boosted_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let regular_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let priority = connected_clients.client_mut(client_id).priority_mut();
+    priority.set_update_budget(Some(1));
+    priority.boost(boosted_entity, 4.0, Duration::from_secs(60));
+
+    // First changes will be sent without limits since nothing was written yet.
+    for entity in [boosted_entity, regular_entity] {
+        server_app.world.get_mut::<BoolComponent>(entity).unwrap().0 = true;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Now the budget is exceeded, so only the boosted entity should be updated.
+    for entity in [boosted_entity, regular_entity] {
+        server_app.world.get_mut::<BoolComponent>(entity).unwrap().0 = false;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    let client_boosted = entity_map.to_client()[&boosted_entity];
+    let client_regular = entity_map.to_client()[&regular_entity];
+    let boosted_component = client_app
+        .world
+        .get::<BoolComponent>(client_boosted)
+        .unwrap();
+    let regular_component = client_app
+        .world
+        .get::<BoolComponent>(client_regular)
+        .unwrap();
+    assert!(!boosted_component.0, "boosted entity should be updated");
+    assert!(regular_component.0, "regular entity should be skipped");
+}
+
+#[test]
+fn send_groups() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                send_groups: 2,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        client_app
+            .world
+            .query::<&BoolComponent>()
+            .iter(&client_app.world)
+            .len(),
+        1,
+        "insertions should be sent regardless of the group"
+    );
+
+    server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let send_group = server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id)
+        .send_group();
+    let on_turn = server_app.world.resource::<ServerTick>().get() % 2 == send_group as u32;
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0, on_turn,
+        "change should be sent only on the client's turn"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0,
+        "skipped change should be sent on the next turn"
+    );
+}
+
 #[test]
 fn package_size_component() {
     let mut server_app = App::new();
@@ -224,6 +382,70 @@ fn marker() {
     assert!(component.0);
 }
 
+#[test]
+fn marker_insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_marker::<ReplaceMarker>()
+        .insert_marker_on::<ReplaceMarker, OriginalComponent>()
+        .replicate::<OriginalComponent>()
+        .set_marker_fns::<ReplaceMarker, _>(
+            replace,
+            command_fns::default_remove::<ReplacedComponent>,
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, OriginalComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        !server_app
+            .world
+            .entity(server_entity)
+            .contains::<ReplaceMarker>(),
+        "marker should be inserted only on client"
+    );
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, (With<ReplaceMarker>, With<OriginalComponent>)>()
+        .single(&client_app.world);
+
+    // Change value.
+    let mut component = server_app
+        .world
+        .get_mut::<OriginalComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .get::<ReplacedComponent>(client_entity)
+        .unwrap();
+    assert!(component.0, "updates should be written using the marker");
+}
+
 #[test]
 fn marker_with_history() {
     let mut server_app = App::new();
@@ -472,6 +694,85 @@ fn marker_with_history_old_update() {
     );
 }
 
+#[test]
+fn marker_with_previous() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_marker_with::<PreviousMarker>(MarkerConfig {
+            need_previous: true,
+            ..Default::default()
+        })
+        .set_marker_fns::<PreviousMarker, BoolComponent>(
+            write_previous,
+            command_fns::default_remove::<BoolComponent>,
+        )
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let client_entity = client_app.world.spawn(PreviousMarker).id();
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world.resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app.world.entity(client_entity);
+    let previous = client_entity.get::<PreviousBool>().unwrap();
+    assert!(
+        previous.0.is_none(),
+        "first write shouldn't have a previous value"
+    );
+    let first_tick = client_entity.get::<Confirmed>().unwrap().last_tick();
+    let client_entity = client_entity.id();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world.entity(client_entity);
+    let component = client_entity.get::<BoolComponent>().unwrap();
+    assert!(component.0);
+    let previous = client_entity.get::<PreviousBool>().unwrap();
+    assert_eq!(
+        previous.0,
+        Some((first_tick, false)),
+        "previous value should be the last written one"
+    );
+}
+
 #[test]
 fn many_entities() {
     let mut server_app = App::new();
@@ -819,25 +1120,1194 @@ fn acknowledgment() {
     );
 }
 
-#[derive(Component, Deserialize, Serialize)]
-struct DummyComponent;
+#[test]
+fn replication_lag() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
 
-#[derive(Clone, Component, Copy, Deserialize, Serialize)]
-struct BoolComponent(bool);
+    server_app.connect_client(&mut client_app);
 
-#[derive(Component, Default, Deserialize, Serialize)]
-struct VecComponent(Vec<u8>);
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
 
-#[derive(Component, Deserialize, Serialize)]
-struct MappedComponent(Entity);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-impl MapEntities for MappedComponent {
-    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    let client = server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id);
+    assert_eq!(client.unacked_updates(), 1);
+    assert_ne!(client.unacked_bytes(), 0);
+    assert_eq!(client.ticks_behind(server_tick), 0);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    // Take and drop ack message.
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    assert_eq!(client.drain_sent().count(), 1);
+
+    server_app.update();
+
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    let client = server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id);
+    assert_eq!(
+        client.unacked_updates(),
+        2,
+        "server should resend the change because of the missed ack"
+    );
+    assert_eq!(client.ticks_behind(server_tick), 1);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    let client = server_app
+        .world
+        .resource::<ConnectedClients>()
+        .client(client_id);
+    assert_eq!(client.unacked_updates(), 0);
+    assert_eq!(client.unacked_bytes(), 0);
+    assert_eq!(client.ticks_behind(server_tick), 0);
+}
+
+#[test]
+fn paused_discard() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.resource_mut::<ReplicationPause>().pause();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app
+        .world
+        .resource_mut::<ReplicationPause>()
+        .resume(ResumeMode::Discard);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "changes during pause should be discarded");
+}
+
+#[test]
+fn paused_resync() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.resource_mut::<ReplicationPause>().pause();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+    server_app.world.spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        client_app.world.entities().len(),
+        1,
+        "nothing should be replicated during pause"
+    );
+
+    server_app
+        .world
+        .resource_mut::<ReplicationPause>()
+        .resume(ResumeMode::Resync);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world.query::<&BoolComponent>();
+    assert_eq!(components.iter(&client_app.world).len(), 2);
+    assert!(
+        components
+            .iter(&client_app.world)
+            .all(|component| component.0),
+        "all entities should be resynced"
+    );
+}
+
+#[test]
+fn replay_log() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .init_resource::<ReplayLog>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_log = server_app.world.resource::<ReplayLog>();
+    let client_log = client_app.world.resource::<ReplayLog>();
+    assert_eq!(client_log.records().len(), 2);
+    assert!(client_log
+        .records()
+        .iter()
+        .all(|record| record.entity() == server_entity));
+    assert_eq!(server_log.diff(client_log), None);
+}
+
+#[test]
+fn replay_log_with_delta() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(RuleFns::default().with_delta(diff_tail, apply_tail))
+        .init_resource::<ReplayLog>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent(vec![1])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap()
+        .0
+        .push(2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, [1, 2]);
+
+    let server_log = server_app.world.resource::<ReplayLog>();
+    let client_log = client_app.world.resource::<ReplayLog>();
+    assert_eq!(client_log.records().len(), 2);
+    assert_eq!(
+        server_log.diff(client_log),
+        None,
+        "deltas should be recorded on both sides"
+    );
+}
+
+#[test]
+fn message_hooks() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    client_app.init_resource::<HookCalls>();
+    let mut hooks = client_app.world.resource_mut::<MessageHooks>();
+    hooks.on_message_begin(|world, tick| {
+        let mut components = world.query::<&BoolComponent>();
+        let values: Vec<_> = components
+            .iter(world)
+            .map(|component| component.0)
+            .collect();
+        world
+            .resource_mut::<HookCalls>()
+            .0
+            .push(("begin", tick, values));
+    });
+    hooks.on_message_end(|world, tick| {
+        let mut components = world.query::<&BoolComponent>();
+        let values: Vec<_> = components
+            .iter(world)
+            .map(|component| component.0)
+            .collect();
+        world
+            .resource_mut::<HookCalls>()
+            .0
+            .push(("end", tick, values));
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let init_tick = **client_app.world.resource::<ServerInitTick>();
+    let update_tick = **server_app.world.resource::<ServerTick>();
+    let calls = &client_app.world.resource::<HookCalls>().0;
+    assert_eq!(
+        *calls,
+        [
+            ("begin", init_tick, vec![]),
+            ("end", init_tick, vec![false]),
+            ("begin", update_tick, vec![false]),
+            ("end", update_tick, vec![true]),
+        ]
+    );
+}
+
+#[test]
+fn custom_sections() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+
+        app.world.resource_mut::<ReplicationSections>().register(
+            "section",
+            |world, _, bytes| {
+                if let Some(value) = world.get_resource::<SectionValue>() {
+                    bytes.push(value.0);
+                }
+            },
+            |world, tick, bytes| {
+                let mut components = world.query::<&BoolComponent>();
+                let components_count = components.iter(world).count();
+                world.resource_mut::<SectionCalls>().0.push((
+                    tick,
+                    bytes.to_vec(),
+                    components_count,
+                ));
+            },
+        );
+    }
+
+    client_app.init_resource::<SectionCalls>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, BoolComponent(false)));
+    server_app.world.insert_resource(SectionValue(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world.remove_resource::<SectionValue>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let init_tick = **client_app.world.resource::<ServerInitTick>();
+    let calls = &client_app.world.resource::<SectionCalls>().0;
+    assert_eq!(
+        *calls,
+        [(init_tick, vec![1], 1)],
+        "section should be applied after insertions from the same tick"
+    );
+}
+
+#[test]
+fn mutation_timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .replicate_with::<BoolComponent>(
+            RuleFns::default().with_mutation_timeout(Duration::from_millis(150)),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Don't deliver updates to keep the change unacknowledged.
+    let mut sent_updates = Vec::new();
+    for _ in 0..3 {
+        server_app.update();
+        let mut server = server_app.world.resource_mut::<RepliconServer>();
+        let updates = server
+            .drain_sent()
+            .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Update.into())
+            .count();
+        sent_updates.push(updates);
+    }
+
+    assert_eq!(
+        sent_updates,
+        [1, 1, 0],
+        "change should be resent only until the timeout"
+    );
+}
+
+#[test]
+fn channels() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<BoolComponent>(
+            RuleFns::default().with_mutation_channel(ReplicationChannel::Init),
+        )
+        .replicate_with::<DummyComponent>(
+            RuleFns::default().with_insert_channel(ReplicationChannel::Update),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    assert_eq!(
+        sent_channels(&mut server_app),
+        [ReplicationChannel::Init.into()],
+        "change should be sent reliably"
+    );
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(DummyComponent);
+
+    server_app.update();
+    assert_eq!(
+        sent_channels(&mut server_app),
+        [ReplicationChannel::Update.into()],
+        "insertion should be sent unreliably"
+    );
+
+    // Drop the insertion to check that it will be resent.
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .for_each(drop);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (component, dummy) = client_app
+        .world
+        .query::<(&BoolComponent, Option<&DummyComponent>)>()
+        .single(&client_app.world);
+    assert!(component.0);
+    assert!(dummy.is_some(), "dropped insertion should be resent");
+}
+
+/// Returns channels of sent messages without removing them.
+fn sent_channels(server_app: &mut App) -> Vec<u8> {
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let messages: Vec<_> = server.drain_sent().collect();
+    let channels = messages
+        .iter()
+        .map(|&(_, channel_id, _)| channel_id)
+        .collect();
+    for (client_id, channel_id, message) in messages {
+        server.send(client_id, channel_id, message);
+    }
+    channels
+}
+
+#[test]
+fn intercept() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(RuleFns::default().with_intercept(truncate));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0 = vec![0; 10];
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0.len(), 2, "sent value should be intercepted");
+
+    let component = server_app.world.get::<VecComponent>(server_entity).unwrap();
+    assert_eq!(
+        component.0.len(),
+        10,
+        "server value shouldn't be affected by interception"
+    );
+}
+
+#[test]
+fn conflict_resolution() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(
+            RuleFns::default().with_conflict_resolution(rule_fns::client_wins),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<VecComponent>>()
+        .single(&client_app.world);
+
+    // Simulate prediction.
+    let mut component = client_app
+        .world
+        .get_mut::<VecComponent>(client_entity)
+        .unwrap();
+    component.0.push(1);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app.world.get::<VecComponent>(client_entity).unwrap();
+    assert_eq!(component.0, [1], "local change should win the conflict");
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(3);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app.world.get::<VecComponent>(client_entity).unwrap();
+    assert_eq!(
+        component.0,
+        [2, 3],
+        "update without local changes should be applied"
+    );
+}
+
+#[test]
+fn should_send() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(RuleFns::default().with_should_send(grown_enough));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0.is_empty(),
+        "change below the threshold shouldn't be sent"
+    );
+
+    // Changes should accumulate against the last sent value.
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0.len(), 2, "accumulated change should be sent");
+}
+
+#[test]
+fn should_send_with_delta() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(
+            RuleFns::default()
+                .with_delta(diff_tail, apply_tail)
+                .with_should_send(grown_enough),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for (pushes, len) in [(1, 0), (1, 2), (2, 4)] {
+        let mut component = server_app
+            .world
+            .get_mut::<VecComponent>(server_entity)
+            .unwrap();
+        for _ in 0..pushes {
+            component.0.push(0);
+        }
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let component = client_app
+            .world
+            .query::<&VecComponent>()
+            .single(&client_app.world);
+        assert_eq!(
+            component.0.len(),
+            len,
+            "client should have {len} elements after pushing {pushes}"
+        );
+    }
+
+    // Removal should drop stored values, so the reinserted component is sent again.
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<VecComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(VecComponent(vec![1]));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, [1]);
+}
+
+#[test]
+fn once() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_once::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(!component.0, "change shouldn't be sent");
+}
+
+#[test]
+fn send_interval() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<BoolComponent>(RuleFns::default().with_send_interval(2));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let tick = **server_app.world.resource::<ServerTick>();
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert_eq!(
+        component.0,
+        tick.get() % 2 == 0,
+        "change should be sent only on interval ticks"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&BoolComponent>()
+        .single(&client_app.world);
+    assert!(
+        component.0,
+        "skipped change should be sent on the next interval"
+    );
+}
+
+#[test]
+fn delta() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(RuleFns::default().with_delta(diff_tail, apply_tail));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent(vec![1])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, [1, 2]);
+
+    // Drop the ack to make the server send the difference against the initial value again.
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    assert_eq!(client.drain_sent().count(), 1);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(3);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, [1, 2, 3]);
+
+    let mut component = server_app
+        .world
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap();
+    component.0.push(4);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&VecComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, [1, 2, 3, 4]);
+}
+
+#[test]
+fn delta_revert() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<VecComponent>(RuleFns::default().with_delta(diff_slots, apply_slots));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, VecComponent(vec![1])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for value in [2, 1] {
+        let mut component = server_app
+            .world
+            .get_mut::<VecComponent>(server_entity)
+            .unwrap();
+        component.0[0] = value;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let component = client_app
+            .world
+            .query::<&VecComponent>()
+            .single(&client_app.world);
+        assert_eq!(component.0, [value]);
+
+        // Drop acks to keep the initial value as the base.
+        let mut client = client_app.world.resource_mut::<RepliconClient>();
+        client.drain_sent().count();
+    }
+}
+
+#[test]
+fn group_with() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_group_with(GroupFns::<(BoolComponent, VecComponent)>::new(
+            serialize_group,
+            deserialize_group,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false), VecComponent(vec![1])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (bool_component, vec_component) = client_app
+        .world
+        .query::<(&BoolComponent, &VecComponent)>()
+        .single(&client_app.world);
+    assert!(!bool_component.0);
+    assert_eq!(vec_component.0, [1]);
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (bool_component, vec_component) = client_app
+        .world
+        .query::<(&BoolComponent, &VecComponent)>()
+        .single(&client_app.world);
+    assert!(bool_component.0);
+    assert_eq!(
+        vec_component.0,
+        [1],
+        "unchanged component should be written with the group"
+    );
+}
+
+#[derive(Resource, Default)]
+struct HookCalls(Vec<(&'static str, RepliconTick, Vec<bool>)>);
+
+#[derive(Resource)]
+struct SectionValue(u8);
+
+#[derive(Resource, Default)]
+struct SectionCalls(Vec<(RepliconTick, Vec<u8>, usize)>);
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Clone, Component, Copy, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Clone, Component, Default, Deserialize, Serialize)]
+struct VecComponent(Vec<u8>);
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
         self.0 = entity_mapper.map_entity(self.0);
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 struct ReplaceMarker;
 
 #[derive(Component, Deserialize, Serialize)]
@@ -852,6 +2322,13 @@ struct HistoryMarker;
 #[derive(Component, Deref, DerefMut)]
 struct BoolHistory(Vec<bool>);
 
+#[derive(Component)]
+struct PreviousMarker;
+
+/// Previously written value of [`BoolComponent`] with its message tick.
+#[derive(Component)]
+struct PreviousBool(Option<(RepliconTick, bool)>);
+
 /// Deserializes [`OriginalComponent`], but inserts it as [`ReplacedComponent`].
 fn replace(
     ctx: &mut WriteCtx,
@@ -885,3 +2362,102 @@ fn write_history(
 
     Ok(())
 }
+
+/// Writes [`BoolComponent`] and stores its previously written value into [`PreviousBool`].
+fn write_previous(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<BoolComponent>,
+    entity: &mut EntityMut,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let previous = rule_fns
+        .deserialize_previous(ctx)?
+        .map(|(tick, component)| (tick, component.0));
+    ctx.commands
+        .entity(entity.id())
+        .insert(PreviousBool(previous));
+
+    command_fns::default_write(ctx, rule_fns, entity, cursor)
+}
+
+/// Limits [`VecComponent`] to 2 elements.
+fn truncate(_ctx: &SerializeCtx, component: &VecComponent) -> Option<VecComponent> {
+    (component.0.len() > 2).then(|| VecComponent(component.0[..2].to_vec()))
+}
+
+/// Sends [`VecComponent`] only if its length changed by at least 2 elements.
+fn grown_enough(component: &VecComponent, last_sent: &VecComponent) -> bool {
+    component.0.len().abs_diff(last_sent.0.len()) >= 2
+}
+
+/// Writes elements of [`VecComponent`] starting from the length of the base.
+fn diff_tail(
+    _ctx: &SerializeCtx,
+    base: &VecComponent,
+    component: &VecComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let start = base.0.len().min(component.0.len());
+    bincode::serialize_into(cursor, &(start, &component.0[start..]))
+}
+
+/// Replaces elements of [`VecComponent`] starting from the written position.
+fn apply_tail(
+    _ctx: &mut WriteCtx,
+    component: &mut VecComponent,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let (start, tail): (usize, Vec<u8>) = bincode::deserialize_from(cursor)?;
+    component.0.truncate(start);
+    component.0.extend(tail);
+
+    Ok(())
+}
+
+/// Writes only elements of [`VecComponent`] that differ from the base.
+fn diff_slots(
+    _ctx: &SerializeCtx,
+    base: &VecComponent,
+    component: &VecComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let changed: Vec<_> = component
+        .0
+        .iter()
+        .enumerate()
+        .filter(|&(index, value)| base.0.get(index) != Some(value))
+        .collect();
+    bincode::serialize_into(cursor, &(component.0.len(), changed))
+}
+
+/// Writes changed elements of [`VecComponent`].
+fn apply_slots(
+    _ctx: &mut WriteCtx,
+    component: &mut VecComponent,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let (len, changed): (usize, Vec<(usize, u8)>) = bincode::deserialize_from(cursor)?;
+    component.0.resize(len, 0);
+    for (index, value) in changed {
+        component.0[index] = value;
+    }
+
+    Ok(())
+}
+
+/// Serializes [`BoolComponent`] and [`VecComponent`] as a single value.
+fn serialize_group(
+    _ctx: &SerializeCtx,
+    (bool_component, vec_component): (&BoolComponent, &VecComponent),
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    bincode::serialize_into(cursor, &(bool_component.0, &vec_component.0))
+}
+
+fn deserialize_group(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<(BoolComponent, VecComponent)> {
+    let (value, elements) = bincode::deserialize_from(cursor)?;
+    Ok((BoolComponent(value), VecComponent(elements)))
+}
@@ -0,0 +1,115 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    prelude::*,
+    server::{replicon_server::RepliconServer, ServerEvent},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn routing() {
+    let mut server_apps = [App::new(), App::new()];
+    for server_app in &mut server_apps {
+        server_app.add_plugins((MinimalPlugins, RepliconPlugins));
+        setup(server_app);
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_client_endpoint(ClientEndpoint(0), setup)
+        .add_client_endpoint(ClientEndpoint(1), setup)
+        .route_client_event::<DummyEvent>()
+        .route_server_event::<ServerDummyEvent>();
+
+    for (index, server_app) in server_apps.iter_mut().enumerate() {
+        let endpoint_world = app.sub_app_mut(ClientEndpoint(index as u8)).world_mut();
+        endpoint_world.resource_mut::<RepliconClient>().set_status(
+            RepliconClientStatus::Connected {
+                client_id: Some(CLIENT_ID),
+            },
+        );
+
+        server_app
+            .world
+            .resource_mut::<RepliconServer>()
+            .set_running(true);
+        server_app.world.send_event(ServerEvent::ClientConnected {
+            client_id: CLIENT_ID,
+        });
+        server_app.update();
+    }
+
+    app.world.send_event(ToEndpoint {
+        endpoint: ClientEndpoint(1),
+        event: DummyEvent,
+    });
+    server_apps[0].world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: ServerDummyEvent,
+        apply_tick: None,
+    });
+
+    app.update();
+    for (index, server_app) in server_apps.iter_mut().enumerate() {
+        exchange(server_app, &mut app, ClientEndpoint(index as u8));
+        server_app.update();
+        exchange(server_app, &mut app, ClientEndpoint(index as u8));
+    }
+
+    // Events from endpoints are routed after the next main world update.
+    app.update();
+    app.update();
+
+    let received: Vec<_> = server_apps
+        .iter_mut()
+        .map(|server_app| {
+            server_app
+                .world
+                .resource_mut::<Events<FromClient<DummyEvent>>>()
+                .drain()
+                .count()
+        })
+        .collect();
+    assert_eq!(
+        received,
+        [0, 1],
+        "event should be routed only to the second endpoint"
+    );
+
+    let endpoints: Vec<_> = app
+        .world
+        .resource_mut::<Events<FromEndpoint<ServerDummyEvent>>>()
+        .drain()
+        .map(|event| event.endpoint)
+        .collect();
+    assert_eq!(endpoints, [ClientEndpoint(0)]);
+}
+
+const CLIENT_ID: ClientId = ClientId::new(1);
+
+fn setup(app: &mut App) {
+    app.add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .add_server_event::<ServerDummyEvent>(ChannelKind::Ordered);
+}
+
+/// Passes messages between a server app and an endpoint.
+fn exchange(server_app: &mut App, app: &mut App, endpoint: ClientEndpoint) {
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let mut client = app
+        .sub_app_mut(endpoint)
+        .world_mut()
+        .resource_mut::<RepliconClient>();
+
+    for (channel_id, message) in client.drain_sent() {
+        server.insert_received(CLIENT_ID, channel_id, message);
+    }
+
+    for (_, channel_id, message) in server.drain_sent() {
+        client.insert_received(channel_id, message);
+    }
+}
+
+#[derive(Clone, Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct ServerDummyEvent;
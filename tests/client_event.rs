@@ -1,11 +1,22 @@
+use std::time::Duration;
+
 use bevy::{
     ecs::{entity::MapEntities, event::Events},
     prelude::*,
     time::TimePlugin,
 };
 use bevy_replicon::{
-    client::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+    client::server_entity_map::ServerEntityMap,
+    network_event::client_event::{
+        rate_limit::{EventRateLimit, RateLimitExceeded, RateLimitOverflow},
+        validation::{ClientEventRejected, RejectReason},
+        ClientEventChannel, CoalescePolicy, WithMetadata,
+    },
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
 };
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -86,6 +97,606 @@ fn mapping_and_sending_receiving() {
     assert_eq!(mapped_entities, [server_entity]);
 }
 
+#[test]
+fn reflect_mapping_and_sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_mapped_reflect_client_event::<ReflectEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = Entity::from_raw(0);
+    let server_entity = Entity::from_raw(client_entity.index() + 1);
+    client_app
+        .world
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    client_app.world.send_event(ReflectEvent {
+        target: client_entity,
+        nested: vec![client_entity],
+        value: 1,
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<FromClient<ReflectEvent>>>()
+        .drain()
+        .map(|event| event.event)
+        .collect();
+    let [event] = events.as_slice() else {
+        panic!("only one event should be received");
+    };
+    assert_eq!(event.target, server_entity);
+    assert_eq!(event.nested, [server_entity]);
+    assert_eq!(event.value, 1);
+}
+
+#[test]
+fn batched_sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_batched_client_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+    client_app.world.send_event(DummyEvent);
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 3);
+}
+
+#[test]
+fn coalescing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_coalesced_client_event::<PayloadEvent>(
+                ChannelKind::Ordered,
+                CoalescePolicy::Latest,
+            )
+            .add_coalesced_client_event::<PriorityEvent>(
+                ChannelKind::Ordered,
+                CoalescePolicy::Merge(|event, other| event.0 += other.0),
+            );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for value in 1..=3 {
+        client_app.world.send_event(PayloadEvent(value));
+        client_app.world.send_event(PriorityEvent(value));
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mut payload_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<PayloadEvent>>>();
+    let payloads: Vec<_> = payload_events
+        .drain()
+        .map(|FromClient { event, .. }| event.0)
+        .collect();
+    assert_eq!(payloads, [3]);
+
+    let mut priority_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<PriorityEvent>>>();
+    let priorities: Vec<_> = priority_events
+        .drain()
+        .map(|FromClient { event, .. }| event.0)
+        .collect();
+    assert_eq!(priorities, [6]);
+}
+
+#[test]
+fn metadata() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event_with_metadata::<PayloadEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(PayloadEvent(1));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let channel = *server_app
+        .world
+        .resource::<ClientEventChannel<PayloadEvent>>();
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<WithMetadata<PayloadEvent>>>>();
+    let metadata: Vec<_> = client_events
+        .drain()
+        .map(|FromClient { event, .. }| (event.event.0, event.bytes, event.channel))
+        .collect();
+    assert_eq!(metadata, [(1, 1, channel.into())]);
+}
+
+#[test]
+fn raw_sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_raw_client_event::<RawEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world
+        .send_event(RawEvent(Bytes::from_static(&[1, 2, 3])));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<RawEvent>>>();
+    let payloads: Vec<_> = client_events
+        .drain()
+        .map(|FromClient { event, .. }| event.0)
+        .collect();
+    assert_eq!(payloads, [Bytes::from_static(&[1, 2, 3])]);
+}
+
+#[test]
+fn acknowledgment() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event_with_ack::<DummyEvent>(ChannelKind::Unreliable);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+
+    let acks = client_app.world.resource::<Events<EventAck<DummyEvent>>>();
+    assert!(acks.is_empty(), "ack shouldn't arrive before the exchange");
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let acks = client_app.world.resource::<Events<EventAck<DummyEvent>>>();
+    assert_eq!(acks.len(), 1);
+}
+
+#[test]
+fn acknowledgment_timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event_with_ack::<DummyEvent>(ChannelKind::Unreliable);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(AckTimeout(Duration::ZERO));
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    client.drain_sent().count(); // Simulate the loss of the event.
+
+    client_app.update();
+
+    let lost_events = client_app.world.resource::<Events<EventLost<DummyEvent>>>();
+    assert_eq!(lost_events.len(), 1);
+    let acks = client_app.world.resource::<Events<EventAck<DummyEvent>>>();
+    assert!(acks.is_empty());
+}
+
+#[test]
+fn upstream_budget() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<PayloadEvent>(ChannelKind::Ordered)
+            .add_client_event::<PriorityEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let priority_channel = *client_app
+        .world
+        .resource::<ClientEventChannel<PriorityEvent>>();
+    let mut budget = client_app.world.resource_mut::<UpstreamBudget>();
+    budget.set_max_bytes(Some(1));
+    budget.set_priority(priority_channel, 1);
+
+    client_app.world.send_event(PayloadEvent(0));
+    client_app.world.send_event(PriorityEvent(0));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<PayloadEvent>>>();
+    assert!(
+        client_events.is_empty(),
+        "event with lower priority should be deferred"
+    );
+    let priority_events = server_app
+        .world
+        .resource::<Events<FromClient<PriorityEvent>>>();
+    assert_eq!(priority_events.len(), 1);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<PayloadEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
+#[test]
+fn gating() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .gate_client_event::<DummyEvent, _>(resource_exists::<GateOpen>);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert!(client_events.is_empty());
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let dropped = server_app
+        .world
+        .resource::<DroppedClientEvents<DummyEvent>>();
+    assert_eq!(dropped.get(client_id), 1);
+
+    server_app.world.insert_resource(GateOpen);
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
+#[test]
+fn rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .limit_client_event::<DummyEvent>(EventRateLimit {
+                max_per_update: Some(1),
+                overflow: RateLimitOverflow::Buffer { max_buffered: 1 },
+                ..Default::default()
+            });
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..3 {
+        client_app.world.send_event(DummyEvent);
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.drain().count(), 1);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut exceeded_events = server_app
+        .world
+        .resource_mut::<Events<RateLimitExceeded<DummyEvent>>>();
+    let exceeded = exceeded_events
+        .drain()
+        .next()
+        .expect("rate limit should be exceeded");
+    assert_eq!(exceeded.client_id, client_id);
+    assert_eq!(exceeded.excess, 2);
+    assert_eq!(exceeded.dropped, 1);
+
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(
+        client_events.len(),
+        1,
+        "buffered event should be received on the next update"
+    );
+}
+
+#[test]
+fn validation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event_with_validation::<DummyEvent>(ChannelKind::Ordered, |_, _, world| {
+                if world.contains_resource::<GateOpen>() {
+                    Ok(())
+                } else {
+                    Err(RejectReason::notify("gate is closed"))
+                }
+            });
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert!(client_events.is_empty());
+
+    let mut rejected_events = client_app
+        .world
+        .resource_mut::<Events<ClientEventRejected<DummyEvent>>>();
+    let rejected = rejected_events
+        .drain()
+        .next()
+        .expect("client should be notified about rejection");
+    assert_eq!(rejected.reason, "gate is closed");
+
+    server_app.world.insert_resource(GateOpen);
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
+#[test]
+fn speculation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_resource::<AppliedEvents>()
+            .add_speculative_client_event::<DummyEvent>(
+                ChannelKind::Ordered,
+                |_, _, world| {
+                    if world.contains_resource::<GateOpen>() {
+                        Ok(())
+                    } else {
+                        Err(RejectReason::notify("gate is closed"))
+                    }
+                },
+                |world, _| world.resource_mut::<AppliedEvents>().0 += 1,
+                |world, _| world.resource_mut::<AppliedEvents>().0 -= 1,
+            );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    assert_eq!(
+        client_app.world.resource::<AppliedEvents>().0,
+        1,
+        "event should be applied before the server decision"
+    );
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert!(client_events.is_empty());
+    assert_eq!(
+        client_app.world.resource::<AppliedEvents>().0,
+        0,
+        "rejected event should be rolled back"
+    );
+
+    let mut rejected_events = client_app
+        .world
+        .resource_mut::<Events<ClientEventRejected<DummyEvent>>>();
+    assert_eq!(rejected_events.drain().count(), 1);
+
+    server_app.world.insert_resource(GateOpen);
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+    assert_eq!(
+        client_app.world.resource::<AppliedEvents>().0,
+        1,
+        "accepted event shouldn't be rolled back"
+    );
+}
+
+#[test]
+fn speculation_with_events_access() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_resource::<AppliedEvents>()
+            .add_speculative_client_event::<DummyEvent>(
+                ChannelKind::Ordered,
+                |_, _, _| Ok(()),
+                |world, _| {
+                    let events_count = world.resource::<Events<DummyEvent>>().len();
+                    world.resource_mut::<AppliedEvents>().0 += events_count;
+                },
+                |_, _| (),
+            );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    assert_eq!(
+        client_app.world.resource::<AppliedEvents>().0,
+        1,
+        "apply function should be able to read events"
+    );
+}
+
+#[test]
+#[should_panic]
+fn unreliable_speculation() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_speculative_client_event::<DummyEvent>(
+            ChannelKind::Unreliable,
+            |_, _, _| Ok(()),
+            |_, _| (),
+            |_, _| (),
+        );
+}
+
+#[test]
+fn receive_ticks() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DummyComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the component to trigger an update message that will be acknowledged.
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    let update_tick = **server_app.world.resource::<ServerTick>();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    client_app.world.send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    // Events are received before incrementing the tick.
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    server_app.update();
+
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<DummyEvent>>>();
+    let ticks: Vec<_> = client_events
+        .drain()
+        .map(|event| (event.server_tick, event.client_tick))
+        .collect();
+    assert_eq!(ticks, [(server_tick, update_tick)]);
+}
+
 #[test]
 fn local_resending() {
     let mut app = App::new();
@@ -103,10 +714,44 @@ fn local_resending() {
     assert_eq!(client_events.len(), 1);
 }
 
-#[derive(Deserialize, Event, Serialize)]
+#[test]
+fn subtick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<WithSubtick<DummyEvent>>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let subtick = Subtick::from_fraction(0.75);
+    client_app
+        .world
+        .send_event(WithSubtick::new(DummyEvent, subtick));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<WithSubtick<DummyEvent>>>>();
+    let event = client_events
+        .drain()
+        .map(|event| event.event)
+        .next()
+        .expect("event should be received");
+    assert_eq!(event.subtick, subtick);
+}
+
+#[derive(Clone, Deserialize, Event, Serialize, TypePath)]
 struct DummyEvent;
 
-#[derive(Deserialize, Event, Serialize, Clone)]
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(bool);
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
 struct MappedEvent(Entity);
 
 impl MapEntities for MappedEvent {
@@ -114,3 +759,37 @@ impl MapEntities for MappedEvent {
         self.0 = entity_mapper.map_entity(self.0);
     }
 }
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct PayloadEvent(u8);
+
+#[derive(Event, Reflect)]
+struct ReflectEvent {
+    target: Entity,
+    nested: Vec<Entity>,
+    value: u8,
+}
+
+#[derive(Event, TypePath)]
+struct RawEvent(Bytes);
+
+impl From<Bytes> for RawEvent {
+    fn from(value: Bytes) -> Self {
+        Self(value)
+    }
+}
+
+impl From<RawEvent> for Bytes {
+    fn from(value: RawEvent) -> Self {
+        value.0
+    }
+}
+
+#[derive(Resource)]
+struct GateOpen;
+
+#[derive(Resource, Default)]
+struct AppliedEvents(usize);
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct PriorityEvent(u8);
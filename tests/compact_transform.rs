@@ -0,0 +1,79 @@
+#![cfg(feature = "compact_transform")]
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication_fns::{
+        compact_transform::{self, TRANSLATION_STEP},
+        rule_fns::RuleFns,
+        test_fns::TestFnsEntityExt,
+        ReplicationFns,
+    },
+    prelude::*,
+    server::server_tick::ServerTick,
+};
+
+#[test]
+fn without_scale() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(
+                world,
+                RuleFns::new(compact_transform::serialize, compact_transform::deserialize),
+            )
+        });
+
+    let original = Transform {
+        translation: Vec3::new(1.5, -20.3, 300.7),
+        rotation: Quat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.5),
+        scale: Vec3::ONE,
+    };
+    let mut entity = app.world.spawn(original);
+    let data = entity.serialize(fns_info);
+    assert!(
+        data.len() < 16,
+        "compact data should be smaller than the full transform"
+    );
+
+    entity.remove::<Transform>();
+    entity.apply_write(&data, fns_info, tick);
+
+    let transform = *entity.get::<Transform>().unwrap();
+    assert!(transform
+        .translation
+        .abs_diff_eq(original.translation, TRANSLATION_STEP));
+    assert!(transform.rotation.angle_between(original.rotation) < 0.01);
+    assert_eq!(transform.scale, Vec3::ONE);
+}
+
+#[test]
+fn with_scale() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(
+                world,
+                RuleFns::new(compact_transform::serialize, compact_transform::deserialize),
+            )
+        });
+
+    let original =
+        Transform::from_rotation(Quat::from_rotation_y(-3.0)).with_scale(Vec3::splat(2.5));
+    let mut entity = app.world.spawn(original);
+    let data = entity.serialize(fns_info);
+    entity.remove::<Transform>();
+    entity.apply_write(&data, fns_info, tick);
+
+    let transform = *entity.get::<Transform>().unwrap();
+    assert_eq!(transform.translation, Vec3::ZERO);
+    assert!(transform.rotation.angle_between(original.rotation) < 0.01);
+    assert_eq!(transform.scale, original.scale);
+}
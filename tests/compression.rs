@@ -0,0 +1,227 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    network_event::client_event::ClientEventChannel, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn negotiation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_compressed_client_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let codecs = client_app.world.resource::<CompressionCodecs>();
+    assert_eq!(codecs.server(), None, "codec should be negotiated on reply");
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let expected = CompressionCodec::supported().next();
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let codecs = server_app.world.resource::<CompressionCodecs>();
+    assert_eq!(codecs.client(client_id), expected);
+
+    let codecs = client_app.world.resource::<CompressionCodecs>();
+    assert_eq!(codecs.server(), expected);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let codecs = server_app.world.resource::<CompressionCodecs>();
+    assert_eq!(codecs.client(client_id), None);
+
+    let codecs = client_app.world.resource::<CompressionCodecs>();
+    assert_eq!(codecs.server(), None);
+}
+
+#[test]
+fn client_event() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_compressed_client_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    for len in [THRESHOLD / 2, THRESHOLD * 4] {
+        client_app.world.send_event(LargeEvent::new(len));
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+
+        let events: Vec<_> = server_app
+            .world
+            .resource_mut::<Events<FromClient<LargeEvent>>>()
+            .drain()
+            .map(|FromClient { event, .. }| event)
+            .collect();
+        assert_eq!(events, [LargeEvent::new(len)]);
+    }
+}
+
+#[test]
+fn server_event() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_compressed_server_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    for len in [THRESHOLD / 2, THRESHOLD * 4] {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: LargeEvent::new(len),
+            apply_tick: None,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let events: Vec<_> = client_app
+            .world
+            .resource_mut::<Events<LargeEvent>>()
+            .drain()
+            .collect();
+        assert_eq!(events, [LargeEvent::new(len)]);
+    }
+}
+
+#[test]
+fn not_negotiated_codec() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_compressed_client_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Send a payload with a codec tag without negotiation.
+    let channel = *client_app
+        .world
+        .resource::<ClientEventChannel<LargeEvent>>();
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    client.send(channel, vec![1, 0, 0, 0, 0]);
+
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let events = server_app
+        .world
+        .resource::<Events<FromClient<LargeEvent>>>();
+    assert!(events.is_empty());
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+#[test]
+fn max_decompressed_size() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_compressed_client_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+    server_app.insert_resource(MaxDecompressedSize(THRESHOLD * 2));
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    for (len, events_count) in [(THRESHOLD, 1), (THRESHOLD * 4, 0)] {
+        client_app.world.send_event(LargeEvent::new(len));
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+
+        let mut events = server_app
+            .world
+            .resource_mut::<Events<FromClient<LargeEvent>>>();
+        assert_eq!(
+            events.drain().count(),
+            events_count,
+            "event with size {len} should be received {events_count} times"
+        );
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+#[test]
+fn server_event_max_decompressed_size() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_compressed_server_event::<LargeEvent>(ChannelKind::Ordered, THRESHOLD);
+    }
+    client_app.insert_resource(MaxDecompressedSize(THRESHOLD * 2));
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    for (len, events_count) in [(THRESHOLD, 1), (THRESHOLD * 4, 0)] {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: LargeEvent::new(len),
+            apply_tick: None,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let mut events = client_app.world.resource_mut::<Events<LargeEvent>>();
+        assert_eq!(
+            events.drain().count(),
+            events_count,
+            "event with size {len} should be received {events_count} times"
+        );
+    }
+}
+
+/// Exchanges supported codecs between apps.
+fn negotiate(server_app: &mut App, client_app: &mut App) {
+    server_app.exchange_with_client(client_app);
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+}
+
+const THRESHOLD: usize = 64;
+
+#[derive(Debug, Deserialize, Event, PartialEq, Serialize, TypePath)]
+struct LargeEvent(Vec<u8>);
+
+impl LargeEvent {
+    /// Creates a well-compressible event.
+    fn new(len: usize) -> Self {
+        Self(vec![1; len])
+    }
+}
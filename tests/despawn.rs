@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_replicon::{
-    client::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+    client::server_entity_map::ServerEntityMap, core::replication_fns::ctx::DespawnCtx, prelude::*,
+    server::server_tick::ServerTick, test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +44,104 @@ fn single() {
     assert!(entity_map.to_server().is_empty());
 }
 
+#[test]
+fn marker() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_marker::<KeepMarker>()
+        .set_marker_despawn::<KeepMarker>(keep);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+    let marked_server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+    let marked_client_entity = *entity_map.to_client().get(&marked_server_entity).unwrap();
+    client_app
+        .world
+        .entity_mut(marked_client_entity)
+        .insert(KeepMarker);
+
+    server_app.world.despawn(server_entity);
+    server_app.world.despawn(marked_server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world.get_entity(client_entity).is_none());
+
+    let marked_client_entity = client_app.world.entity(marked_client_entity);
+    assert!(marked_client_entity.contains::<Kept>());
+    assert!(!marked_client_entity.contains::<Replicated>());
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+}
+
+#[test]
+fn tombstones() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                despawn_retention: 1,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+
+    let tombstones = server_app.world.resource::<DespawnTombstones>();
+    assert_eq!(tombstones.was_replicated(server_entity), None);
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    let tombstones = server_app.world.resource::<DespawnTombstones>();
+    assert_eq!(tombstones.was_replicated(server_entity), Some(server_tick));
+
+    server_app.update();
+
+    let tombstones = server_app.world.resource::<DespawnTombstones>();
+    assert!(
+        tombstones.was_replicated(server_entity).is_some(),
+        "entity should be kept within the retention window"
+    );
+
+    server_app.update();
+
+    let tombstones = server_app.world.resource::<DespawnTombstones>();
+    assert_eq!(tombstones.was_replicated(server_entity), None);
+    assert!(tombstones.is_empty());
+}
+
 #[test]
 fn with_heirarchy() {
     let mut server_app = App::new();
@@ -118,5 +219,356 @@ fn after_spawn() {
     assert!(client_app.world.entities().is_empty());
 }
 
+#[test]
+fn predicted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+
+    client_app.world.commands().predicted_despawn(client_entity);
+    client_app.world.flush();
+
+    let client_entity_ref = client_app.world.entity(client_entity);
+    assert!(client_entity_ref.contains::<PredictedDespawn>());
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "mapping should be kept until the server confirms the despawn"
+    );
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world.entities().is_empty());
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+    assert!(entity_map.to_server().is_empty());
+}
+
+#[test]
+fn mispredicted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<DummyComponent>>()
+        .single(&client_app.world);
+
+    client_app.world.commands().predicted_despawn(client_entity);
+    client_app.world.flush();
+    client_app
+        .world
+        .resource_mut::<DespawnPredictionTimeout>()
+        .0 = Duration::ZERO;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity_ref = client_app.world.entity(client_entity);
+    assert!(client_entity_ref.contains::<DummyComponent>());
+    assert!(
+        !client_entity_ref.contains::<PredictedDespawn>(),
+        "entity should be restored after the timeout"
+    );
+}
+
+#[test]
+fn mispredicted_with_update() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(&client_app.world);
+
+    client_app.world.commands().predicted_despawn(client_entity);
+    client_app.world.flush();
+
+    let mut component = server_app
+        .world
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity_ref = client_app.world.entity(client_entity);
+    assert!(client_entity_ref.get::<BoolComponent>().unwrap().0);
+    assert!(
+        !client_entity_ref.contains::<PredictedDespawn>(),
+        "entity should be restored after receiving newer data before the timeout"
+    );
+}
+
+#[test]
+fn predicted_without_connection() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let entity = app.world.spawn(Replicated).id();
+
+    app.world.commands().predicted_despawn(entity);
+    app.world.flush();
+
+    assert!(app.world.get_entity(entity).is_none());
+}
+
+#[test]
+fn reset_clients() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let kept_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+    let removed_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    let old_client_entity = *entity_map.to_client().get(&kept_entity).unwrap();
+    assert_eq!(entity_map.to_client().len(), 2);
+
+    // Simulate the entity removal without sending despawn to clients.
+    server_app
+        .world
+        .entity_mut(removed_entity)
+        .remove::<Replicated>();
+    let client_local_entity = client_app.world.spawn_empty().id();
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .reset_clients();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world.get_entity(old_client_entity).is_none(),
+        "all replicated entities should be despawned"
+    );
+    assert!(
+        client_app.world.get_entity(client_local_entity).is_some(),
+        "non-replicated entities should be kept"
+    );
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 1);
+    let client_entity = *entity_map
+        .to_client()
+        .get(&kept_entity)
+        .expect("entity that is still replicated should be sent again");
+    assert!(client_app
+        .world
+        .entity(client_entity)
+        .contains::<DummyComponent>());
+}
+
+#[test]
+fn ghost() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<DummyComponent>>()
+        .single(&client_app.world);
+    client_app.world.despawn(client_entity);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app.world.entities().is_empty(),
+        "data for ghosts should be ignored"
+    );
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 1);
+    assert_eq!(entity_map.ghosts_len(), 1);
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.is_empty());
+    assert_eq!(entity_map.ghosts_len(), 0);
+}
+
+#[test]
+fn ghost_eviction() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(EntityMapGc {
+            max_ghosts: 1,
+            ..Default::default()
+        });
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn(Replicated);
+    server_app.world.spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entities: Vec<_> = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .iter(&client_app.world)
+        .collect();
+    assert_eq!(client_entities.len(), 2);
+    for client_entity in client_entities {
+        client_app.world.despawn(client_entity);
+    }
+
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 1, "oldest ghost should be evicted");
+    assert_eq!(entity_map.ghosts_len(), 1);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component)]
+struct KeepMarker;
+
+#[derive(Component)]
+struct Kept;
+
+/// Keeps the entity, but stops its replication.
+fn keep(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
+    entity.remove::<Replicated>().insert(Kept);
+}
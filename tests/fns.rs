@@ -327,6 +327,109 @@ fn remove_with_priority_marker() {
     assert!(!entity.contains::<ReplacedComponent>());
 }
 
+#[test]
+fn write_with_runtime_marker() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_systems(Update, |mut commands: Commands| {
+            commands.set_marker_fns::<ReplaceMarker, _>(
+                replace,
+                command_fns::default_remove::<ReplacedComponent>,
+            )
+        });
+
+    app.update();
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+        });
+
+    let mut entity = app.world.spawn((OriginalComponent, ReplaceMarker));
+    let data = entity.serialize(fns_info);
+    entity.apply_write(&data, fns_info, tick);
+    assert!(
+        entity.contains::<ReplacedComponent>(),
+        "marker should be registered on first use"
+    );
+}
+
+#[test]
+fn write_with_disabled_marker() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_marker::<ReplaceMarker>()
+        .set_marker_fns::<ReplaceMarker, _>(
+            replace,
+            command_fns::default_remove::<ReplacedComponent>,
+        )
+        .add_systems(Update, |mut commands: Commands| {
+            commands.set_marker_enabled::<ReplaceMarker>(false)
+        });
+
+    app.update();
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+        });
+
+    let mut entity = app.world.spawn((OriginalComponent, ReplaceMarker));
+    let data = entity.serialize(fns_info);
+    entity.remove::<OriginalComponent>();
+    entity.apply_write(&data, fns_info, tick);
+    assert!(
+        entity.contains::<OriginalComponent>(),
+        "disabled marker should be ignored"
+    );
+    assert!(!entity.contains::<ReplacedComponent>());
+}
+
+#[test]
+fn write_with_changed_priority() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_marker::<ReplaceMarker>()
+        .register_marker_with::<DummyMarker>(MarkerConfig {
+            priority: 1,
+            ..Default::default()
+        })
+        .set_marker_fns::<ReplaceMarker, _>(
+            replace,
+            command_fns::default_remove::<ReplacedComponent>,
+        )
+        .set_marker_fns::<DummyMarker, _>(
+            command_fns::default_write::<OriginalComponent>,
+            command_fns::default_remove::<OriginalComponent>,
+        )
+        .add_systems(Update, |mut commands: Commands| {
+            commands.set_marker_priority::<ReplaceMarker>(2)
+        });
+
+    app.update();
+
+    let tick = **app.world.resource::<ServerTick>();
+    let fns_info = app
+        .world
+        .resource_scope(|world, mut replication_fns: Mut<ReplicationFns>| {
+            replication_fns.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+        });
+
+    let mut entity = app
+        .world
+        .spawn((OriginalComponent, ReplaceMarker, DummyMarker));
+    let data = entity.serialize(fns_info);
+    entity.apply_write(&data, fns_info, tick);
+    assert!(
+        entity.contains::<ReplacedComponent>(),
+        "marker with raised priority should take precedence"
+    );
+}
+
 #[test]
 fn despawn() {
     let mut app = App::new();
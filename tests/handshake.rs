@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn completed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, HandshakePlugin::default()))
+            .insert_resource(Version(1))
+            .add_handshake_stage::<VersionStage>()
+            .add_handshake_stage::<TokenStage>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    assert_eq!(
+        server_app.world.resource::<Handshakes>().status(client_id),
+        Some(&HandshakeStatus::Pending { stage: 0 })
+    );
+
+    for _ in 0..2 {
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    assert!(server_app
+        .world
+        .resource::<Handshakes>()
+        .is_completed(client_id));
+    assert_eq!(
+        **client_app.world.resource::<ClientHandshake>(),
+        HandshakeStatus::Completed
+    );
+
+    let mut handshake_events = server_app.world.resource_mut::<Events<HandshakeEvent>>();
+    assert_eq!(
+        handshake_events.drain().collect::<Vec<_>>(),
+        [HandshakeEvent::Completed { client_id }]
+    );
+}
+
+#[test]
+fn rejected() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for (app, version) in [(&mut server_app, 1), (&mut client_app, 2)] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, HandshakePlugin::default()))
+            .insert_resource(Version(version))
+            .add_handshake_stage::<VersionStage>()
+            .add_handshake_stage::<TokenStage>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let reason = "version 2 doesn't match 1".to_string();
+    assert_eq!(
+        server_app.world.resource::<Handshakes>().status(client_id),
+        Some(&HandshakeStatus::Rejected {
+            reason: reason.clone()
+        })
+    );
+    assert_eq!(
+        **client_app.world.resource::<ClientHandshake>(),
+        HandshakeStatus::Rejected {
+            reason: reason.clone()
+        }
+    );
+
+    let mut handshake_events = server_app.world.resource_mut::<Events<HandshakeEvent>>();
+    assert_eq!(
+        handshake_events.drain().collect::<Vec<_>>(),
+        [HandshakeEvent::Rejected { client_id, reason }]
+    );
+}
+
+#[test]
+fn timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            HandshakePlugin {
+                timeout: Duration::ZERO,
+            },
+        ))
+        .insert_resource(Version(1))
+        .add_handshake_stage::<VersionStage>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Don't deliver the request to let the handshake expire.
+    client_app
+        .world
+        .resource_mut::<RepliconClient>()
+        .drain_sent()
+        .for_each(drop);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    assert!(matches!(
+        server_app.world.resource::<Handshakes>().status(client_id),
+        Some(HandshakeStatus::Rejected { .. })
+    ));
+    assert!(matches!(
+        **client_app.world.resource::<ClientHandshake>(),
+        HandshakeStatus::Rejected { .. }
+    ));
+}
+
+#[test]
+fn holding() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HandshakePlugin::default(),
+        ))
+        .insert_resource(Version(1))
+        .add_handshake_stage::<VersionStage>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert!(connected_clients.client(client_id).is_held());
+
+    server_app.world.spawn(Replicated);
+
+    // Update before delivering the request to replicate while the handshake is in progress.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world.query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(&client_app.world).count(),
+        0,
+        "client shouldn't receive replication until the handshake completes"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert!(!connected_clients.client(client_id).is_held());
+    assert_eq!(replicated.iter(&client_app.world).count(), 1);
+}
+
+#[derive(Resource)]
+struct Version(u32);
+
+struct VersionStage;
+
+impl HandshakeStage for VersionStage {
+    type Request = u32;
+
+    fn request(world: &mut World) -> Self::Request {
+        world.resource::<Version>().0
+    }
+
+    fn validate(world: &mut World, _client_id: ClientId, version: u32) -> Result<(), String> {
+        let server_version = world.resource::<Version>().0;
+        if version == server_version {
+            Ok(())
+        } else {
+            Err(format!("version {version} doesn't match {server_version}"))
+        }
+    }
+}
+
+struct TokenStage;
+
+impl HandshakeStage for TokenStage {
+    type Request = String;
+
+    fn request(_world: &mut World) -> Self::Request {
+        TOKEN.to_string()
+    }
+
+    fn validate(_world: &mut World, _client_id: ClientId, token: String) -> Result<(), String> {
+        if token == TOKEN {
+            Ok(())
+        } else {
+            Err("invalid token".to_string())
+        }
+    }
+}
+
+const TOKEN: &str = "secret";
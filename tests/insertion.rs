@@ -1,12 +1,23 @@
 use std::io::Cursor;
 
-use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy::{
+    ecs::entity::{EntityHashMap, MapEntities},
+    prelude::*,
+};
 use bevy_replicon::{
     client::server_entity_map::ServerEntityMap,
-    core::replication_fns::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+    core::{
+        entity_containers::{self, MapEntityContainer},
+        replication_fns::{
+            command_fns,
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::{self, RuleFns},
+        },
+    },
     prelude::*,
     test_app::ServerTestAppExt,
 };
+use bincode::{DefaultOptions, Options};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -182,6 +193,48 @@ fn mapped_new_entity() {
     assert_eq!(client_app.world.entities().len(), 2);
 }
 
+#[test]
+fn mapped_container() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_mapped::<MappedContainer>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Make client and server have different entity IDs.
+    server_app.world.spawn_empty();
+
+    let server_map_entity = server_app.world.spawn_empty().id();
+    server_app.world.spawn((
+        Replicated,
+        MappedContainer {
+            entities: vec![server_map_entity],
+            keys: EntityHashMap::from_iter([(server_map_entity, 1)]),
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let container = client_app
+        .world
+        .query::<&MappedContainer>()
+        .single(&client_app.world);
+    let client_map_entity = container.entities[0];
+    assert!(client_app.world.get_entity(client_map_entity).is_some());
+    assert_eq!(container.keys.get(&client_map_entity), Some(&1));
+}
+
 #[test]
 fn command_fns() {
     let mut server_app = App::new();
@@ -317,6 +370,82 @@ fn group() {
         .single(&client_app.world);
 }
 
+#[test]
+fn serialize_with_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(RuleFns::new(
+            serialize_with_offset,
+            rule_fns::default_deserialize::<OffsetComponent>,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, OffsetComponent(1), Offset(2)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world
+        .query::<&OffsetComponent>()
+        .single(&client_app.world);
+    assert_eq!(component.0, 3);
+}
+
+#[test]
+fn view() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_as::<ServerComponent, ViewComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(ServerComponent(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (view_component, server_component) = client_app
+        .world
+        .query::<(&ViewComponent, Has<ServerComponent>)>()
+        .single(&client_app.world);
+    assert_eq!(view_component.0, 2);
+    assert!(!server_component);
+}
+
 #[test]
 fn not_replicated() {
     let mut server_app = App::new();
@@ -401,6 +530,21 @@ fn after_removal() {
 #[derive(Component, Deserialize, Serialize)]
 struct MappedComponent(Entity);
 
+#[derive(Component, Deserialize, Serialize)]
+struct MappedContainer {
+    #[serde(with = "entity_containers::compact_entities")]
+    entities: Vec<Entity>,
+    #[serde(with = "entity_containers::compact_keys")]
+    keys: EntityHashMap<u32>,
+}
+
+impl MapEntities for MappedContainer {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entities.map_container(entity_mapper);
+        self.keys.map_container(entity_mapper);
+    }
+}
+
 impl MapEntities for MappedComponent {
     fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
         self.0 = entity_mapper.map_entity(self.0);
@@ -423,6 +567,35 @@ struct GroupComponentB;
 #[derive(Component, Deserialize, Serialize)]
 struct NotReplicatedComponent;
 
+#[derive(Component)]
+struct ServerComponent(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct ViewComponent(u32);
+
+impl From<&ServerComponent> for ViewComponent {
+    fn from(component: &ServerComponent) -> Self {
+        Self(component.0 + 1)
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct OffsetComponent(u32);
+
+/// Not replicated, but affects serialization of [`OffsetComponent`].
+#[derive(Component)]
+struct Offset(u32);
+
+/// Serializes [`OffsetComponent`] with [`Offset`] from the same entity applied.
+fn serialize_with_offset(
+    ctx: &SerializeCtx,
+    component: &OffsetComponent,
+    cursor: &mut Cursor<Vec<u8>>,
+) -> bincode::Result<()> {
+    let offset = ctx.entity.get::<Offset>().map_or(0, |offset| offset.0);
+    DefaultOptions::new().serialize_into(cursor, &OffsetComponent(component.0 + offset))
+}
+
 #[derive(Component)]
 struct ReplaceMarker;
 
@@ -0,0 +1,68 @@
+#![cfg(feature = "inspector")]
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn hierarchy() {
+    let mut server_app = App::new();
+    let mut inspector_app = App::new();
+    let mut player_app = App::new();
+    for app in [&mut server_app, &mut inspector_app, &mut player_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            InspectorPlugin::default(),
+        ));
+    }
+
+    server_app.connect_client(&mut inspector_app);
+    server_app.connect_client(&mut player_app);
+
+    let client = inspector_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ConnectedClients>()
+        .groups_mut()
+        .insert(InspectorPlugin::DEFAULT_GROUP, client_id);
+
+    let server_parent = server_app
+        .world
+        .spawn((Replicated, Name::new("Parent")))
+        .id();
+    server_app
+        .world
+        .spawn((Replicated, Name::new("Child")))
+        .set_parent(server_parent);
+
+    server_app.update();
+    for client_app in [&mut inspector_app, &mut player_app] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let (child_name, parent) = inspector_app
+        .world
+        .query::<(&Name, &Parent)>()
+        .single(&inspector_app.world);
+    assert_eq!(child_name.as_str(), "Child");
+
+    let parent_name = inspector_app.world.get::<Name>(**parent).unwrap();
+    assert_eq!(parent_name.as_str(), "Parent");
+
+    let mut player_entities = player_app
+        .world
+        .query_filtered::<(Has<Name>, Has<Parent>), With<Replicated>>();
+    assert_eq!(player_entities.iter(&player_app.world).count(), 2);
+    assert!(
+        player_entities
+            .iter(&player_app.world)
+            .all(|(name, parent)| !name && !parent),
+        "inspection data should be sent only to inspectors"
+    );
+}
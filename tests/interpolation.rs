@@ -0,0 +1,279 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_replicon::{
+    client::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn interpolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .replicate_interpolated::<Position>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, Position(0.0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let buffer = client_app
+        .world
+        .get::<InterpolationBuffer<Position>>(client_entity)
+        .unwrap();
+    assert_eq!(buffer.target().0, 10.0);
+
+    let mut positions = Vec::new();
+    for _ in 0..3 {
+        let position = client_app.world.get::<Position>(client_entity).unwrap();
+        positions.push(position.0);
+        client_app.update();
+    }
+
+    assert_eq!(
+        positions,
+        [0.0, 5.0, 10.0],
+        "value should be blended over the interval between received values"
+    );
+}
+
+#[test]
+fn extrapolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(ExtrapolationSettings {
+            max_duration: Duration::from_millis(200),
+            correction_duration: Duration::from_millis(200),
+        })
+        .replicate_extrapolated::<Position>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, Position(0.0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let buffer = client_app
+        .world
+        .get::<ExtrapolationBuffer<Position>>(client_entity)
+        .unwrap();
+    assert_eq!(buffer.last().0, 10.0);
+
+    let mut positions = Vec::new();
+    for _ in 0..4 {
+        let position = client_app.world.get::<Position>(client_entity).unwrap();
+        positions.push(position.0);
+        client_app.update();
+    }
+
+    assert_eq!(
+        positions,
+        [0.0, 7.5, 20.0, 20.0],
+        "value should be predicted past the received one, blended and limited"
+    );
+}
+
+#[test]
+fn removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_interpolated::<Position>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, Position(0.0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<Position>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    let client_entity = client_app.world.entity(client_entity);
+    assert!(!client_entity.contains::<Position>());
+    assert!(!client_entity.contains::<InterpolationBuffer<Position>>());
+}
+
+#[test]
+fn marked_interpolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            InterpolationPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .replicate::<Position>()
+        .interpolate_marked::<Position>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_interpolated = server_app.world.spawn((Replicated, Position(0.0))).id();
+    let server_entity = server_app.world.spawn((Replicated, Position(0.0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    let client_interpolated = *entity_map.to_client().get(&server_interpolated).unwrap();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+    client_app
+        .world
+        .entity_mut(client_interpolated)
+        .insert(Interpolated);
+
+    for entity in [server_interpolated, server_entity] {
+        server_app.world.get_mut::<Position>(entity).unwrap().0 = 10.0;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    client_app.update();
+
+    assert_eq!(
+        client_app
+            .world
+            .get::<Position>(client_interpolated)
+            .unwrap()
+            .0,
+        10.0,
+        "first value after marker insertion should be applied as is"
+    );
+
+    for entity in [server_interpolated, server_entity] {
+        server_app.world.get_mut::<Position>(entity).unwrap().0 = 20.0;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let position = client_app.world.get::<Position>(client_entity).unwrap();
+    assert_eq!(
+        position.0, 20.0,
+        "entity without marker should receive values as is"
+    );
+    assert!(!client_app
+        .world
+        .entity(client_entity)
+        .contains::<InterpolationBuffer<Position>>());
+
+    let mut positions = Vec::new();
+    for _ in 0..3 {
+        let position = client_app
+            .world
+            .get::<Position>(client_interpolated)
+            .unwrap();
+        positions.push(position.0);
+        client_app.update();
+    }
+
+    assert_eq!(positions, [10.0, 15.0, 20.0]);
+}
+
+#[derive(Component, Clone, Deserialize, Serialize)]
+struct Position(f32);
+
+impl Interpolate for Position {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self(self.0.interpolate(&other.0, t))
+    }
+}
+
+impl Extrapolate for Position {
+    fn extrapolate(&self, other: &Self, interval: Duration, elapsed: Duration) -> Self {
+        Self(self.0.extrapolate(&other.0, interval, elapsed))
+    }
+}
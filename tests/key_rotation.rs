@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replicon_tick::RepliconTick, prelude::*, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn rotation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            KeyRotationPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let rotation_tick = **server_app.world.resource::<ServerTick>() + 10;
+    server_app
+        .world
+        .resource_mut::<ServerKeyRotation>()
+        .rotate(KEY_ID, rotation_tick);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_rotation = server_app.world.resource::<ServerKeyRotation>();
+    let client_rotation = client_app.world.resource::<ClientKeyRotation>();
+    for tick in [rotation_tick - 1, rotation_tick, rotation_tick + 1] {
+        assert_eq!(
+            server_rotation.key_id_at(tick),
+            client_rotation.key_id_at(tick)
+        );
+    }
+    assert_eq!(client_rotation.key_id_at(rotation_tick - 1), 0);
+    assert_eq!(client_rotation.key_id_at(rotation_tick), KEY_ID);
+
+    let mut scheduled_events = client_app
+        .world
+        .resource_mut::<Events<KeyRotationScheduled>>();
+    assert_eq!(
+        scheduled_events.drain().collect::<Vec<_>>(),
+        [KeyRotationScheduled {
+            key_id: KEY_ID,
+            tick: rotation_tick,
+        }]
+    );
+}
+
+#[test]
+fn late_connection() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            KeyRotationPlugin,
+        ));
+    }
+
+    server_app
+        .world
+        .resource_mut::<ServerKeyRotation>()
+        .rotate(KEY_ID, RepliconTick::new(1));
+
+    server_app.update();
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_rotation = client_app.world.resource::<ClientKeyRotation>();
+    assert_eq!(client_rotation.key_id_at(RepliconTick::new(1)), KEY_ID);
+}
+
+const KEY_ID: u32 = 1;
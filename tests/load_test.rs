@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn load_test() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            LoadTestPlugin {
+                clients: 10,
+                ..Default::default()
+            },
+        ))
+        .replicate::<DummyComponent>()
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .add_load_test_event(Duration::ZERO, |_, index| DummyEvent(index));
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(connected_clients.len(), 10);
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 10);
+
+    server_app.update();
+
+    let metrics = server_app.world.resource::<LoadTestMetrics>();
+    assert_eq!(metrics.frames, 3);
+    assert_eq!(metrics.events_sent, 20);
+    assert!(metrics.messages_received >= 10);
+    assert!(metrics.bytes_received > 0);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_sent().count(),
+        0,
+        "messages for simulated clients shouldn't reach the backend"
+    );
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    server_app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins,
+        LoadTestPlugin {
+            clients: 10,
+            ..Default::default()
+        },
+    ));
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    server_app.update();
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(false);
+
+    server_app.update();
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    server_app.update();
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.len(),
+        10,
+        "clients should be connected again after restart"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent(usize);
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn transition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            MapTransitionPlugin::default(),
+        ))
+        .replicate::<Level>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let old_level = server_app.world.spawn((Replicated, Level(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<ServerMapTransition>()
+        .start(LEVEL_ID);
+    server_app.world.despawn(old_level);
+    server_app.world.spawn((Replicated, Level(LEVEL_ID)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        server_app.world.resource::<ServerMapTransition>().state(),
+        MapTransitionState::Loading { level_id: LEVEL_ID }
+    );
+    assert_eq!(
+        client_app.world.resource::<ClientMapTransition>().state(),
+        MapTransitionState::Loading { level_id: LEVEL_ID }
+    );
+    let mut started_events = client_app
+        .world
+        .resource_mut::<Events<MapTransitionStarted>>();
+    assert_eq!(
+        started_events.drain().collect::<Vec<_>>(),
+        [MapTransitionStarted { level_id: LEVEL_ID }]
+    );
+
+    assert_eq!(
+        client_levels(&mut client_app),
+        [0],
+        "replication should be paused during loading"
+    );
+
+    client_app
+        .world
+        .resource_mut::<ClientMapTransition>()
+        .ready();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        server_app.world.resource::<ServerMapTransition>().state(),
+        MapTransitionState::Idle
+    );
+    assert_eq!(
+        client_app.world.resource::<ClientMapTransition>().state(),
+        MapTransitionState::Idle
+    );
+    let mut finished_events = server_app
+        .world
+        .resource_mut::<Events<MapTransitionFinished>>();
+    assert_eq!(
+        finished_events.drain().collect::<Vec<_>>(),
+        [MapTransitionFinished {
+            level_id: LEVEL_ID,
+            timed_out: Vec::new(),
+        }]
+    );
+
+    assert_eq!(
+        client_levels(&mut client_app),
+        [LEVEL_ID],
+        "client should receive the new level"
+    );
+}
+
+#[test]
+fn timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            MapTransitionPlugin {
+                timeout: Duration::ZERO,
+            },
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<ServerMapTransition>()
+        .start(LEVEL_ID);
+
+    server_app.update();
+    server_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let mut finished_events = server_app
+        .world
+        .resource_mut::<Events<MapTransitionFinished>>();
+    assert_eq!(
+        finished_events.drain().collect::<Vec<_>>(),
+        [MapTransitionFinished {
+            level_id: LEVEL_ID,
+            timed_out: vec![client_id],
+        }]
+    );
+    assert!(!server_app.world.resource::<ReplicationPause>().is_paused());
+}
+
+fn client_levels(app: &mut App) -> Vec<u64> {
+    app.world
+        .query::<&Level>()
+        .iter(&app.world)
+        .map(|level| level.0)
+        .collect()
+}
+
+const LEVEL_ID: u64 = 1;
+
+#[derive(Component, Deserialize, Serialize)]
+struct Level(u64);
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            NetIdPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let net_id = *server_app.world.get::<NetId>(server_entity).unwrap();
+    let server_net_ids = server_app.world.resource::<NetIdMap>();
+    assert_eq!(server_net_ids.get(net_id), Some(server_entity));
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    let client_net_ids = client_app.world.resource::<NetIdMap>();
+    assert_eq!(client_net_ids.get(net_id), Some(client_entity));
+    assert_eq!(client_net_ids.net_id(client_entity), Some(net_id));
+
+    server_app.world.despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world.resource::<NetIdMap>().is_empty());
+    assert!(client_app.world.resource::<NetIdMap>().is_empty());
+}
+
+#[test]
+fn loaded_ids() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, NetIdPlugin));
+
+    let loaded_entity = app.world.spawn((Replicated, NetId::new(10))).id();
+    let new_entity = app.world.spawn(Replicated).id();
+
+    app.update();
+
+    let loaded_id = app.world.get::<NetId>(loaded_entity).unwrap();
+    assert_eq!(loaded_id.get(), 10, "loaded ID should be kept");
+
+    let new_id = app.world.get::<NetId>(new_entity).unwrap();
+    assert_eq!(new_id.get(), 11, "new IDs should continue after loaded");
+}
+
+#[test]
+fn duplicate_ids() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, NetIdPlugin));
+
+    let original_entity = app.world.spawn(Replicated).id();
+
+    app.update();
+
+    let original_id = *app.world.get::<NetId>(original_entity).unwrap();
+    let duplicate_entity = app.world.spawn((Replicated, original_id)).id();
+
+    app.update();
+
+    let duplicate_id = *app.world.get::<NetId>(duplicate_entity).unwrap();
+    assert_ne!(duplicate_id, original_id, "duplicate should get a new ID");
+    assert_eq!(
+        *app.world.get::<NetId>(original_entity).unwrap(),
+        original_id
+    );
+
+    let net_ids = app.world.resource::<NetIdMap>();
+    assert_eq!(net_ids.get(original_id), Some(original_entity));
+    assert_eq!(net_ids.get(duplicate_id), Some(duplicate_entity));
+    assert_eq!(net_ids.len(), 2);
+}
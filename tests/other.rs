@@ -1,8 +1,17 @@
-use bevy::prelude::*;
+use std::sync::mpsc;
+
+use bevy::{diagnostic::DiagnosticsStore, prelude::*};
 use bevy_replicon::{
-    core::replicon_channels::ReplicationChannel, prelude::*, server::server_tick::ServerTick,
+    client::server_entity_map::ServerEntityMap,
+    core::replicon_channels::ReplicationChannel,
+    prelude::*,
+    server::{
+        flow_trace::{FlowDirection, FlowTrace},
+        server_tick::ServerTick,
+    },
     test_app::ServerTestAppExt,
 };
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -39,6 +48,38 @@ fn client_to_server() {
     assert_eq!(messages, MESSAGES);
 }
 
+#[test]
+fn received_batches() {
+    let mut server_app = App::new();
+    server_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    server_app.update();
+
+    const CLIENT_ID: ClientId = ClientId::new(0);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    server.set_running(true);
+    server.insert_received(CLIENT_ID, ReplicationChannel::Init, &[0][..]);
+    server.insert_received(CLIENT_ID, ReplicationChannel::Init, &[1][..]);
+
+    let batches: Vec<_> = server.split_received().collect();
+    assert_eq!(
+        batches.len(),
+        1,
+        "only non-empty channels should be returned"
+    );
+
+    let batch = &batches[0];
+    assert_eq!(batch.channel_id(), u8::from(ReplicationChannel::Init));
+    let messages: Vec<_> = batch
+        .messages()
+        .iter()
+        .map(|(_, message)| message)
+        .collect();
+    assert_eq!(messages, [&[0][..], &[1][..]]);
+
+    assert!(server.take_received(ReplicationChannel::Init).is_empty());
+}
+
 #[test]
 fn server_to_client() {
     let mut server_app = App::new();
@@ -70,6 +111,67 @@ fn server_to_client() {
     assert_eq!(messages, MESSAGES);
 }
 
+#[test]
+fn flush_now() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+        app.update();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    const MESSAGE: &[u8] = &[0];
+    const CHANNEL_ID: u8 = 0;
+
+    let (sender, receiver) = mpsc::channel();
+    client_app
+        .world
+        .resource_scope(|world, mut budget: Mut<UpstreamBudget>| {
+            let mut client = world.resource_mut::<RepliconClient>();
+            client.set_flush_fn(move |channel_id, message| {
+                sender.send((channel_id, message)).unwrap();
+            });
+
+            client.send(CHANNEL_ID, MESSAGE);
+            client.flush_now(&mut budget);
+            assert_eq!(client.drain_sent().count(), 0);
+
+            let flushed: Vec<_> = receiver.try_iter().collect();
+            assert_eq!(flushed, [(CHANNEL_ID, Bytes::from_static(MESSAGE))]);
+
+            budget.set_max_bytes(Some(MESSAGE.len()));
+            client.send(CHANNEL_ID, MESSAGE);
+            client.send(CHANNEL_ID, MESSAGE);
+            client.flush_now(&mut budget);
+            assert_eq!(client.drain_sent().count(), 0);
+            assert_eq!(receiver.try_iter().count(), 1);
+            assert_eq!(
+                budget.deferred_count(),
+                1,
+                "message that exceeds the budget should be deferred"
+            );
+        });
+
+    let (sender, receiver) = mpsc::channel();
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    server.set_flush_fn(move |client_id, channel_id, message| {
+        sender.send((client_id, channel_id, message)).unwrap();
+    });
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server.send(client_id, CHANNEL_ID, MESSAGE);
+    server.flush_now();
+    assert_eq!(server.drain_sent().count(), 0);
+
+    let flushed: Vec<_> = receiver.try_iter().collect();
+    assert_eq!(
+        flushed,
+        [(client_id, CHANNEL_ID, Bytes::from_static(MESSAGE))]
+    );
+}
+
 #[test]
 fn connect_disconnect() {
     let mut server_app = App::new();
@@ -263,5 +365,198 @@ fn diagnostics() {
     assert_eq!(stats.bytes, 33);
 }
 
+#[test]
+fn timing_diagnostics() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TimingDiagnosticsPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let diagnostics = server_app.world.resource::<DiagnosticsStore>();
+    for path in [
+        TimingDiagnosticsPlugin::SERVER_RECEIVE,
+        TimingDiagnosticsPlugin::SERVER_SEND,
+    ] {
+        let diagnostic = diagnostics.get(&path).unwrap();
+        assert!(
+            diagnostic.measurement().is_some(),
+            "{path} should be measured"
+        );
+    }
+    let diagnostic = diagnostics
+        .get(&TimingDiagnosticsPlugin::CLIENT_RECEIVE)
+        .unwrap();
+    assert!(
+        diagnostic.measurement().is_none(),
+        "client spans shouldn't be measured on server"
+    );
+
+    let diagnostics = client_app.world.resource::<DiagnosticsStore>();
+    for path in [
+        TimingDiagnosticsPlugin::CLIENT_RECEIVE,
+        TimingDiagnosticsPlugin::CLIENT_APPLY,
+        TimingDiagnosticsPlugin::CLIENT_SEND,
+    ] {
+        let diagnostic = diagnostics.get(&path).unwrap();
+        assert!(
+            diagnostic.measurement().is_some(),
+            "{path} should be measured"
+        );
+    }
+}
+
+#[test]
+fn flow_trace() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    server_app.insert_resource(FlowTrace::new(client.id().unwrap()));
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let trace = server_app.world.resource::<FlowTrace>();
+    let records: Vec<_> = trace
+        .records()
+        .iter()
+        .map(|record| (record.direction, record.channel_id))
+        .collect();
+    assert_eq!(
+        records,
+        [
+            (FlowDirection::Sent, ReplicationChannel::Init.into()),
+            (FlowDirection::Received, ReplicationChannel::Init.into()),
+            (FlowDirection::Received, ReplicationChannel::Init.into()),
+            (FlowDirection::Sent, ReplicationChannel::Update.into()),
+            (FlowDirection::Received, ReplicationChannel::Init.into()),
+        ],
+        "client should send acks on every update"
+    );
+    assert_eq!(trace.records()[1].summary, "acks for update messages []");
+    assert_eq!(trace.records()[4].summary, "acks for update messages [0]");
+
+    let mut log = Vec::new();
+    trace.write_log(&mut log).unwrap();
+    let log = String::from_utf8(log).unwrap();
+    assert_eq!(
+        log.lines().count(),
+        trace.records().len() + 1,
+        "log should contain header and all records"
+    );
+}
+
+#[test]
+fn tick_changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    client_app
+        .world
+        .resource_mut::<Events<TickChangesApplied>>()
+        .clear();
+
+    let server_entity1 = server_app.world.spawn((Replicated, DummyComponent)).id();
+    let server_entity2 = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    let client_entity1 = *entity_map.to_client().get(&server_entity1).unwrap();
+    let client_entity2 = *entity_map.to_client().get(&server_entity2).unwrap();
+
+    let mut tick_changes = client_app
+        .world
+        .resource_mut::<Events<TickChangesApplied>>();
+    let changes: Vec<_> = tick_changes.drain().collect();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].spawned.len(), 2);
+    assert!(changes[0].spawned.contains(&client_entity1));
+    assert!(changes[0].spawned.contains(&client_entity2));
+    assert!(changes[0].despawned.is_empty());
+    assert_eq!(changes[0].updated, 0);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity1)
+        .unwrap()
+        .set_changed();
+    server_app.world.despawn(server_entity2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_tick = *server_app.world.resource::<ServerTick>();
+    let mut tick_changes = client_app
+        .world
+        .resource_mut::<Events<TickChangesApplied>>();
+    assert_eq!(
+        tick_changes.drain().collect::<Vec<_>>(),
+        [TickChangesApplied {
+            tick: *server_tick,
+            spawned: Vec::new(),
+            despawned: vec![client_entity2],
+            updated: 1,
+        }]
+    );
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
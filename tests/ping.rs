@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+
+#[test]
+fn measurement() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            PingPlugin {
+                interval: FRAME_TIME,
+                ..Default::default()
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ClientNames>()
+        .insert(client_id, "Alice");
+
+    for _ in 0..4 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let stats = client_app.world.resource::<NetworkStats>();
+    assert_ne!(stats.samples, 0);
+    assert_eq!(stats.rtt, FRAME_TIME);
+    assert_eq!(stats.one_way_delay(), FRAME_TIME / 2);
+
+    let client_stats = server_app.world.resource::<ClientNetworkStats>();
+    let stats = client_stats
+        .get(client_id)
+        .expect("server should receive pongs from the client");
+    assert_ne!(stats.samples, 0);
+    assert_eq!(stats.rtt, FRAME_TIME);
+    let names = server_app.world.resource::<ClientNames>();
+    let (label, _) = client_stats.iter_labeled(names).next().unwrap();
+    assert_eq!(label.to_string(), names.label(client_id).to_string());
+    assert!(label.to_string().contains("Alice"));
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client_stats = server_app.world.resource::<ClientNetworkStats>();
+    assert!(client_stats.get(client_id).is_none());
+    let stats = client_app.world.resource::<NetworkStats>();
+    assert_eq!(stats.samples, 0);
+}
+
+#[test]
+fn pause() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            PingPlugin {
+                interval: FRAME_TIME,
+                ..Default::default()
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..4 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    // Simulate a throttled client.
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(5)));
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let stats = client_app.world.resource::<NetworkStats>();
+    assert_eq!(stats.samples, 0, "measurement should restart after pause");
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    for _ in 0..4 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let stats = client_app.world.resource::<NetworkStats>();
+    assert_ne!(stats.samples, 0);
+    assert_eq!(stats.rtt, FRAME_TIME, "stale pings should be discarded");
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let client_stats = server_app.world.resource::<ClientNetworkStats>();
+    let stats = client_stats.get(client_id).unwrap();
+    assert_eq!(
+        stats.rtt, FRAME_TIME,
+        "pings delayed by the pause shouldn't be echoed"
+    );
+}
+
+#[test]
+fn coarse_clock() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for (app, clock) in [
+        (&mut server_app, PingPlugin::default().clock),
+        (&mut client_app, coarse_elapsed),
+    ] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            PingPlugin {
+                interval: FRAME_TIME,
+                clock,
+                ..Default::default()
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..12 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let clock = client_app.world.resource::<PingClock>();
+    assert_eq!(clock.resolution(), CLOCK_RESOLUTION);
+
+    let stats = client_app.world.resource::<NetworkStats>();
+    assert_ne!(stats.samples, 0);
+    assert!(
+        !stats.rtt.is_zero(),
+        "pings returned before the clock advanced shouldn't report zero latency"
+    );
+
+    let clock = server_app.world.resource::<PingClock>();
+    assert_eq!(clock.resolution(), Duration::ZERO);
+}
+
+/// Rounds time down to [`CLOCK_RESOLUTION`] to simulate a coarse timer.
+fn coarse_elapsed(time: &Time<Real>) -> Duration {
+    let steps = time.elapsed().as_millis() / CLOCK_RESOLUTION.as_millis();
+    CLOCK_RESOLUTION * steps as u32
+}
+
+const CLOCK_RESOLUTION: Duration = Duration::from_millis(500);
+const FRAME_TIME: Duration = Duration::from_millis(100);
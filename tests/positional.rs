@@ -0,0 +1,187 @@
+use bevy::{
+    ecs::event::Events,
+    prelude::*,
+    time::{TimePlugin, TimeUpdateStrategy},
+    utils::Duration,
+};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_positional_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    for (client_position, events_count) in
+        [(None, 0), (Some(Vec3::X * 20.0), 0), (Some(Vec3::X), 1)]
+    {
+        let mut positions = server_app.world.resource_mut::<RelevancyPositions>();
+        if let Some(position) = client_position {
+            positions.set(client_id, position);
+        }
+
+        server_app.world.send_event(ToNearby {
+            position: Vec3::ZERO,
+            radius: 10.0,
+            event: DummyEvent,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut dummy_events = client_app
+            .world
+            .resource_mut::<Events<Positional<DummyEvent>>>();
+        let events: Vec<_> = dummy_events.drain().collect();
+        assert_eq!(
+            events.len(),
+            events_count,
+            "event should be emited {events_count} times for {client_position:?}"
+        );
+        if let Some(event) = events.first() {
+            assert_eq!(event.position, Vec3::ZERO);
+        }
+    }
+
+    server_app.disconnect_client(&mut client_app);
+
+    let positions = server_app.world.resource::<RelevancyPositions>();
+    assert_eq!(
+        positions.get(client_id),
+        None,
+        "position should be removed on disconnect"
+    );
+}
+
+#[test]
+fn interpolation_delay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .replicate::<DummyComponent>()
+        .add_positional_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app
+        .world
+        .resource_mut::<RelevancyPositions>()
+        .set(client_id, Vec3::ZERO);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent(0))).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let interpolation_delay = client_app.world.resource::<InterpolationDelay>();
+    assert_eq!(interpolation_delay.get(), Duration::from_millis(100));
+
+    server_app.world.send_event(ToNearby {
+        position: Vec3::ZERO,
+        radius: 10.0,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut dummy_events = client_app
+        .world
+        .resource_mut::<Events<Positional<DummyEvent>>>();
+    assert_eq!(
+        dummy_events.drain().count(),
+        0,
+        "event should be delayed by interpolation"
+    );
+
+    client_app.update();
+
+    let mut dummy_events = client_app
+        .world
+        .resource_mut::<Events<Positional<DummyEvent>>>();
+    assert_eq!(dummy_events.drain().count(), 1);
+}
+
+#[test]
+fn local_resending() {
+    let mut app = App::new();
+    app.add_plugins((
+        TimePlugin,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_positional_event::<DummyEvent>(ChannelKind::Ordered);
+
+    app.world
+        .resource_mut::<RelevancyPositions>()
+        .set(ClientId::SERVER, Vec3::ZERO);
+
+    for (position, events_count) in [(Vec3::X, 1), (Vec3::X * 20.0, 0)] {
+        app.world.send_event(ToNearby {
+            position,
+            radius: 10.0,
+            event: DummyEvent,
+        });
+
+        app.update();
+
+        let mut dummy_events = app.world.resource_mut::<Events<Positional<DummyEvent>>>();
+        assert_eq!(
+            dummy_events.drain().count(),
+            events_count,
+            "event should be emited {events_count} times for {position}"
+        );
+    }
+}
+
+#[derive(Debug, Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, replicon_protocol, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+replicon_protocol! {
+    struct TestProtocol;
+    channels: [server => ChannelKind::Unreliable, client => ChannelKind::Unordered],
+    components: [DummyComponent],
+    client_events: [DummyEvent => ChannelKind::Ordered],
+    server_events: [ServerDummyEvent => ChannelKind::Ordered],
+}
+
+#[test]
+fn registration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            TestProtocol,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+    client_app.world.send_event(DummyEvent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+
+    let client_events = server_app
+        .world
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
+#[test]
+fn channels() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, TestProtocol));
+
+    // Custom channels are created right after the replication channels.
+    let channels = app.world.resource::<RepliconChannels>();
+    assert_eq!(channels.server_channels()[2].kind, ChannelKind::Unreliable);
+    assert_eq!(channels.client_channels()[2].kind, ChannelKind::Unordered);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct ServerDummyEvent;
@@ -0,0 +1,85 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+#[should_panic]
+fn unmapped_event() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<EntityEvent>()
+        .add_server_event::<EntityEvent>(ChannelKind::Ordered);
+
+    app.world.resource_mut::<ProtocolValidation>().strict = true;
+    app.finish();
+}
+
+#[test]
+#[should_panic]
+fn unmapped_component() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<EntityComponent>()
+        .replicate::<EntityComponent>();
+
+    app.world.resource_mut::<ProtocolValidation>().strict = true;
+    app.finish();
+}
+
+#[test]
+fn mapped() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<EntityEvent>()
+        .register_type::<EntityComponent>()
+        .add_mapped_client_event::<EntityEvent>(ChannelKind::Ordered)
+        .add_mapped_server_event::<EntityEvent>(ChannelKind::Ordered)
+        .replicate_mapped::<EntityComponent>();
+
+    app.world.resource_mut::<ProtocolValidation>().strict = true;
+    app.finish();
+}
+
+#[test]
+#[should_panic]
+fn channel_limit() {
+    let mut reference_app = App::new();
+    reference_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    reference_app.finish();
+    let channels_count = reference_app
+        .world
+        .resource::<RepliconChannels>()
+        .server_channels()
+        .len();
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let mut validation = app.world.resource_mut::<ProtocolValidation>();
+    validation.strict = true;
+    validation.max_server_channels = Some(channels_count);
+
+    app.add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    app.finish();
+}
+
+#[derive(Deserialize, Event, Reflect, Serialize)]
+struct EntityEvent(Entity);
+
+impl MapEntities for EntityEvent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Component, Deserialize, Reflect, Serialize)]
+struct EntityComponent(Entity);
+
+impl MapEntities for EntityComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
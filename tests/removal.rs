@@ -50,6 +50,50 @@ fn single() {
     assert!(!client_entity.contains::<DummyComponent>());
 }
 
+#[test]
+fn view() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_as::<ServerComponent, ViewComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, ServerComponent(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<ViewComponent>>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<ServerComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world.entity(client_entity);
+    assert!(!client_entity.contains::<ViewComponent>());
+}
 #[test]
 fn command_fns() {
     let mut server_app = App::new();
@@ -360,3 +404,15 @@ fn replace(
 
     Ok(())
 }
+
+#[derive(Component)]
+struct ServerComponent(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct ViewComponent(u32);
+
+impl From<&ServerComponent> for ViewComponent {
+    fn from(component: &ServerComponent) -> Self {
+        Self(component.0)
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn response() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_request::<Ping, Pong>(ChannelKind::Ordered, Duration::from_secs(60));
+    }
+    server_app.add_systems(Update, reply);
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.add_systems(Update, send_pings.run_if(run_once()));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let results: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<RequestResult<Pong>>>()
+        .drain()
+        .map(|result| result.result)
+        .collect();
+    assert_eq!(
+        results,
+        [
+            Ok(Pong(1)),
+            Err(RequestError::Rejected(REJECT_REASON.into()))
+        ]
+    );
+    assert!(client_app
+        .world
+        .resource::<PendingRequests<Pong>>()
+        .is_empty());
+}
+
+#[test]
+fn timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_request::<Ping, Pong>(ChannelKind::Ordered, Duration::ZERO);
+    }
+    server_app.add_systems(Update, reply);
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.add_systems(Update, send_pings.run_if(run_once()));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    client_app.update();
+
+    let results: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<RequestResult<Pong>>>()
+        .drain()
+        .map(|result| result.result)
+        .collect();
+    assert_eq!(results, vec![Err(RequestError::TimedOut); 2]);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let events = client_app.world.resource::<Events<RequestResult<Pong>>>();
+    assert!(
+        events.is_empty(),
+        "responses after timeout should be ignored"
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_request::<Ping, Pong>(ChannelKind::Ordered, Duration::from_secs(60));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.add_systems(Update, send_pings.run_if(run_once()));
+    client_app.update();
+
+    server_app.disconnect_client(&mut client_app);
+
+    let results: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<RequestResult<Pong>>>()
+        .drain()
+        .map(|result| result.result)
+        .collect();
+    assert_eq!(results, vec![Err(RequestError::Disconnected); 2]);
+}
+
+#[test]
+#[should_panic]
+fn conflicting_registration() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_request::<Ping, Pong>(ChannelKind::Ordered, Duration::from_secs(60))
+        .add_request::<OtherPing, Pong>(ChannelKind::Ordered, Duration::from_secs(5));
+}
+
+fn send_pings(mut requests: RequestSender<Ping, Pong>) {
+    requests.send_request(Ping(1));
+    requests.send_request(Ping(0));
+}
+
+/// Replies with the same number or rejects zero.
+fn reply(
+    mut requests: EventReader<FromClient<Request<Ping>>>,
+    mut responses: ResponseSender<Pong>,
+) {
+    for FromClient {
+        client_id, event, ..
+    } in requests.read()
+    {
+        if event.request.0 == 0 {
+            responses.reject(*client_id, event.id, REJECT_REASON);
+        } else {
+            responses.respond(*client_id, event.id, Pong(event.request.0));
+        }
+    }
+}
+
+const REJECT_REASON: &str = "zero";
+
+#[derive(Deserialize, Serialize, TypePath)]
+struct Ping(u32);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, TypePath)]
+struct Pong(u32);
+
+#[derive(Deserialize, Serialize, TypePath)]
+struct OtherPing;
@@ -2,10 +2,14 @@ use bevy::{
     ecs::{entity::MapEntities, event::Events},
     prelude::*,
     time::TimePlugin,
+    utils::Duration,
 };
 use bevy_replicon::{
     client::{server_entity_map::ServerEntityMap, ServerInitTick},
+    core::replicon_channels::ReplicationChannel,
+    network_event::server_event::StickyPolicy,
     prelude::*,
+    server::server_tick::ServerTick,
     test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
@@ -58,10 +62,15 @@ fn sending_receiving() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::Only(vec![ClientId::SERVER, client_id]), 1),
+        (SendMode::Only(vec![ClientId::SERVER]), 0),
+        (SendMode::Except(vec![ClientId::SERVER]), 1),
+        (SendMode::Except(vec![ClientId::SERVER, client_id]), 0),
     ] {
         server_app.world.send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
+            apply_tick: None,
         });
 
         server_app.update();
@@ -78,6 +87,292 @@ fn sending_receiving() {
     }
 }
 
+#[test]
+fn visible_sending() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app.world.spawn(Replicated).id();
+
+    for (visible, events_count) in [(false, 0), (true, 1)] {
+        server_app
+            .world
+            .resource_mut::<ConnectedClients>()
+            .client_mut(client_id)
+            .visibility_mut()
+            .set_visibility(server_entity, visible);
+
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Visible(server_entity),
+            event: DummyEvent,
+            apply_tick: None,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+        assert_eq!(
+            dummy_events.drain().count(),
+            events_count,
+            "event should be received {events_count} times when visibility is {visible}"
+        );
+    }
+}
+
+#[test]
+fn group_sending() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    const GROUP: GroupId = GroupId(0);
+    for (member, events_count) in [(false, 0), (true, 1)] {
+        let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+        if member {
+            assert!(connected_clients.groups_mut().insert(GROUP, client_id));
+        }
+
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Group(GROUP),
+            event: DummyEvent,
+            apply_tick: None,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+        assert_eq!(
+            dummy_events.drain().count(),
+            events_count,
+            "event should be received {events_count} times when membership is {member}"
+        );
+    }
+
+    server_app.disconnect_client(&mut client_app);
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert!(
+        !connected_clients.groups().contains(GROUP, client_id),
+        "client should be removed from groups on disconnect"
+    );
+}
+
+#[test]
+fn sticky() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<ValueEvent>(ChannelKind::Ordered)
+        .make_sticky::<ValueEvent>(StickyPolicy::Last(1));
+    }
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    for value in [1, 2] {
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: ValueEvent(value),
+            apply_tick: None,
+        });
+    }
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Direct(ClientId::new(1)),
+        event: ValueEvent(3),
+        apply_tick: None,
+    });
+
+    server_app.update();
+
+    server_app.connect_client(&mut client_app);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let values: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<ValueEvent>>()
+        .drain()
+        .map(|event| event.0)
+        .collect();
+    assert_eq!(
+        values,
+        [2],
+        "only the last broadcasted event should be resent"
+    );
+}
+
+#[test]
+fn scheduled() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world.resource::<ServerTick>();
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+        apply_tick: Some(server_tick + 2),
+    });
+
+    for events_count in [0, 1] {
+        // Spawn entities to advance replication on client.
+        server_app.world.spawn(Replicated);
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+        assert_eq!(
+            dummy_events.drain().count(),
+            events_count,
+            "event should be applied only after reaching its tick"
+        );
+    }
+}
+
+#[test]
+fn scheduled_locally() {
+    let mut app = App::new();
+    app.add_plugins((
+        TimePlugin,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+
+    app.world.resource_mut::<RepliconServer>().set_running(true);
+
+    let server_tick = **app.world.resource::<ServerTick>();
+    app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+        apply_tick: Some(server_tick + 2),
+    });
+
+    for events_count in [0, 1] {
+        app.update();
+
+        let mut dummy_events = app.world.resource_mut::<Events<DummyEvent>>();
+        assert_eq!(
+            dummy_events.drain().count(),
+            events_count,
+            "local event should be emitted only after reaching its tick"
+        );
+    }
+}
+
+#[test]
+fn waits_for_init() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn(Replicated);
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+        apply_tick: None,
+    });
+
+    server_app.update();
+
+    // Deliver everything except the init message.
+    let init_channel: u8 = ReplicationChannel::Init.into();
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    let (init_messages, other_messages): (Vec<_>, Vec<_>) = server
+        .drain_sent()
+        .partition(|&(_, channel_id, _)| channel_id == init_channel);
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in other_messages {
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+    assert!(
+        client_app.world.resource::<Events<DummyEvent>>().is_empty(),
+        "event should wait for the init message sent before it"
+    );
+
+    let mut client = client_app.world.resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in init_messages {
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+    assert_eq!(client_app.world.resource::<Events<DummyEvent>>().len(), 1);
+}
+
 #[test]
 fn sending_receiving_and_mapping() {
     let mut server_app = App::new();
@@ -105,6 +400,7 @@ fn sending_receiving_and_mapping() {
     server_app.world.send_event(ToClients {
         mode: SendMode::Broadcast,
         event: MappedEvent(server_entity),
+        apply_tick: None,
     });
 
     server_app.update();
@@ -120,6 +416,99 @@ fn sending_receiving_and_mapping() {
     assert_eq!(mapped_entities, [client_entity]);
 }
 
+#[test]
+fn buffering_unmapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_mapped_server_event::<MappedEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = Entity::from_raw(0);
+    let server_entity = Entity::from_raw(client_entity.index() + 1);
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: MappedEvent(server_entity),
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mapped_events = client_app.world.resource::<Events<MappedEvent>>();
+    assert!(
+        mapped_events.is_empty(),
+        "event should be buffered until the entity is replicated"
+    );
+
+    client_app
+        .world
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    client_app.update();
+
+    let mapped_entities: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<MappedEvent>>()
+        .drain()
+        .map(|event| event.0)
+        .collect();
+    assert_eq!(mapped_entities, [client_entity]);
+}
+
+#[test]
+fn unmapped_expiration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_mapped_server_event::<MappedEvent>(ChannelKind::Ordered);
+    }
+
+    client_app.insert_resource(UnmappedEventTimeout(Duration::ZERO));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = Entity::from_raw(0);
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: MappedEvent(server_entity),
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mapped_events = client_app.world.resource::<Events<MappedEvent>>();
+    assert!(mapped_events.is_empty());
+
+    let expired_entities: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<UnmappedEventExpired<MappedEvent>>>()
+        .drain()
+        .flat_map(|event| event.entities)
+        .collect();
+    assert_eq!(expired_entities, [server_entity]);
+}
+
 #[test]
 fn local_resending() {
     let mut app = App::new();
@@ -139,10 +528,17 @@ fn local_resending() {
         (SendMode::Direct(DUMMY_CLIENT_ID), 0),
         (SendMode::BroadcastExcept(ClientId::SERVER), 0),
         (SendMode::BroadcastExcept(DUMMY_CLIENT_ID), 1),
+        (SendMode::Only(vec![ClientId::SERVER, DUMMY_CLIENT_ID]), 1),
+        (SendMode::Only(vec![DUMMY_CLIENT_ID]), 0),
+        (SendMode::Except(vec![DUMMY_CLIENT_ID]), 1),
+        (SendMode::Except(vec![ClientId::SERVER, DUMMY_CLIENT_ID]), 0),
+        (SendMode::Visible(Entity::PLACEHOLDER), 1),
+        (SendMode::Group(GroupId(0)), 0),
     ] {
         app.world.send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
+            apply_tick: None,
         });
 
         app.update();
@@ -192,6 +588,7 @@ fn event_queue() {
     server_app.world.send_event(ToClients {
         mode: SendMode::Broadcast,
         event: DummyEvent,
+        apply_tick: None,
     });
 
     server_app.update();
@@ -244,6 +641,7 @@ fn different_ticks() {
     server_app.world.send_event(ToClients {
         mode: SendMode::Broadcast,
         event: DummyEvent,
+        apply_tick: None,
     });
 
     // If any client does not have a replicon tick >= the change tick associated with this event,
@@ -258,13 +656,167 @@ fn different_ticks() {
     assert_eq!(client_app2.world.resource::<Events<DummyEvent>>().len(), 1);
 }
 
+#[test]
+fn sender() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let sender = server_app
+        .world
+        .resource::<ServerEventSender<DummyEvent>>()
+        .clone();
+    std::thread::spawn(move || {
+        sender.send(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+            apply_tick: None,
+        });
+    })
+    .join()
+    .unwrap();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let dummy_events = client_app.world.resource::<Events<DummyEvent>>();
+    assert_eq!(dummy_events.len(), 1);
+}
+
+#[test]
+fn registration_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .add_mapped_server_event::<MappedEvent>(ChannelKind::Ordered);
+    client_app
+        .add_mapped_server_event::<MappedEvent>(ChannelKind::Ordered)
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let dummy_events = client_app.world.resource::<Events<DummyEvent>>();
+    assert_eq!(dummy_events.len(), 1);
+
+    let mapped_events = client_app.world.resource::<Events<MappedEvent>>();
+    assert!(mapped_events.is_empty());
+}
+
+#[test]
+fn skip_on_fast_forward() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .skip_on_fast_forward::<DummyEvent>();
+    }
+
+    client_app
+        .world
+        .resource_mut::<FastForward>()
+        .backlog_threshold = Some(2);
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..2 {
+        server_app.world.spawn((Replicated, DummyComponent));
+        server_app.world.send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+            apply_tick: None,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    client_app.update();
+
+    assert!(client_app.world.resource::<FastForward>().is_active());
+    let mut fast_forwarded = client_app.world.resource_mut::<Events<FastForwarded>>();
+    assert_eq!(fast_forwarded.drain().count(), 1);
+
+    let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+    assert_eq!(
+        dummy_events.drain().count(),
+        0,
+        "events should be skipped during fast-forward"
+    );
+
+    let mut replicated = client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>();
+    assert_eq!(
+        replicated.iter(&client_app.world).count(),
+        2,
+        "init messages should be applied during fast-forward"
+    );
+
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!client_app.world.resource::<FastForward>().is_active());
+    let mut dummy_events = client_app.world.resource_mut::<Events<DummyEvent>>();
+    assert_eq!(dummy_events.drain().count(), 1);
+}
+
 #[derive(Component, Serialize, Deserialize)]
 struct DummyComponent;
 
-#[derive(Deserialize, Event, Serialize)]
+#[derive(Deserialize, Event, Serialize, TypePath)]
 struct DummyEvent;
 
-#[derive(Deserialize, Event, Serialize)]
+#[derive(Clone, Deserialize, Event, Serialize, TypePath)]
+struct ValueEvent(u8);
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
 struct MappedEvent(Entity);
 
 impl MapEntities for MappedEvent {
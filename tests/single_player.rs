@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn replication() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        SinglePlayerWithNetworkingPlugin::new(setup),
+    ));
+
+    app.update();
+
+    let connected_clients = app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.iter_client_ids().collect::<Vec<_>>(),
+        [SinglePlayerWithNetworkingPlugin::CLIENT_ID]
+    );
+
+    app.world.spawn((Replicated, DummyComponent(1)));
+
+    app.update();
+
+    let client_world = app.sub_app_mut(SinglePlayerClient).world_mut();
+    let component = client_world
+        .query_filtered::<&DummyComponent, With<Replicated>>()
+        .single(client_world);
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn client_event() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins,
+        SinglePlayerWithNetworkingPlugin::new(setup),
+    ))
+    .init_resource::<ReceivedEvents>()
+    .add_systems(Update, count_events);
+
+    app.update();
+
+    app.sub_app_mut(SinglePlayerClient)
+        .world_mut()
+        .send_event(DummyEvent);
+
+    // Sent by the client after the first main world update, passed to the server
+    // after the second and received by the server on the third.
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<ReceivedEvents>().0, 1);
+}
+
+fn setup(app: &mut App) {
+    app.replicate::<DummyComponent>()
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+}
+
+fn count_events(
+    mut dummy_events: EventReader<FromClient<DummyEvent>>,
+    mut received: ResMut<ReceivedEvents>,
+) {
+    for event in dummy_events.read() {
+        assert_eq!(event.client_id, SinglePlayerWithNetworkingPlugin::CLIENT_ID);
+        received.0 += 1;
+    }
+}
+
+#[derive(Resource, Default)]
+struct ReceivedEvents(usize);
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
+
+#[derive(Event, Deserialize, Serialize, TypePath)]
+struct DummyEvent;
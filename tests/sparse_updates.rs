@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn patches() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_sparse::<Tiles>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Sparse::new(Tiles(vec![0; 4]))))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_tiles = client_app
+        .world
+        .query::<&Sparse<Tiles>>()
+        .single(&client_app.world);
+    assert_eq!(client_tiles.0, [0; 4]);
+
+    for (index, tile) in [(1, 1), (2, 2)] {
+        let mut tiles = server_app
+            .world
+            .get_mut::<Sparse<Tiles>>(server_entity)
+            .unwrap();
+        tiles.0[index] = tile;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let client_tiles = client_app
+        .world
+        .query::<&Sparse<Tiles>>()
+        .single(&client_app.world);
+    assert_eq!(client_tiles.0, [0, 1, 2, 0]);
+}
+
+#[test]
+fn unacknowledged_patches() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_sparse::<Tiles>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, Sparse::new(Tiles(vec![0; 4]))))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change tiles over multiple ticks without delivering updates.
+    for (index, tile) in [(1, 1), (2, 2)] {
+        let mut tiles = server_app
+            .world
+            .get_mut::<Sparse<Tiles>>(server_entity)
+            .unwrap();
+        tiles.0[index] = tile;
+
+        server_app.update();
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_tiles = client_app
+        .world
+        .query::<&Sparse<Tiles>>()
+        .single(&client_app.world);
+    assert_eq!(
+        client_tiles.0,
+        [0, 1, 2, 0],
+        "patch should include all changes since the last acknowledged tick"
+    );
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct Tiles(Vec<u8>);
+
+impl SparseData for Tiles {
+    type Item = u8;
+
+    fn diff(&self, previous: &Self) -> Vec<(usize, u8)> {
+        self.0
+            .iter()
+            .zip(&previous.0)
+            .enumerate()
+            .filter(|(_, (tile, previous))| tile != previous)
+            .map(|(index, (&tile, _))| (index, tile))
+            .collect()
+    }
+
+    fn apply(&mut self, index: usize, tile: u8) {
+        self.0[index] = tile;
+    }
+}
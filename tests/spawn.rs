@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy_replicon::{
     client::{confirmed::Confirmed, server_entity_map::ServerEntityMap},
+    core::{
+        command_markers::SpawnMarkers,
+        replication_fns::{ctx::SpawnCtx, ReplicationFns},
+    },
     prelude::*,
     test_app::ServerTestAppExt,
 };
@@ -75,6 +79,45 @@ fn with_component() {
         .single(&client_app.world);
 }
 
+#[test]
+fn multiple() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entities: Vec<_> = server_app
+        .world
+        .spawn_batch([(Replicated, DummyComponent); 3])
+        .collect();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    for server_entity in server_entities {
+        let client_entity = *entity_map
+            .to_client()
+            .get(&server_entity)
+            .expect("server entity should be mapped");
+        assert!(client_app
+            .world
+            .get::<DummyComponent>(client_entity)
+            .is_some());
+    }
+}
+
 #[test]
 fn with_old_component() {
     let mut server_app = App::new();
@@ -118,6 +161,83 @@ fn with_old_component() {
         .single(&client_app.world);
 }
 
+#[test]
+fn cloned() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .register_type::<ReflectedComponent>()
+        .replicate::<ReflectedComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, ReflectedComponent(1)))
+        .id();
+    server_app
+        .world
+        .resource_mut::<ConnectedClients>()
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(server_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let copied_entity = server_app
+        .world
+        .commands()
+        .clone_replicated(server_entity, CloneVisibility::Copy)
+        .id();
+    let reset_entity = server_app
+        .world
+        .commands()
+        .clone_replicated(server_entity, CloneVisibility::Reset)
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    for entity in [copied_entity, reset_entity] {
+        let component = server_app
+            .world
+            .get::<ReflectedComponent>(entity)
+            .expect("component should be cloned");
+        assert_eq!(component.0, 1);
+    }
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&copied_entity));
+    assert!(
+        !entity_map.to_client().contains_key(&reset_entity),
+        "entity with reset visibility shouldn't be visible with whitelist"
+    );
+
+    let components: Vec<_> = client_app
+        .world
+        .query::<&ReflectedComponent>()
+        .iter(&client_app.world)
+        .map(|component| component.0)
+        .collect();
+    assert_eq!(components, [1, 1]);
+}
+
 #[test]
 fn before_connection() {
     let mut server_app = App::new();
@@ -219,6 +339,92 @@ fn pre_spawn() {
     );
 }
 
+#[test]
+fn duplicate_pre_spawn() {
+    for policy in [
+        DuplicateSpawnPolicy::Reuse,
+        DuplicateSpawnPolicy::Replace,
+        DuplicateSpawnPolicy::Warn,
+    ] {
+        let mut server_app = App::new();
+        let mut client_app = App::new();
+        for app in [&mut server_app, &mut client_app] {
+            app.add_plugins((
+                MinimalPlugins,
+                RepliconPlugins.set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }),
+            ))
+            .replicate::<DummyComponent>();
+        }
+        client_app.insert_resource(policy);
+
+        server_app.connect_client(&mut client_app);
+
+        let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let existing_entity = client_app
+            .world
+            .query_filtered::<Entity, With<Replicated>>()
+            .single(&client_app.world);
+
+        // Simulate a mapping that arrives after the entity was already replicated.
+        let new_entity = client_app.world.spawn_empty().id();
+        let client = client_app.world.resource::<RepliconClient>();
+        let client_id = client.id().unwrap();
+        let mut entity_map = server_app.world.resource_mut::<ClientEntityMap>();
+        entity_map.insert(
+            client_id,
+            ClientMapping {
+                server_entity,
+                client_entity: new_entity,
+            },
+        );
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        let events = client_app.world.resource::<Events<DuplicateSpawn>>();
+        let duplicate = *events
+            .get_reader()
+            .read(events)
+            .next()
+            .expect("duplicate should be reported");
+        assert_eq!(duplicate.server_entity, server_entity);
+        assert_eq!(duplicate.existing_entity, existing_entity);
+        assert_eq!(duplicate.new_entity, new_entity);
+        assert_eq!(duplicate.policy, policy);
+
+        let entity_map = client_app.world.resource::<ServerEntityMap>();
+        let mapped_entity = *entity_map.to_client().get(&server_entity).unwrap();
+        let existing_alive = client_app.world.get_entity(existing_entity).is_some();
+        let new_alive = client_app.world.get_entity(new_entity).is_some();
+        match policy {
+            DuplicateSpawnPolicy::Reuse => {
+                assert_eq!(mapped_entity, existing_entity);
+                assert!(existing_alive);
+                assert!(!new_alive);
+            }
+            DuplicateSpawnPolicy::Replace => {
+                assert_eq!(mapped_entity, new_entity);
+                assert!(!existing_alive);
+                assert!(new_alive);
+            }
+            DuplicateSpawnPolicy::Warn => {
+                assert_eq!(mapped_entity, new_entity);
+                assert!(existing_alive);
+                assert!(new_alive);
+            }
+        }
+    }
+}
+
 #[test]
 fn after_despawn() {
     let mut server_app = App::new();
@@ -253,5 +459,159 @@ fn after_despawn() {
         .single(&client_app.world);
 }
 
-#[derive(Component, Deserialize, Serialize)]
+#[test]
+fn apply_stages() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    client_app.init_resource::<StageEntities>().add_systems(
+        PreUpdate,
+        (|mut stage_entities: ResMut<StageEntities>,
+          spawned: Query<(), (With<Replicated>, Without<DummyComponent>)>| {
+            stage_entities.spawned = spawned.iter().count();
+        })
+        .after(ClientSet::ApplySpawns)
+        .before(ClientSet::ApplyInserts),
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let stage_entities = client_app.world.resource::<StageEntities>();
+    assert_eq!(
+        stage_entities.spawned, 1,
+        "entity should be spawned without components before insertions"
+    );
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn custom_spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    client_app.world.resource_mut::<ReplicationFns>().spawn = Some(spawn_pooled);
+    let pooled_entity = client_app.world.spawn(Pooled).id();
+    client_app.insert_resource(EntityPool(vec![pooled_entity]));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&pooled_entity),
+        "server entity should be mapped to the entity from pool"
+    );
+
+    let client_entity = client_app.world.entity(pooled_entity);
+    assert!(client_entity.contains::<Replicated>());
+    assert!(client_entity.contains::<DummyComponent>());
+    assert!(client_app.world.resource::<EntityPool>().0.is_empty());
+}
+
+#[test]
+fn marker_spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    client_app.set_marker_spawn::<Pooled>(spawn_pooled);
+    let pooled_entity = client_app.world.spawn(Pooled).id();
+    client_app.insert_resource(EntityPool(vec![pooled_entity]));
+
+    server_app.connect_client(&mut client_app);
+
+    let pooled_server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+    let regular_server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+    client_app
+        .world
+        .resource_mut::<SpawnMarkers>()
+        .insert::<Pooled>(pooled_server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world.resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&pooled_server_entity),
+        Some(&pooled_entity),
+        "entity with the marker should be taken from pool"
+    );
+    let regular_entity = *entity_map
+        .to_client()
+        .get(&regular_server_entity)
+        .expect("entity without the marker should be spawned");
+    assert!(!client_app.world.entity(regular_entity).contains::<Pooled>());
+    assert!(client_app.world.resource::<EntityPool>().0.is_empty());
+    assert!(client_app.world.resource::<SpawnMarkers>().is_empty());
+}
+
+#[derive(Clone, Component, Copy, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+struct ReflectedComponent(u8);
+
+#[derive(Resource, Default)]
+struct StageEntities {
+    spawned: usize,
+}
+
+/// Takes an entity from [`EntityPool`] or spawns a new one if the pool is empty.
+fn spawn_pooled(_ctx: &SpawnCtx, world: &mut World) -> Entity {
+    match world.resource_mut::<EntityPool>().0.pop() {
+        Some(entity) => entity,
+        None => world.spawn(Pooled).id(),
+    }
+}
+
+#[derive(Resource)]
+struct EntityPool(Vec<Entity>);
+
+#[derive(Component)]
+struct Pooled;
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn different_registration_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            StableFnsIdsPlugin,
+        ));
+    }
+
+    server_app
+        .replicate::<ComponentA>()
+        .replicate::<ComponentB>();
+    client_app
+        .replicate::<ComponentB>()
+        .replicate::<ComponentA>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, ComponentA(1), ComponentB(2)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component_a, component_b) = client_app
+        .world
+        .query::<(&ComponentA, &ComponentB)>()
+        .single(&client_app.world);
+    assert_eq!(component_a.0, 1);
+    assert_eq!(component_b.0, 2);
+}
+
+#[test]
+fn unknown_component() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            StableFnsIdsPlugin,
+        ))
+        .replicate::<ComponentA>();
+    }
+
+    // Registered only on server, but never sent.
+    server_app.replicate::<ComponentB>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, ComponentA(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component_a = client_app
+        .world
+        .query::<&ComponentA>()
+        .single(&client_app.world);
+    assert_eq!(component_a.0, 1);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentA(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentB(u8);
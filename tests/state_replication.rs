@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn transition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_state::<GameState>()
+            .replicate_state::<GameState>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let state = client_app.world.resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::InGame);
+}
+
+#[test]
+fn late_join() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_state::<GameState>()
+            .replicate_state::<GameState>();
+    }
+
+    server_app.connect_client(&mut client_app1);
+
+    server_app
+        .world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+
+    server_app.connect_client(&mut client_app2);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    let state = client_app2.world.resource::<State<GameState>>();
+    assert_eq!(
+        *state.get(),
+        GameState::InGame,
+        "connected client should receive the current state"
+    );
+}
+
+#[derive(
+    States, Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, TypePath,
+)]
+enum GameState {
+    #[default]
+    Lobby,
+    InGame,
+}
@@ -0,0 +1,113 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    bincode::{DefaultOptions, Options},
+    network_event::topics::TopicMessage,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn client_event_topic() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .add_client_event_topic::<DummyEvent>("dummy");
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for subscribed in [false, true] {
+        if subscribed {
+            server_app
+                .world
+                .resource_mut::<EventTopics>()
+                .subscribe("dummy");
+        }
+
+        client_app.world.send_event(DummyEvent(42));
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+
+        let client_events = server_app
+            .world
+            .resource_mut::<Events<FromClient<DummyEvent>>>()
+            .drain()
+            .count();
+        assert_eq!(client_events, 1, "typed events shouldn't be consumed");
+
+        let messages: Vec<_> = server_app
+            .world
+            .resource_mut::<Events<TopicMessage>>()
+            .drain()
+            .collect();
+        if subscribed {
+            let [message] = messages.as_slice() else {
+                panic!("subscribed topic should emit a single message");
+            };
+            let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+            assert_eq!(message.topic, "dummy");
+            assert_eq!(message.client_id, client_id);
+            let event: DummyEvent = DefaultOptions::new().deserialize(&message.payload).unwrap();
+            assert_eq!(event.0, 42);
+        } else {
+            assert!(
+                messages.is_empty(),
+                "topics shouldn't be subscribed by default"
+            );
+        }
+    }
+}
+
+#[test]
+fn server_event_topic() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .add_server_event_topic::<DummyEvent>("dummy");
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut topics = client_app.world.resource_mut::<EventTopics>();
+    assert!(topics.subscribe("dummy"));
+    assert!(!topics.subscribe("missing"));
+    assert_eq!(topics.name::<DummyEvent>(), Some("dummy"));
+
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent(42),
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let messages: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<TopicMessage>>()
+        .drain()
+        .collect();
+    let [message] = messages.as_slice() else {
+        panic!("subscribed topic should emit a single message");
+    };
+    assert_eq!(message.client_id, ClientId::SERVER);
+    let event: DummyEvent = DefaultOptions::new().deserialize(&message.payload).unwrap();
+    assert_eq!(event.0, 42);
+}
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent(u8);
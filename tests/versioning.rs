@@ -0,0 +1,154 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn older_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+    }
+
+    server_app
+        .add_versioned_client_event::<Chat>(ChannelKind::Ordered, CHAT_KEY, 2)
+        .add_client_event_version::<Chat, ChatV1>(1, upgrade, downgrade);
+    client_app.add_versioned_client_event::<ChatV1>(ChannelKind::Ordered, CHAT_KEY, 1);
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let versions = server_app.world.resource::<EventVersions>();
+    assert_eq!(versions.client(client_id, CHAT_KEY), Some(1));
+
+    client_app.world.send_event(ChatV1("Hello".into()));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<FromClient<Chat>>>()
+        .drain()
+        .map(|FromClient { event, .. }| event)
+        .collect();
+    assert_eq!(
+        events,
+        [Chat {
+            text: "Hello".into(),
+            room: 0,
+        }]
+    );
+}
+
+#[test]
+fn newer_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins));
+    }
+
+    server_app.add_versioned_client_event::<ChatV1>(ChannelKind::Ordered, CHAT_KEY, 1);
+    client_app
+        .add_versioned_client_event::<Chat>(ChannelKind::Ordered, CHAT_KEY, 2)
+        .add_client_event_version::<Chat, ChatV1>(1, upgrade, downgrade);
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    let versions = client_app.world.resource::<EventVersions>();
+    assert_eq!(versions.server(CHAT_KEY), Some(1));
+
+    client_app.world.send_event(Chat {
+        text: "Hello".into(),
+        room: 1,
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let events: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<FromClient<ChatV1>>>()
+        .drain()
+        .map(|FromClient { event, .. }| event)
+        .collect();
+    assert_eq!(events, [ChatV1("Hello".into())]);
+}
+
+#[test]
+fn server_event() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app
+        .add_versioned_server_event::<Chat>(ChannelKind::Ordered, CHAT_KEY, 2)
+        .add_server_event_version::<Chat, ChatV1>(1, upgrade, downgrade);
+    client_app.add_versioned_server_event::<ChatV1>(ChannelKind::Ordered, CHAT_KEY, 1);
+
+    server_app.connect_client(&mut client_app);
+    negotiate(&mut server_app, &mut client_app);
+
+    server_app.world.send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: Chat {
+            text: "Hello".into(),
+            room: 1,
+        },
+        apply_tick: None,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let events: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<ChatV1>>()
+        .drain()
+        .collect();
+    assert_eq!(events, [ChatV1("Hello".into())]);
+}
+
+/// Exchanges supported versions between apps.
+fn negotiate(server_app: &mut App, client_app: &mut App) {
+    server_app.exchange_with_client(client_app);
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+}
+
+fn upgrade(chat: ChatV1) -> Chat {
+    Chat {
+        text: chat.0,
+        room: 0,
+    }
+}
+
+fn downgrade(chat: &Chat) -> ChatV1 {
+    ChatV1(chat.text.clone())
+}
+
+const CHAT_KEY: &str = "chat";
+
+#[derive(Debug, Deserialize, Event, PartialEq, Serialize)]
+struct Chat {
+    text: String,
+    room: u8,
+}
+
+#[derive(Debug, Deserialize, Event, PartialEq, Serialize)]
+struct ChatV1(String);
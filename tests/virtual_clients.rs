@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replicon_channels::ReplicationChannel, network_event::client_event::ClientEventChannel,
+    prelude::*,
+};
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn replication() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let client_id = server_app.world.resource_mut::<VirtualClients>().connect();
+
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert!(connected_clients.get_client(client_id).is_some());
+
+    let visible_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    connected_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(visible_entity, true);
+
+    server_app.update();
+
+    let mut virtual_clients = server_app.world.resource_mut::<VirtualClients>();
+    let messages: Vec<_> = virtual_clients
+        .receive(client_id, ReplicationChannel::Init)
+        .collect();
+    assert_eq!(messages.len(), 1);
+
+    let mut server = server_app.world.resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_sent().count(),
+        0,
+        "messages for virtual clients shouldn't be passed to the backend"
+    );
+
+    server_app
+        .world
+        .resource_mut::<VirtualClients>()
+        .disconnect(client_id);
+
+    server_app.update();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert!(connected_clients.is_empty());
+}
+
+#[test]
+fn sending() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+    server_app.finish();
+
+    server_app
+        .world
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let channel = *server_app
+        .world
+        .resource::<ClientEventChannel<DummyEvent>>();
+    let message = DefaultOptions::new().serialize(&DummyEvent).unwrap();
+    let mut virtual_clients = server_app.world.resource_mut::<VirtualClients>();
+    let client_id = virtual_clients.connect();
+    virtual_clients.send(client_id, channel, message);
+
+    server_app.update();
+
+    let mut client_events = server_app
+        .world
+        .resource_mut::<Events<FromClient<DummyEvent>>>();
+    let event = client_events
+        .drain()
+        .next()
+        .expect("event from virtual client should be received");
+    assert_eq!(event.client_id, client_id);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize, TypePath)]
+struct DummyEvent;
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
-use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use bevy_replicon::{
+    core::replication_fns::rule_fns::RuleFns, prelude::*, test_app::ServerTestAppExt,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -130,6 +132,54 @@ fn blacklist() {
         .single(&client_app.world);
 }
 
+#[test]
+fn replication_targets() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.replication_targets(server_entity).count(),
+        0,
+        "entity shouldn't be a target before sending"
+    );
+
+    server_app.update();
+
+    let client_id = client_app.world.resource::<RepliconClient>().id().unwrap();
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    let targets: Vec<_> = connected_clients
+        .replication_targets(server_entity)
+        .collect();
+    assert_eq!(targets, [client_id]);
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, false);
+
+    let connected_clients = server_app.world.resource::<ConnectedClients>();
+    assert_eq!(
+        connected_clients.replication_targets(server_entity).count(),
+        0,
+        "hidden entity shouldn't be a target"
+    );
+}
+
 #[test]
 fn blacklist_despawn() {
     let mut server_app = App::new();
@@ -249,6 +299,90 @@ fn whitelist() {
     );
 }
 
+#[test]
+fn incremental_reveal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                reveal_policy: RevealPolicy::Incremental,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<ValueComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DummyComponent, ValueComponent(0)))
+        .id();
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, false);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity_ref = client_app.world.entity(client_entity);
+    assert!(
+        client_entity_ref.contains::<Obscured>(),
+        "entity should be kept after losing visibility"
+    );
+
+    // Change only value on server and dummy component on client to check what will be sent.
+    server_app
+        .world
+        .get_mut::<ValueComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+    client_app
+        .world
+        .entity_mut(client_entity)
+        .remove::<DummyComponent>();
+
+    let mut connected_clients = server_app.world.resource_mut::<ConnectedClients>();
+    let visibility = connected_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity_ref = client_app.world.entity(client_entity);
+    assert!(!client_entity_ref.contains::<Obscured>());
+    assert!(
+        !client_entity_ref.contains::<DummyComponent>(),
+        "unchanged component shouldn't be sent"
+    );
+    assert_eq!(client_entity_ref.get::<ValueComponent>().unwrap().0, 1);
+}
+
 #[test]
 fn whitelist_despawn() {
     let mut server_app = App::new();
@@ -287,5 +421,406 @@ fn whitelist_despawn() {
     assert!(!visibility.is_visible(server_entity));
 }
 
+#[test]
+fn owner_condition() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client = client_app1.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let server_entity = server_app
+        .world
+        .spawn((Replicated, DummyComponent, VisibleToOwner(client_id)))
+        .id();
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    client_app1
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app1.world);
+    assert!(
+        client_app2.world.entities().is_empty(),
+        "entity should be visible only to the owner"
+    );
+
+    // Remove the condition to reset visibility to the policy default.
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<VisibleToOwner>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+
+    assert!(
+        client_app1.world.entities().is_empty(),
+        "entity should be hidden after removing the condition"
+    );
+}
+
+#[test]
+fn team_condition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, VisibleToTeam(TeamId(1))));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world.entities().is_empty());
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ClientTeams>()
+        .insert(client_id, TeamId(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn group_condition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    const SQUAD: GroupId = GroupId(1);
+    server_app
+        .world
+        .spawn((Replicated, DummyComponent, VisibleToGroup(SQUAD)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world.entities().is_empty());
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app
+        .world
+        .resource_mut::<ConnectedClients>()
+        .groups_mut()
+        .insert(SQUAD, client_id);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+
+    server_app
+        .world
+        .resource_mut::<ConnectedClients>()
+        .groups_mut()
+        .remove(SQUAD, client_id);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world.entities().is_empty(),
+        "entity should be hidden after leaving the group"
+    );
+}
+
+#[test]
+fn invisible_condition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app.world.spawn((
+        Replicated,
+        DummyComponent,
+        VisibleToAll,
+        InvisibleTo(client_id),
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world.entities().is_empty(),
+        "`InvisibleTo` should take priority"
+    );
+}
+
+#[test]
+fn owner_only() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_to_owner::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world.resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let other_id = ClientId::new(client_id.get() + 1);
+    server_app
+        .world
+        .spawn((Replicated, Owner(client_id), DummyComponent));
+    server_app
+        .world
+        .spawn((Replicated, Owner(other_id), DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entities = client_app
+        .world
+        .query_filtered::<(), With<Replicated>>()
+        .iter(&client_app.world)
+        .count();
+    assert_eq!(entities, 2, "entities should be visible to all clients");
+
+    client_app
+        .world
+        .query_filtered::<(), (With<Replicated>, With<DummyComponent>)>()
+        .single(&client_app.world);
+}
+
+#[test]
+fn optional_subscription() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_optional::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world.spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    assert!(
+        !client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "optional component shouldn't be sent without subscription"
+    );
+
+    client_app
+        .world
+        .resource_mut::<ComponentSubscriptions>()
+        .subscribe::<DummyComponent>();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "existing value should be sent after subscription"
+    );
+
+    client_app
+        .world
+        .resource_mut::<ComponentSubscriptions>()
+        .unsubscribe::<DummyComponent>();
+
+    client_app.update();
+
+    assert!(
+        !client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "component should be removed after unsubscription"
+    );
+}
+
+#[test]
+fn rule_condition() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with::<DummyComponent>(
+            RuleFns::default().with_condition(|entity| entity.contains::<EnabledMarker>()),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world.spawn((Replicated, DummyComponent)).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(&client_app.world);
+    assert!(
+        !client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "component shouldn't be sent while the condition isn't met"
+    );
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .insert(EnabledMarker);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "existing value should be sent after the condition is met"
+    );
+
+    server_app
+        .world
+        .entity_mut(server_entity)
+        .remove::<EnabledMarker>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        !client_app
+            .world
+            .entity(client_entity)
+            .contains::<DummyComponent>(),
+        "component should be removed after the condition stops being met"
+    );
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component)]
+struct EnabledMarker;
+
+#[derive(Component, Deserialize, Serialize)]
+struct ValueComponent(u8);